@@ -226,6 +226,15 @@ pub fn handle_map_right_click(
         }
     }
 
+    // Revendiquer — le lord étend le territoire de son établissement sur la cellule cliquée
+    // (le serveur vérifie qu'elle est libre et touche le territoire)
+    if let Some(lord) = &player_info.lord
+        && player_info.organization.is_some()
+        && unit_selection.selected_ids().contains(&lord.id)
+    {
+        actions.push(crate::ui::resources::ContextMenuAction::Claim);
+    }
+
     // Construire — disponible si le lord est sélectionné
     // et qu'on est dans le territoire du joueur
     if let Some(lord) = &player_info.lord {
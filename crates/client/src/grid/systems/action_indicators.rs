@@ -124,6 +124,14 @@ fn spawn_action_indicator(
             Color::srgba_u8(220, 50, 50, 220), // red
             Color::srgba_u8(40, 40, 40, 160),
         ),
+        ActionStatusEnum::Paused => (
+            Color::srgba_u8(160, 160, 160, 200), // grey
+            Color::srgba_u8(40, 40, 40, 160),
+        ),
+        ActionStatusEnum::Cancelled => (
+            Color::srgba_u8(120, 120, 120, 160), // dark grey
+            Color::srgba_u8(40, 40, 40, 120),
+        ),
     };
 
     // Initial fill width
@@ -210,7 +218,10 @@ fn spawn_action_indicator(
 
     // Add status markers
     match status {
-        ActionStatusEnum::Pending | ActionStatusEnum::Failed => {
+        ActionStatusEnum::Pending
+        | ActionStatusEnum::Failed
+        | ActionStatusEnum::Paused
+        | ActionStatusEnum::Cancelled => {
             entity.insert(PendingIndicator);
         }
         ActionStatusEnum::InProgress => {
@@ -240,7 +251,7 @@ pub fn update_action_progress(
     let current_time = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
-        .as_secs();
+        .as_millis() as u64;
 
     for (indicator, children) in indicator_query.iter() {
         let Some(action) = action_tracker.get_action(indicator.action_id) else {
@@ -248,7 +259,7 @@ pub fn update_action_progress(
         };
 
         let progress = action.progress(current_time);
-        let remaining = action.completion_time.saturating_sub(current_time);
+        let remaining = action.completion_time.saturating_sub(current_time).div_ceil(1000);
         let minutes = remaining / 60;
         let seconds = remaining % 60;
 
@@ -7,6 +7,7 @@ use std::collections::VecDeque;
 use std::sync::mpsc::{Receiver, Sender, channel};
 use std::sync::{Arc, Mutex};
 use std::thread;
+use shared::protocol::{ClientMessage, ServerMessage};
 use tungstenite::{Message, protocol::WebSocketConfig};
 
 #[derive(Resource)]
@@ -15,6 +16,47 @@ pub struct NetworkClient {
     incoming: Arc<Mutex<VecDeque<shared::protocol::ServerMessage>>>,
     connected: Arc<Mutex<bool>>,
     reconnect_handle: Arc<Mutex<Option<thread::JoinHandle<()>>>>,
+    resume: Arc<Mutex<ResumeState>>,
+}
+
+/// What the network thread needs to resume the session after a reconnect.
+/// Updated by the thread itself so `last_seen_seq` never lags behind the socket.
+#[derive(Default)]
+struct ResumeState {
+    token: Option<String>,
+    last_seen_seq: u64,
+}
+
+impl ResumeState {
+    /// Suit les jetons et numéros de séquence, et déballe les messages `Sequenced`
+    fn track(&mut self, message: ServerMessage) -> ServerMessage {
+        match message {
+            ServerMessage::Sequenced { seq, message } => {
+                self.last_seen_seq = self.last_seen_seq.max(seq);
+                *message
+            }
+            ServerMessage::LoginSuccess {
+                ref resume_token, ..
+            } => {
+                // Nouveau login : le serveur recommence la numérotation
+                self.token = Some(resume_token.clone());
+                self.last_seen_seq = 0;
+                message
+            }
+            ServerMessage::SessionResumed {
+                ref resume_token, ..
+            } => {
+                self.token = Some(resume_token.clone());
+                message
+            }
+            // Plus rien à reprendre : le compte n'existe plus
+            ServerMessage::ResumeFailed { .. } | ServerMessage::AccountDeleted => {
+                self.token = None;
+                message
+            }
+            other => other,
+        }
+    }
 }
 
 impl NetworkClient {
@@ -27,10 +69,12 @@ impl NetworkClient {
         let incoming = Arc::new(Mutex::new(VecDeque::new()));
         let connected = Arc::new(Mutex::new(true));
         let reconnect_handle = Arc::new(Mutex::new(None));
+        let resume = Arc::new(Mutex::new(ResumeState::default()));
 
         // Spawn a thread to read messages
         let incoming_clone = incoming.clone();
         let connected_clone = connected.clone();
+        let resume_clone = resume.clone();
         let server_url_str = server_url.to_string();
 
         let handle = thread::spawn(move || {
@@ -43,6 +87,7 @@ impl NetworkClient {
                     &rx,
                     &incoming_clone,
                     &connected_clone,
+                    &resume_clone,
                 ) {
                     Ok(_) => {
                         retry_count = 0;
@@ -66,6 +111,8 @@ impl NetworkClient {
                             retry_count += 1;
                         } else {
                             error!("Max retries reached, giving up");
+                            // Plus de reprise possible : le jeu doit revenir à l'écran de login
+                            resume_clone.lock().unwrap().token = None;
                             break;
                         }
                     }
@@ -84,14 +131,16 @@ impl NetworkClient {
             incoming,
             connected,
             reconnect_handle,
+            resume,
         })
     }
 
-    pub fn establish_connection(
+    fn establish_connection(
         server_url: &str,
         rx: &Receiver<Vec<u8>>,
         incoming: &Arc<Mutex<VecDeque<shared::protocol::ServerMessage>>>,
         connected: &Arc<Mutex<bool>>,
+        resume: &Arc<Mutex<ResumeState>>,
     ) -> Result<(), String> {
         let url = url::Url::parse(server_url).map_err(|e| format!("Invalid URL: {}", e))?;
         let host = url.host_str().ok_or("No host in URL")?;
@@ -118,6 +167,32 @@ impl NetworkClient {
         *connected.lock().unwrap() = true;
         info!("✓ Connected to server");
 
+        // Chaque nouvelle socket est une nouvelle session : s'annoncer avant tout autre message,
+        // puis reprendre la session précédente si on en avait une
+        let mut opening = vec![ClientMessage::Hello {
+            protocol_version: shared::protocol::PROTOCOL_VERSION,
+            client_build: env!("CARGO_PKG_VERSION").to_string(),
+        }];
+        {
+            let resume = resume.lock().unwrap();
+            if let Some(token) = &resume.token {
+                info!("Resuming session after message #{}", resume.last_seen_seq);
+                opening.push(ClientMessage::ResumeSession {
+                    token: token.clone(),
+                    last_seen_seq: resume.last_seen_seq,
+                });
+            }
+        }
+        for message in opening {
+            let raw = bincode::encode_to_vec(&message, bincode::config::standard())
+                .map_err(|e| format!("Serialize error: {}", e))?;
+            socket
+                .send(Message::Binary(
+                    shared::protocol::compression::compress(&raw).into(),
+                ))
+                .map_err(|e| format!("Failed to send opening message: {}", e))?;
+        }
+
         loop {
             // Check if disconnected
             if !*connected.lock().unwrap() {
@@ -172,6 +247,7 @@ impl NetworkClient {
                             ) {
                                 Ok((server_msg, _)) => {
                                     // info!("✓ Deserialized ServerMessage: {:?}", server_msg);
+                                    let server_msg = resume.lock().unwrap().track(server_msg);
                                     incoming.lock().unwrap().push_back(server_msg);
                                 }
                                 Err(e) => {
@@ -246,4 +322,9 @@ impl NetworkClient {
     pub fn is_connected(&self) -> bool {
         *self.connected.lock().unwrap()
     }
+
+    /// True while a dropped connection can still be resumed without logging in again
+    pub fn can_resume(&self) -> bool {
+        self.resume.lock().unwrap().token.is_some()
+    }
 }
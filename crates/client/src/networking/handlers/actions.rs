@@ -4,7 +4,7 @@ use shared::protocol::ServerMessage;
 
 use crate::networking::client::NetworkClient;
 use crate::networking::events::ServerEvent;
-use crate::state::resources::{ActionTracker, NotificationState, TrackedAction};
+use crate::state::resources::{ActionTracker, NotificationState, TrackedAction, UnitsDataCache};
 
 /// Handles action-related messages (status updates, completions).
 pub fn handle_action_events(
@@ -12,11 +12,12 @@ pub fn handle_action_events(
     mut action_tracker: Option<ResMut<ActionTracker>>,
     mut network_client: Option<ResMut<NetworkClient>>,
     mut notifications: ResMut<NotificationState>,
+    units_data_cache: Option<Res<UnitsDataCache>>,
 ) {
     let current_time = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
-        .as_secs();
+        .as_millis() as u64;
 
     for event in events.read() {
         match &event.0 {
@@ -39,11 +40,24 @@ pub fn handle_action_events(
                     action_id, status, player_id, chunk_id.x, chunk_id.y, cell.q, cell.r
                 );
 
-                // Capture start_time: use existing if upgrading, else now
-                let start_time = action_tracker
-                    .get_action(*action_id)
-                    .map(|a| a.start_time)
+                if *status == ActionStatusEnum::Cancelled {
+                    action_tracker.remove_action(*action_id);
+                    notifications.push_info(format!("{} annulée", action_type.to_name()));
+                    continue;
+                }
+
+                // Capture start_time: use existing if upgrading, else now.
+                // A resumed action comes back with a later completion time:
+                // shift the start by the same amount so progress picks up where it stopped.
+                let previous = action_tracker.get_action(*action_id);
+                let start_time = previous
+                    .map(|a| a.start_time + completion_time.saturating_sub(a.completion_time))
                     .unwrap_or(current_time);
+                let paused_at = (*status == ActionStatusEnum::Paused).then(|| {
+                    previous
+                        .and_then(|a| a.paused_at)
+                        .unwrap_or(current_time)
+                });
 
                 let tracked_action = TrackedAction {
                     action_id: *action_id,
@@ -54,6 +68,7 @@ pub fn handle_action_events(
                     status: *status,
                     start_time,
                     completion_time: *completion_time,
+                    paused_at,
                     action_name: action_name.clone(),
                     unit_ids: unit_ids.clone()
                 };
@@ -71,10 +86,33 @@ pub fn handle_action_events(
                     ActionStatusEnum::Failed => {
                         notifications.push_error(format!("{} échouée", action_type.to_name()));
                     }
+                    ActionStatusEnum::Paused => {
+                        notifications.push_info(format!("{} en pause", action_type.to_name()));
+                    }
                     _ => {}
                 }
             }
 
+            ServerMessage::SkillLevelUp {
+                unit_id,
+                skill,
+                level,
+            } => {
+                info!("Unit {} reached level {} in {:?}", unit_id, level, skill);
+
+                let unit_name = units_data_cache
+                    .as_ref()
+                    .and_then(|cache| cache.get_unit(*unit_id))
+                    .map(|unit| unit.full_name())
+                    .unwrap_or_else(|| format!("Unité {}", unit_id));
+                notifications.push_success(format!(
+                    "{} atteint le niveau {} en {}",
+                    unit_name,
+                    level,
+                    skill.to_name()
+                ));
+            }
+
             ServerMessage::ActionCompleted {
                 action_id,
                 chunk_id,
@@ -101,9 +139,31 @@ pub fn handle_action_events(
                 }
             }
 
-            ServerMessage::ActionError { reason } => {
-                warn!("Action rejected by server: {}", reason);
-                notifications.push_error(reason.clone());
+            ServerMessage::ActionQueueUpdate { unit_id, entries } => {
+                if let Some(ref mut action_tracker) = action_tracker {
+                    action_tracker.set_queue(*unit_id, entries.clone());
+                }
+            }
+
+            ServerMessage::ActionQueueSkipped {
+                unit_id,
+                action,
+                code,
+                detail,
+            } => {
+                let reason = code.describe(detail.as_deref());
+                warn!("Queued {:?} of unit {} skipped: {}", action, unit_id, reason);
+                notifications.push_error(format!(
+                    "{} abandonnée : {}",
+                    action.action_type().to_name(),
+                    reason
+                ));
+            }
+
+            ServerMessage::ActionError { code, detail } => {
+                let reason = code.describe(detail.as_deref());
+                warn!("Action rejected by server ({:?}): {}", code, reason);
+                notifications.push_error(reason);
             }
 
             _ => {}
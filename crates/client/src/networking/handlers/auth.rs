@@ -1,5 +1,5 @@
 use bevy::prelude::*;
-use shared::protocol::{ClientMessage, ServerMessage};
+use shared::protocol::{ClientMessage, PROTOCOL_VERSION, ServerMessage};
 
 use crate::networking::client::NetworkClient;
 use crate::networking::events::ServerEvent;
@@ -18,7 +18,29 @@ pub fn handle_auth_events(
 ) {
     for event in events.read() {
         match &event.0 {
-            ServerMessage::LoginSuccess { player, character } => {
+            ServerMessage::HelloAck {
+                protocol_version,
+                server_build,
+            } => {
+                info!(
+                    "✓ Handshake accepted (protocol v{}, server build {})",
+                    protocol_version, server_build
+                );
+            }
+
+            ServerMessage::HelloRejected {
+                code,
+                server_protocol_version,
+            } => {
+                error!(
+                    "Server refused handshake: {} (client v{}, server v{})",
+                    code.message(),
+                    PROTOCOL_VERSION,
+                    server_protocol_version
+                );
+            }
+
+            ServerMessage::LoginSuccess { player, character, .. } => {
                 info!("✓ Login successful, player ID: {}", player.id);
                 connection.logged_in = true;
                 connection.player_id = Some(player.id as u64);
@@ -99,8 +121,12 @@ pub fn handle_auth_events(
             }
 
             // ── NOUVEAU : Échec de création du lord ──
-            ServerMessage::LordCreateError { reason } => {
-                warn!("Failed to create lord: {}", reason);
+            ServerMessage::LordCreateError { code, detail } => {
+                warn!(
+                    "Failed to create lord ({:?}): {}",
+                    code,
+                    code.describe(detail.as_deref())
+                );
                 // Rester sur CharacterCreation — TODO: afficher l'erreur dans l'UI
             }
 
@@ -113,6 +139,8 @@ pub fn handle_auth_events(
                     player_info.organization = Some(org.clone());
                 } else {
                     info!("No organization found for player");
+                    // Le seigneur a quitté son organisation ou en a été exclu
+                    player_info.organization = None;
                 }
             }
 
@@ -129,8 +157,29 @@ pub fn handle_auth_events(
                 );
             }
 
-            ServerMessage::LoginError { reason } => {
-                warn!("Error while logging in: {}", reason);
+            ServerMessage::LoginError { code, detail } => {
+                warn!(
+                    "Error while logging in ({:?}): {}",
+                    code,
+                    code.describe(detail.as_deref())
+                );
+            }
+            ServerMessage::SessionResumed { replayed, .. } => {
+                info!("✓ Session resumed, {} missed message(s) replayed", replayed);
+            }
+            ServerMessage::ResumeFailed { code } => {
+                warn!("Could not resume session: {}", code.message());
+                connection.reset_auth();
+                next_app_state.set(AppState::Login);
+            }
+            ServerMessage::RateLimited { retry_after_ms } => {
+                warn!("Server is rate limiting us, retry in {} ms", retry_after_ms);
+            }
+            ServerMessage::NotAuthenticated => {
+                // Le serveur ne nous connaît plus : notre état local est périmé
+                warn!("Server rejected a gameplay message: session not authenticated");
+                connection.reset_auth();
+                next_app_state.set(AppState::Login);
             }
             ServerMessage::RegisterSuccess { message: msg } => {
                 info!("✓ Registration successful: {}", msg);
@@ -138,6 +187,22 @@ pub fn handle_auth_events(
             ServerMessage::RegisterError { reason } => {
                 warn!("Registration failed: {}", reason);
             }
+            ServerMessage::PasswordChanged => {
+                info!("✓ Password changed");
+            }
+            ServerMessage::AccountError { code, detail } => {
+                warn!(
+                    "Account operation failed ({:?}): {}",
+                    code,
+                    code.describe(detail.as_deref())
+                );
+            }
+            ServerMessage::AccountDeleted => {
+                info!("Account deleted, back to login");
+                connection.reset_auth();
+                *player_info = PlayerInfo::default();
+                next_app_state.set(AppState::Login);
+            }
             _ => {}
         }
     }
@@ -19,8 +19,8 @@ pub fn handle_debug_events(mut events: MessageReader<ServerEvent>) {
             ServerMessage::DebugOrganizationDeleted { organization_id } => {
                 info!("✓ Organization {} deleted", organization_id);
             }
-            ServerMessage::DebugError { reason } => {
-                warn!("Debug error: {}", reason);
+            ServerMessage::DebugError { code, detail } => {
+                warn!("Debug error ({:?}): {}", code, code.describe(detail.as_deref()));
             }
             ServerMessage::Pong => {}
             _ => {}
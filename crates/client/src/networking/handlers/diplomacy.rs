@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use shared::protocol::ServerMessage;
+
+use crate::networking::events::ServerEvent;
+use crate::state::resources::{DiplomacyCache, NotificationState, PlayerInfo};
+
+/// Handles diplomacy messages (relations, envoy proposals and answers).
+pub fn handle_diplomacy_events(
+    mut events: MessageReader<ServerEvent>,
+    player_info: Res<PlayerInfo>,
+    mut diplomacy_cache: Option<ResMut<DiplomacyCache>>,
+    mut notifications: ResMut<NotificationState>,
+) {
+    let own_id = player_info.organization.as_ref().map(|o| o.id);
+
+    for event in events.read() {
+        match &event.0 {
+            ServerMessage::DiplomacyData {
+                organization_id,
+                relations,
+                proposals,
+                organizations,
+            } => {
+                info!(
+                    "✓ Received {} diplomatic relations and {} proposals",
+                    relations.len(),
+                    proposals.len()
+                );
+                if let Some(ref mut diplomacy_cache) = diplomacy_cache {
+                    diplomacy_cache.set(
+                        *organization_id,
+                        relations.clone(),
+                        proposals.clone(),
+                        organizations.clone(),
+                    );
+                }
+            }
+
+            ServerMessage::DiplomaticProposalReceived { proposal } => {
+                if own_id == Some(proposal.target_organization_id) {
+                    notifications.push_info(format!(
+                        "Un émissaire de {} propose : {}",
+                        proposal.organization_name,
+                        proposal.relation_type.to_name()
+                    ));
+                } else {
+                    notifications.push_info(format!(
+                        "Votre émissaire a remis sa proposition à {}",
+                        proposal.target_name
+                    ));
+                }
+                if let Some(ref mut diplomacy_cache) = diplomacy_cache {
+                    diplomacy_cache.stale = true;
+                }
+            }
+
+            ServerMessage::DiplomaticProposalRejected { proposal } => {
+                if own_id == Some(proposal.organization_id) {
+                    notifications.push_error(format!(
+                        "{} refuse : {}",
+                        proposal.target_name,
+                        proposal.relation_type.to_name()
+                    ));
+                }
+                if let Some(ref mut diplomacy_cache) = diplomacy_cache {
+                    diplomacy_cache.stale = true;
+                }
+            }
+
+            ServerMessage::DiplomaticRelationChanged {
+                organization_id,
+                target_organization_id,
+                relation_type,
+            } => {
+                info!(
+                    "✓ Relation between {} and {} is now {:?}",
+                    organization_id, target_organization_id, relation_type
+                );
+                match relation_type {
+                    Some(relation_type) => notifications.push_success(format!(
+                        "Nouvelle relation diplomatique : {}",
+                        relation_type.to_name()
+                    )),
+                    None => notifications.push_info("Un traité est arrivé à échéance"),
+                }
+                if let Some(ref mut diplomacy_cache) = diplomacy_cache {
+                    diplomacy_cache.stale = true;
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
@@ -4,11 +4,13 @@ use shared::protocol::ServerMessage;
 use crate::networking::events::ServerEvent;
 use crate::state::resources::GameDataCache;
 use crate::state::resources::InventoryCache;
+use crate::state::resources::TreasuryCache;
 
 /// Handles inventory-related server messages.
 pub fn handle_inventory_events(
     mut events: MessageReader<ServerEvent>,
     mut cache: ResMut<InventoryCache>,
+    mut treasury: ResMut<TreasuryCache>,
     game_data: Res<GameDataCache>,
 ) {
     for event in events.read() {
@@ -53,6 +55,35 @@ pub fn handle_inventory_events(
                 );
             }
 
+            ServerMessage::EquipmentChanged {
+                unit_id,
+                equipment,
+                derived_stats,
+            } => {
+                cache.set_equipment(*unit_id, equipment.clone(), *derived_stats);
+                info!(
+                    "Equipment of unit {} changed ({} slots used, max HP {})",
+                    unit_id,
+                    equipment.len(),
+                    derived_stats.max_hp
+                );
+            }
+
+            ServerMessage::TreasuryData {
+                organization_id,
+                gold,
+                items,
+                can_withdraw,
+            } => {
+                treasury.set(*organization_id, *gold, items.clone(), *can_withdraw);
+                info!(
+                    "Received treasury of organization {} ({} gold, {} item types)",
+                    organization_id,
+                    gold,
+                    items.len()
+                );
+            }
+
             _ => {}
         }
     }
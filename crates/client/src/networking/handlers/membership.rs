@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+use shared::MembershipRequestKind;
+use shared::protocol::ServerMessage;
+
+use crate::networking::events::ServerEvent;
+use crate::state::resources::{MembersCache, NotificationState, PlayerInfo};
+
+/// Handles membership messages (invitations, applications, expulsions, officers).
+pub fn handle_membership_events(
+    mut events: MessageReader<ServerEvent>,
+    player_info: Res<PlayerInfo>,
+    mut members_cache: Option<ResMut<MembersCache>>,
+    mut notifications: ResMut<NotificationState>,
+) {
+    let lord_id = player_info.lord.as_ref().map(|lord| lord.id);
+
+    for event in events.read() {
+        match &event.0 {
+            ServerMessage::OrganizationMembersData {
+                organization_id,
+                members,
+                requests,
+                permissions,
+            } => {
+                info!(
+                    "✓ Received {} members and {} pending requests",
+                    members.len(),
+                    requests.len()
+                );
+                if let Some(ref mut members_cache) = members_cache {
+                    members_cache.set(
+                        *organization_id,
+                        members.clone(),
+                        requests.clone(),
+                        permissions.clone(),
+                    );
+                }
+            }
+
+            ServerMessage::MembershipRequestReceived { request } => {
+                let mine = lord_id == Some(request.unit_id);
+                match (request.kind, mine) {
+                    (MembershipRequestKind::Invitation, true) => notifications.push_info(format!(
+                        "{} vous invite à la rejoindre",
+                        request.organization_name
+                    )),
+                    (MembershipRequestKind::Invitation, false) => notifications.push_info(format!(
+                        "Invitation envoyée à {}",
+                        request.unit_name
+                    )),
+                    (MembershipRequestKind::Application, true) => notifications.push_info(format!(
+                        "Candidature envoyée à {}",
+                        request.organization_name
+                    )),
+                    (MembershipRequestKind::Application, false) => notifications.push_info(format!(
+                        "{} demande à rejoindre votre organisation",
+                        request.unit_name
+                    )),
+                }
+                if let Some(ref mut members_cache) = members_cache {
+                    members_cache.stale = true;
+                }
+            }
+
+            ServerMessage::MembershipRequestClosed { request_id } => {
+                info!("Membership request {} closed", request_id);
+                if let Some(ref mut members_cache) = members_cache {
+                    members_cache.stale = true;
+                }
+            }
+
+            ServerMessage::MembershipChanged {
+                organization_id,
+                unit_id,
+                member,
+            } => {
+                info!(
+                    "✓ Lord {} {} organization {}",
+                    unit_id,
+                    if *member { "joined" } else { "left" },
+                    organization_id
+                );
+                match (lord_id == Some(*unit_id), member) {
+                    (true, true) => notifications.push_success("Vous avez rejoint l'organisation"),
+                    (true, false) => notifications.push_info("Vous ne faites plus partie de l'organisation"),
+                    (false, true) => notifications.push_info("Un nouveau membre a rejoint l'organisation"),
+                    (false, false) => notifications.push_info("Un membre a quitté l'organisation"),
+                }
+                if let Some(ref mut members_cache) = members_cache {
+                    members_cache.stale = true;
+                }
+            }
+
+            ServerMessage::OfficerChanged { unit_id, role, .. } => {
+                if lord_id == Some(*unit_id) {
+                    match role {
+                        Some(role) => notifications
+                            .push_success(format!("Vous êtes nommé {}", role.to_name())),
+                        None => notifications.push_info("Vous avez été relevé de votre charge"),
+                    }
+                }
+                if let Some(ref mut members_cache) = members_cache {
+                    members_cache.stale = true;
+                }
+            }
+
+            ServerMessage::MembershipError { code, detail } => {
+                warn!("Membership refused ({:?})", code);
+                notifications.push_error(code.describe(detail.as_deref()));
+            }
+
+            _ => {}
+        }
+    }
+}
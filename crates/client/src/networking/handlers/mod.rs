@@ -5,7 +5,9 @@
 pub mod actions;
 pub mod auth;
 pub mod debug;
+pub mod diplomacy;
 pub mod inventory;
+pub mod membership;
 pub mod territory;
 pub mod units;
 pub mod world;
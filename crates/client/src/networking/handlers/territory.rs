@@ -5,15 +5,20 @@ use crate::networking::events::ServerEvent;
 use crate::rendering::territory::{
     TerritoryBorderCellsDebug, TerritoryBorderSdfCache, TerritoryContourCache,
 };
-use crate::state::resources::{CurrentOrganization, PlayerInfo};
+use crate::state::resources::{
+    CurrentOrganization, HierarchyCache, NotificationState, PlayerInfo,
+};
 
-/// Handles territory-related messages (contours, border SDF, border cells, organization at cell).
+/// Handles territory-related messages (contours, border SDF, border cells, organization at cell,
+/// settlement upgrades, vassalage).
 pub fn handle_territory_events(
     mut events: MessageReader<ServerEvent>,
     mut territory_border_cache: ResMut<TerritoryBorderSdfCache>,
     mut territory_contour_cache: ResMut<TerritoryContourCache>,
     mut player_info: ResMut<PlayerInfo>,
     mut current_organization: Option<ResMut<CurrentOrganization>>,
+    mut notifications: ResMut<NotificationState>,
+    mut hierarchy_cache: Option<ResMut<HierarchyCache>>,
     mut commands: Commands,
 ) {
     for event in events.read() {
@@ -42,11 +47,137 @@ pub fn handle_territory_events(
                 });
             }
 
-            ServerMessage::HamletFoundError { reason } => {
-                warn!("Failed to found hamlet: {}", reason);
+            ServerMessage::HamletFoundError { code, detail } => {
+                warn!(
+                    "Failed to found hamlet ({:?}): {}",
+                    code,
+                    code.describe(detail.as_deref())
+                );
                 // TODO: afficher l'erreur dans l'UI
             }
 
+            ServerMessage::OrganizationUpgraded {
+                organization_id,
+                organization_type,
+                name,
+            } => {
+                info!(
+                    "✓ Organization {} is now {} '{}'",
+                    organization_id,
+                    organization_type.to_string(),
+                    name
+                );
+
+                if let Some(ref mut organization) = player_info.organization
+                    && organization.id == *organization_id
+                {
+                    organization.organization_type = *organization_type;
+                    organization.name = name.clone();
+                    notifications.push_success(format!("{} s'élève au rang de {}", name, organization_type.to_string()));
+                }
+
+                // Le libellé de la cellule survolée suit le nouveau palier
+                if let Some(ref mut current_organization) = current_organization
+                    && let Some(ref mut organization) = current_organization.organization
+                    && organization.id == *organization_id
+                {
+                    organization.organization_type = *organization_type;
+                    organization.name = name.clone();
+                }
+            }
+
+            ServerMessage::OrganizationUpgradeError { code, detail } => {
+                let reason = code.describe(detail.as_deref());
+                warn!("Settlement upgrade refused ({:?}): {}", code, reason);
+                notifications.push_error(reason);
+            }
+
+            ServerMessage::OrganizationHierarchyData {
+                root_organization_id,
+                organizations,
+                hierarchy,
+                offers,
+            } => {
+                info!(
+                    "✓ Received hierarchy of {} organizations ({} pending offers)",
+                    organizations.len(),
+                    offers.len()
+                );
+                if let Some(ref mut hierarchy_cache) = hierarchy_cache {
+                    hierarchy_cache.set(
+                        *root_organization_id,
+                        organizations.clone(),
+                        hierarchy.clone(),
+                        offers.clone(),
+                    );
+                }
+            }
+
+            ServerMessage::VassalageOffered { offer } => {
+                let own_id = player_info.organization.as_ref().map(|o| o.id);
+                if own_id != Some(offer.offered_by_organization_id) {
+                    notifications.push_info(format!(
+                        "{} propose que {} devienne vassal de {}",
+                        if offer.offered_by_organization_id == offer.liege_organization_id {
+                            &offer.liege_name
+                        } else {
+                            &offer.vassal_name
+                        },
+                        offer.vassal_name,
+                        offer.liege_name
+                    ));
+                } else {
+                    notifications.push_info("Proposition de vassalité envoyée");
+                }
+                if let Some(ref mut hierarchy_cache) = hierarchy_cache {
+                    hierarchy_cache.stale = true;
+                }
+            }
+
+            ServerMessage::VassalageOfferClosed { offer_id } => {
+                info!("Vassalage offer {} closed", offer_id);
+                if let Some(ref mut hierarchy_cache) = hierarchy_cache {
+                    hierarchy_cache.stale = true;
+                }
+            }
+
+            ServerMessage::VassalageChanged {
+                vassal_organization_id,
+                liege_organization_id,
+            } => {
+                info!(
+                    "✓ Organization {} liege is now {:?}",
+                    vassal_organization_id, liege_organization_id
+                );
+                if liege_organization_id.is_some() {
+                    notifications.push_success("Un serment de vassalité a été prêté");
+                } else {
+                    notifications.push_info("Un lien de vassalité a été rompu");
+                }
+                if let Some(ref mut hierarchy_cache) = hierarchy_cache {
+                    hierarchy_cache.stale = true;
+                }
+            }
+
+            ServerMessage::VassalageError { code, detail } => {
+                let reason = code.describe(detail.as_deref());
+                warn!("Vassalage refused ({:?}): {}", code, reason);
+                notifications.push_error(reason);
+            }
+
+            ServerMessage::TributePaid {
+                vassal_organization_id,
+                liege_organization_id,
+                gold,
+            } => {
+                let own_id = player_info.organization.as_ref().map(|o| o.id);
+                if own_id == Some(*liege_organization_id) {
+                    notifications.push_success(format!("Un vassal vous verse un tribut de {} or", gold));
+                } else if own_id == Some(*vassal_organization_id) {
+                    notifications.push_info(format!("Tribut de {} or versé à votre suzerain", gold));
+                }
+            }
+
             ServerMessage::TerritoryContourUpdate { chunk_id, contours } => {
                 info!(
                     "✓ Received {} territory contours for chunk ({},{})",
@@ -1,18 +1,22 @@
 use bevy::prelude::*;
-use shared::protocol::ServerMessage;
+use shared::protocol::{DepartureReason, ServerMessage};
 
 use crate::networking::events::ServerEvent;
-use crate::state::resources::{PlayerInfo, UnitWorkState, UnitsCache, UnitsDataCache};
+use crate::state::resources::{
+    NotificationState, PlayerInfo, RoutinesCache, UnitWorkState, UnitsCache, UnitsDataCache,
+};
 use crate::ui::components::{SlotIndicator, SlotUnitPortrait};
 use crate::ui::systems::panels::InSlot;
 
-/// Handles unit-related messages (slot updates, debug spawns).
+/// Handles unit-related messages (slot updates, debug spawns, routines).
 pub fn handle_unit_events(
     mut events: MessageReader<ServerEvent>,
     mut units_cache: Option<ResMut<UnitsCache>>,
     mut units_data_cache: Option<ResMut<UnitsDataCache>>,
     mut unit_work_state: ResMut<UnitWorkState>,
+    mut routines_cache: Option<ResMut<RoutinesCache>>,
     mut player_info: ResMut<PlayerInfo>,
+    mut notifications: ResMut<NotificationState>,
     mut commands: Commands,
     unit_query: Query<(Entity, &InSlot, &SlotUnitPortrait)>,
     slot_query: Query<(Entity, &SlotIndicator)>,
@@ -188,7 +192,8 @@ pub fn handle_unit_events(
             ServerMessage::PopulationChanged {
                 organization_id,
                 new_population,
-                immigrant,
+                immigrant: _,
+                departure,
             } => {
                 info!(
                     "Population changed for org {}: now {} inhabitants",
@@ -205,6 +210,45 @@ pub fn handle_unit_events(
 
                 // L'unité immigrante est aussi envoyée via DebugUnitSpawned
                 // (qui est déjà géré par ce handler), donc pas besoin de la traiter ici
+
+                // Un villageois est parti (émigré ou mort de faim) : on l'oublie
+                if let Some(departure) = departure {
+                    let cell = units_data_cache
+                        .as_ref()
+                        .and_then(|cache| cache.get_unit(departure.unit_id))
+                        .map(|unit| unit.current_cell);
+                    if let Some(ref mut cache) = units_cache {
+                        if let Some(cell) = cell
+                            && let Some(slot) = cache.get_unit_slot(&cell, departure.unit_id)
+                        {
+                            cache.remove_unit_from_slot(cell, slot);
+                        }
+                        cache.remove_unit(departure.unit_id);
+                    }
+                    if let Some(ref mut cache) = units_data_cache {
+                        cache.remove_unit(departure.unit_id);
+                    }
+                    for (entity, _, portrait) in unit_query.iter() {
+                        if portrait.unit_id == departure.unit_id {
+                            commands.entity(entity).despawn();
+                        }
+                    }
+
+                    let name = format!("{} {}", departure.first_name, departure.last_name);
+                    match departure.reason {
+                        DepartureReason::Emigrated => notifications
+                            .push_info(format!("{} a quitté le hameau, faute de nourriture", name)),
+                        DepartureReason::Starved => {
+                            notifications.push_error(format!("{} est mort de faim", name))
+                        }
+                    }
+                }
+            }
+
+            ServerMessage::UnitRoutines { unit_id, routines } => {
+                if let Some(ref mut cache) = routines_cache {
+                    cache.set(*unit_id, routines.clone());
+                }
             }
 
             _ => {}
@@ -25,6 +25,8 @@ pub fn handle_world_events(
                 cell_data,
                 building_data,
                 unit_data,
+                ground_items,
+                resource_stocks,
             } => {
                 let Some(ref mut cache) = cache else { continue };
                 let Some(ref mut units_cache) = units_cache else {
@@ -69,6 +71,8 @@ pub fn handle_world_events(
 
                 cache.insert_cells(cell_data);
                 cache.insert_buildings(building_data);
+                cache.insert_ground_items(ground_items);
+                cache.insert_resource_stocks(resource_stocks);
 
                 for unit in unit_data {
                     let cell = unit.current_cell;
@@ -90,6 +94,22 @@ pub fn handle_world_events(
                 }
             }
 
+            ServerMessage::GroundItemsUpdate { cell, items, .. } => {
+                let Some(ref mut cache) = cache else { continue };
+                cache.set_cell_ground_items(*cell, items.clone());
+            }
+
+            ServerMessage::CellResourcesUpdate { cell, stocks, .. } => {
+                let Some(ref mut cache) = cache else { continue };
+                cache.set_cell_resource_stocks(*cell, stocks.clone());
+            }
+
+            ServerMessage::BuildingRemoved { cell, .. } => {
+                let Some(ref mut cache) = cache else { continue };
+                info!("Building at ({},{}) removed", cell.q, cell.r);
+                cache.remove_building(cell);
+            }
+
             ServerMessage::OceanData { ocean_data } => {
                 let Some(ref mut cache) = cache else { continue };
                 info!("✓ Received ocean data for world: {}", ocean_data.name);
@@ -31,6 +31,10 @@ impl Plugin for NetworkingPlugin {
                         .run_if(in_state(AppState::InGame)),
                     handlers::inventory::handle_inventory_events
                         .run_if(in_state(AppState::InGame)),
+                    handlers::diplomacy::handle_diplomacy_events
+                        .run_if(in_state(AppState::InGame)),
+                    handlers::membership::handle_membership_events
+                        .run_if(in_state(AppState::InGame)),
                     // 4. Debug — always active (lightweight)
                     handlers::debug::handle_debug_events,
                     // 5. Connection monitoring
@@ -41,7 +41,8 @@ pub fn poll_server(
 }
 
 /// Detects when the network connection drops and transitions back to Login.
-/// Also resets ConnectionStatus so the player must re-authenticate.
+/// Also resets ConnectionStatus so the player must re-authenticate,
+/// unless the network thread can still resume the session.
 pub fn detect_disconnection(
     network_client_opt: Option<Res<NetworkClient>>,
     mut connection: ResMut<ConnectionStatus>,
@@ -62,8 +63,13 @@ pub fn detect_disconnection(
         connection.connected = true;
     }
 
+    let can_resume = network_client_opt
+        .as_ref()
+        .is_some_and(|c| c.can_resume());
+
     // If we lost connection while InGame, go back to Login
-    if !connected && *app_state.get() == AppState::InGame && connection.logged_in {
+    // (sauf si la reconnexion en cours peut reprendre la session)
+    if !connected && *app_state.get() == AppState::InGame && connection.logged_in && !can_resume {
         warn!("Lost connection while in-game, returning to login screen");
         connection.reset_auth();
         next_app_state.set(AppState::Login);
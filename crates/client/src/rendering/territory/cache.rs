@@ -19,7 +19,7 @@ pub struct OrganizationContour {
 }
 
 impl TerritoryContourCache {
-    /// Add a contour to the cache
+    /// Add a contour to the cache, replacing the organization's previous contour in that chunk
     pub fn add_contour(
         &mut self,
         chunk_id: TerrainChunkId,
@@ -36,10 +36,12 @@ impl TerritoryContourCache {
             fill_color,
         };
 
-        self.contours
-            .entry(chunk_id)
-            .or_insert_with(Vec::new)
-            .push(contour);
+        let chunk_contours = self.contours.entry(chunk_id).or_insert_with(Vec::new);
+        chunk_contours.retain(|c| c.organization_id != organization_id);
+        // Un contour vide efface la frontière de l'organisation dans ce chunk
+        if !contour.segments.is_empty() {
+            chunk_contours.push(contour);
+        }
     }
 
     /// Get all contours for a specific chunk
@@ -50,6 +50,11 @@ fn init_world_resources(mut commands: Commands) {
     commands.insert_resource(resources::ActionTracker::default());
     commands.insert_resource(resources::CurrentOrganization::default());
     commands.insert_resource(resources::InventoryCache::default());
+    commands.insert_resource(resources::TreasuryCache::default());
+    commands.insert_resource(resources::HierarchyCache::default());
+    commands.insert_resource(resources::DiplomacyCache::default());
+    commands.insert_resource(resources::MembersCache::default());
+    commands.insert_resource(resources::RoutinesCache::default());
     commands.insert_resource(resources::UnitWorkState::default());
 }
 
@@ -60,5 +65,10 @@ fn cleanup_world_resources(mut commands: Commands) {
     commands.remove_resource::<resources::ActionTracker>();
     commands.remove_resource::<resources::CurrentOrganization>();
     commands.remove_resource::<resources::InventoryCache>();
+    commands.remove_resource::<resources::TreasuryCache>();
+    commands.remove_resource::<resources::HierarchyCache>();
+    commands.remove_resource::<resources::DiplomacyCache>();
+    commands.remove_resource::<resources::MembersCache>();
+    commands.remove_resource::<resources::RoutinesCache>();
     commands.remove_resource::<resources::UnitWorkState>();
 }
@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use shared::protocol::QueuedActionEntry;
 use shared::{ActionStatusEnum, ActionTypeEnum, TerrainChunkId, grid::GridCell};
 use std::collections::HashMap;
 
@@ -10,19 +11,29 @@ pub struct TrackedAction {
     pub cell: GridCell,
     pub action_type: ActionTypeEnum,
     pub status: ActionStatusEnum,
+    // Timestamps Unix en millisecondes, comme côté serveur
     pub start_time: u64,
     pub completion_time: u64,
+    // Moment où l'action a été mise en pause : la progression reste figée
+    pub paused_at: Option<u64>,
     pub action_name: Option<String>,
     pub unit_ids: Vec<u64>, 
 }
 
 impl TrackedAction {
-    /// Progress from 0.0 to 1.0 based on current time
+    /// Temps de référence pour la progression : maintenant, ou l'instant de la pause
+    pub fn effective_time(&self, current_time: u64) -> u64 {
+        self.paused_at.unwrap_or(current_time)
+    }
+
+    /// Progress from 0.0 to 1.0 based on current time (in ms)
     pub fn progress(&self, current_time: u64) -> f32 {
         if self.completion_time <= self.start_time {
             return 1.0;
         }
-        let elapsed = current_time.saturating_sub(self.start_time) as f32;
+        let elapsed = self
+            .effective_time(current_time)
+            .saturating_sub(self.start_time) as f32;
         let total = (self.completion_time - self.start_time) as f32;
         (elapsed / total).clamp(0.0, 1.0)
     }
@@ -36,6 +47,9 @@ pub struct ActionTracker {
 
     /// Index des actions par cellule pour un accès rapide
     actions_by_cell: HashMap<(TerrainChunkId, GridCell), Vec<u64>>,
+
+    /// Actions en attente de chaque unité, dans l'ordre d'exécution
+    queues: HashMap<u64, Vec<QueuedActionEntry>>,
 }
 
 impl ActionTracker {
@@ -43,9 +57,24 @@ impl ActionTracker {
         Self {
             actions: HashMap::new(),
             actions_by_cell: HashMap::new(),
+            queues: HashMap::new(),
+        }
+    }
+
+    /// Remplace la file d'actions d'une unité
+    pub fn set_queue(&mut self, unit_id: u64, entries: Vec<QueuedActionEntry>) {
+        if entries.is_empty() {
+            self.queues.remove(&unit_id);
+        } else {
+            self.queues.insert(unit_id, entries);
         }
     }
 
+    /// File d'actions d'une unité (vide si aucune)
+    pub fn queue_for(&self, unit_id: u64) -> &[QueuedActionEntry] {
+        self.queues.get(&unit_id).map(Vec::as_slice).unwrap_or_default()
+    }
+
     /// Ajoute ou met à jour une action
     pub fn update_action(&mut self, action: TrackedAction) {
         let cell_key = (action.chunk_id, action.cell);
@@ -106,14 +135,14 @@ impl ActionTracker {
         }
     }
 
-    /// Supprime les actions complétées plus anciennes qu'un certain temps
+    /// Supprime les actions complétées plus anciennes qu'un certain temps (`current_time` en ms)
     pub fn cleanup_completed_actions(&mut self, current_time: u64, retention_seconds: u64) {
         let mut to_remove = Vec::new();
 
         for (action_id, action) in self.actions.iter() {
             if action.status == ActionStatusEnum::Completed {
                 // Garder les actions complétées pendant retention_seconds secondes
-                if current_time > action.completion_time + retention_seconds {
+                if current_time > action.completion_time + retention_seconds * 1000 {
                     to_remove.push(*action_id);
                 }
             }
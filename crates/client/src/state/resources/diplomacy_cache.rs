@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use shared::{DiplomaticProposal, DiplomaticRelation, OrganizationSummary};
+
+/// Cache local des relations diplomatiques de l'organisation du joueur
+/// (source : `DiplomacyData`)
+#[derive(Resource, Default)]
+pub struct DiplomacyCache {
+    pub organization_id: Option<u64>,
+    pub relations: Vec<DiplomaticRelation>,
+    /// Propositions en attente, reçues ou envoyées
+    pub proposals: Vec<DiplomaticProposal>,
+    /// Organisations citées par les relations et les propositions
+    pub organizations: Vec<OrganizationSummary>,
+    /// Une relation ou une proposition a changé : les données doivent être redemandées
+    pub stale: bool,
+}
+
+impl DiplomacyCache {
+    pub fn set(
+        &mut self,
+        organization_id: u64,
+        relations: Vec<DiplomaticRelation>,
+        proposals: Vec<DiplomaticProposal>,
+        organizations: Vec<OrganizationSummary>,
+    ) {
+        self.organization_id = Some(organization_id);
+        self.relations = relations;
+        self.proposals = proposals;
+        self.organizations = organizations;
+        self.stale = false;
+    }
+
+    pub fn organization_name(&self, organization_id: u64) -> &str {
+        self.organizations
+            .iter()
+            .find(|o| o.id == organization_id)
+            .map(|o| o.name.as_str())
+            .unwrap_or("?")
+    }
+}
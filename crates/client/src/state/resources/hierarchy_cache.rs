@@ -0,0 +1,44 @@
+use bevy::prelude::*;
+use shared::{OrganizationHierarchy, OrganizationSummary, VassalageOffer};
+
+/// Cache local de l'arbre de vassalité de l'organisation du joueur
+/// (source : `OrganizationHierarchyData`)
+#[derive(Resource, Default)]
+pub struct HierarchyCache {
+    /// Plus haut suzerain de l'arbre
+    pub root_organization_id: Option<u64>,
+    pub organizations: Vec<OrganizationSummary>,
+    pub hierarchy: Vec<OrganizationHierarchy>,
+    /// Propositions en cours qui concernent l'organisation du joueur
+    pub offers: Vec<VassalageOffer>,
+    /// Un lien ou une proposition a changé : l'arbre doit être redemandé
+    pub stale: bool,
+}
+
+impl HierarchyCache {
+    pub fn set(
+        &mut self,
+        root_organization_id: u64,
+        organizations: Vec<OrganizationSummary>,
+        hierarchy: Vec<OrganizationHierarchy>,
+        offers: Vec<VassalageOffer>,
+    ) {
+        self.root_organization_id = Some(root_organization_id);
+        self.organizations = organizations;
+        self.hierarchy = hierarchy;
+        self.offers = offers;
+        self.stale = false;
+    }
+
+    pub fn organization(&self, organization_id: u64) -> Option<&OrganizationSummary> {
+        self.organizations.iter().find(|o| o.id == organization_id)
+    }
+
+    /// Suzerain direct d'une organisation de l'arbre
+    pub fn liege_of(&self, organization_id: u64) -> Option<u64> {
+        self.hierarchy
+            .iter()
+            .find(|node| node.organization_id == organization_id)
+            .and_then(|node| node.parent_id)
+    }
+}
@@ -1,5 +1,6 @@
 use bevy::prelude::*;
 use shared::protocol::InventoryItemData;
+use shared::{EquipmentSlotEnum, EquippedItem, UnitDerivedStats};
 use std::collections::HashMap;
 
 /// Cache local de l'inventaire du joueur
@@ -7,6 +8,10 @@ use std::collections::HashMap;
 pub struct InventoryCache {
     /// Inventaire par unit_id -> liste d'items
     pub inventories: HashMap<u64, Vec<InventoryItemData>>,
+    /// Équipement porté par unit_id (source : `EquipmentChanged`)
+    pub equipment: HashMap<u64, Vec<EquippedItem>>,
+    /// Stats dérivées reçues avec le dernier changement d'équipement
+    pub derived_stats: HashMap<u64, UnitDerivedStats>,
     /// Flag pour savoir si on a déjà demandé l'inventaire
     pub requested: bool,
 }
@@ -103,6 +108,33 @@ impl InventoryCache {
         }
     }
 
+    /// Remplace l'équipement d'une unité et marque les items portés
+    pub fn set_equipment(
+        &mut self,
+        unit_id: u64,
+        equipment: Vec<EquippedItem>,
+        derived_stats: UnitDerivedStats,
+    ) {
+        if let Some(items) = self.inventories.get_mut(&unit_id) {
+            for item in items.iter_mut() {
+                let equipped = equipment.iter().find(|e| e.item_id == item.item_id);
+                item.is_equipped = equipped.is_some();
+                item.equipment_slot = equipped.map(|e| e.slot);
+            }
+        }
+        self.equipment.insert(unit_id, equipment);
+        self.derived_stats.insert(unit_id, derived_stats);
+    }
+
+    /// Item porté dans un slot donné
+    pub fn equipped_in(&self, unit_id: u64, slot: EquipmentSlotEnum) -> Option<i32> {
+        self.equipment
+            .get(&unit_id)?
+            .iter()
+            .find(|e| e.slot == slot)
+            .map(|e| e.item_id)
+    }
+
     pub fn get_inventory(&self, unit_id: u64) -> Option<&Vec<InventoryItemData>> {
         self.inventories.get(&unit_id)
     }
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use shared::{MembershipRequest, OrganizationMemberSummary, OrganizationPermission};
+
+/// Cache local des membres de l'organisation du joueur et des invitations
+/// ou candidatures en attente (source : `OrganizationMembersData`)
+#[derive(Resource, Default)]
+pub struct MembersCache {
+    pub organization_id: Option<u64>,
+    pub members: Vec<OrganizationMemberSummary>,
+    pub requests: Vec<MembershipRequest>,
+    /// Droits que le rang du joueur lui donne dans son organisation
+    pub permissions: Vec<OrganizationPermission>,
+    pub loaded: bool,
+    /// Un membre, une charge ou une demande a changé : les données doivent être redemandées
+    pub stale: bool,
+}
+
+impl MembersCache {
+    pub fn set(
+        &mut self,
+        organization_id: Option<u64>,
+        members: Vec<OrganizationMemberSummary>,
+        requests: Vec<MembershipRequest>,
+        permissions: Vec<OrganizationPermission>,
+    ) {
+        self.organization_id = organization_id;
+        self.members = members;
+        self.requests = requests;
+        self.permissions = permissions;
+        self.loaded = true;
+        self.stale = false;
+    }
+
+    pub fn can(&self, permission: OrganizationPermission) -> bool {
+        self.permissions.contains(&permission)
+    }
+}
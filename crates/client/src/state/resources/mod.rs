@@ -2,15 +2,20 @@ mod action_tracker;
 mod building_atlas;
 mod connection_status;
 mod current_organization;
+mod diplomacy_cache;
 mod game_data_cache;
 mod game_time_config;
 mod gauge_atlas;
+mod hierarchy_cache;
 mod inventory_cache;
+mod members_cache;
 mod moon_atlas;
 mod notification_state;
 mod player_info;
+mod routines_cache;
 mod streaming_config;
 mod tree_atlas;
+mod treasury_cache;
 mod unit_work_state;
 mod units_cache;
 mod units_data_cache;
@@ -20,15 +25,20 @@ pub use action_tracker::{ActionTracker, TrackedAction};
 pub use building_atlas::setup_building_atlas;
 pub use connection_status::ConnectionStatus;
 pub use current_organization::CurrentOrganization;
+pub use diplomacy_cache::DiplomacyCache;
 pub use game_data_cache::GameDataCache;
 pub use game_time_config::GameTimeConfig;
 pub use gauge_atlas::setup_gauge_atlas;
+pub use hierarchy_cache::HierarchyCache;
 pub use inventory_cache::InventoryCache;
+pub use members_cache::MembersCache;
 pub use moon_atlas::setup_moon_atlas;
 pub use notification_state::{NotificationKind, NotificationState};
 pub use player_info::PlayerInfo;
+pub use routines_cache::RoutinesCache;
 pub use streaming_config::StreamingConfig;
 pub use tree_atlas::setup_tree_atlas;
+pub use treasury_cache::TreasuryCache;
 pub use unit_work_state::UnitWorkState;
 pub use units_cache::UnitsCache;
 pub use units_data_cache::UnitsDataCache;
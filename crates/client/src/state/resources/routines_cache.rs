@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use shared::AutomatedAction;
+use std::collections::HashMap;
+
+/// Routines des unités PNJ du joueur (source : `UnitRoutines`)
+#[derive(Resource, Default)]
+pub struct RoutinesCache {
+    routines: HashMap<u64, Vec<AutomatedAction>>,
+}
+
+impl RoutinesCache {
+    pub fn set(&mut self, unit_id: u64, routines: Vec<AutomatedAction>) {
+        self.routines.insert(unit_id, routines);
+    }
+
+    pub fn for_unit(&self, unit_id: u64) -> &[AutomatedAction] {
+        self.routines.get(&unit_id).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
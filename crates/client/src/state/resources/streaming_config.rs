@@ -1,4 +1,5 @@
 use bevy::prelude::*;
+use shared::TerrainChunkId;
 
 #[derive(Resource)]
 pub struct StreamingConfig {
@@ -6,7 +7,9 @@ pub struct StreamingConfig {
     pub unload_distance: i32,
     pub request_cooldown: f32,
     pub request_timeout: f32,
-    pub last_request: f32
+    pub last_request: f32,
+    /// Last camera chunk reported to the server with `UpdateInterestArea`
+    pub last_interest_center: Option<TerrainChunkId>,
 }
 
 impl Default for StreamingConfig {
@@ -17,6 +20,7 @@ impl Default for StreamingConfig {
             request_cooldown: 0.3,
             request_timeout: 3.0,
             last_request: -999.0,
+            last_interest_center: None,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,29 @@
+use bevy::prelude::*;
+use shared::protocol::TreasuryItemNet;
+
+/// Cache local du trésor de l'organisation du joueur (source : `TreasuryData`)
+#[derive(Resource, Default)]
+pub struct TreasuryCache {
+    /// Organisation dont on a reçu le trésor
+    pub organization_id: Option<u64>,
+    pub gold: i32,
+    /// Stock regroupé par item
+    pub items: Vec<TreasuryItemNet>,
+    /// Le lord du joueur peut retirer des objets
+    pub can_withdraw: bool,
+}
+
+impl TreasuryCache {
+    pub fn set(
+        &mut self,
+        organization_id: u64,
+        gold: i32,
+        items: Vec<TreasuryItemNet>,
+        can_withdraw: bool,
+    ) {
+        self.organization_id = Some(organization_id);
+        self.gold = gold;
+        self.items = items;
+        self.can_withdraw = can_withdraw;
+    }
+}
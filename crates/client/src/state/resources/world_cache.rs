@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 use shared::{
-    BiomeChunkData, BiomeChunkId, BuildingData, LakeData, OceanData, TerrainChunkData,
+    BiomeChunkData, BiomeChunkId, BuildingData, CellResourceStock, GroundItem, LakeData, OceanData, TerrainChunkData,
     TerrainChunkId, TerrainGlobalData,
     grid::{CellData, GridCell},
 };
@@ -11,6 +11,8 @@ pub struct WorldCache {
     terrains: TerrainCache,
     biomes: BiomeCache,
     cells: CellCache,
+    ground_items: GroundItemCache,
+    resource_stocks: ResourceStockCache,
     buildings: BuildingCache,
     ocean: OceanCache,
     lake: LakeCache,
@@ -142,6 +144,64 @@ impl CellCache {
     }
 }
 
+/// Objets posés au sol, par cellule
+#[derive(Default, Clone)]
+pub struct GroundItemCache {
+    loaded: HashMap<GridCell, Vec<GroundItem>>,
+}
+
+impl GroundItemCache {
+    pub fn insert_ground_items(&mut self, items: &[GroundItem]) {
+        items.iter().for_each(|item| {
+            let cell_items = self.loaded.entry(item.cell).or_default();
+            cell_items.retain(|existing| existing.item_id != item.item_id);
+            cell_items.push(*item);
+        });
+    }
+
+    /// Remplace tous les objets au sol d'une cellule (`GroundItemsUpdate`)
+    pub fn set_cell_ground_items(&mut self, cell: GridCell, items: Vec<GroundItem>) {
+        if items.is_empty() {
+            self.loaded.remove(&cell);
+        } else {
+            self.loaded.insert(cell, items);
+        }
+    }
+
+    pub fn get_ground_items(&self, cell: &GridCell) -> &[GroundItem] {
+        self.loaded.get(cell).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// Réserves naturelles restantes, par cellule
+#[derive(Default, Clone)]
+pub struct ResourceStockCache {
+    loaded: HashMap<GridCell, Vec<CellResourceStock>>,
+}
+
+impl ResourceStockCache {
+    pub fn insert_resource_stocks(&mut self, stocks: &[CellResourceStock]) {
+        stocks.iter().for_each(|stock| {
+            let cell_stocks = self.loaded.entry(stock.cell).or_default();
+            cell_stocks.retain(|existing| existing.resource_specific_type != stock.resource_specific_type);
+            cell_stocks.push(*stock);
+        });
+    }
+
+    /// Remplace toutes les réserves d'une cellule (`CellResourcesUpdate`)
+    pub fn set_cell_resource_stocks(&mut self, cell: GridCell, stocks: Vec<CellResourceStock>) {
+        if stocks.is_empty() {
+            self.loaded.remove(&cell);
+        } else {
+            self.loaded.insert(cell, stocks);
+        }
+    }
+
+    pub fn get_resource_stocks(&self, cell: &GridCell) -> &[CellResourceStock] {
+        self.loaded.get(cell).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
 #[derive(Default, Clone)]
 pub struct BuildingCache {
     loaded: HashMap<GridCell, BuildingData>,
@@ -166,6 +226,13 @@ impl BuildingCache {
         self.loaded.get(cell)
     }
 
+    /// Retire un bâtiment disparu (arbre abattu) et relance le rendu
+    pub fn remove_building(&mut self, cell: &GridCell) {
+        if self.loaded.remove(cell).is_some() {
+            self.dirty = true;
+        }
+    }
+
     pub fn unload_distant(
         &mut self,
         center: &TerrainChunkId,
@@ -560,6 +627,32 @@ impl WorldCache {
         self.cells.get_cell(cell)
     }
 
+    // GROUND ITEMS
+    pub fn insert_ground_items(&mut self, items: &[GroundItem]) {
+        self.ground_items.insert_ground_items(items);
+    }
+
+    pub fn set_cell_ground_items(&mut self, cell: GridCell, items: Vec<GroundItem>) {
+        self.ground_items.set_cell_ground_items(cell, items);
+    }
+
+    pub fn get_ground_items(&self, cell: &GridCell) -> &[GroundItem] {
+        self.ground_items.get_ground_items(cell)
+    }
+
+    // RESOURCE STOCKS
+    pub fn insert_resource_stocks(&mut self, stocks: &[CellResourceStock]) {
+        self.resource_stocks.insert_resource_stocks(stocks);
+    }
+
+    pub fn set_cell_resource_stocks(&mut self, cell: GridCell, stocks: Vec<CellResourceStock>) {
+        self.resource_stocks.set_cell_resource_stocks(cell, stocks);
+    }
+
+    pub fn get_resource_stocks(&self, cell: &GridCell) -> &[CellResourceStock] {
+        self.resource_stocks.get_resource_stocks(cell)
+    }
+
     // BUILDINGS
     pub fn insert_buildings(&mut self, buildings: &[BuildingData]) {
         self.buildings.insert_buildings(buildings);
@@ -573,6 +666,10 @@ impl WorldCache {
         self.buildings.get_building(cell)
     }
 
+    pub fn remove_building(&mut self, cell: &GridCell) {
+        self.buildings.remove_building(cell);
+    }
+
     pub fn unload_distant_building(
         &mut self,
         center: &TerrainChunkId,
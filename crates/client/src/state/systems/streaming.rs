@@ -104,6 +104,16 @@ pub fn request_chunks_around_camera(
         dx * dx + dy * dy
     });
 
+    // Keep the server's view of our loaded area in sync so it only sends us
+    // updates for chunks we can see
+    if streaming_config.last_interest_center != Some(*terrain_chunk_id) || !to_request.is_empty() {
+        network_client.send_message(shared::protocol::ClientMessage::UpdateInterestArea {
+            center_chunk: *terrain_chunk_id,
+            unload_distance: streaming_config.unload_distance,
+        });
+        streaming_config.last_interest_center = Some(*terrain_chunk_id);
+    }
+
     if !to_request.is_empty() {
         info!("Requesting {} chunks", to_request.len());
         network_client.send_message(shared::protocol::ClientMessage::RequestTerrainChunks {
@@ -138,6 +138,26 @@ impl Plugin for UiPlugin {
                 OnEnter(GameView::CityManagement),
                 systems::panels::setup_management_panel,
             )
+            .add_systems(
+                Update,
+                (
+                    systems::panels::update_treasury_panel,
+                    systems::panels::update_treasury_deposit_list,
+                    systems::panels::handle_treasury_buttons,
+                    systems::panels::handle_settlement_upgrade_button,
+                    systems::panels::refresh_hierarchy,
+                    systems::panels::update_hierarchy_list,
+                    systems::panels::handle_vassalage_buttons,
+                    systems::panels::update_management_tabs,
+                    systems::panels::refresh_diplomacy,
+                    systems::panels::update_diplomacy_list,
+                    systems::panels::handle_diplomacy_buttons,
+                    systems::panels::refresh_members,
+                    systems::panels::update_members_list,
+                    systems::panels::handle_membership_buttons,
+                )
+                    .run_if(in_state(GameView::CityManagement)),
+            )
             .add_systems(
                 OnEnter(GameView::Messages),
                 systems::panels::setup_messages_panel,
@@ -160,7 +180,14 @@ impl Plugin for UiPlugin {
             )
             .add_systems(
                 Update,
-                systems::panels::update_inventory_panel.run_if(in_state(GameView::Inventory)),
+                (
+                    systems::panels::update_inventory_panel,
+                    systems::panels::update_unit_item_lists,
+                    systems::panels::update_ground_items_list,
+                    systems::panels::handle_equipment_buttons,
+                    systems::panels::handle_pick_up_buttons,
+                )
+                    .run_if(in_state(GameView::Inventory)),
             )
             // ─── Pause menu overlay ────────────────────────────────────
             .add_systems(
@@ -221,6 +248,8 @@ impl Plugin for UiPlugin {
                         .before(systems::panels::update_unit_portraits),
                     systems::panels::sync_slot_hierarchy_on_relation_change,
                     systems::panels::sync_slot_visuals,
+                    systems::panels::update_cell_ground_items,
+                    systems::panels::update_cell_resource_stocks,
                     systems::panels::auto_assign_unslotted_units
                         .run_if(resource_exists::<CellState>.and(resource_changed::<CellState>)),
                 )
@@ -243,7 +272,9 @@ impl Plugin for UiPlugin {
                     systems::update_panel_visibility,
                     systems::update_tab_badge,
                     systems::handle_tab_click,
+                    systems::request_selected_unit_queues,
                     systems::update_panel_content,
+                    systems::handle_routine_buttons,
                     systems::collapse_on_deselect,
                 )
                     .run_if(in_state(GameView::Cell)),
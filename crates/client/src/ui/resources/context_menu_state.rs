@@ -7,6 +7,7 @@ use shared::TerrainChunkId;
 pub enum ContextMenuAction {
     Move,
     Found,
+    Claim,
     Build(shared::BuildingTypeEnum),
     // Futures actions :
     // Harvest,
@@ -17,6 +18,7 @@ impl ContextMenuAction {
         match self {
             Self::Move => "Déplacer",
             Self::Found => "Fonder un hameau",
+            Self::Claim => "Revendiquer",
             Self::Build(bt) => bt.to_name_lowercase(),
         }
     }
@@ -25,6 +27,7 @@ impl ContextMenuAction {
         match self {
             Self::Move => "➤",
             Self::Found => "⛫",
+            Self::Claim => "⚑",
             Self::Build(_) => "🔨",
         }
     }
@@ -5,6 +5,8 @@ use shared::SlotPosition;
 pub struct DragState {
     pub active: Option<DragInfo>,
     pub hovered_slot: Option<Entity>,
+    /// Pile d'objets glissée depuis le panneau d'inventaire
+    pub item: Option<ItemDragInfo>,
 }
 
 pub struct DragInfo {
@@ -12,4 +14,10 @@ pub struct DragInfo {
     pub unit_entity: Entity,
     pub source_position: SlotPosition,
     pub origin: Vec2,
-}
\ No newline at end of file
+}
+
+pub struct ItemDragInfo {
+    pub unit_id: u64,
+    pub item_id: i32,
+    pub quantity: i32,
+}
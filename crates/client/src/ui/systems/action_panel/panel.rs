@@ -1021,6 +1021,10 @@ pub fn handle_execute_button(
     unit_selection: Res<crate::ui::resources::UnitSelectionState>,
     grid_config: Res<shared::grid::GridConfig>,
     selected_hexes: Res<crate::grid::resources::SelectedHexes>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    units_data_cache: Option<Res<crate::state::resources::UnitsDataCache>>,
+    current_organization: Option<Res<crate::state::resources::CurrentOrganization>>,
+    mut notifications: ResMut<crate::state::resources::NotificationState>,
     mut selection: ResMut<ActionSelectionState>,
 ) {
     for (interaction, button) in &button_query {
@@ -1037,10 +1041,10 @@ pub fn handle_execute_button(
             warn!("Cannot execute action: not logged in");
             return;
         }
-        let Some(player_id) = connection.player_id else {
+        if connection.player_id.is_none() {
             warn!("Cannot execute action: no player ID");
             return;
-        };
+        }
         let Some(network_client) = network_client_opt.as_mut() else {
             warn!("Cannot execute action: no network client");
             return;
@@ -1098,7 +1102,6 @@ pub fn handle_execute_button(
                 "theater" => shared::BuildingTypeEnum::Theater,
                 "road_segment" => {
                     network_client.send_message(shared::protocol::ClientMessage::ActionBuildRoad {
-                        player_id,
                         start_cell: cell,
                         end_cell: cell,
                     });
@@ -1111,7 +1114,6 @@ pub fn handle_execute_button(
                 }
             };
             network_client.send_message(shared::protocol::ClientMessage::ActionBuildBuilding {
-                player_id,
                 chunk_id,
                 cell,
                 building_type,
@@ -1126,14 +1128,33 @@ pub fn handle_execute_button(
             let start = shared::grid::GridCell::from_hex(&hexes[0]);
             let end = shared::grid::GridCell::from_hex(hexes.last().unwrap());
             network_client.send_message(shared::protocol::ClientMessage::ActionBuildRoad {
-                player_id,
                 start_cell: start,
                 end_cell: end,
             });
+        } else if let Some(recipe_id) = action_id.strip_prefix("produce_")
+            && keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight])
+        {
+            // Maj : la production devient une routine des PNJ sélectionnés
+            let routine = shared::Routine::Craft {
+                recipe_id: recipe_id.to_string(),
+                max_stock: shared::DEFAULT_ROUTINE_MAX_STOCK,
+            };
+            for &unit_id in unit_selection.selected_ids() {
+                let is_npc = units_data_cache
+                    .as_ref()
+                    .and_then(|cache| cache.get_unit(unit_id))
+                    .is_some_and(|unit| unit.is_npc());
+                if is_npc {
+                    network_client.send_message(shared::protocol::ClientMessage::SaveUnitRoutine {
+                        unit_id,
+                        routine: routine.to_automated_action(0, true),
+                    });
+                }
+            }
+            info!("✓ Production routine {} request sent", recipe_id);
         } else if let Some(recipe_id) = action_id.strip_prefix("produce_") {
             let unit_ids: Vec<u64> = unit_selection.selected_ids().to_vec();
             network_client.send_message(shared::protocol::ClientMessage::ActionCraftResource {
-                player_id,
                 chunk_id,
                 cell,
                 recipe_id: recipe_id.to_string(),
@@ -1144,7 +1165,6 @@ pub fn handle_execute_button(
         } else if let Some(recipe_id) = action_id.strip_prefix("harvest_") {
             // let unit_ids: Vec<u64> = unit_selection.selected_ids().to_vec();
             // network_client.send_message(shared::protocol::ClientMessage::ActionHarvestResource {
-            //     player_id,
             //     chunk_id,
             //     cell,
             //     unit_ids,
@@ -1175,7 +1195,6 @@ pub fn handle_execute_button(
             };
             if let Some(&unit_id) = unit_selection.selected_ids().first() {
                 network_client.send_message(shared::protocol::ClientMessage::ActionTrainUnit {
-                    player_id,
                     unit_id,
                     chunk_id,
                     cell,
@@ -1190,10 +1209,34 @@ pub fn handle_execute_button(
             }
         } else if action_id.starts_with("upgrade_") {
             info!("Upgrade action: {} (not yet implemented)", action_id);
-        } else if matches!(
-            action_id.as_str(),
-            "send_envoy" | "propose_trade" | "research"
-        ) {
+        } else if matches!(action_id.as_str(), "send_envoy" | "propose_trade") {
+            // L'émissaire part vers l'organisation de la cellule
+            let target = current_organization
+                .as_ref()
+                .and_then(|current| current.organization.as_ref());
+            match (unit_selection.selected_ids().first(), target) {
+                (Some(&unit_id), Some(target)) => {
+                    let relation_type = if action_id == "propose_trade" {
+                        shared::DiplomaticRelationType::TradeAgreement
+                    } else {
+                        shared::DiplomaticRelationType::NonAggression
+                    };
+                    network_client.send_message(
+                        shared::protocol::ClientMessage::ActionSendEnvoy {
+                            unit_id,
+                            target_organization_id: target.id,
+                            mission: shared::EnvoyMission::Propose {
+                                relation_type,
+                                duration_days: Some(shared::DEFAULT_TREATY_DAYS),
+                            },
+                        },
+                    );
+                    info!("✓ Envoy {} sent to {} ({:?})", unit_id, target.name, relation_type);
+                }
+                (None, _) => warn!("No unit selected as envoy"),
+                (_, None) => notifications.push_error("Aucune organisation à qui envoyer un émissaire"),
+            }
+        } else if action_id == "research" {
             info!("Diplomacy action: {} (not yet implemented)", action_id);
         } else {
             warn!("Unknown action: {}", action_id);
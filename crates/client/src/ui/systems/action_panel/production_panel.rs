@@ -227,14 +227,16 @@ pub fn update_production_panel(
 
     let chunk_id = cell_data.chunk;
 
-    // Get active actions on this cell (Pending + InProgress only)
+    // Get active actions on this cell (Pending + InProgress + Paused only)
     let mut active_actions: Vec<&TrackedAction> = action_tracker
         .get_actions_on_cell(&chunk_id, &cell)
         .into_iter()
         .filter(|a| {
             matches!(
                 a.status,
-                ActionStatusEnum::Pending | ActionStatusEnum::InProgress
+                ActionStatusEnum::Pending
+                    | ActionStatusEnum::InProgress
+                    | ActionStatusEnum::Paused
             )
         })
         .collect();
@@ -243,7 +245,7 @@ pub fn update_production_panel(
     let current_time = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
-        .as_secs();
+        .as_millis() as u64;
 
     for (mut slot_entry, children) in slot_query.iter_mut() {
         let action = active_actions.get(slot_entry.slot_index);
@@ -253,7 +255,10 @@ pub fn update_production_panel(
             // Update text
             if let Ok((mut text, mut color)) = text_query.get_mut(child) {
                 if let Some(action) = action {
-                    let remaining = action.completion_time.saturating_sub(current_time);
+                    let remaining = action
+                        .completion_time
+                        .saturating_sub(action.effective_time(current_time))
+                        .div_ceil(1000);
                     let minutes = remaining / 60;
                     let seconds = remaining % 60;
 
@@ -275,6 +280,7 @@ pub fn update_production_panel(
                     *color = match action.status {
                         ActionStatusEnum::InProgress => TextColor(Color::srgb_u8(200, 220, 255)),
                         ActionStatusEnum::Pending => TextColor(Color::srgb_u8(255, 220, 150)),
+                        ActionStatusEnum::Paused => TextColor(Color::srgb_u8(190, 190, 190)),
                         _ => TextColor(Color::srgba_u8(180, 170, 150, 180)),
                     };
                 } else {
@@ -291,6 +297,7 @@ pub fn update_production_panel(
                     *bg = BackgroundColor(match action.status {
                         ActionStatusEnum::InProgress => Color::srgba_u8(50, 150, 255, 200),
                         ActionStatusEnum::Pending => Color::srgba_u8(255, 200, 50, 180),
+                        ActionStatusEnum::Paused => Color::srgba_u8(160, 160, 160, 180),
                         _ => Color::srgba_u8(80, 220, 80, 200),
                     });
                 } else {
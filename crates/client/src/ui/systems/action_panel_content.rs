@@ -578,10 +578,10 @@ fn execute_action(
         return;
     }
 
-    let Some(player_id) = connection.player_id else {
+    if connection.player_id.is_none() {
         warn!("Cannot execute action: no player ID");
         return;
-    };
+    }
 
     let Some(network_client) = network_client_opt.as_mut() else {
         warn!("Cannot execute action: no network client");
@@ -643,7 +643,6 @@ fn execute_action(
 
                 // Send construction request to server
                 network_client.send_message(shared::protocol::ClientMessage::ActionBuildBuilding {
-                    player_id,
                     chunk_id,
                     cell,
                     building_type,
@@ -678,7 +677,6 @@ fn execute_action(
             info!("Building road from {:?} to {:?}", start_cell, end_cell);
 
             network_client.send_message(shared::protocol::ClientMessage::ActionBuildRoad {
-                player_id,
                 start_cell,
                 end_cell,
             });
@@ -54,13 +54,12 @@ pub fn update_cell_action_display(
                 let current_time = std::time::SystemTime::now()
                     .duration_since(std::time::UNIX_EPOCH)
                     .unwrap()
-                    .as_secs();
+                    .as_millis() as u64;
 
-                let remaining_seconds = if current_time < action.completion_time {
-                    action.completion_time - current_time
-                } else {
-                    0
-                };
+                let remaining_seconds = action
+                    .completion_time
+                    .saturating_sub(current_time)
+                    .div_ceil(1000);
 
                 let minutes = remaining_seconds / 60;
                 let seconds = remaining_seconds % 60;
@@ -69,6 +68,8 @@ pub fn update_cell_action_display(
             }
             ActionStatusEnum::Completed => "✓ Terminée".to_string(),
             ActionStatusEnum::Failed => "✗ Échouée".to_string(),
+            ActionStatusEnum::Paused => "⏸ En pause".to_string(),
+            ActionStatusEnum::Cancelled => "✗ Annulée".to_string(),
         };
 
         let action_type_text = &format!("{:?}", action.action_type);
@@ -1,9 +1,12 @@
-use crate::state::resources::UnitsDataCache;
+use crate::networking::client::NetworkClient;
+use crate::state::resources::{ActionTracker, GameDataCache, RoutinesCache, UnitsDataCache};
 use crate::states::GameView;
 use crate::ui::resources::UnitSelectionState;
+use bevy::ecs::relationship::RelatedSpawnerCommands;
 use bevy::prelude::*;
 use bevy::state::state_scoped::DespawnOnExit;
-use shared::ProfessionEnum;
+use shared::protocol::{ClientMessage, QueuedAction};
+use shared::{AutomatedAction, ProfessionEnum, ResourceSpecificTypeEnum, Routine};
 
 // ─── Marker components (local to this module) ───────────────
 
@@ -33,6 +36,34 @@ pub struct UnitDetailsListItem {
     pub unit_id: u64,
 }
 
+/// Turns a routine on or off.
+#[derive(Component)]
+pub struct RoutineToggleButton {
+    pub unit_id: u64,
+    pub routine: AutomatedAction,
+}
+
+/// Raises or lowers the stock at which a routine stops.
+#[derive(Component)]
+pub struct RoutineStockButton {
+    pub unit_id: u64,
+    pub routine: AutomatedAction,
+    pub delta: i32,
+}
+
+#[derive(Component)]
+pub struct RoutineDeleteButton {
+    pub unit_id: u64,
+    pub routine_id: u64,
+}
+
+/// Adds a harvest routine, run where the unit stands.
+#[derive(Component)]
+pub struct RoutineAddHarvestButton {
+    pub unit_id: u64,
+    pub resource_specific_type: ResourceSpecificTypeEnum,
+}
+
 /// Tracks whether the panel is expanded.
 #[derive(Component)]
 pub struct UnitDetailsPanelState {
@@ -234,16 +265,48 @@ pub fn handle_tab_click(
     }
 }
 
+/// Ask the server for the action queue (and routines, for NPCs) of newly selected units.
+pub fn request_selected_unit_queues(
+    unit_selection: Res<UnitSelectionState>,
+    units_data_cache: Res<UnitsDataCache>,
+    mut network_client: Option<ResMut<NetworkClient>>,
+) {
+    if !unit_selection.is_changed() {
+        return;
+    }
+    let Some(ref mut client) = network_client else {
+        return;
+    };
+    for &unit_id in unit_selection.selected_ids() {
+        client.send_message(ClientMessage::RequestActionQueue { unit_id });
+        if units_data_cache
+            .get_unit(unit_id)
+            .is_some_and(|unit| unit.is_npc())
+        {
+            client.send_message(ClientMessage::RequestUnitRoutines { unit_id });
+        }
+    }
+}
+
 /// Rebuild the unit list when selection or data changes.
 pub fn update_panel_content(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     unit_selection: Res<UnitSelectionState>,
     units_data_cache: Res<UnitsDataCache>,
+    action_tracker: Option<Res<ActionTracker>>,
+    routines_cache: Option<Res<RoutinesCache>>,
+    game_data: Option<Res<GameDataCache>>,
     list_container: Query<Entity, With<UnitDetailsListContainer>>,
     existing_items: Query<Entity, With<UnitDetailsListItem>>,
 ) {
-    if !unit_selection.is_changed() && !units_data_cache.is_changed() {
+    let queues_changed = action_tracker.as_ref().is_some_and(|t| t.is_changed());
+    let routines_changed = routines_cache.as_ref().is_some_and(|r| r.is_changed());
+    if !unit_selection.is_changed()
+        && !units_data_cache.is_changed()
+        && !queues_changed
+        && !routines_changed
+    {
         return;
     }
 
@@ -262,6 +325,11 @@ pub fn update_panel_content(
             continue;
         };
 
+        let queue = action_tracker
+            .as_deref()
+            .map(|t| t.queue_for(unit_id))
+            .unwrap_or_default();
+
         let avatar_path = unit_data
             .avatar_url
             .as_deref()
@@ -379,6 +447,31 @@ pub fn update_panel_content(
                             },
                             TextColor(Color::srgb_u8(140, 130, 80)),
                         ));
+
+                        // Queued actions, in execution order
+                        for (index, entry) in queue.iter().enumerate() {
+                            col.spawn((
+                                Text::new(format!(
+                                    "{}. {}",
+                                    index + 1,
+                                    queued_action_label(&entry.action)
+                                )),
+                                TextFont {
+                                    font_size: 10.0,
+                                    ..default()
+                                },
+                                TextColor(Color::srgb_u8(90, 100, 130)),
+                            ));
+                        }
+
+                        // Routines (NPCs only)
+                        if unit_data.is_npc() {
+                            let routines = routines_cache
+                                .as_deref()
+                                .map(|r| r.for_unit(unit_id))
+                                .unwrap_or_default();
+                            spawn_routines(col, unit_id, routines, game_data.as_deref());
+                        }
                     });
             })
             .id();
@@ -406,8 +499,278 @@ pub fn collapse_on_deselect(
     }
 }
 
+/// Send routine changes made from the unit list. The list is rebuilt on `UnitRoutines`.
+pub fn handle_routine_buttons(
+    mut network_client: Option<ResMut<NetworkClient>>,
+    toggle_query: Query<(&Interaction, &RoutineToggleButton), Changed<Interaction>>,
+    stock_query: Query<(&Interaction, &RoutineStockButton), Changed<Interaction>>,
+    delete_query: Query<(&Interaction, &RoutineDeleteButton), Changed<Interaction>>,
+    add_query: Query<(&Interaction, &RoutineAddHarvestButton), Changed<Interaction>>,
+) {
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+
+    for (interaction, button) in &toggle_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        let mut routine = button.routine.clone();
+        routine.is_enabled = !routine.is_enabled;
+        client.send_message(ClientMessage::SaveUnitRoutine {
+            unit_id: button.unit_id,
+            routine,
+        });
+    }
+
+    for (interaction, button) in &stock_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        let Some(mut routine) = Routine::from_automated_action(&button.routine) else {
+            continue;
+        };
+        match &mut routine {
+            Routine::Harvest { max_stock, .. } | Routine::Craft { max_stock, .. } => {
+                *max_stock = (*max_stock + button.delta).max(ROUTINE_STOCK_STEP);
+            }
+        }
+        client.send_message(ClientMessage::SaveUnitRoutine {
+            unit_id: button.unit_id,
+            routine: routine.to_automated_action(button.routine.id, button.routine.is_enabled),
+        });
+    }
+
+    for (interaction, button) in &delete_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        client.send_message(ClientMessage::DeleteUnitRoutine {
+            unit_id: button.unit_id,
+            routine_id: button.routine_id,
+        });
+    }
+
+    for (interaction, button) in &add_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        let routine = Routine::Harvest {
+            resource_specific_type: button.resource_specific_type,
+            max_stock: shared::DEFAULT_ROUTINE_MAX_STOCK,
+        };
+        client.send_message(ClientMessage::SaveUnitRoutine {
+            unit_id: button.unit_id,
+            routine: routine.to_automated_action(0, true),
+        });
+    }
+}
+
 // ─── Helpers ────────────────────────────────────────────────
 
+/// Step of the routine stock buttons
+const ROUTINE_STOCK_STEP: i32 = 5;
+
+/// Routine lines of an NPC, then one button per harvestable resource to add one
+fn spawn_routines(
+    col: &mut RelatedSpawnerCommands<ChildOf>,
+    unit_id: u64,
+    routines: &[AutomatedAction],
+    game_data: Option<&GameDataCache>,
+) {
+    for automation in routines {
+        let label = match Routine::from_automated_action(automation) {
+            Some(routine) => format!(
+                "↻ {} (≤ {})",
+                routine_label(&routine, game_data),
+                routine.max_stock()
+            ),
+            None => format!("↻ {}", automation.action_type),
+        };
+
+        col.spawn((
+            Node {
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::Center,
+                column_gap: Val::Px(4.0),
+                ..default()
+            },
+            Pickable {
+                should_block_lower: false,
+                is_hoverable: false,
+            },
+        ))
+        .with_children(|row| {
+            row.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 10.0,
+                    ..default()
+                },
+                TextColor(if automation.is_enabled {
+                    Color::srgb_u8(60, 110, 60)
+                } else {
+                    Color::srgb_u8(140, 130, 110)
+                }),
+            ));
+            spawn_small_button(
+                row,
+                if automation.is_enabled { "on" } else { "off" },
+                RoutineToggleButton {
+                    unit_id,
+                    routine: automation.clone(),
+                },
+            );
+            spawn_small_button(
+                row,
+                "-",
+                RoutineStockButton {
+                    unit_id,
+                    routine: automation.clone(),
+                    delta: -ROUTINE_STOCK_STEP,
+                },
+            );
+            spawn_small_button(
+                row,
+                "+",
+                RoutineStockButton {
+                    unit_id,
+                    routine: automation.clone(),
+                    delta: ROUTINE_STOCK_STEP,
+                },
+            );
+            spawn_small_button(
+                row,
+                "x",
+                RoutineDeleteButton {
+                    unit_id,
+                    routine_id: automation.id,
+                },
+            );
+        });
+    }
+
+    // Harvest routines, one per resource type that yields something
+    let Some(game_data) = game_data else {
+        return;
+    };
+    let mut resource_types: Vec<i16> = game_data
+        .harvest_yields
+        .iter()
+        .map(|y| y.resource_specific_type_id)
+        .collect();
+    resource_types.sort();
+    resource_types.dedup();
+
+    col.spawn((
+        Node {
+            flex_direction: FlexDirection::Row,
+            flex_wrap: FlexWrap::Wrap,
+            align_items: AlignItems::Center,
+            column_gap: Val::Px(4.0),
+            ..default()
+        },
+        Pickable {
+            should_block_lower: false,
+            is_hoverable: false,
+        },
+    ))
+    .with_children(|row| {
+        row.spawn((
+            Text::new("Récolter ici :"),
+            TextFont {
+                font_size: 10.0,
+                ..default()
+            },
+            TextColor(Color::srgb_u8(100, 90, 70)),
+        ));
+        for id in resource_types {
+            let Some(resource_specific_type) = ResourceSpecificTypeEnum::from_id(id) else {
+                continue;
+            };
+            let routine = Routine::Harvest {
+                resource_specific_type,
+                max_stock: 0,
+            };
+            spawn_small_button(
+                row,
+                &routine_label(&routine, Some(game_data)),
+                RoutineAddHarvestButton {
+                    unit_id,
+                    resource_specific_type,
+                },
+            );
+        }
+    });
+}
+
+fn spawn_small_button(parent: &mut RelatedSpawnerCommands<ChildOf>, label: &str, marker: impl Bundle) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                padding: UiRect::axes(Val::Px(4.0), Val::Px(1.0)),
+                border_radius: BorderRadius::all(Val::Px(3.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.12)),
+            marker,
+        ))
+        .with_children(|button| {
+            button.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 10.0,
+                    ..default()
+                },
+                TextColor(Color::srgb_u8(67, 60, 37)),
+                Pickable {
+                    should_block_lower: false,
+                    is_hoverable: false,
+                },
+            ));
+        });
+}
+
+/// Name of what the routine produces
+fn routine_label(routine: &Routine, game_data: Option<&GameDataCache>) -> String {
+    match routine {
+        Routine::Harvest {
+            resource_specific_type,
+            ..
+        } => game_data
+            .and_then(|data| {
+                data.harvest_yields
+                    .iter()
+                    .find(|y| y.resource_specific_type_id == resource_specific_type.to_id())
+            })
+            .zip(game_data)
+            .map(|(harvest_yield, data)| data.item_name(harvest_yield.result_item_id, 1)) // FR
+            .unwrap_or_else(|| format!("{:?}", resource_specific_type)),
+        Routine::Craft { recipe_id, .. } => game_data
+            .zip(recipe_id.parse::<i32>().ok())
+            .map(|(data, id)| data.recipe_name(id, 1)) // FR
+            .unwrap_or_else(|| recipe_id.clone()),
+    }
+}
+
+fn queued_action_label(action: &QueuedAction) -> String {
+    let cell = action.cell();
+    match action {
+        QueuedAction::CraftResource {
+            recipe_id, quantity, ..
+        } => format!(
+            "{} {} x{} ({}, {})",
+            action.action_type().to_name(),
+            recipe_id,
+            quantity,
+            cell.q,
+            cell.r
+        ),
+        _ => format!("{} ({}, {})", action.action_type().to_name(), cell.q, cell.r),
+    }
+}
+
 fn profession_color(profession: &ProfessionEnum) -> Color {
     use ProfessionEnum::*;
     match profession {
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::networking::client::NetworkClient;
-use crate::state::resources::{ConnectionStatus, UnitsDataCache};
+use crate::state::resources::{ConnectionStatus, PlayerInfo, UnitsDataCache};
 use crate::ui::components::{ContextMenuEntry, ContextMenuRoot};
 use crate::ui::resources::{ContextMenuAction, ContextMenuState, UnitSelectionState};
 
@@ -145,6 +145,7 @@ pub fn handle_context_menu_click(
     mut context_menu: ResMut<ContextMenuState>,
     unit_selection: Res<UnitSelectionState>,
     units_data_cache: Option<Res<UnitsDataCache>>,
+    player_info: Res<PlayerInfo>,
     connection: Res<ConnectionStatus>,
     mut network_client: Option<ResMut<NetworkClient>>,
 ) {
@@ -159,9 +160,9 @@ pub fn handle_context_menu_click(
         let Some(target_chunk) = context_menu.target_chunk else {
             continue;
         };
-        let Some(player_id) = connection.player_id else {
+        if connection.player_id.is_none() {
             continue;
-        };
+        }
 
         match entry.action {
             ContextMenuAction::Move => {
@@ -188,7 +189,6 @@ pub fn handle_context_menu_click(
                         }
 
                         client.send_message(shared::protocol::ClientMessage::ActionMoveUnit {
-                            player_id,
                             unit_id: *unit_id,
                             chunk_id: target_chunk,
                             cell: target_cell,
@@ -205,6 +205,17 @@ pub fn handle_context_menu_click(
                     info!("Sent FoundHamlet to server");
                 }
             }
+            ContextMenuAction::Claim => {
+                info!("Claiming ({},{})", target_cell.q, target_cell.r);
+
+                if let (Some(lord), Some(client)) = (&player_info.lord, network_client.as_mut()) {
+                    client.send_message(shared::protocol::ClientMessage::ActionClaimCells {
+                        unit_id: lord.id,
+                        cells: vec![target_cell],
+                    });
+                    info!("✓ Claim request sent");
+                }
+            }
             ContextMenuAction::Build(building_type) => {
                 info!(
                     "Building {:?} at ({},{})",
@@ -213,7 +224,6 @@ pub fn handle_context_menu_click(
 
                 if let Some(ref mut client) = network_client {
                     client.send_message(shared::protocol::ClientMessage::ActionBuildBuilding {
-                        player_id,
                         chunk_id: target_chunk,
                         cell: target_cell,
                         building_type,
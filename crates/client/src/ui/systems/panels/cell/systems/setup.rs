@@ -2,13 +2,14 @@ use bevy::prelude::*;
 use bevy::state::state_scoped::DespawnOnExit;
 
 use crate::camera::resources::CELL_SCENE_LAYER;
+use crate::state::resources::{GameDataCache, WorldCache};
 use crate::states::GameView;
 use crate::ui::{
     components::CellSceneVisual,
     resources::CellState,
     systems::{
         load_building_background, load_separators, load_terrain_background,
-        panels::components::CellViewPanel,
+        panels::components::{CellGroundItemsText, CellResourcesText, CellViewPanel},
     },
 };
 
@@ -29,7 +30,114 @@ pub fn setup_cell_panel(mut commands: Commands) {
             should_block_lower: false,
             is_hoverable: false
         }
-    ));
+    ))
+    .with_children(|panel| {
+        // Objets au sol, rempli par `update_cell_ground_items`
+        panel.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(16.0),
+                bottom: Val::Px(16.0),
+                ..default()
+            },
+            Pickable::IGNORE,
+            CellGroundItemsText,
+        ));
+
+        // Réserves naturelles, rempli par `update_cell_resource_stocks`
+        panel.spawn((
+            Text::new(""),
+            TextFont {
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(16.0),
+                bottom: Val::Px(36.0),
+                ..default()
+            },
+            Pickable::IGNORE,
+            CellResourcesText,
+        ));
+    });
+}
+
+/// Liste les objets posés au sol de la cellule affichée
+pub fn update_cell_ground_items(
+    cell_state: Res<CellState>,
+    world_cache: Res<WorldCache>,
+    game_data: Res<GameDataCache>,
+    mut text_query: Query<&mut Text, With<CellGroundItemsText>>,
+) {
+    let Some(viewed_cell) = cell_state.cell() else {
+        return;
+    };
+
+    let items = world_cache.get_ground_items(&viewed_cell);
+    let label = if items.is_empty() {
+        String::new()
+    } else {
+        let names: Vec<String> = items
+            .iter()
+            .map(|item| format!("{} x{}", game_data.item_name(item.item_id, 1), item.quantity)) // FR
+            .collect();
+        format!("Au sol : {}", names.join(", "))
+    };
+
+    for mut text in &mut text_query {
+        if text.as_str() != label {
+            **text = label.clone();
+        }
+    }
+}
+
+/// Affiche ce qu'il reste à récolter sur la cellule affichée
+pub fn update_cell_resource_stocks(
+    cell_state: Res<CellState>,
+    world_cache: Res<WorldCache>,
+    game_data: Res<GameDataCache>,
+    mut text_query: Query<&mut Text, With<CellResourcesText>>,
+) {
+    let Some(viewed_cell) = cell_state.cell() else {
+        return;
+    };
+
+    let stocks = world_cache.get_resource_stocks(&viewed_cell);
+    let label = if stocks.is_empty() {
+        String::new()
+    } else {
+        let reserves: Vec<String> = stocks
+            .iter()
+            .map(|stock| {
+                let name = game_data
+                    .harvest_yields
+                    .iter()
+                    .find(|y| y.resource_specific_type_id == stock.resource_specific_type.to_id())
+                    .map(|y| game_data.item_name(y.result_item_id, 1)) // FR
+                    .unwrap_or_else(|| format!("{:?}", stock.resource_specific_type));
+                if stock.is_depleted() {
+                    format!("{} épuisé", name)
+                } else {
+                    format!("{} {}/{}", name, stock.stock, stock.max_stock)
+                }
+            })
+            .collect();
+        format!("Réserves : {}", reserves.join(", "))
+    };
+
+    for mut text in &mut text_query {
+        if text.as_str() != label {
+            **text = label.clone();
+        }
+    }
 }
 
 pub fn setup_cell_layout(
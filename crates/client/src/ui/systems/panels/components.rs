@@ -1,4 +1,5 @@
 use bevy::ecs::component::Component;
+use shared::{EquipmentSlotEnum, RoleType};
 
 
 // Cell view components (detailed cell view mode)
@@ -26,8 +27,190 @@ pub struct SettingsPanel;
 #[derive(Component)]
 pub struct InventoryPanel;
 
-/// Marker for inventory item rows (for interaction/updating)
+/// Marker for inventory item rows (for interaction/updating).
+/// Rows can be dragged onto an `InventoryDropTarget`.
 #[derive(Component)]
 pub struct InventoryItemRow {
+    pub unit_id: u64,
     pub item_id: i32,
 }
+
+/// Where a dragged inventory row can be dropped
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ItemDropTarget {
+    /// Another unit standing on the same cell
+    Unit(u64),
+    /// The ground of the cell
+    Ground,
+}
+
+#[derive(Component)]
+pub struct InventoryDropTarget {
+    pub target: ItemDropTarget,
+}
+
+/// Item list of a unit other than the lord, rebuilt when its inventory changes
+#[derive(Component)]
+pub struct UnitItemsList {
+    pub unit_id: u64,
+}
+
+/// Items lying on the ground of the lord's cell, rebuilt when they change
+#[derive(Component)]
+pub struct GroundItemsList;
+
+/// Click → pick the items up from the ground
+#[derive(Component)]
+pub struct PickUpItemButton {
+    pub item_id: i32,
+    pub quantity: i32,
+}
+
+/// Cell view label listing the items on the ground
+#[derive(Component)]
+pub struct CellGroundItemsText;
+
+/// Cell view label showing the natural reserves left on the cell
+#[derive(Component)]
+pub struct CellResourcesText;
+
+/// Management panel label showing the treasury gold
+#[derive(Component)]
+pub struct TreasuryGoldText;
+
+/// Items stored in the organization treasury, rebuilt when `TreasuryCache` changes
+#[derive(Component)]
+pub struct TreasuryItemsList;
+
+/// Lord items that can be deposited, rebuilt when the inventory changes
+#[derive(Component)]
+pub struct TreasuryDepositList;
+
+/// Click → ask the server to raise the settlement to its next tier
+#[derive(Component)]
+pub struct SettlementUpgradeButton;
+
+/// Click → withdraw one item for the lord (shift-click: the whole stack)
+#[derive(Component)]
+pub struct TreasuryWithdrawButton {
+    pub item_id: i32,
+    pub quantity: i32,
+}
+
+/// Click → deposit one item of the lord (shift-click: the whole stack)
+#[derive(Component)]
+pub struct TreasuryDepositButton {
+    pub item_id: i32,
+    pub quantity: i32,
+}
+
+/// Vassalage tree and pending offers of the player's organization,
+/// rebuilt when `HierarchyCache` changes
+#[derive(Component)]
+pub struct HierarchyList;
+
+/// Click → offer a vassalage bond between two organizations
+#[derive(Component)]
+pub struct VassalageOfferButton {
+    pub liege_organization_id: u64,
+    pub vassal_organization_id: u64,
+}
+
+/// Click → accept a pending vassalage offer
+#[derive(Component)]
+pub struct AcceptVassalageButton {
+    pub offer_id: u64,
+}
+
+/// Click → break the bond (or withdraw the offers) with another organization
+#[derive(Component)]
+pub struct RevokeVassalageButton {
+    pub organization_id: u64,
+}
+
+/// Tabs of the management panel
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ManagementTab {
+    Overview,
+    Members,
+    Diplomacy,
+}
+
+/// Click → show the tab
+#[derive(Component)]
+pub struct ManagementTabButton(pub ManagementTab);
+
+/// Content shown only while its tab is selected
+#[derive(Component)]
+pub struct ManagementTabContent(pub ManagementTab);
+
+/// Relations and pending proposals, rebuilt when `DiplomacyCache` changes
+#[derive(Component)]
+pub struct DiplomacyList;
+
+/// Click → send the lord as envoy with the answer to a proposal
+#[derive(Component)]
+pub struct AnswerProposalButton {
+    pub proposal_id: u64,
+    pub organization_id: u64,
+    pub accept: bool,
+}
+
+/// Members, officers and pending invitations, rebuilt when `MembersCache` changes
+#[derive(Component)]
+pub struct MembersList;
+
+/// Click → accept or decline an invitation or an application
+#[derive(Component)]
+pub struct MembershipAnswerButton {
+    pub request_id: u64,
+    pub accept: bool,
+}
+
+/// Click → invite a neighbouring lord
+#[derive(Component)]
+pub struct InviteLordButton {
+    pub unit_id: u64,
+}
+
+/// Click → ask to join the organization under the camera
+#[derive(Component)]
+pub struct ApplyToOrganizationButton {
+    pub organization_id: u64,
+}
+
+/// Click → expel a member
+#[derive(Component)]
+pub struct ExpelMemberButton {
+    pub unit_id: u64,
+}
+
+/// Click → give a member the next officer role (`None` dismisses)
+#[derive(Component)]
+pub struct AppointOfficerButton {
+    pub unit_id: u64,
+    pub role: Option<RoleType>,
+}
+
+/// Click → leave the organization
+#[derive(Component)]
+pub struct LeaveOrganizationButton;
+
+/// Paper-doll slot label (item currently worn in that slot)
+#[derive(Component)]
+pub struct EquipmentSlotText {
+    pub slot: EquipmentSlotEnum,
+}
+
+/// Click → unequip the slot
+#[derive(Component)]
+pub struct UnequipSlotButton {
+    pub slot: EquipmentSlotEnum,
+}
+
+/// Click → equip the item in its slot
+#[derive(Component)]
+pub struct EquipItemButton {
+    pub item_id: i32,
+    pub slot: EquipmentSlotEnum,
+}
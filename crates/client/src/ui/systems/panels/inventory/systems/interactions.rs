@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use shared::protocol::ClientMessage;
+
+use crate::networking::client::NetworkClient;
+use crate::state::resources::{InventoryCache, PlayerInfo};
+use crate::ui::resources::{DragState, ItemDragInfo};
+use crate::ui::systems::panels::components::{
+    EquipItemButton, InventoryDropTarget, InventoryItemRow, ItemDropTarget, PickUpItemButton,
+    UnequipSlotButton,
+};
+
+/// Équipe / déséquipe le seigneur depuis le paper-doll.
+/// Le panneau est mis à jour à la réception de `EquipmentChanged`.
+pub fn handle_equipment_buttons(
+    player_info: Res<PlayerInfo>,
+    mut network_client: Option<ResMut<NetworkClient>>,
+    equip_query: Query<(&Interaction, &EquipItemButton), Changed<Interaction>>,
+    unequip_query: Query<(&Interaction, &UnequipSlotButton), Changed<Interaction>>,
+) {
+    let Some(lord) = &player_info.lord else {
+        return;
+    };
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+
+    for (interaction, button) in &equip_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        client.send_message(ClientMessage::EquipItem {
+            unit_id: lord.id,
+            item_id: button.item_id,
+            slot: button.slot,
+        });
+    }
+
+    for (interaction, button) in &unequip_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        client.send_message(ClientMessage::UnequipItem {
+            unit_id: lord.id,
+            slot: button.slot,
+        });
+    }
+}
+
+/// Ramasse pour le seigneur les objets posés au sol de sa cellule
+pub fn handle_pick_up_buttons(
+    player_info: Res<PlayerInfo>,
+    mut network_client: Option<ResMut<NetworkClient>>,
+    query: Query<(&Interaction, &PickUpItemButton), Changed<Interaction>>,
+) {
+    let Some(lord) = &player_info.lord else {
+        return;
+    };
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+
+    for (interaction, button) in &query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        client.send_message(ClientMessage::PickUpItems {
+            unit_id: lord.id,
+            item_id: button.item_id,
+            quantity: button.quantity,
+        });
+    }
+}
+
+/// Début du glisser d'une ligne d'inventaire : toute la pile, sauf les exemplaires équipés
+pub(super) fn on_item_drag_start(
+    event: On<Pointer<DragStart>>,
+    mut drag_state: ResMut<DragState>,
+    inventory_cache: Res<InventoryCache>,
+    row_query: Query<&InventoryItemRow>,
+) {
+    let Ok(row) = row_query.get(event.event_target()) else {
+        return;
+    };
+
+    let owned = inventory_cache
+        .get_inventory(row.unit_id)
+        .and_then(|items| items.iter().find(|i| i.item_id == row.item_id))
+        .map(|item| item.quantity)
+        .unwrap_or(0);
+    let equipped = inventory_cache
+        .equipment
+        .get(&row.unit_id)
+        .map(|slots| slots.iter().filter(|e| e.item_id == row.item_id).count() as i32)
+        .unwrap_or(0);
+
+    let quantity = owned - equipped;
+    if quantity <= 0 {
+        return;
+    }
+
+    drag_state.item = Some(ItemDragInfo {
+        unit_id: row.unit_id,
+        item_id: row.item_id,
+        quantity,
+    });
+}
+
+pub(super) fn on_item_drag_end(_event: On<Pointer<DragEnd>>, mut drag_state: ResMut<DragState>) {
+    drag_state.item = None;
+}
+
+/// Dépôt d'une pile sur une autre unité (transfert) ou sur le sol (dépôt).
+/// L'inventaire est mis à jour à la réception des `InventoryUpdate`.
+pub(super) fn on_item_drop(
+    mut event: On<Pointer<DragDrop>>,
+    mut drag_state: ResMut<DragState>,
+    mut network_client: Option<ResMut<NetworkClient>>,
+    target_query: Query<&InventoryDropTarget>,
+) {
+    event.propagate(false);
+
+    let Ok(drop_target) = target_query.get(event.event_target()) else {
+        return;
+    };
+    let Some(drag_info) = drag_state.item.take() else {
+        return;
+    };
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+
+    match drop_target.target {
+        ItemDropTarget::Unit(to_unit) if to_unit != drag_info.unit_id => {
+            client.send_message(ClientMessage::TransferItems {
+                from_unit: drag_info.unit_id,
+                to_unit,
+                item_id: drag_info.item_id,
+                quantity: drag_info.quantity,
+            });
+        }
+        ItemDropTarget::Unit(_) => {}
+        ItemDropTarget::Ground => {
+            client.send_message(ClientMessage::DropItems {
+                unit_id: drag_info.unit_id,
+                item_id: drag_info.item_id,
+                quantity: drag_info.quantity,
+            });
+        }
+    }
+}
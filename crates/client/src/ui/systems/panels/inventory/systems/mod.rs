@@ -1,5 +1,7 @@
+mod interactions;
 mod setup;
 mod update;
 
+pub use interactions::*;
 pub use setup::*;
 pub use update::*;
@@ -1,12 +1,104 @@
-use bevy::prelude::*;
+use bevy::{ecs::relationship::RelatedSpawnerCommands, prelude::*};
 use bevy::state::state_scoped::DespawnOnExit;
 
 use crate::camera::resources::SceneRenderTarget;
 use crate::networking::client::NetworkClient;
-use crate::state::resources::{InventoryCache, PlayerInfo};
+use crate::state::resources::{GameDataCache, InventoryCache, PlayerInfo, UnitsCache, UnitsDataCache};
 use crate::states::GameView;
 use crate::ui::frosted_glass::{FrostedGlassConfig, FrostedGlassMaterial};
-use crate::ui::systems::panels::components::{InventoryItemRow, InventoryPanel};
+use crate::ui::systems::panels::components::{
+    EquipItemButton, EquipmentSlotText, GroundItemsList, InventoryDropTarget, InventoryItemRow,
+    InventoryPanel, ItemDropTarget, UnequipSlotButton, UnitItemsList,
+};
+use shared::EquipmentSlotEnum;
+
+use super::interactions::{on_item_drag_end, on_item_drag_start, on_item_drop};
+
+/// Unités du joueur présentes sur la cellule du seigneur (seigneur compris, en premier)
+pub(super) fn units_on_lord_cell(
+    player_info: &PlayerInfo,
+    units_cache: &UnitsCache,
+    units_data_cache: &UnitsDataCache,
+) -> Vec<u64> {
+    let Some(lord) = &player_info.lord else {
+        return vec![];
+    };
+    let cell = units_data_cache
+        .get_unit(lord.id)
+        .map(|unit| unit.current_cell)
+        .unwrap_or(lord.current_cell);
+
+    let mut unit_ids = vec![lord.id];
+    if let Some(units) = units_cache.get_units_at_cell(&cell) {
+        unit_ids.extend(units.iter().copied().filter(|unit_id| {
+            *unit_id != lord.id
+                && units_data_cache
+                    .get_unit(*unit_id)
+                    .is_some_and(|unit| unit.player_id == lord.player_id)
+        }));
+    }
+    unit_ids
+}
+
+/// Zone de dépôt d'une pile glissée (une unité ou le sol)
+fn spawn_drop_target(parent: &mut RelatedSpawnerCommands<ChildOf>, label: String, target: ItemDropTarget) {
+    parent
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                border: UiRect::all(Val::Px(1.0)),
+                border_radius: BorderRadius::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.05)),
+            BorderColor::all(Color::srgba(0.0, 0.0, 0.0, 0.2)),
+            InventoryDropTarget { target },
+        ))
+        .observe(on_item_drop)
+        .with_children(|zone| {
+            zone.spawn((
+                Text::new(label),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(Color::BLACK),
+                Pickable::IGNORE,
+            ));
+        });
+}
+
+/// Nom affiché d'un slot d'équipement
+pub(super) fn slot_label(slot: EquipmentSlotEnum) -> &'static str {
+    match slot {
+        EquipmentSlotEnum::Unknown => "Inconnu",
+        EquipmentSlotEnum::Head => "Tête",
+        EquipmentSlotEnum::Chest => "Torse",
+        EquipmentSlotEnum::Legs => "Jambes",
+        EquipmentSlotEnum::Feet => "Pieds",
+        EquipmentSlotEnum::Hands => "Mains",
+        EquipmentSlotEnum::MainHand => "Main droite",
+        EquipmentSlotEnum::OffHand => "Main gauche",
+        EquipmentSlotEnum::Back => "Dos",
+        EquipmentSlotEnum::Neck => "Cou",
+        EquipmentSlotEnum::Ring1 => "Anneau 1",
+        EquipmentSlotEnum::Ring2 => "Anneau 2",
+    }
+}
+
+/// Texte d'un slot du paper-doll : l'item porté, ou un tiret si le slot est libre
+pub(super) fn equipped_label(
+    inventory_cache: &InventoryCache,
+    game_data: &GameDataCache,
+    unit_id: u64,
+    slot: EquipmentSlotEnum,
+) -> String {
+    match inventory_cache.equipped_in(unit_id, slot) {
+        Some(item_id) => game_data.item_name(item_id, 1), // FR
+        None => "—".to_string(),
+    }
+}
 
 pub fn setup_inventory_panel(
     mut commands: Commands,
@@ -14,6 +106,9 @@ pub fn setup_inventory_panel(
     render_target: Res<SceneRenderTarget>,
     player_info: Res<PlayerInfo>,
     inventory_cache: Res<InventoryCache>,
+    game_data: Res<GameDataCache>,
+    units_cache: Res<UnitsCache>,
+    units_data_cache: Res<UnitsDataCache>,
     mut network_client: Option<ResMut<NetworkClient>>,
 ) {
     let config = FrostedGlassConfig::dialog()
@@ -26,9 +121,14 @@ pub fn setup_inventory_panel(
     // Get lord unit_id to fetch inventory
     let lord_unit_id = player_info.lord.as_ref().map(|l| l.id);
 
+    // Unités avec qui le seigneur peut échanger des objets
+    let cell_unit_ids = units_on_lord_cell(&player_info, &units_cache, &units_data_cache);
+
     // Re-request fresh inventory data
-    if let (Some(uid), Some(client)) = (lord_unit_id, &mut network_client) {
-        client.send_message(shared::protocol::ClientMessage::RequestInventory { unit_id: uid });
+    if let Some(client) = &mut network_client {
+        for &uid in &cell_unit_ids {
+            client.send_message(shared::protocol::ClientMessage::RequestInventory { unit_id: uid });
+        }
     }
 
     let lord_name = player_info
@@ -114,6 +214,93 @@ pub fn setup_inventory_panel(
                         BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.2)),
                     ));
 
+                    // Paper-doll: un slot par ligne
+                    panel.spawn((
+                        Text::new("ÉQUIPEMENT"),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::BLACK),
+                    ));
+
+                    for slot in EquipmentSlotEnum::iter()
+                        .filter(|slot| *slot != EquipmentSlotEnum::Unknown)
+                    {
+                        let label = lord_unit_id
+                            .map(|uid| equipped_label(&inventory_cache, &game_data, uid, slot))
+                            .unwrap_or_else(|| "—".to_string());
+
+                        panel
+                            .spawn((Node {
+                                width: Val::Percent(100.0),
+                                flex_direction: FlexDirection::Row,
+                                justify_content: JustifyContent::SpaceBetween,
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(8.0),
+                                ..default()
+                            },))
+                            .with_children(|row| {
+                                row.spawn((
+                                    Text::new(slot_label(slot)),
+                                    TextFont {
+                                        font_size: 13.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::srgba(0.4, 0.4, 0.4, 1.0)),
+                                    Node {
+                                        width: Val::Px(110.0),
+                                        ..default()
+                                    },
+                                ));
+
+                                row.spawn((
+                                    Text::new(label),
+                                    TextFont {
+                                        font_size: 14.0,
+                                        ..default()
+                                    },
+                                    TextColor(Color::BLACK),
+                                    Node {
+                                        flex_grow: 1.0,
+                                        ..default()
+                                    },
+                                    EquipmentSlotText { slot },
+                                ));
+
+                                row.spawn((
+                                    Button,
+                                    Node {
+                                        padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                                        ..default()
+                                    },
+                                    BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.1)),
+                                    UnequipSlotButton { slot },
+                                ))
+                                .with_children(|btn| {
+                                    btn.spawn((
+                                        Text::new("Retirer"),
+                                        TextFont {
+                                            font_size: 11.0,
+                                            ..default()
+                                        },
+                                        TextColor(Color::BLACK),
+                                    ));
+                                });
+                            });
+                    }
+
+                    // Separator
+                    panel.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(1.0),
+                            margin: UiRect::vertical(Val::Px(4.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.2)),
+                    ));
+
                     if items.is_empty() {
                         panel.spawn((
                             Text::new("Aucun objet dans l'inventaire."),
@@ -139,9 +326,12 @@ pub fn setup_inventory_panel(
                                     },
                                     BorderColor::all(Color::srgba(0.0, 0.0, 0.0, 0.1)),
                                     InventoryItemRow {
+                                        unit_id: lord_unit_id.unwrap_or_default(),
                                         item_id: item.item_id,
                                     },
                                 ))
+                                .observe(on_item_drag_start)
+                                .observe(on_item_drag_end)
                                 .with_children(|row| {
                                     // Left: Name + type
                                     row.spawn((
@@ -205,10 +395,94 @@ pub fn setup_inventory_panel(
                                             },
                                             TextColor(Color::srgba(0.4, 0.4, 0.4, 1.0)),
                                         ));
+
+                                        // Équipable : bouton pour le porter dans son slot
+                                        let equip_slot = game_data
+                                            .get_item(item.item_id)
+                                            .filter(|def| def.is_equipable)
+                                            .and_then(|def| def.equipment_slot_id)
+                                            .and_then(EquipmentSlotEnum::from_id);
+                                        if let Some(slot) = equip_slot {
+                                            right.spawn((
+                                                Button,
+                                                Node {
+                                                    padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                                                    margin: UiRect::top(Val::Px(2.0)),
+                                                    ..default()
+                                                },
+                                                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.1)),
+                                                EquipItemButton {
+                                                    item_id: item.item_id,
+                                                    slot,
+                                                },
+                                            ))
+                                            .with_children(|btn| {
+                                                btn.spawn((
+                                                    Text::new("Équiper"),
+                                                    TextFont {
+                                                        font_size: 11.0,
+                                                        ..default()
+                                                    },
+                                                    TextColor(Color::BLACK),
+                                                ));
+                                            });
+                                        }
                                     });
                                 });
                         }
                     }
+
+                    // Échanges : glisser une ligne sur une unité de la cellule ou sur le sol
+                    panel.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            height: Val::Px(1.0),
+                            margin: UiRect::vertical(Val::Px(4.0)),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.2)),
+                    ));
+
+                    panel.spawn((
+                        Text::new("SUR LA CELLULE"),
+                        TextFont {
+                            font_size: 18.0,
+                            ..default()
+                        },
+                        TextColor(Color::BLACK),
+                    ));
+
+                    for &unit_id in &cell_unit_ids {
+                        let name = units_data_cache
+                            .get_unit(unit_id)
+                            .map(|unit| unit.full_name())
+                            .unwrap_or_else(|| lord_name.clone());
+                        spawn_drop_target(panel, name, ItemDropTarget::Unit(unit_id));
+
+                        // Les objets du seigneur sont listés plus haut
+                        if Some(unit_id) != lord_unit_id {
+                            panel.spawn((
+                                Node {
+                                    width: Val::Percent(100.0),
+                                    flex_direction: FlexDirection::Column,
+                                    padding: UiRect::left(Val::Px(12.0)),
+                                    ..default()
+                                },
+                                UnitItemsList { unit_id },
+                            ));
+                        }
+                    }
+
+                    spawn_drop_target(panel, "Au sol".to_string(), ItemDropTarget::Ground);
+                    panel.spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            flex_direction: FlexDirection::Column,
+                            padding: UiRect::left(Val::Px(12.0)),
+                            ..default()
+                        },
+                        GroundItemsList,
+                    ));
                 });
         });
 }
@@ -1,16 +1,24 @@
 use bevy::prelude::*;
+use shared::GroundItem;
 
-use crate::state::resources::{InventoryCache, PlayerInfo};
-use crate::ui::systems::panels::components::InventoryItemRow;
+use crate::state::resources::{GameDataCache, InventoryCache, PlayerInfo, UnitsDataCache, WorldCache};
+use crate::ui::systems::panels::components::{
+    EquipmentSlotText, GroundItemsList, InventoryItemRow, PickUpItemButton, UnitItemsList,
+};
+
+use super::interactions::{on_item_drag_end, on_item_drag_start};
+use super::setup::equipped_label;
 
 /// Update inventory item quantities and weights in real-time.
 /// Runs every frame when inventory panel is visible and cache has changed.
 pub fn update_inventory_panel(
     inventory_cache: Res<InventoryCache>,
     player_info: Res<PlayerInfo>,
+    game_data: Res<GameDataCache>,
     row_query: Query<(&InventoryItemRow, &Children)>,
     children_query: Query<&Children>,
-    mut text_query: Query<&mut Text>,
+    mut text_query: Query<&mut Text, Without<EquipmentSlotText>>,
+    mut slot_text_query: Query<(&EquipmentSlotText, &mut Text)>,
 ) {
     if !inventory_cache.is_changed() {
         return;
@@ -20,11 +28,21 @@ pub fn update_inventory_panel(
         return;
     };
 
+    // Paper-doll
+    for (slot_text, mut text) in &mut slot_text_query {
+        **text = equipped_label(&inventory_cache, &game_data, lord.id, slot_text.slot);
+    }
+
     let Some(items) = inventory_cache.get_inventory(lord.id) else {
         return;
     };
 
     for (row, row_children) in &row_query {
+        // Les lignes des autres unités sont reconstruites par `update_unit_item_lists`
+        if row.unit_id != lord.id {
+            continue;
+        }
+
         // Find the matching item in cache
         let Some(item) = items.iter().find(|i| i.item_id == row.item_id) else {
             continue;
@@ -59,4 +77,141 @@ pub fn update_inventory_panel(
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// Reconstruit la liste d'objets des unités qui accompagnent le seigneur
+/// (une ligne glissable par objet)
+pub fn update_unit_item_lists(
+    mut commands: Commands,
+    inventory_cache: Res<InventoryCache>,
+    game_data: Res<GameDataCache>,
+    list_query: Query<(Entity, &UnitItemsList)>,
+    added_query: Query<(), Added<UnitItemsList>>,
+) {
+    if !inventory_cache.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    for (entity, list) in &list_query {
+        let items = inventory_cache
+            .get_inventory(list.unit_id)
+            .cloned()
+            .unwrap_or_default();
+
+        commands.entity(entity).despawn_related::<Children>();
+        commands.entity(entity).with_children(|parent| {
+            for item in items.iter().filter(|item| item.quantity > 0) {
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Percent(100.0),
+                            padding: UiRect::axes(Val::Px(4.0), Val::Px(2.0)),
+                            ..default()
+                        },
+                        InventoryItemRow {
+                            unit_id: list.unit_id,
+                            item_id: item.item_id,
+                        },
+                    ))
+                    .observe(on_item_drag_start)
+                    .observe(on_item_drag_end)
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::new(format!(
+                                "{} x{}",
+                                game_data.item_name(item.item_id, 1), // FR
+                                item.quantity
+                            )),
+                            TextFont {
+                                font_size: 13.0,
+                                ..default()
+                            },
+                            TextColor(Color::BLACK),
+                            Pickable::IGNORE,
+                        ));
+                    });
+            }
+        });
+    }
+}
+
+/// Reconstruit la liste des objets au sol de la cellule du seigneur,
+/// avec un bouton pour les ramasser
+pub fn update_ground_items_list(
+    mut commands: Commands,
+    world_cache: Res<WorldCache>,
+    player_info: Res<PlayerInfo>,
+    units_data_cache: Res<UnitsDataCache>,
+    game_data: Res<GameDataCache>,
+    list_query: Query<Entity, With<GroundItemsList>>,
+    added_query: Query<(), Added<GroundItemsList>>,
+    mut shown: Local<Vec<GroundItem>>,
+) {
+    let Some(lord) = &player_info.lord else {
+        return;
+    };
+    let cell = units_data_cache
+        .get_unit(lord.id)
+        .map(|unit| unit.current_cell)
+        .unwrap_or(lord.current_cell);
+
+    let items = world_cache.get_ground_items(&cell);
+    if added_query.is_empty() && *shown == items {
+        return;
+    }
+    *shown = items.to_vec();
+
+    for entity in &list_query {
+        commands.entity(entity).despawn_related::<Children>();
+        commands.entity(entity).with_children(|parent| {
+            for item in items {
+                parent
+                    .spawn((Node {
+                        width: Val::Percent(100.0),
+                        flex_direction: FlexDirection::Row,
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::Center,
+                        padding: UiRect::axes(Val::Px(4.0), Val::Px(2.0)),
+                        ..default()
+                    },))
+                    .with_children(|row| {
+                        row.spawn((
+                            Text::new(format!(
+                                "{} x{}",
+                                game_data.item_name(item.item_id, 1), // FR
+                                item.quantity
+                            )),
+                            TextFont {
+                                font_size: 13.0,
+                                ..default()
+                            },
+                            TextColor(Color::BLACK),
+                        ));
+
+                        row.spawn((
+                            Button,
+                            Node {
+                                padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.1)),
+                            PickUpItemButton {
+                                item_id: item.item_id,
+                                quantity: item.quantity,
+                            },
+                        ))
+                        .with_children(|btn| {
+                            btn.spawn((
+                                Text::new("Ramasser"),
+                                TextFont {
+                                    font_size: 11.0,
+                                    ..default()
+                                },
+                                TextColor(Color::BLACK),
+                            ));
+                        });
+                    });
+            }
+        });
+    }
+}
@@ -0,0 +1,215 @@
+use bevy::prelude::*;
+use shared::EnvoyMission;
+use shared::protocol::ClientMessage;
+
+use crate::networking::client::NetworkClient;
+use crate::state::resources::PlayerInfo;
+use crate::ui::systems::panels::components::{
+    AcceptVassalageButton, AnswerProposalButton, ApplyToOrganizationButton, AppointOfficerButton,
+    ExpelMemberButton, InviteLordButton, LeaveOrganizationButton, MembershipAnswerButton,
+    RevokeVassalageButton, SettlementUpgradeButton, TreasuryDepositButton,
+    TreasuryWithdrawButton, VassalageOfferButton,
+};
+
+/// Dépôts et retraits du trésor pour le seigneur : un exemplaire par clic,
+/// toute la pile avec Maj. Le panneau est mis à jour à la réception de `TreasuryData`.
+pub fn handle_treasury_buttons(
+    player_info: Res<PlayerInfo>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut network_client: Option<ResMut<NetworkClient>>,
+    withdraw_query: Query<(&Interaction, &TreasuryWithdrawButton), Changed<Interaction>>,
+    deposit_query: Query<(&Interaction, &TreasuryDepositButton), Changed<Interaction>>,
+) {
+    let Some(lord) = &player_info.lord else {
+        return;
+    };
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+    let whole_stack = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+    for (interaction, button) in &withdraw_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        client.send_message(ClientMessage::WithdrawFromTreasury {
+            unit_id: lord.id,
+            item_id: button.item_id,
+            quantity: if whole_stack { button.quantity } else { 1 },
+        });
+    }
+
+    for (interaction, button) in &deposit_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        client.send_message(ClientMessage::DepositToTreasury {
+            unit_id: lord.id,
+            item_id: button.item_id,
+            quantity: if whole_stack { button.quantity } else { 1 },
+        });
+    }
+}
+
+/// Demande d'élévation de l'établissement ; la réponse arrive en notification
+pub fn handle_settlement_upgrade_button(
+    mut network_client: Option<ResMut<NetworkClient>>,
+    query: Query<&Interaction, (Changed<Interaction>, With<SettlementUpgradeButton>)>,
+) {
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+    for interaction in &query {
+        if matches!(interaction, Interaction::Pressed) {
+            client.send_message(ClientMessage::UpgradeOrganization);
+        }
+    }
+}
+
+/// Propositions, acceptations et ruptures de vassalité ; le serveur répond aux deux chefs
+pub fn handle_vassalage_buttons(
+    mut network_client: Option<ResMut<NetworkClient>>,
+    offer_query: Query<(&Interaction, &VassalageOfferButton), Changed<Interaction>>,
+    accept_query: Query<(&Interaction, &AcceptVassalageButton), Changed<Interaction>>,
+    revoke_query: Query<(&Interaction, &RevokeVassalageButton), Changed<Interaction>>,
+) {
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+
+    for (interaction, button) in &offer_query {
+        if matches!(interaction, Interaction::Pressed) {
+            client.send_message(ClientMessage::OfferVassalage {
+                liege_organization_id: button.liege_organization_id,
+                vassal_organization_id: button.vassal_organization_id,
+            });
+        }
+    }
+
+    for (interaction, button) in &accept_query {
+        if matches!(interaction, Interaction::Pressed) {
+            client.send_message(ClientMessage::AcceptVassalage {
+                offer_id: button.offer_id,
+            });
+        }
+    }
+
+    for (interaction, button) in &revoke_query {
+        if matches!(interaction, Interaction::Pressed) {
+            client.send_message(ClientMessage::RevokeVassalage {
+                organization_id: button.organization_id,
+            });
+        }
+    }
+}
+
+/// Réponse à une proposition diplomatique : le seigneur part en émissaire
+/// jusqu'au siège de l'organisation qui l'a faite
+pub fn handle_diplomacy_buttons(
+    player_info: Res<PlayerInfo>,
+    mut network_client: Option<ResMut<NetworkClient>>,
+    query: Query<(&Interaction, &AnswerProposalButton), Changed<Interaction>>,
+) {
+    let Some(lord) = &player_info.lord else {
+        return;
+    };
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+
+    for (interaction, button) in &query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        let mission = if button.accept {
+            EnvoyMission::Accept {
+                proposal_id: button.proposal_id,
+            }
+        } else {
+            EnvoyMission::Reject {
+                proposal_id: button.proposal_id,
+            }
+        };
+        client.send_message(ClientMessage::ActionSendEnvoy {
+            unit_id: lord.id,
+            target_organization_id: button.organization_id,
+            mission,
+        });
+    }
+}
+
+/// Invitations, candidatures, exclusions et charges ; le serveur prévient les
+/// membres concernés, qui redemandent la liste
+pub fn handle_membership_buttons(
+    mut network_client: Option<ResMut<NetworkClient>>,
+    answer_query: Query<(&Interaction, &MembershipAnswerButton), Changed<Interaction>>,
+    invite_query: Query<(&Interaction, &InviteLordButton), Changed<Interaction>>,
+    apply_query: Query<(&Interaction, &ApplyToOrganizationButton), Changed<Interaction>>,
+    expel_query: Query<(&Interaction, &ExpelMemberButton), Changed<Interaction>>,
+    appoint_query: Query<(&Interaction, &AppointOfficerButton), Changed<Interaction>>,
+    leave_query: Query<&Interaction, (Changed<Interaction>, With<LeaveOrganizationButton>)>,
+) {
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+
+    for (interaction, button) in &answer_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        client.send_message(if button.accept {
+            ClientMessage::AcceptMembershipRequest {
+                request_id: button.request_id,
+            }
+        } else {
+            ClientMessage::DeclineMembershipRequest {
+                request_id: button.request_id,
+            }
+        });
+    }
+
+    for (interaction, button) in &invite_query {
+        if matches!(interaction, Interaction::Pressed) {
+            client.send_message(ClientMessage::InviteToOrganization {
+                unit_id: button.unit_id,
+            });
+        }
+    }
+
+    for (interaction, button) in &apply_query {
+        if matches!(interaction, Interaction::Pressed) {
+            client.send_message(ClientMessage::ApplyToOrganization {
+                organization_id: button.organization_id,
+            });
+        }
+    }
+
+    for (interaction, button) in &expel_query {
+        if matches!(interaction, Interaction::Pressed) {
+            client.send_message(ClientMessage::ExpelMember {
+                unit_id: button.unit_id,
+            });
+        }
+    }
+
+    for (interaction, button) in &appoint_query {
+        if !matches!(interaction, Interaction::Pressed) {
+            continue;
+        }
+        client.send_message(match button.role {
+            Some(role) => ClientMessage::AppointOfficer {
+                unit_id: button.unit_id,
+                role,
+            },
+            None => ClientMessage::DismissOfficer {
+                unit_id: button.unit_id,
+            },
+        });
+    }
+
+    for interaction in &leave_query {
+        if matches!(interaction, Interaction::Pressed) {
+            client.send_message(ClientMessage::LeaveOrganization);
+        }
+    }
+}
@@ -1,3 +1,7 @@
+mod interactions;
 mod setup;
+mod update;
 
-pub use setup::*;
\ No newline at end of file
+pub use interactions::*;
+pub use setup::*;
+pub use update::*;
@@ -3,15 +3,23 @@ use bevy::prelude::*;
 use bevy::state::state_scoped::DespawnOnExit;
 
 use crate::camera::resources::SceneRenderTarget;
+use crate::networking::client::NetworkClient;
 use crate::state::resources::PlayerInfo;
 use crate::states::GameView;
 use crate::ui::frosted_glass::{FrostedGlassConfig, FrostedGlassMaterial};
-use crate::ui::systems::panels::components::ManagementPanel;
+use crate::ui::systems::panels::components::{
+    DiplomacyList, HierarchyList, ManagementPanel, ManagementTab, ManagementTabButton,
+    ManagementTabContent, MembersList, SettlementUpgradeButton, TreasuryDepositList, TreasuryGoldText,
+    TreasuryItemsList,
+};
+use shared::protocol::ClientMessage;
 
 const GOLD: Color = Color::srgb(0.79, 0.66, 0.30);
 const TEXT_LIGHT: Color = Color::srgb(0.92, 0.88, 0.80);
 const TEXT_DIM: Color = Color::srgb(0.60, 0.52, 0.40);
 const TEXT_DARK: Color = Color::srgb(0.20, 0.15, 0.10);
+pub(crate) const TAB_SELECTED: Color = Color::srgba(0.0, 0.0, 0.0, 0.2);
+pub(crate) const TAB_IDLE: Color = Color::srgba(0.0, 0.0, 0.0, 0.05);
 
 pub fn setup_management_panel(
     mut commands: Commands,
@@ -19,7 +27,23 @@ pub fn setup_management_panel(
     render_target: Res<SceneRenderTarget>,
     asset_server: Res<AssetServer>,
     player_info: Res<PlayerInfo>,
+    mut network_client: Option<ResMut<NetworkClient>>,
 ) {
+    // Le trésor, les membres et l'arbre de vassalité sont rechargés à chaque ouverture
+    // du panneau ; sans organisation, les membres portent les invitations reçues
+    if let Some(client) = network_client.as_mut() {
+        client.send_message(ClientMessage::RequestMembers);
+    }
+    if let Some(organization) = &player_info.organization
+        && let Some(client) = network_client.as_mut()
+    {
+        client.send_message(ClientMessage::RequestTreasury);
+        client.send_message(ClientMessage::RequestOrganizationHierarchy {
+            organization_id: organization.id,
+        });
+        client.send_message(ClientMessage::RequestDiplomacy);
+    }
+
     let font_bold = asset_server.load("fonts/FiraSans-Bold.ttf");
     let font_regular = asset_server.load("fonts/FiraSans-Regular.ttf");
 
@@ -69,7 +93,48 @@ pub fn setup_management_panel(
             .with_children(|panel| {
                 match &player_info.organization {
                     Some(org) => {
-                        spawn_org_content(panel, org, &font_bold, &font_regular, &asset_server);
+                        spawn_tab_bar(panel, &font_bold);
+                        panel
+                            .spawn((
+                                Node {
+                                    width: Val::Percent(100.0),
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(16.0),
+                                    ..default()
+                                },
+                                ManagementTabContent(ManagementTab::Overview),
+                            ))
+                            .with_children(|overview| {
+                                spawn_org_content(overview, org, &font_bold, &font_regular, &asset_server);
+                            });
+                        panel
+                            .spawn((
+                                Node {
+                                    width: Val::Percent(100.0),
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(8.0),
+                                    display: Display::None,
+                                    ..default()
+                                },
+                                ManagementTabContent(ManagementTab::Members),
+                            ))
+                            .with_children(|members| {
+                                spawn_members_content(members, &font_bold);
+                            });
+                        panel
+                            .spawn((
+                                Node {
+                                    width: Val::Percent(100.0),
+                                    flex_direction: FlexDirection::Column,
+                                    row_gap: Val::Px(8.0),
+                                    display: Display::None,
+                                    ..default()
+                                },
+                                ManagementTabContent(ManagementTab::Diplomacy),
+                            ))
+                            .with_children(|diplomacy| {
+                                spawn_diplomacy_content(diplomacy, &font_bold, &font_regular);
+                            });
                     }
                     None => {
                         spawn_no_org_content(panel, &font_bold, &font_regular);
@@ -79,6 +144,115 @@ pub fn setup_management_panel(
         });
 }
 
+/// Onglets du panneau : vue d'ensemble, membres et diplomatie
+fn spawn_tab_bar(panel: &mut RelatedSpawnerCommands<ChildOf>, font_bold: &Handle<Font>) {
+    panel
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(8.0),
+            ..default()
+        })
+        .with_children(|tabs| {
+            for (tab, label) in [
+                (ManagementTab::Overview, "Domaine"),
+                (ManagementTab::Members, "Membres"),
+                (ManagementTab::Diplomacy, "Diplomatie"),
+            ] {
+                tabs.spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                        border_radius: BorderRadius::all(Val::Px(4.0)),
+                        ..default()
+                    },
+                    BackgroundColor(if tab == ManagementTab::Overview {
+                        TAB_SELECTED
+                    } else {
+                        TAB_IDLE
+                    }),
+                    ManagementTabButton(tab),
+                ))
+                .with_children(|btn| {
+                    btn.spawn((
+                        Text::new(label),
+                        TextFont {
+                            font: font_bold.clone(),
+                            font_size: 13.0,
+                            ..default()
+                        },
+                        TextColor(TEXT_DARK),
+                    ));
+                });
+            }
+        });
+}
+
+fn spawn_members_content(panel: &mut RelatedSpawnerCommands<ChildOf>, font_bold: &Handle<Font>) {
+    panel.spawn((
+        Text::new("MEMBRES"),
+        TextFont {
+            font: font_bold.clone(),
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(TEXT_DARK),
+    ));
+
+    spawn_members_list(panel);
+}
+
+fn spawn_members_list(panel: &mut RelatedSpawnerCommands<ChildOf>) {
+    panel.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(2.0),
+            max_height: Val::Px(400.0),
+            overflow: Overflow::scroll_y(),
+            ..default()
+        },
+        MembersList,
+    ));
+}
+
+fn spawn_diplomacy_content(
+    panel: &mut RelatedSpawnerCommands<ChildOf>,
+    font_bold: &Handle<Font>,
+    font_regular: &Handle<Font>,
+) {
+    panel.spawn((
+        Text::new("RELATIONS"),
+        TextFont {
+            font: font_bold.clone(),
+            font_size: 14.0,
+            ..default()
+        },
+        TextColor(TEXT_DARK),
+    ));
+
+    panel.spawn((
+        Text::new("Les réponses partent avec votre seigneur comme émissaire"),
+        TextFont {
+            font: font_regular.clone(),
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(TEXT_DIM),
+    ));
+
+    panel.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(2.0),
+            max_height: Val::Px(400.0),
+            overflow: Overflow::scroll_y(),
+            ..default()
+        },
+        DiplomacyList,
+    ));
+}
+
 fn spawn_org_content(
     panel: &mut RelatedSpawnerCommands<ChildOf>,
     org: &shared::OrganizationSummary,
@@ -177,24 +351,141 @@ fn spawn_org_content(
             });
     }
 
-    // Placeholder for future: buildings list, treasury, etc.
-    panel.spawn((
-        Node {
-            margin: UiRect::top(Val::Px(16.0)),
-            ..default()
-        },
-    ))
-    .with_children(|section| {
-        section.spawn((
-            Text::new("Bâtiments et gestion à venir..."),
+    // Settlement growth: the server checks population, territory, buildings and gold
+    if org.organization_type.category() == shared::OrganizationCategory::Territorial {
+        panel
+            .spawn((
+                Button,
+                Node {
+                    align_self: AlignSelf::FlexEnd,
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(4.0)),
+                    border_radius: BorderRadius::all(Val::Px(4.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.1)),
+                SettlementUpgradeButton,
+            ))
+            .with_children(|btn| {
+                btn.spawn((
+                    Text::new("Élever l'établissement"),
+                    TextFont {
+                        font: font_bold.clone(),
+                        font_size: 13.0,
+                        ..default()
+                    },
+                    TextColor(TEXT_DARK),
+                ));
+            });
+    }
+
+    // Vassalage: liege, vassals and pending offers
+    if org.organization_type.category() == shared::OrganizationCategory::Territorial {
+        panel.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(1.0),
+                margin: UiRect::top(Val::Px(8.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.55, 0.45, 0.30, 0.3)),
+        ));
+
+        panel.spawn((
+            Text::new("VASSALITÉ"),
             TextFont {
-                font: font_regular.clone(),
-                font_size: 13.0,
+                font: font_bold.clone(),
+                font_size: 14.0,
+                ..default()
+            },
+            TextColor(TEXT_DARK),
+        ));
+
+        panel.spawn((
+            Node {
+                width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(2.0),
+                max_height: Val::Px(160.0),
+                overflow: Overflow::scroll_y(),
                 ..default()
             },
-            TextColor(Color::srgba(0.5, 0.4, 0.3, 0.5)),
+            HierarchyList,
         ));
-    });
+    }
+
+    // Treasury: gold, stored items and what the lord can deposit
+    panel.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Px(1.0),
+            margin: UiRect::top(Val::Px(8.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.55, 0.45, 0.30, 0.3)),
+    ));
+
+    panel
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            justify_content: JustifyContent::SpaceBetween,
+            width: Val::Percent(100.0),
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new("TRÉSOR"),
+                TextFont {
+                    font: font_bold.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(TEXT_DARK),
+            ));
+            row.spawn((
+                Text::new("- or"),
+                TextFont {
+                    font: font_bold.clone(),
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor(GOLD),
+                TreasuryGoldText,
+            ));
+        });
+
+    panel.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(2.0),
+            max_height: Val::Px(160.0),
+            overflow: Overflow::scroll_y(),
+            ..default()
+        },
+        TreasuryItemsList,
+    ));
+
+    panel.spawn((
+        Text::new("Déposer (le seigneur doit être sur le territoire)"),
+        TextFont {
+            font: font_regular.clone(),
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(TEXT_DIM),
+    ));
+
+    panel.spawn((
+        Node {
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            row_gap: Val::Px(2.0),
+            max_height: Val::Px(120.0),
+            overflow: Overflow::scroll_y(),
+            ..default()
+        },
+        TreasuryDepositList,
+    ));
 }
 
 fn spawn_no_org_content(
@@ -213,7 +504,7 @@ fn spawn_no_org_content(
     ));
 
     panel.spawn((
-        Text::new("Fondez un hameau pour commencer !\nSélectionnez votre Lord, puis clic droit → Fonder un hameau."),
+        Text::new("Fondez un hameau pour commencer !\nSélectionnez votre Lord, puis clic droit → Fonder un hameau.\nOu rejoignez une organisation existante."),
         TextFont {
             font: font_regular.clone(),
             font_size: 14.0,
@@ -221,4 +512,7 @@ fn spawn_no_org_content(
         },
         TextColor(TEXT_DIM),
     ));
+
+    // Invitations reçues et candidature à l'organisation sous la caméra
+    spawn_members_list(panel);
 }
\ No newline at end of file
@@ -0,0 +1,668 @@
+use bevy::ecs::relationship::RelatedSpawnerCommands;
+use bevy::prelude::*;
+
+use shared::{MembershipRequestKind, OrganizationHierarchy, OrganizationPermission, RoleType};
+use shared::protocol::ClientMessage;
+
+use super::setup::{TAB_IDLE, TAB_SELECTED};
+
+use crate::networking::client::NetworkClient;
+use crate::state::resources::{
+    CurrentOrganization, DiplomacyCache, GameDataCache, HierarchyCache, InventoryCache,
+    MembersCache, PlayerInfo, TreasuryCache, UnitsDataCache,
+};
+use crate::ui::systems::panels::components::{
+    AcceptVassalageButton, AnswerProposalButton, ApplyToOrganizationButton, AppointOfficerButton,
+    DiplomacyList, ExpelMemberButton, HierarchyList, InviteLordButton, LeaveOrganizationButton,
+    ManagementTabButton, ManagementTabContent, MembersList, MembershipAnswerButton,
+    RevokeVassalageButton, TreasuryDepositButton, TreasuryDepositList, TreasuryGoldText,
+    TreasuryItemsList, TreasuryWithdrawButton, VassalageOfferButton,
+};
+
+/// Met à jour l'or et la liste des objets du trésor
+pub fn update_treasury_panel(
+    mut commands: Commands,
+    treasury: Res<TreasuryCache>,
+    game_data: Res<GameDataCache>,
+    mut gold_query: Query<&mut Text, With<TreasuryGoldText>>,
+    list_query: Query<Entity, With<TreasuryItemsList>>,
+    added_query: Query<(), Added<TreasuryItemsList>>,
+) {
+    if !treasury.is_changed() && added_query.is_empty() {
+        return;
+    }
+
+    for mut text in &mut gold_query {
+        **text = format!("{} or", treasury.gold);
+    }
+
+    for entity in &list_query {
+        commands.entity(entity).despawn_related::<Children>();
+        commands.entity(entity).with_children(|parent| {
+            if treasury.items.is_empty() {
+                spawn_empty_line(parent, "Le trésor est vide");
+            }
+            for item in &treasury.items {
+                spawn_item_row(
+                    parent,
+                    format!(
+                        "{} x{} (qualité {:.0}%)",
+                        game_data.item_name(item.item_id, 1), // FR
+                        item.quantity,
+                        item.quality * 100.0
+                    ),
+                    treasury.can_withdraw.then_some((
+                        "Retirer",
+                        TreasuryWithdrawButton {
+                            item_id: item.item_id,
+                            quantity: item.quantity,
+                        },
+                    )),
+                );
+            }
+        });
+    }
+}
+
+/// Reconstruit la liste des objets que le seigneur peut déposer (hors équipement)
+pub fn update_treasury_deposit_list(
+    mut commands: Commands,
+    inventory_cache: Res<InventoryCache>,
+    player_info: Res<PlayerInfo>,
+    game_data: Res<GameDataCache>,
+    list_query: Query<Entity, With<TreasuryDepositList>>,
+    added_query: Query<(), Added<TreasuryDepositList>>,
+) {
+    if !inventory_cache.is_changed() && added_query.is_empty() {
+        return;
+    }
+    let Some(lord) = &player_info.lord else {
+        return;
+    };
+
+    let equipped = inventory_cache.equipment.get(&lord.id);
+    let items: Vec<(i32, i32)> = inventory_cache
+        .get_inventory(lord.id)
+        .map(|items| {
+            items
+                .iter()
+                .map(|item| {
+                    let worn = equipped
+                        .map(|slots| slots.iter().filter(|e| e.item_id == item.item_id).count() as i32)
+                        .unwrap_or(0);
+                    (item.item_id, item.quantity - worn)
+                })
+                .filter(|(_, movable)| *movable > 0)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    for entity in &list_query {
+        commands.entity(entity).despawn_related::<Children>();
+        commands.entity(entity).with_children(|parent| {
+            if items.is_empty() {
+                spawn_empty_line(parent, "Rien à déposer");
+            }
+            for &(item_id, quantity) in &items {
+                spawn_item_row(
+                    parent,
+                    format!("{} x{}", game_data.item_name(item_id, 1), quantity), // FR
+                    Some(("Déposer", TreasuryDepositButton { item_id, quantity })),
+                );
+            }
+        });
+    }
+}
+
+/// Redemande l'arbre de vassalité quand un lien ou une proposition a changé
+pub fn refresh_hierarchy(
+    mut hierarchy_cache: ResMut<HierarchyCache>,
+    player_info: Res<PlayerInfo>,
+    mut network_client: Option<ResMut<NetworkClient>>,
+) {
+    if !hierarchy_cache.stale {
+        return;
+    }
+    let (Some(organization), Some(client)) = (&player_info.organization, network_client.as_mut())
+    else {
+        return;
+    };
+    client.send_message(ClientMessage::RequestOrganizationHierarchy {
+        organization_id: organization.id,
+    });
+    hierarchy_cache.stale = false;
+}
+
+/// Reconstruit l'arbre de vassalité, les propositions en cours et, si la caméra est
+/// sur une autre organisation, les propositions qu'on peut lui faire
+pub fn update_hierarchy_list(
+    mut commands: Commands,
+    hierarchy_cache: Res<HierarchyCache>,
+    player_info: Res<PlayerInfo>,
+    current_organization: Res<CurrentOrganization>,
+    list_query: Query<Entity, With<HierarchyList>>,
+    added_query: Query<(), Added<HierarchyList>>,
+) {
+    if !hierarchy_cache.is_changed() && !current_organization.is_changed() && added_query.is_empty() {
+        return;
+    }
+    let Some(own) = &player_info.organization else {
+        return;
+    };
+    // Seul le chef noue et rompt les liens
+    let is_leader = player_info.lord.as_ref().map(|lord| lord.id) == own.leader_unit_id;
+    let liege_id = hierarchy_cache.liege_of(own.id);
+
+    for entity in &list_query {
+        commands.entity(entity).despawn_related::<Children>();
+        commands.entity(entity).with_children(|parent| {
+            match hierarchy_cache.root_organization_id {
+                Some(root) => {
+                    for (organization_id, depth) in
+                        OrganizationHierarchy::walk(root, &hierarchy_cache.hierarchy)
+                    {
+                        let Some(organization) = hierarchy_cache.organization(organization_id) else {
+                            continue;
+                        };
+                        let is_direct_vassal = hierarchy_cache.liege_of(organization_id) == Some(own.id);
+                        let button = if !is_leader {
+                            None
+                        } else if liege_id == Some(organization_id) {
+                            Some("Rompre")
+                        } else if is_direct_vassal {
+                            Some("Affranchir")
+                        } else {
+                            None
+                        };
+                        // Décalage selon la profondeur dans l'arbre
+                        let node = Node {
+                            padding: UiRect::new(
+                                Val::Px(4.0 + 16.0 * depth as f32),
+                                Val::Px(4.0),
+                                Val::Px(2.0),
+                                Val::Px(2.0),
+                            ),
+                            ..row_node()
+                        };
+                        parent.spawn(node).with_children(|row| {
+                            spawn_row_label(
+                                row,
+                                format!(
+                                    "{}{} ({})",
+                                    if organization_id == own.id { "▸ " } else { "" },
+                                    organization.name,
+                                    organization.organization_type.to_string()
+                                ),
+                            );
+                            if let Some(text) = button {
+                                spawn_row_button(row, text, RevokeVassalageButton { organization_id });
+                            }
+                        });
+                    }
+                }
+                None => spawn_empty_line(parent, "Chargement..."),
+            }
+
+            for offer in &hierarchy_cache.offers {
+                let offered_by_us = offer.offered_by_organization_id == own.id;
+                let other_id = if offer.liege_organization_id == own.id {
+                    offer.vassal_organization_id
+                } else {
+                    offer.liege_organization_id
+                };
+                let label = format!(
+                    "Proposition : {} vassal de {}{}",
+                    offer.vassal_name,
+                    offer.liege_name,
+                    if offered_by_us { " (en attente)" } else { "" }
+                );
+                parent
+                    .spawn(row_node())
+                    .with_children(|row| {
+                        spawn_row_label(row, label);
+                        if !is_leader {
+                            return;
+                        }
+                        if !offered_by_us {
+                            spawn_row_button(row, "Accepter", AcceptVassalageButton { offer_id: offer.id });
+                        }
+                        spawn_row_button(
+                            row,
+                            if offered_by_us { "Retirer" } else { "Refuser" },
+                            RevokeVassalageButton { organization_id: other_id },
+                        );
+                    });
+            }
+
+            // Organisation sous la caméra : prêter allégeance ou proposer la vassalité
+            if !is_leader {
+                return;
+            }
+            let Some(other) = &current_organization.organization else {
+                return;
+            };
+            if other.id == own.id || hierarchy_cache.organization(other.id).is_some() {
+                return;
+            }
+            let (Some(own_rank), Some(other_rank)) = (
+                own.organization_type.territorial_rank(),
+                other.organization_type.territorial_rank(),
+            ) else {
+                return;
+            };
+            if other_rank > own_rank && liege_id.is_none() {
+                spawn_item_row(
+                    parent,
+                    other.name.clone(),
+                    Some((
+                        "Prêter allégeance",
+                        VassalageOfferButton {
+                            liege_organization_id: other.id,
+                            vassal_organization_id: own.id,
+                        },
+                    )),
+                );
+            } else if other_rank < own_rank {
+                spawn_item_row(
+                    parent,
+                    other.name.clone(),
+                    Some((
+                        "Proposer la vassalité",
+                        VassalageOfferButton {
+                            liege_organization_id: own.id,
+                            vassal_organization_id: other.id,
+                        },
+                    )),
+                );
+            }
+        });
+    }
+}
+
+/// Redemande les relations diplomatiques après un changement annoncé par le serveur
+pub fn refresh_diplomacy(
+    mut diplomacy_cache: ResMut<DiplomacyCache>,
+    mut network_client: Option<ResMut<NetworkClient>>,
+) {
+    if !diplomacy_cache.stale {
+        return;
+    }
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+    client.send_message(ClientMessage::RequestDiplomacy);
+    diplomacy_cache.stale = false;
+}
+
+/// Affiche l'onglet sélectionné
+pub fn update_management_tabs(
+    tab_query: Query<(&Interaction, &ManagementTabButton), Changed<Interaction>>,
+    mut buttons_query: Query<(&ManagementTabButton, &mut BackgroundColor)>,
+    mut content_query: Query<(&ManagementTabContent, &mut Node)>,
+) {
+    let Some(selected) = tab_query
+        .iter()
+        .find(|(interaction, _)| matches!(interaction, Interaction::Pressed))
+        .map(|(_, button)| button.0)
+    else {
+        return;
+    };
+    for (button, mut background) in &mut buttons_query {
+        background.0 = if button.0 == selected { TAB_SELECTED } else { TAB_IDLE };
+    }
+    for (content, mut node) in &mut content_query {
+        node.display = if content.0 == selected {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+/// Reconstruit la liste des relations et des propositions en attente
+pub fn update_diplomacy_list(
+    mut commands: Commands,
+    diplomacy_cache: Res<DiplomacyCache>,
+    player_info: Res<PlayerInfo>,
+    list_query: Query<Entity, With<DiplomacyList>>,
+    added_query: Query<(), Added<DiplomacyList>>,
+) {
+    if !diplomacy_cache.is_changed() && added_query.is_empty() {
+        return;
+    }
+    let Some(own) = &player_info.organization else {
+        return;
+    };
+    let is_leader = player_info.lord.as_ref().map(|lord| lord.id) == own.leader_unit_id;
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    for entity in &list_query {
+        commands.entity(entity).despawn_related::<Children>();
+        commands.entity(entity).with_children(|parent| {
+            if diplomacy_cache.organization_id.is_none() {
+                spawn_empty_line(parent, "Chargement...");
+                return;
+            }
+
+            let relations: Vec<_> = diplomacy_cache
+                .relations
+                .iter()
+                .filter(|relation| !relation.is_expired(now))
+                .collect();
+            if relations.is_empty() {
+                spawn_empty_line(parent, "Aucune relation");
+            }
+            for relation in relations {
+                let remaining = relation
+                    .expires_at
+                    .map(|expires_at| format!(" (encore {} j)", (expires_at - now + 86_399) / 86_400))
+                    .unwrap_or_default();
+                spawn_item_row(
+                    parent,
+                    format!(
+                        "{} : {}{}",
+                        diplomacy_cache.organization_name(relation.target_organization_id),
+                        relation.relation_type.to_name(),
+                        remaining
+                    ),
+                    None::<(&str, AnswerProposalButton)>,
+                );
+            }
+
+            for proposal in &diplomacy_cache.proposals {
+                if proposal.organization_id == own.id {
+                    spawn_item_row(
+                        parent,
+                        format!(
+                            "Proposé à {} : {} (en attente)",
+                            proposal.target_name,
+                            proposal.relation_type.to_name()
+                        ),
+                        None::<(&str, AnswerProposalButton)>,
+                    );
+                    continue;
+                }
+                let label = format!(
+                    "{} propose : {}",
+                    proposal.organization_name,
+                    proposal.relation_type.to_name()
+                );
+                parent.spawn(row_node()).with_children(|row| {
+                    spawn_row_label(row, label);
+                    if !is_leader {
+                        return;
+                    }
+                    for (text, accept) in [("Accepter", true), ("Refuser", false)] {
+                        spawn_row_button(
+                            row,
+                            text,
+                            AnswerProposalButton {
+                                proposal_id: proposal.id,
+                                organization_id: proposal.organization_id,
+                                accept,
+                            },
+                        );
+                    }
+                });
+            }
+        });
+    }
+}
+
+/// Redemande les membres après un changement annoncé par le serveur
+pub fn refresh_members(
+    mut members_cache: ResMut<MembersCache>,
+    mut network_client: Option<ResMut<NetworkClient>>,
+) {
+    if !members_cache.stale {
+        return;
+    }
+    let Some(client) = network_client.as_mut() else {
+        return;
+    };
+    client.send_message(ClientMessage::RequestMembers);
+    members_cache.stale = false;
+}
+
+/// Charge suivante dans l'ordre des charges, `None` après la dernière (révocation)
+fn next_role(role: Option<RoleType>) -> Option<RoleType> {
+    match role {
+        None => Some(RoleType::APPOINTABLE[0]),
+        Some(role) => RoleType::APPOINTABLE
+            .iter()
+            .position(|r| *r == role)
+            .and_then(|i| RoleType::APPOINTABLE.get(i + 1))
+            .copied(),
+    }
+}
+
+/// Reconstruit la liste des membres, des demandes en attente et des seigneurs
+/// qu'on peut inviter ; sans organisation, les invitations reçues et la candidature
+/// à l'organisation sous la caméra
+pub fn update_members_list(
+    mut commands: Commands,
+    members_cache: Res<MembersCache>,
+    player_info: Res<PlayerInfo>,
+    current_organization: Res<CurrentOrganization>,
+    units_data_cache: Option<Res<UnitsDataCache>>,
+    list_query: Query<Entity, With<MembersList>>,
+    added_query: Query<(), Added<MembersList>>,
+) {
+    if !members_cache.is_changed() && !current_organization.is_changed() && added_query.is_empty() {
+        return;
+    }
+    let Some(lord) = &player_info.lord else {
+        return;
+    };
+    let is_leader = player_info
+        .organization
+        .as_ref()
+        .is_some_and(|own| own.leader_unit_id == Some(lord.id));
+    let can_manage = members_cache.can(OrganizationPermission::ManageMembers);
+
+    for entity in &list_query {
+        commands.entity(entity).despawn_related::<Children>();
+        commands.entity(entity).with_children(|parent| {
+            if !members_cache.loaded {
+                spawn_empty_line(parent, "Chargement...");
+                return;
+            }
+
+            for member in &members_cache.members {
+                let title = match (member.is_leader, member.role) {
+                    (true, _) => " (chef)".to_string(),
+                    (false, Some(role)) => format!(" — {}", role.to_name()),
+                    (false, None) => String::new(),
+                };
+                let label = format!("{}{}", member.name, title);
+                parent.spawn(row_node()).with_children(|row| {
+                    spawn_row_label(row, label);
+                    if member.is_leader {
+                        return;
+                    }
+                    if member.unit_id == lord.id {
+                        spawn_row_button(row, "Quitter", LeaveOrganizationButton);
+                        return;
+                    }
+                    if is_leader {
+                        let role = next_role(member.role);
+                        let text = role.map(|role| role.to_name()).unwrap_or("Révoquer");
+                        spawn_row_button(
+                            row,
+                            text,
+                            AppointOfficerButton {
+                                unit_id: member.unit_id,
+                                role,
+                            },
+                        );
+                    }
+                    if can_manage {
+                        spawn_row_button(row, "Exclure", ExpelMemberButton { unit_id: member.unit_id });
+                    }
+                });
+            }
+
+            for request in &members_cache.requests {
+                let mine = request.unit_id == lord.id;
+                // Qui doit répondre : le seigneur pour une invitation, l'organisation pour une candidature
+                let to_answer = mine == (request.kind == MembershipRequestKind::Invitation);
+                let label = match (request.kind, mine) {
+                    (MembershipRequestKind::Invitation, true) => {
+                        format!("{} vous invite", request.organization_name)
+                    }
+                    (MembershipRequestKind::Invitation, false) => {
+                        format!("Invitation envoyée à {} (en attente)", request.unit_name)
+                    }
+                    (MembershipRequestKind::Application, true) => {
+                        format!("Candidature à {} (en attente)", request.organization_name)
+                    }
+                    (MembershipRequestKind::Application, false) => {
+                        format!("{} demande à entrer", request.unit_name)
+                    }
+                };
+                parent.spawn(row_node()).with_children(|row| {
+                    spawn_row_label(row, label);
+                    if to_answer {
+                        spawn_row_button(
+                            row,
+                            "Accepter",
+                            MembershipAnswerButton {
+                                request_id: request.id,
+                                accept: true,
+                            },
+                        );
+                    }
+                    spawn_row_button(
+                        row,
+                        if to_answer { "Refuser" } else { "Retirer" },
+                        MembershipAnswerButton {
+                            request_id: request.id,
+                            accept: false,
+                        },
+                    );
+                });
+            }
+
+            if player_info.organization.is_none() {
+                // Candidature à l'organisation sous la caméra
+                let Some(other) = &current_organization.organization else {
+                    return;
+                };
+                if members_cache.requests.iter().any(|r| r.organization_id == other.id) {
+                    return;
+                }
+                spawn_item_row(
+                    parent,
+                    other.name.clone(),
+                    Some((
+                        "Demander à entrer",
+                        ApplyToOrganizationButton {
+                            organization_id: other.id,
+                        },
+                    )),
+                );
+                return;
+            }
+
+            // Seigneurs des autres joueurs visibles alentour
+            let (true, Some(units)) = (can_manage, &units_data_cache) else {
+                return;
+            };
+            for unit_id in units.get_all_unit_ids() {
+                let Some(unit) = units.get_unit(unit_id) else {
+                    continue;
+                };
+                if !unit.is_lord
+                    || unit.player_id.is_none()
+                    || unit.id == lord.id
+                    || members_cache.members.iter().any(|m| m.unit_id == unit.id)
+                    || members_cache.requests.iter().any(|r| r.unit_id == unit.id)
+                {
+                    continue;
+                }
+                spawn_item_row(
+                    parent,
+                    unit.full_name(),
+                    Some(("Inviter", InviteLordButton { unit_id: unit.id })),
+                );
+            }
+        });
+    }
+}
+
+fn spawn_empty_line(parent: &mut RelatedSpawnerCommands<ChildOf>, label: &str) {
+    parent.spawn((
+        Text::new(label),
+        TextFont {
+            font_size: 12.0,
+            ..default()
+        },
+        TextColor(Color::srgba(0.5, 0.4, 0.3, 0.7)),
+    ));
+}
+
+/// Ligne « nom xN » avec un bouton d'action optionnel
+fn spawn_item_row(
+    parent: &mut RelatedSpawnerCommands<ChildOf>,
+    label: String,
+    button: Option<(&str, impl Component)>,
+) {
+    parent.spawn(row_node()).with_children(|row| {
+        spawn_row_label(row, label);
+        if let Some((text, marker)) = button {
+            spawn_row_button(row, text, marker);
+        }
+    });
+}
+
+fn row_node() -> Node {
+    Node {
+        width: Val::Percent(100.0),
+        flex_direction: FlexDirection::Row,
+        justify_content: JustifyContent::SpaceBetween,
+        align_items: AlignItems::Center,
+        column_gap: Val::Px(4.0),
+        padding: UiRect::axes(Val::Px(4.0), Val::Px(2.0)),
+        ..default()
+    }
+}
+
+fn spawn_row_label(row: &mut RelatedSpawnerCommands<ChildOf>, label: String) {
+    row.spawn((
+        Node {
+            flex_grow: 1.0,
+            ..default()
+        },
+        Text::new(label),
+        TextFont {
+            font_size: 13.0,
+            ..default()
+        },
+        TextColor(Color::srgb(0.20, 0.15, 0.10)),
+    ));
+}
+
+fn spawn_row_button(row: &mut RelatedSpawnerCommands<ChildOf>, text: &str, marker: impl Component) {
+    row.spawn((
+        Button,
+        Node {
+            padding: UiRect::axes(Val::Px(8.0), Val::Px(2.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.1)),
+        marker,
+    ))
+    .with_children(|btn| {
+        btn.spawn((
+            Text::new(text),
+            TextFont {
+                font_size: 11.0,
+                ..default()
+            },
+            TextColor(Color::BLACK),
+        ));
+    });
+}
@@ -1,20 +1,29 @@
 use bevy::prelude::*;
 use shared::{
-    ActionStatusEnum, ActionTypeEnum, TerrainChunkId,
+    ActionStatusEnum, ActionTypeEnum, SkillEnum, TerrainChunkId, WorldPosition,
     grid::{GridCell, GridConfig},
-    protocol::ServerMessage,
+    protocol::{ErrorCode, ServerMessage},
 };
+use rand::{SeedableRng, rngs::StdRng};
 use sqlx::Row;
 use std::{
-    collections::{HashMap, HashSet},
-    sync::Arc,
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
 };
-use tokio::sync::RwLock;
+use tokio::sync::{Notify, RwLock, mpsc};
 
 use crate::dev::DevConfig;
 use crate::networking::Sessions;
 use crate::road::RoadSegment;
-use crate::{database::client::DatabaseTables, units::PortraitGenerator};
+use crate::database::client::DatabaseTables;
+use crate::database::tables::MaterialSource;
+use crate::units::{
+    PortraitGenerator, average_quality, award_skill_xp, best_effective_skill,
+    free_carry_capacity_kg, plan_material_draw, roll_craft_quality, roll_harvest,
+    split_by_capacity,
+};
 use shared::GameState;
 
 /// Convertit une cellule hexagonale en position monde (en pixels)
@@ -31,6 +40,62 @@ fn cell_to_world_pos(cell: &GridCell) -> Vec2 {
     layout.hex_to_world_pos(hex)
 }
 
+/// Temps Unix en millisecondes, l'unité de `start_time` / `completion_time`
+pub fn now_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+/// Prochaines échéances des actions (démarrage d'une action Pending, fin d'une action
+/// InProgress), triées par temps en ms. Les entrées d'une action annulée ou échouée sont
+/// retirées ; une entrée devenue inutile (pause, déjà traitée) est ignorée à son échéance.
+#[derive(Debug, Default)]
+struct ActionSchedule {
+    heap: BinaryHeap<Reverse<(u64, u64)>>,
+}
+
+impl ActionSchedule {
+    fn push(&mut self, due_ms: u64, action_id: u64) {
+        self.heap.push(Reverse((due_ms, action_id)));
+    }
+
+    fn next_due(&self) -> Option<u64> {
+        self.heap.peek().map(|Reverse((due_ms, _))| *due_ms)
+    }
+
+    /// Retire toutes les échéances d'une action
+    fn remove(&mut self, action_id: u64) {
+        self.heap.retain(|Reverse((_, id))| *id != action_id);
+    }
+
+    /// Retire et renvoie les actions arrivées à échéance à `now`
+    fn pop_due(&mut self, now: u64) -> Vec<u64> {
+        let mut due = Vec::new();
+        while let Some(Reverse((due_ms, action_id))) = self.heap.peek().copied() {
+            if due_ms > now {
+                break;
+            }
+            self.heap.pop();
+            due.push(action_id);
+        }
+        due
+    }
+}
+
+/// Délai avant de retenter un démarrage dont l'écriture en base a échoué
+const START_RETRY_MS: u64 = 1_000;
+
+/// Attente entre deux essais d'annulation d'une action en transition
+const TRANSITION_WAIT: Duration = Duration::from_millis(50);
+
+/// Unité qui vient de se libérer : (player_id, unit_id)
+pub type IdleUnit = (u64, u64);
+
+/// Ingrédients prélevés chez une unité : (unit_id, item_id, [(instance_id, qualité)])
+type ConsumedLot = (u64, i32, Vec<(u64, f32)>);
+
 #[derive(Debug, Clone)]
 pub struct ActionInfo {
     pub action_id: u64,
@@ -42,6 +107,11 @@ pub struct ActionInfo {
     pub start_time: u64,
     pub duration_ms: u64,
     pub completion_time: u64,
+    /// Temps restant au moment de la pause (uniquement pour une action Paused)
+    pub paused_remaining_ms: Option<u64>,
+    /// Un changement de statut est en cours d'écriture en base, hors du verrou du cache :
+    /// aucune autre transition n'est acceptée d'ici là (jamais persisté)
+    pub in_transition: bool,
     // TODO: Ajouter action_name et unit_ids
 }
 
@@ -53,6 +123,14 @@ pub struct ActionProcessor {
     dev_config: Arc<DevConfig>,
     // Cache des actions actives en mémoire pour éviter les requêtes DB constantes
     active_actions: Arc<RwLock<HashMap<u64, ActionInfo>>>,
+    schedule: Arc<Mutex<ActionSchedule>>,
+    // Réveille la boucle du processeur quand une action arrive à échéance plus tôt que prévu
+    wake: Arc<Notify>,
+    // Unités libérées, pour lancer l'action suivante de leur file
+    idle_units_tx: mpsc::UnboundedSender<IdleUnit>,
+    idle_units_rx: Mutex<Option<mpsc::UnboundedReceiver<IdleUnit>>>,
+    // Tirages des résultats de récolte / fabrication (graine fixe possible en dev)
+    outcome_rng: Mutex<StdRng>,
 }
 
 impl ActionProcessor {
@@ -63,6 +141,11 @@ impl ActionProcessor {
         grid_config: Arc<GridConfig>,
         dev_config: Arc<DevConfig>,
     ) -> Self {
+        let (idle_units_tx, idle_units_rx) = mpsc::unbounded_channel();
+        let outcome_rng = match dev_config.outcome_seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_os_rng(),
+        };
         Self {
             db_tables,
             sessions,
@@ -70,14 +153,30 @@ impl ActionProcessor {
             grid_config,
             dev_config,
             active_actions: Arc::new(RwLock::new(HashMap::new())),
+            schedule: Arc::new(Mutex::new(ActionSchedule::default())),
+            wake: Arc::new(Notify::new()),
+            idle_units_tx,
+            idle_units_rx: Mutex::new(Some(idle_units_rx)),
+            outcome_rng: Mutex::new(outcome_rng),
         }
     }
 
+    /// Récepteur des unités libérées (une seule fois, pour le gestionnaire de files)
+    pub fn take_idle_units_receiver(&self) -> Option<mpsc::UnboundedReceiver<IdleUnit>> {
+        self.idle_units_rx.lock().unwrap().take()
+    }
+
+    /// Signale qu'une unité est libre et peut lancer l'action suivante de sa file
+    pub fn notify_unit_idle(&self, player_id: u64, unit_id: u64) {
+        let _ = self.idle_units_tx.send((player_id, unit_id));
+    }
+
     /// Charge les actions actives depuis la base de données au démarrage
     pub async fn load_active_actions(&self) -> Result<(), String> {
         let actions = self.db_tables.actions.load_active_actions().await?;
 
         let mut active_actions = self.active_actions.write().await;
+        let mut schedule = self.schedule.lock().unwrap();
 
         for (
             action_id,
@@ -89,6 +188,7 @@ impl ActionProcessor {
             start_time,
             duration_ms,
             completion_time,
+            paused_remaining_ms,
         ) in actions
         {
             let action_info = ActionInfo {
@@ -101,8 +201,14 @@ impl ActionProcessor {
                 start_time,
                 duration_ms,
                 completion_time,
+                paused_remaining_ms,
+                in_transition: false,
             };
 
+            // Une action en pause n'a pas d'échéance tant qu'elle n'est pas reprise
+            if status != ActionStatusEnum::Paused {
+                schedule.push(Self::due_time(&action_info), action_id);
+            }
             active_actions.insert(action_id, action_info);
         }
 
@@ -110,762 +216,938 @@ impl ActionProcessor {
         Ok(())
     }
 
+    /// Prochaine échéance d'une action : son démarrage si elle est Pending, sa fin sinon
+    fn due_time(action_info: &ActionInfo) -> u64 {
+        match action_info.status {
+            ActionStatusEnum::Pending => action_info.start_time,
+            _ => action_info.completion_time,
+        }
+    }
+
     /// Ajoute une nouvelle action au cache
     pub async fn add_action(&self, action_info: ActionInfo) {
-        tracing::info!(
+        tracing::debug!(
             "Adding new action {} to active actions",
             action_info.action_id
         );
-        let mut active_actions = self.active_actions.write().await;
-        active_actions.insert(action_info.action_id, action_info);
+        let due = Self::due_time(&action_info);
+        let action_id = action_info.action_id;
+        self.active_actions
+            .write()
+            .await
+            .insert(action_id, action_info);
+
+        self.schedule_at(due, action_id);
     }
 
-    /// Système de tick principal - à appeler régulièrement
+    /// Planifie une échéance, en réveillant la boucle si elle passe avant les autres
+    fn schedule_at(&self, due: u64, action_id: u64) {
+        let mut schedule = self.schedule.lock().unwrap();
+        let earlier = schedule.next_due().is_none_or(|next| due < next);
+        schedule.push(due, action_id);
+        if earlier {
+            self.wake.notify_one();
+        }
+    }
+
+    /// Délai avant la prochaine échéance (None si aucune action n'est prévue)
+    fn time_until_next_due(&self) -> Option<Duration> {
+        let next = self.schedule.lock().unwrap().next_due()?;
+        Some(Duration::from_millis(next.saturating_sub(now_ms())))
+    }
+
+    /// Traite les actions arrivées à échéance
     pub async fn tick(&self) {
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+        let current_time = now_ms();
+        let due = self.schedule.lock().unwrap().pop_due(current_time);
+        if due.is_empty() {
+            return;
+        }
 
         // Traiter les transitions Pending -> InProgress
-        self.process_pending_actions(current_time).await;
+        self.process_pending_actions(&due).await;
 
         // Traiter les actions terminées InProgress -> Completed
-        self.process_completed_actions(current_time).await;
+        self.process_completed_actions(&due, current_time).await;
     }
 
-    /// Traite les actions Pending qui doivent passer à InProgress
-    async fn process_pending_actions(&self, _current_time: u64) {
-        let mut active_actions = self.active_actions.write().await;
-        let mut to_start = Vec::new();
+    /// Traite les actions Pending arrivées à échéance, qui passent à InProgress
+    async fn process_pending_actions(&self, due: &[u64]) {
+        // Passer les actions à InProgress sous le verrou, puis le relâcher pour les écritures :
+        // marquées en transition, elles ne peuvent être ni annulées ni mises en pause d'ici là
+        let started: Vec<ActionInfo> = {
+            let mut active_actions = self.active_actions.write().await;
+            due.iter()
+                .filter_map(|action_id| {
+                    let action_info = active_actions.get_mut(action_id).filter(|action_info| {
+                        action_info.status == ActionStatusEnum::Pending
+                            && !action_info.in_transition
+                    })?;
+                    action_info.status = ActionStatusEnum::InProgress;
+                    action_info.in_transition = true;
+                    Some(action_info.clone())
+                })
+                .collect()
+        };
+
+        for action_info in &started {
+            self.start_action(action_info).await;
+        }
+    }
+
+    /// Enregistre le démarrage d'une action, prépare son chantier et prévient le joueur
+    async fn start_action(&self, action_info: &ActionInfo) {
+        let action_id = action_info.action_id;
 
-        // Trouver les actions Pending qui doivent démarrer
-        for (action_id, action_info) in active_actions.iter() {
-            if action_info.status == ActionStatusEnum::Pending {
-                tracing::info!("Action {} is Pending, ready to start", action_id);
-                // Les actions démarrent immédiatement pour l'instant
-                // On pourrait ajouter une logique de file d'attente ici
-                to_start.push(*action_id);
+        // Mettre à jour la DB via la table
+        if let Err(e) = self
+            .db_tables
+            .actions
+            .update_action_status(action_id, ActionStatusEnum::InProgress)
+            .await
+        {
+            tracing::error!("Failed to update action {} to InProgress: {}", action_id, e);
+            // L'action redevient Pending et son démarrage sera retenté
+            if let Some(action_info) = self.active_actions.write().await.get_mut(&action_id) {
+                action_info.status = ActionStatusEnum::Pending;
+                action_info.in_transition = false;
             }
+            self.schedule_at(now_ms() + START_RETRY_MS, action_id);
+            return;
+        }
+
+        // Si c'est une action BuildBuilding, créer le bâtiment en construction
+        if action_info.action_type == ActionTypeEnum::BuildBuilding
+            && let Err(e) = self.create_building_for_action(action_id, action_info).await
+        {
+            tracing::error!("Failed to create building for action {}: {}", action_id, e);
+            // Continue quand même, l'action peut se terminer mais sans bâtiment
         }
 
-        // Mettre à jour les actions qui démarrent
-        for action_id in to_start {
-            if let Some(action_info) = active_actions.get_mut(&action_id) {
-                action_info.status = ActionStatusEnum::InProgress;
+        // Si c'est une action BuildRoad, créer le segment de route
+        if action_info.action_type == ActionTypeEnum::BuildRoad
+            && let Err(e) = self.create_road_for_action(action_id, action_info).await
+        {
+            tracing::error!("Failed to create road for action {}: {}", action_id, e);
+            // Continue quand même
+        }
 
-                // Mettre à jour la DB via la table
-                if let Err(e) = self
-                    .db_tables
-                    .actions
-                    .update_action_status(action_id, ActionStatusEnum::InProgress)
+        // La fin n'est planifiée qu'une fois le démarrage enregistré
+        if let Some(action_info) = self.active_actions.write().await.get_mut(&action_id) {
+            action_info.in_transition = false;
+        }
+        self.schedule_at(action_info.completion_time, action_id);
+
+        // Envoyer notification au joueur
+        let message = ServerMessage::ActionStatusUpdate {
+            action_id,
+            player_id: action_info.player_id,
+            chunk_id: action_info.chunk_id,
+            cell: action_info.cell,
+            status: ActionStatusEnum::InProgress,
+            action_type: action_info.action_type,
+            completion_time: action_info.completion_time,
+            action_name: None,
+            unit_ids: vec![],
+        };
+
+        self.send_message_to_player(action_info.player_id, message)
+            .await;
+
+        tracing::info!(
+            "Action {} started (InProgress) for player {}",
+            action_id,
+            action_info.player_id
+        );
+    }
+
+    /// Traite les actions InProgress arrivées à échéance
+    async fn process_completed_actions(&self, due: &[u64], current_time: u64) {
+        // Marquer les actions terminées sous le verrou, puis le relâcher pendant le traitement :
+        // une action Completed ne peut plus être annulée ni mise en pause entre-temps
+        let completed: Vec<ActionInfo> = {
+            let mut active_actions = self.active_actions.write().await;
+            due.iter()
+                .filter_map(|action_id| {
+                    let action_info = active_actions.get_mut(action_id).filter(|action_info| {
+                        action_info.status == ActionStatusEnum::InProgress
+                            && !action_info.in_transition
+                            && current_time >= action_info.completion_time
+                    })?;
+                    action_info.status = ActionStatusEnum::Completed;
+                    Some(action_info.clone())
+                })
+                .collect()
+        };
+        if completed.is_empty() {
+            return;
+        }
+
+        for action_info in &completed {
+            self.complete_action(action_info).await;
+        }
+
+        // Nettoyer les actions complétées du cache
+        let mut active_actions = self.active_actions.write().await;
+        for action_info in &completed {
+            if active_actions
+                .get(&action_info.action_id)
+                .is_some_and(|action| action.status == ActionStatusEnum::Completed)
+            {
+                active_actions.remove(&action_info.action_id);
+            }
+        }
+    }
+
+    /// Applique les effets d'une action terminée, donne l'XP et prévient les joueurs
+    async fn complete_action(&self, action_info: &ActionInfo) {
+        let action_id = action_info.action_id;
+
+        // Mettre à jour la DB via la table
+        if let Err(e) = self
+            .db_tables
+            .actions
+            .update_action_status(action_id, ActionStatusEnum::Completed)
+            .await
+        {
+            tracing::error!("Failed to update action {} to Completed: {}", action_id, e);
+            return;
+        }
+
+        // Unités affectées : leur skill joue sur la qualité et elles gagnent de l'XP
+        let worker_ids = self
+            .db_tables
+            .units
+            .load_units_working_on(action_id)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::error!("{}", e);
+                vec![]
+            });
+        let default_skill = self
+            .game_state
+            .action_xp_for(action_info.action_type)
+            .and_then(|xp| xp.skill);
+
+        // Compétence entraînée par l'action et multiplicateur d'XP
+        let training = match action_info.action_type {
+            ActionTypeEnum::BuildBuilding => {
+                self.complete_build_building(action_info).await;
+                default_skill.map(|skill| (skill, 1))
+            }
+            ActionTypeEnum::BuildRoad => {
+                self.complete_build_road(action_info).await;
+                default_skill.map(|skill| (skill, 1))
+            }
+            ActionTypeEnum::TrainUnit => {
+                self.complete_train_unit(action_info).await;
+                default_skill.map(|skill| (skill, 1))
+            }
+            ActionTypeEnum::ClaimCells => {
+                self.complete_claim_cells(action_info).await;
+                default_skill.map(|skill| (skill, 1))
+            }
+            ActionTypeEnum::SendEnvoy => {
+                self.complete_send_envoy(action_info).await;
+                default_skill.map(|skill| (skill, 1))
+            }
+            ActionTypeEnum::MoveUnit => {
+                self.complete_move_unit(action_info).await;
+                default_skill.map(|skill| (skill, 1))
+            }
+            ActionTypeEnum::HarvestResource => {
+                self.complete_harvest(action_info, &worker_ids, default_skill)
+                    .await
+            }
+            ActionTypeEnum::CraftResource => {
+                match self
+                    .complete_craft(action_info, &worker_ids, default_skill)
                     .await
                 {
-                    tracing::error!("Failed to update action {} to InProgress: {}", action_id, e);
-                    continue;
+                    Ok(training) => training,
+                    Err(reason) => {
+                        // Rien n'a été fabriqué : l'action échoue, sans XP
+                        tracing::error!("Craft action {} failed: {}", action_id, reason);
+                        if let Err(e) = self.fail_action(action_id).await {
+                            tracing::error!("Failed to fail craft action {}: {}", action_id, e);
+                        }
+                        return;
+                    }
                 }
+            }
+            _ => default_skill.map(|skill| (skill, 1)),
+        };
+
+        if let Some((skill, xp_multiplier)) = training {
+            self.award_action_xp(
+                action_info.player_id,
+                action_info.action_type,
+                &worker_ids,
+                skill,
+                xp_multiplier,
+            )
+            .await;
+        }
+
+        // Free units assigned to this action
+        self.release_units(action_id, action_info.player_id).await;
+
+        // Envoyer notification au joueur qui a lancé l'action
+        let status_message = ServerMessage::ActionStatusUpdate {
+            action_id,
+            player_id: action_info.player_id,
+            chunk_id: action_info.chunk_id,
+            cell: action_info.cell,
+            status: ActionStatusEnum::Completed,
+            action_type: action_info.action_type,
+            completion_time: action_info.completion_time,
+            action_name: None,
+            unit_ids: vec![],
+        };
+
+        self.send_message_to_player(action_info.player_id, status_message)
+            .await;
+
+        // Au prochain tick, on enverra le résultat aux joueurs du chunk
+        // Pour l'instant on envoie immédiatement
+        let completion_message = ServerMessage::ActionCompleted {
+            action_id,
+            chunk_id: action_info.chunk_id,
+            cell: action_info.cell,
+            action_type: action_info.action_type,
+        };
+
+        self.broadcast_to_chunk(&action_info.chunk_id, completion_message)
+            .await;
+
+        tracing::info!(
+            "Action {} completed for player {} at chunk ({}, {}) cell ({}, {})",
+            action_id,
+            action_info.player_id,
+            action_info.chunk_id.x,
+            action_info.chunk_id.y,
+            action_info.cell.q,
+            action_info.cell.r
+        );
+    }
+
+    /// Fin d'une construction : consommer les matériaux puis marquer le bâtiment comme construit
+    async fn complete_build_building(&self, action_info: &ActionInfo) {
+        let action_id = action_info.action_id;
+
+        // 1. Get building type ID from action
+        let bt_id = self
+            .db_tables
+            .actions
+            .get_build_building_type(action_id)
+            .await
+            .ok()
+            .flatten();
+
+        // 2. Consume construction materials, unless they were reserved
+        // when the action was scheduled
+        let reserved = self
+            .db_tables
+            .actions
+            .take_reservations(action_id)
+            .await
+            .unwrap_or_default();
+        if !self.dev_config.skip_resource_check()
+            && reserved.is_empty()
+            && let Some(bt_id) = bt_id
+        {
+            self.consume_building_costs(action_info, bt_id).await;
+        }
+
+        // 3. Mark building as built
+        if let Err(e) = self
+            .db_tables
+            .buildings
+            .mark_building_as_built(action_id)
+            .await
+        {
+            tracing::error!("Failed to mark building {} as built: {}", action_id, e);
+        } else {
+            tracing::info!("Building {} marked as built", action_id);
+        }
+    }
+
+    /// Une construction n'a pas d'unité affectée : le Lord paie les matériaux
+    async fn consume_building_costs(&self, action_info: &ActionInfo, bt_id: i16) {
+        let action_id = action_info.action_id;
+        let costs = self.game_state.building_costs(bt_id as i32);
+        if costs.is_empty() {
+            return;
+        }
+
+        let lord_unit_id = match self.find_lord_unit_id(action_info.player_id).await {
+            Ok(Some(lord_unit_id)) => lord_unit_id,
+            Ok(None) => {
+                tracing::error!(
+                    "No lord for player {} (build action {})",
+                    action_info.player_id,
+                    action_id
+                );
+                return;
+            }
+            Err(e) => {
+                tracing::error!("Failed to find lord for build: {}", e);
+                return;
+            }
+        };
 
-                // Si c'est une action BuildBuilding, créer le bâtiment en construction
-                if action_info.action_type == ActionTypeEnum::BuildBuilding {
-                    if let Err(e) = self
-                        .create_building_for_action(action_id, action_info)
+        for cost in costs {
+            match self
+                .db_tables
+                .resources
+                .consume_items(lord_unit_id, cost.item_id, cost.quantity)
+                .await
+            {
+                Ok(_) => {
+                    // Notify client
+                    let remaining = self
+                        .db_tables
+                        .resources
+                        .count_item_for_unit(lord_unit_id, cost.item_id)
                         .await
-                    {
-                        tracing::error!(
-                            "Failed to create building for action {}: {}",
-                            action_id,
-                            e
-                        );
-                        // Continue quand même, l'action peut se terminer mais sans bâtiment
-                    }
+                        .unwrap_or(0);
+                    let msg = ServerMessage::InventoryUpdate {
+                        unit_id: lord_unit_id,
+                        item_id: cost.item_id,
+                        quantity_delta: -cost.quantity,
+                        new_total: remaining,
+                    };
+                    self.send_message_to_player(action_info.player_id, msg)
+                        .await;
                 }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to consume building material item {} for action {}: {}",
+                        cost.item_id,
+                        action_id,
+                        e
+                    );
+                }
+            }
+        }
+        tracing::info!(
+            "Consumed construction materials for building type {} (action {})",
+            bt_id,
+            action_id
+        );
+    }
 
-                // Si c'est une action BuildRoad, créer le segment de route
-                if action_info.action_type == ActionTypeEnum::BuildRoad {
-                    if let Err(e) = self.create_road_for_action(action_id, action_info).await {
-                        tracing::error!("Failed to create road for action {}: {}", action_id, e);
-                        // Continue quand même
-                    }
+    /// Fin d'un segment de route : régénérer et envoyer la SDF de route
+    async fn complete_build_road(&self, action_info: &ActionInfo) {
+        tracing::info!(
+            "Road segment {} completed at chunk ({}, {}) cell ({}, {})",
+            action_info.action_id,
+            action_info.chunk_id.x,
+            action_info.chunk_id.y,
+            action_info.cell.q,
+            action_info.cell.r
+        );
+
+        // Note: La SDF a déjà été envoyée lors de la création du segment,
+        // mais on la régénère à nouveau pour garantir la cohérence
+        // On régénère pour le chunk et ses voisins pour gérer les routes diagonales
+        self.regenerate_road_sdf_for_chunk_and_neighbors(&action_info.chunk_id)
+            .await;
+    }
+
+    /// Fin d'une formation : mettre à jour la profession et le portrait de l'unité
+    async fn complete_train_unit(&self, action_info: &ActionInfo) {
+        let action_id = action_info.action_id;
+        let (unit_id, target_profession) =
+            match self.db_tables.actions.load_train_unit_data(action_id).await {
+                Ok(Some(data)) => data,
+                Ok(None) => {
+                    tracing::error!("No train_unit data found for action {}", action_id);
+                    return;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to load train_unit data for action {}: {}",
+                        action_id,
+                        e
+                    );
+                    return;
                 }
+            };
 
-                // Envoyer notification au joueur
-                let message = ServerMessage::ActionStatusUpdate {
-                    action_id,
-                    player_id: action_info.player_id,
-                    chunk_id: action_info.chunk_id,
-                    cell: action_info.cell,
-                    status: ActionStatusEnum::InProgress,
-                    action_type: action_info.action_type,
-                    completion_time: action_info.completion_time,
-                    action_name: None,
-                    unit_ids: vec![],
-                };
+        // Update profession in DB
+        if let Err(e) = self
+            .db_tables
+            .units
+            .update_unit_profession(unit_id, target_profession)
+            .await
+        {
+            tracing::error!("Failed to update unit {} profession: {}", unit_id, e);
+            return;
+        }
+        tracing::info!(
+            "Unit {} trained to {:?} (action {})",
+            unit_id,
+            target_profession,
+            action_id
+        );
+
+        // Regenerate avatar for new profession
+        let unit = self.db_tables.units.load_unit(unit_id).await.ok();
+        let new_avatar_url = if let Some(ref u) = unit {
+            let avatar_url = PortraitGenerator::generate_portrait_url(
+                &u.gender,
+                u.portrait_variant_id.as_deref().unwrap_or("02m"),
+                target_profession,
+            );
+            // Update avatar in DB
+            let _ = sqlx::query("UPDATE units.units SET avatar_url = $1 WHERE id = $2")
+                .bind(&avatar_url)
+                .bind(unit_id as i64)
+                .execute(&self.db_tables.pool)
+                .await;
+            Some(avatar_url)
+        } else {
+            None
+        };
+
+        // Notify the player
+        let profession_msg = ServerMessage::UnitProfessionChanged {
+            unit_id,
+            new_profession: target_profession,
+            new_avatar_url,
+        };
+        self.send_message_to_player(action_info.player_id, profession_msg)
+            .await;
+    }
 
-                self.send_message_to_player(action_info.player_id, message)
+    /// Fin d'une revendication : borner les cellules et redessiner les frontières
+    async fn complete_claim_cells(&self, action_info: &ActionInfo) {
+        let action_id = action_info.action_id;
+        match self.db_tables.actions.load_claim_cells_data(action_id).await {
+            Ok(Some((unit_id, organization_id, cells))) => {
+                self.complete_claim(action_info.player_id, unit_id, organization_id, &cells)
                     .await;
+            }
+            Ok(None) => {
+                tracing::error!("No claim_cells data found for action {}", action_id);
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load claim_cells data for action {}: {}",
+                    action_id,
+                    e
+                );
+            }
+        }
+    }
 
-                tracing::info!(
-                    "Action {} started (InProgress) for player {}",
+    /// Arrivée d'un émissaire : remettre sa proposition ou sa réponse
+    async fn complete_send_envoy(&self, action_info: &ActionInfo) {
+        let action_id = action_info.action_id;
+        match self
+            .db_tables
+            .actions
+            .load_send_envoy_data(
+                action_id,
+                action_info.player_id,
+                action_info.chunk_id,
+                action_info.cell,
+            )
+            .await
+        {
+            Ok(Some(envoy)) => {
+                crate::diplomacy::complete_envoy_mission(&self.db_tables, &self.sessions, &envoy)
+                    .await;
+            }
+            Ok(None) => {
+                tracing::error!("No send_envoy data found for action {}", action_id);
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to load send_envoy data for action {}: {}",
                     action_id,
-                    action_info.player_id
+                    e
                 );
             }
         }
     }
 
-    /// Traite les actions InProgress qui sont arrivées à échéance
-    async fn process_completed_actions(&self, current_time: u64) {
-        let mut active_actions = self.active_actions.write().await;
-        let mut to_complete = Vec::new();
+    /// Fin d'un déplacement : mettre à jour la position de l'unité
+    async fn complete_move_unit(&self, action_info: &ActionInfo) {
+        let action_id = action_info.action_id;
+
+        // Charger les données de l'action pour récupérer unit_id
+        let (unit_id, target_cell, target_chunk) =
+            match self.db_tables.actions.load_move_unit_data(action_id).await {
+                Ok(Some(data)) => data,
+                Ok(None) => {
+                    tracing::error!("No move_unit data found for action {}", action_id);
+                    return;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to load move_unit data for action {}: {}",
+                        action_id,
+                        e
+                    );
+                    return;
+                }
+            };
 
-        // Trouver les actions InProgress qui sont terminées
-        for (action_id, action_info) in active_actions.iter() {
-            tracing::info!(
-                "Checking action {}: status={:?}, completion_time={}, current_time={}",
-                action_id,
-                action_info.status,
-                action_info.completion_time,
-                current_time
-            );
-            if action_info.status == ActionStatusEnum::InProgress
-                && current_time >= action_info.completion_time
-            {
-                to_complete.push(*action_id);
+        // Récupérer la position actuelle avant la mise à jour
+        let (from_cell, from_chunk) = match self.db_tables.units.load_unit(unit_id).await {
+            Ok(unit) => (unit.current_cell, unit.current_chunk),
+            Err(e) => {
+                tracing::error!("Failed to load unit {} for move: {}", unit_id, e);
+                (action_info.cell, action_info.chunk_id)
             }
+        };
+
+        // Mettre à jour la position en DB
+        if let Err(e) = self
+            .db_tables
+            .units
+            .update_unit_position(unit_id, &target_cell, &target_chunk)
+            .await
+        {
+            tracing::error!("Failed to update unit {} position: {}", unit_id, e);
+            return;
         }
+        tracing::info!(
+            "Unit {} moved from ({},{}) to ({},{}) (action {})",
+            unit_id,
+            from_cell.q,
+            from_cell.r,
+            target_cell.q,
+            target_cell.r,
+            action_id
+        );
 
-        // Mettre à jour les actions terminées
-        for action_id in to_complete {
-            if let Some(action_info) = active_actions.get_mut(&action_id) {
-                action_info.status = ActionStatusEnum::Completed;
+        // Notifier le joueur et ceux qui voient le départ ou l'arrivée
+        let move_msg = ServerMessage::UnitPositionUpdated {
+            unit_id,
+            from_cell,
+            from_chunk,
+            to_cell: target_cell,
+            to_chunk: target_chunk,
+        };
+        self.sessions
+            .broadcast_to_chunks(
+                &[from_chunk, target_chunk],
+                Some(action_info.player_id),
+                move_msg,
+            )
+            .await;
+    }
 
-                // Mettre à jour la DB via la table
-                if let Err(e) = self
-                    .db_tables
-                    .actions
-                    .update_action_status(action_id, ActionStatusEnum::Completed)
-                    .await
-                {
-                    tracing::error!("Failed to update action {} to Completed: {}", action_id, e);
-                    continue;
-                }
+    /// Fin d'une récolte : crée des items chez les unités affectées (ou le Lord).
+    /// Renvoie la compétence entraînée et le multiplicateur d'XP (aucun si la cellule
+    /// était vide).
+    async fn complete_harvest(
+        &self,
+        action_info: &ActionInfo,
+        worker_ids: &[u64],
+        default_skill: Option<SkillEnum>,
+    ) -> Option<(SkillEnum, i64)> {
+        let action_id = action_info.action_id;
+        let resource_type = match self.db_tables.actions.load_harvest_data(action_id).await {
+            Ok(Some((_player_id, resource_type))) => resource_type,
+            Ok(None) => {
+                tracing::error!("No harvest data for action {}", action_id);
+                return default_skill.map(|skill| (skill, 1));
+            }
+            Err(e) => {
+                tracing::error!("Failed to load harvest data for action {}: {}", action_id, e);
+                return default_skill.map(|skill| (skill, 1));
+            }
+        };
 
-                // Si c'est une action BuildBuilding, consommer les matériaux puis marquer comme construit
-                if action_info.action_type == ActionTypeEnum::BuildBuilding {
-                    // 1. Get building type ID from action
-                    let bt_id = self
-                        .db_tables
-                        .actions
-                        .get_build_building_type(action_id)
-                        .await
-                        .ok()
-                        .flatten();
-
-                    // 2. Consume construction materials
-                    if !self.dev_config.skip_resource_check()
-                        && let Some(bt_id) = bt_id
-                    {
-                        let costs = self.game_state.building_costs(bt_id as i32);
-
-                        if !costs.is_empty() {
-                            match self.find_lord_unit_id(action_info.player_id).await {
-                                Ok(Some(lord_unit_id)) => {
-                                    for cost in costs {
-                                        match self
-                                            .db_tables
-                                            .resources
-                                            .consume_items(
-                                                lord_unit_id,
-                                                cost.item_id,
-                                                cost.quantity,
-                                            )
-                                            .await
-                                        {
-                                            Ok(_) => {
-                                                // Notify client
-                                                let remaining = self
-                                                    .db_tables
-                                                    .resources
-                                                    .count_item_for_unit(lord_unit_id, cost.item_id)
-                                                    .await
-                                                    .unwrap_or(0);
-                                                let msg = ServerMessage::InventoryUpdate {
-                                                    unit_id: lord_unit_id,
-                                                    item_id: cost.item_id,
-                                                    quantity_delta: -cost.quantity,
-                                                    new_total: remaining,
-                                                };
-                                                self.send_message_to_player(
-                                                    action_info.player_id,
-                                                    msg,
-                                                )
-                                                .await;
-                                            }
-                                            Err(e) => {
-                                                tracing::error!(
-                                                    "Failed to consume building material \
-                                                     item {} for action {}: {}",
-                                                    cost.item_id,
-                                                    action_id,
-                                                    e
-                                                );
-                                            }
-                                        }
-                                    }
-                                    tracing::info!(
-                                        "Consumed construction materials for building \
-                                         type {} (action {})",
-                                        bt_id,
-                                        action_id
-                                    );
-                                }
-                                Ok(None) => {
-                                    tracing::error!(
-                                        "No lord for player {} (build action {})",
-                                        action_info.player_id,
-                                        action_id
-                                    );
-                                }
-                                Err(e) => {
-                                    tracing::error!("Failed to find lord for build: {}", e);
-                                }
-                            }
-                        }
-                    }
+        // Read harvest yields from GameState cache
+        let yields = self.game_state.harvest_yields_for(resource_type.to_id());
+        if yields.is_empty() {
+            tracing::error!(
+                "No harvest yields defined for resource type {:?} (action {})",
+                resource_type,
+                action_id
+            );
+            return default_skill.map(|skill| (skill, 1));
+        }
 
-                    // 3. Mark building as built
-                    if let Err(e) = self
-                        .db_tables
-                        .buildings
-                        .mark_building_as_built(action_id)
-                        .await
-                    {
-                        tracing::error!("Failed to mark building {} as built: {}", action_id, e);
-                    } else {
-                        tracing::info!("Building {} marked as built", action_id);
-                    }
-                }
+        let trained_skill = yields[0].skill.or(default_skill);
+        let harvest_skill = match yields[0].skill {
+            Some(skill) => best_effective_skill(
+                &self.db_tables,
+                worker_ids,
+                skill,
+                &self.game_state.profession_skill_bonuses,
+            )
+            .await
+            .unwrap_or(0),
+            None => 0,
+        };
+
+        let recipients = match self.action_units(action_info.player_id, worker_ids).await {
+            Ok(Some(recipients)) => recipients,
+            Ok(None) => {
+                tracing::error!(
+                    "No lord found for player {} (action {})",
+                    action_info.player_id,
+                    action_id
+                );
+                return trained_skill.map(|skill| (skill, 1));
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to find lord for player {}: {}",
+                    action_info.player_id,
+                    e
+                );
+                return trained_skill.map(|skill| (skill, 1));
+            }
+        };
 
-                // Si c'est une action BuildRoad, régénérer et envoyer la SDF de route
-                if action_info.action_type == ActionTypeEnum::BuildRoad {
+        for (i, hy) in yields.iter().enumerate() {
+            let mut roll = roll_harvest(
+                &mut *self.outcome_rng.lock().unwrap(),
+                hy,
+                harvest_skill,
+            );
+            // La récolte principale puise dans la réserve de la cellule
+            if i == 0 {
+                roll.quantity = crate::resource_stocks::draw_harvest(
+                    &self.db_tables,
+                    &self.sessions,
+                    &action_info.chunk_id,
+                    &action_info.cell,
+                    resource_type,
+                    roll.quantity,
+                )
+                .await;
+                // Cellule vidée par une récolte concurrente : rien récolté, rien appris
+                if roll.quantity == 0 {
                     tracing::info!(
-                        "Road segment {} completed at chunk ({}, {}) cell ({}, {})",
+                        "Harvest action {} found nothing left at ({},{})",
                         action_id,
-                        action_info.chunk_id.x,
-                        action_info.chunk_id.y,
                         action_info.cell.q,
                         action_info.cell.r
                     );
-
-                    // Note: La SDF a déjà été envoyée lors de la création du segment,
-                    // mais on la régénère à nouveau pour garantir la cohérence
-                    // On régénère pour le chunk et ses voisins pour gérer les routes diagonales
-                    self.regenerate_road_sdf_for_chunk_and_neighbors(&action_info.chunk_id)
+                    let message = ServerMessage::ActionError {
+                        code: ErrorCode::ResourceDepleted,
+                        detail: None,
+                    };
+                    self.send_message_to_player(action_info.player_id, message)
                         .await;
+                    return None;
                 }
+            }
+            tracing::info!(
+                "Harvest completed (action {}): {} x item {} for units {:?}",
+                action_id,
+                roll.quantity,
+                hy.result_item_id,
+                recipients
+            );
+            self.deliver_items(
+                action_info,
+                &recipients,
+                hy.result_item_id,
+                roll.quantity,
+                roll.quality,
+            )
+            .await;
+        }
 
-                // Si c'est une action TrainUnit, mettre à jour la profession de l'unité
-                if action_info.action_type == ActionTypeEnum::TrainUnit {
-                    match self.db_tables.actions.load_train_unit_data(action_id).await {
-                        Ok(Some((unit_id, target_profession))) => {
-                            // Update profession in DB
-                            if let Err(e) = self
-                                .db_tables
-                                .units
-                                .update_unit_profession(unit_id, target_profession)
-                                .await
-                            {
-                                tracing::error!(
-                                    "Failed to update unit {} profession: {}",
-                                    unit_id,
-                                    e
-                                );
-                            } else {
-                                tracing::info!(
-                                    "Unit {} trained to {:?} (action {})",
-                                    unit_id,
-                                    target_profession,
-                                    action_id
-                                );
-
-                                // Regenerate avatar for new profession
-                                let unit = self.db_tables.units.load_unit(unit_id).await.ok();
-                                let new_avatar_url = if let Some(ref u) = unit {
-                                    let avatar_url = PortraitGenerator::generate_portrait_url(
-                                        &u.gender,
-                                        u.portrait_variant_id.as_deref().unwrap_or("02m"),
-                                        target_profession,
-                                    );
-                                    // Update avatar in DB
-                                    let _ = sqlx::query(
-                                        "UPDATE units.units SET avatar_url = $1 WHERE id = $2",
-                                    )
-                                    .bind(&avatar_url)
-                                    .bind(unit_id as i64)
-                                    .execute(&self.db_tables.pool)
-                                    .await;
-                                    Some(avatar_url)
-                                } else {
-                                    None
-                                };
-
-                                // Notify the player
-                                let profession_msg = ServerMessage::UnitProfessionChanged {
-                                    unit_id,
-                                    new_profession: target_profession,
-                                    new_avatar_url,
-                                };
-                                self.send_message_to_player(action_info.player_id, profession_msg)
-                                    .await;
-                            }
-                        }
-                        Ok(None) => {
-                            tracing::error!("No train_unit data found for action {}", action_id);
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                "Failed to load train_unit data for action {}: {}",
-                                action_id,
-                                e
-                            );
-                        }
-                    }
-                }
+        trained_skill.map(|skill| (skill, 1))
+    }
 
-                // Si c'est une action MoveUnit, mettre à jour la position de l'unité
-                if action_info.action_type == ActionTypeEnum::MoveUnit {
-                    // Charger les données de l'action pour récupérer unit_id
-                    match self.db_tables.actions.load_move_unit_data(action_id).await {
-                        Ok(Some((unit_id, target_cell, target_chunk))) => {
-                            // Récupérer la position actuelle avant la mise à jour
-                            let from_cell;
-                            let from_chunk;
-                            match self.db_tables.units.load_unit(unit_id).await {
-                                Ok(unit) => {
-                                    from_cell = unit.current_cell;
-                                    from_chunk = unit.current_chunk;
-                                }
-                                Err(e) => {
-                                    tracing::error!(
-                                        "Failed to load unit {} for move: {}",
-                                        unit_id,
-                                        e
-                                    );
-                                    from_cell = action_info.cell;
-                                    from_chunk = action_info.chunk_id;
-                                }
-                            }
-
-                            // Mettre à jour la position en DB
-                            if let Err(e) = self
-                                .db_tables
-                                .units
-                                .update_unit_position(unit_id, &target_cell, &target_chunk)
-                                .await
-                            {
-                                tracing::error!(
-                                    "Failed to update unit {} position: {}",
-                                    unit_id,
-                                    e
-                                );
-                            } else {
-                                tracing::info!(
-                                    "Unit {} moved from ({},{}) to ({},{}) (action {})",
-                                    unit_id,
-                                    from_cell.q,
-                                    from_cell.r,
-                                    target_cell.q,
-                                    target_cell.r,
-                                    action_id
-                                );
-
-                                // Notifier le joueur
-                                let move_msg = ServerMessage::UnitPositionUpdated {
-                                    unit_id,
-                                    from_cell,
-                                    from_chunk,
-                                    to_cell: target_cell,
-                                    to_chunk: target_chunk,
-                                };
-                                self.send_message_to_player(action_info.player_id, move_msg)
-                                    .await;
-                            }
-                        }
-                        Ok(None) => {
-                            tracing::error!("No move_unit data found for action {}", action_id);
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                "Failed to load move_unit data for action {}: {}",
-                                action_id,
-                                e
-                            );
-                        }
-                    }
-                }
+    /// Fin d'une fabrication : consomme les ingrédients et crée le résultat.
+    /// Renvoie la compétence entraînée et le multiplicateur d'XP, ou la raison de
+    /// l'échec si rien n'a pu être fabriqué.
+    async fn complete_craft(
+        &self,
+        action_info: &ActionInfo,
+        worker_ids: &[u64],
+        default_skill: Option<SkillEnum>,
+    ) -> Result<Option<(SkillEnum, i64)>, String> {
+        let action_id = action_info.action_id;
+        let (recipe_id_str, quantity) = self
+            .db_tables
+            .actions
+            .load_craft_data(action_id)
+            .await
+            .map_err(|e| format!("Failed to load craft data: {}", e))?
+            .map(|(_player_id, recipe_id_str, quantity)| (recipe_id_str, quantity))
+            .ok_or_else(|| "No craft data".to_string())?;
+
+        // Charger la recette — par ID numérique ou par slug
+        let recipe = match recipe_id_str.parse::<i32>() {
+            Ok(numeric_id) => self.db_tables.resources.load_recipe(numeric_id).await,
+            Err(_) => {
+                self.db_tables
+                    .resources
+                    .load_recipe_by_slug(&recipe_id_str)
+                    .await
+            }
+        }
+        .map_err(|e| format!("Failed to load recipe {}: {}", recipe_id_str, e))?;
 
-                // ================================================================
-                // HARVEST RESOURCE — Crée des items dans l'inventaire du Lord
-                // ================================================================
-                if action_info.action_type == ActionTypeEnum::HarvestResource {
-                    match self.db_tables.actions.load_harvest_data(action_id).await {
-                        Ok(Some((_player_id, resource_type))) => {
-                            // Read harvest yields from GameState cache
-                            let yields = self.game_state.harvest_yields_for(resource_type.to_id());
-
-                            if yields.is_empty() {
-                                tracing::error!(
-                                    "No harvest yields defined for resource type {:?} (action {})",
-                                    resource_type,
-                                    action_id
-                                );
-                            } else {
-                                match self.find_lord_unit_id(action_info.player_id).await {
-                                    Ok(Some(lord_unit_id)) => {
-                                        for hy in &yields {
-                                            let quality = (hy.quality_min + hy.quality_max) / 2.0;
-                                            let quantity = hy.base_quantity;
-
-                                            match self
-                                                .db_tables
-                                                .resources
-                                                .create_items_for_unit(
-                                                    lord_unit_id,
-                                                    hy.result_item_id,
-                                                    quantity,
-                                                    quality,
-                                                )
-                                                .await
-                                            {
-                                                Ok(created_ids) => {
-                                                    tracing::info!(
-                                                        "Harvest completed (action {}): created {} x item {} for unit {}",
-                                                        action_id,
-                                                        created_ids.len(),
-                                                        hy.result_item_id,
-                                                        lord_unit_id
-                                                    );
-
-                                                    let inv_msg = ServerMessage::InventoryUpdate {
-                                                        unit_id: lord_unit_id,
-                                                        item_id: hy.result_item_id,
-                                                        quantity_delta: quantity,
-                                                        new_total: self
-                                                            .db_tables
-                                                            .resources
-                                                            .count_item_for_unit(
-                                                                lord_unit_id,
-                                                                hy.result_item_id,
-                                                            )
-                                                            .await
-                                                            .unwrap_or(quantity),
-                                                    };
-                                                    self.send_message_to_player(
-                                                        action_info.player_id,
-                                                        inv_msg,
-                                                    )
-                                                    .await;
-                                                }
-                                                Err(e) => {
-                                                    tracing::error!(
-                                                        "Failed to create harvest items for action {}: {}",
-                                                        action_id,
-                                                        e
-                                                    );
-                                                }
-                                            }
-                                        }
-                                    }
-                                    Ok(None) => {
-                                        tracing::error!(
-                                            "No lord found for player {} (action {})",
-                                            action_info.player_id,
-                                            action_id
-                                        );
-                                    }
-                                    Err(e) => {
-                                        tracing::error!(
-                                            "Failed to find lord for player {}: {}",
-                                            action_info.player_id,
-                                            e
-                                        );
-                                    }
-                                }
-                            }
-                        }
-                        Ok(None) => {
-                            tracing::error!("No harvest data for action {}", action_id);
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                "Failed to load harvest data for action {}: {}",
-                                action_id,
-                                e
-                            );
-                        }
-                    }
-                }
+        let action_units = self
+            .action_units(action_info.player_id, worker_ids)
+            .await
+            .map_err(|e| format!("Failed to find lord for craft: {}", e))?
+            .ok_or_else(|| format!("No lord found for player {}", action_info.player_id))?;
 
-                // ================================================================
-                // CRAFT RESOURCE — Consomme des ingrédients, crée le résultat
-                // ================================================================
-                if action_info.action_type == ActionTypeEnum::CraftResource {
-                    match self.db_tables.actions.load_craft_data(action_id).await {
-                        Ok(Some((_player_id, recipe_id_str, quantity))) => {
-                            // Charger la recette — par ID numérique ou par slug
-                            let recipe = match recipe_id_str.parse::<i32>() {
-                                Ok(numeric_id) => {
-                                    self.db_tables.resources.load_recipe(numeric_id).await
-                                }
-                                Err(_) => {
-                                    self.db_tables
-                                        .resources
-                                        .load_recipe_by_slug(&recipe_id_str)
-                                        .await
-                                }
-                            };
-                            match recipe {
-                                Ok(recipe) => {
-                                    match self.find_lord_unit_id(action_info.player_id).await {
-                                        Ok(Some(lord_unit_id)) => {
-                                            let skip_resources =
-                                                self.dev_config.skip_resource_check();
-
-                                            let mut ingredients_ok = skip_resources;
-                                            if !skip_resources {
-                                                for ingredient in &recipe.ingredients {
-                                                    let needed =
-                                                        ingredient.quantity * quantity as i32;
-                                                    let have = self
-                                                        .db_tables
-                                                        .resources
-                                                        .count_item_for_unit(
-                                                            lord_unit_id,
-                                                            ingredient.item_id,
-                                                        )
-                                                        .await
-                                                        .unwrap_or(0);
-
-                                                    if have < needed {
-                                                        tracing::error!(
-                                                            "Craft action {}: not enough item {} (need {}, have {})",
-                                                            action_id,
-                                                            ingredient.item_id,
-                                                            needed,
-                                                            have
-                                                        );
-                                                        ingredients_ok = false;
-                                                        break;
-                                                    }
-                                                }
-                                            }
-
-                                            if ingredients_ok {
-                                                if !skip_resources {
-                                                    for ingredient in &recipe.ingredients {
-                                                        let needed =
-                                                            ingredient.quantity * quantity as i32;
-                                                        if let Err(e) = self
-                                                            .db_tables
-                                                            .resources
-                                                            .consume_items(
-                                                                lord_unit_id,
-                                                                ingredient.item_id,
-                                                                needed,
-                                                            )
-                                                            .await
-                                                        {
-                                                            tracing::error!(
-                                                                "Failed to consume ingredient {} for action {}: {}",
-                                                                ingredient.item_id,
-                                                                action_id,
-                                                                e
-                                                            );
-                                                        } else {
-                                                            // Notify client that ingredient was consumed
-                                                            let remaining = self
-                                                                .db_tables
-                                                                .resources
-                                                                .count_item_for_unit(
-                                                                    lord_unit_id,
-                                                                    ingredient.item_id,
-                                                                )
-                                                                .await
-                                                                .unwrap_or(0);
-                                                            let ing_msg =
-                                                                ServerMessage::InventoryUpdate {
-                                                                    unit_id: lord_unit_id,
-                                                                    item_id: ingredient.item_id,
-                                                                    quantity_delta: -needed,
-                                                                    new_total: remaining,
-                                                                };
-                                                            self.send_message_to_player(
-                                                                action_info.player_id,
-                                                                ing_msg,
-                                                            )
-                                                            .await;
-                                                        }
-                                                    }
-                                                }
-
-                                                let result_qty =
-                                                    recipe.result_quantity * quantity as i32;
-                                                let craft_quality = 0.8;
-                                                match self
-                                                    .db_tables
-                                                    .resources
-                                                    .create_items_for_unit(
-                                                        lord_unit_id,
-                                                        recipe.result_item_id,
-                                                        result_qty,
-                                                        craft_quality,
-                                                    )
-                                                    .await
-                                                {
-                                                    Ok(created_ids) => {
-                                                        tracing::info!(
-                                                            "Craft completed (action {}): {} x item {} for unit {}",
-                                                            action_id,
-                                                            created_ids.len(),
-                                                            recipe.result_item_id,
-                                                            lord_unit_id
-                                                        );
-
-                                                        let inv_msg =
-                                                            ServerMessage::InventoryUpdate {
-                                                                unit_id: lord_unit_id,
-                                                                item_id: recipe.result_item_id,
-                                                                quantity_delta: result_qty,
-                                                                new_total: self
-                                                                    .db_tables
-                                                                    .resources
-                                                                    .count_item_for_unit(
-                                                                        lord_unit_id,
-                                                                        recipe.result_item_id,
-                                                                    )
-                                                                    .await
-                                                                    .unwrap_or(result_qty),
-                                                            };
-                                                        self.send_message_to_player(
-                                                            action_info.player_id,
-                                                            inv_msg,
-                                                        )
-                                                        .await;
-                                                    }
-                                                    Err(e) => {
-                                                        tracing::error!(
-                                                            "Failed to create craft result for action {}: {}",
-                                                            action_id,
-                                                            e
-                                                        );
-                                                    }
-                                                }
-                                            } else {
-                                                tracing::error!(
-                                                    "Craft action {} failed: missing ingredients",
-                                                    action_id
-                                                );
-                                            }
-                                        }
-                                        Ok(None) => {
-                                            tracing::error!(
-                                                "No lord found for player {} (craft action {})",
-                                                action_info.player_id,
-                                                action_id
-                                            );
-                                        }
-                                        Err(e) => {
-                                            tracing::error!("Failed to find lord for craft: {}", e);
-                                        }
-                                    }
-                                }
-                                Err(e) => {
-                                    tracing::error!(
-                                        "Failed to load recipe {} for action {}: {}",
-                                        recipe_id_str,
-                                        action_id,
-                                        e
-                                    );
-                                }
-                            }
-                        }
-                        Ok(None) => {
-                            tracing::error!("No craft data for action {}", action_id);
-                        }
-                        Err(e) => {
-                            tracing::error!(
-                                "Failed to load craft data for action {}: {}",
-                                action_id,
-                                e
-                            );
-                        }
-                    }
-                }
+        // Ingrédients déjà retirés à la planification ?
+        let reserved = self
+            .db_tables
+            .actions
+            .take_reservations(action_id)
+            .await
+            .unwrap_or_default();
+        let skip_resources = self.dev_config.skip_resource_check() || !reserved.is_empty();
 
-                // Free units assigned to this action
-                match self.db_tables.units.clear_units_working_on(action_id).await {
-                    Ok(freed_unit_ids) => {
-                        for uid in &freed_unit_ids {
-                            let msg = ServerMessage::UnitWorkStatusUpdate {
-                                unit_id: *uid,
-                                working_on_action_id: None,
-                            };
-                            self.send_message_to_player(action_info.player_id, msg)
-                                .await;
-                        }
-                        tracing::info!(
-                            "Freed {} units from action {}",
-                            freed_unit_ids.len(),
-                            action_id
-                        );
-                    }
-                    Err(e) => {
-                        tracing::error!("Failed to free units from action {}: {}", action_id, e);
-                    }
-                }
+        // Qualité des ingrédients, héritée par le résultat
+        let mut ingredient_lots: Vec<(i32, f32)> = reserved
+            .iter()
+            .map(|(_, _, quantity, quality)| (*quantity, *quality))
+            .collect();
 
-                // Envoyer notification au joueur qui a lancé l'action
-                let status_message = ServerMessage::ActionStatusUpdate {
-                    action_id,
-                    player_id: action_info.player_id,
-                    chunk_id: action_info.chunk_id,
-                    cell: action_info.cell,
-                    status: ActionStatusEnum::Completed,
-                    action_type: action_info.action_type,
-                    completion_time: action_info.completion_time,
-                    action_name: None,
-                    unit_ids: vec![],
-                };
+        // Sinon, prélever sur les unités de l'action
+        let mut draws = Vec::new();
+        if !skip_resources {
+            let materials: Vec<(i32, i32)> = recipe
+                .ingredients
+                .iter()
+                .map(|ingredient| (ingredient.item_id, ingredient.quantity * quantity as i32))
+                .collect();
+            let mut holdings = Vec::new();
+            for &unit_id in &action_units {
+                holdings.push((
+                    unit_id,
+                    self.db_tables
+                        .resources
+                        .load_inventory_summary(unit_id)
+                        .await
+                        .unwrap_or_default(),
+                ));
+            }
+            draws = plan_material_draw(&holdings, &materials).map_err(|missing| {
+                format!("Missing ingredients (item, need, have): {:?}", missing)
+            })?;
+        }
 
-                self.send_message_to_player(action_info.player_id, status_message)
-                    .await;
+        let mut consumed_lots = Vec::new();
+        for &(unit_id, item_id, needed) in &draws {
+            match self
+                .db_tables
+                .resources
+                .consume_items(unit_id, item_id, needed)
+                .await
+            {
+                Ok(consumed) => {
+                    ingredient_lots.extend(consumed.iter().map(|(_, quality)| (1, *quality)));
+                    consumed_lots.push((unit_id, item_id, consumed));
+                }
+                Err(e) => {
+                    // Rendre ce qui a déjà été prélevé : rien ne sera fabriqué
+                    self.restore_ingredients(action_id, &consumed_lots).await;
+                    return Err(format!("Failed to consume ingredient {}: {}", item_id, e));
+                }
+            }
+        }
 
-                // Au prochain tick, on enverra le résultat aux joueurs du chunk
-                // Pour l'instant on envoie immédiatement
-                let completion_message = ServerMessage::ActionCompleted {
-                    action_id,
-                    chunk_id: action_info.chunk_id,
-                    cell: action_info.cell,
-                    action_type: action_info.action_type,
-                };
+        // Notify client that ingredients were consumed
+        for (unit_id, item_id, consumed) in &consumed_lots {
+            let remaining = self
+                .db_tables
+                .resources
+                .count_item_for_unit(*unit_id, *item_id)
+                .await
+                .unwrap_or(0);
+            let ing_msg = ServerMessage::InventoryUpdate {
+                unit_id: *unit_id,
+                item_id: *item_id,
+                quantity_delta: -(consumed.len() as i32),
+                new_total: remaining,
+            };
+            self.send_message_to_player(action_info.player_id, ing_msg)
+                .await;
+        }
 
-                self.broadcast_to_chunk(&action_info.chunk_id, completion_message)
-                    .await;
+        let result_qty = recipe.result_quantity * quantity as i32;
+        let craft_skill = match recipe.required_skill {
+            Some(skill) => best_effective_skill(
+                &self.db_tables,
+                worker_ids,
+                skill,
+                &self.game_state.profession_skill_bonuses,
+            )
+            .await
+            .unwrap_or(0),
+            None => 0,
+        };
+        let workshop = self
+            .db_tables
+            .buildings
+            .get_building_condition_at_cell(&action_info.cell)
+            .await
+            .unwrap_or_else(|e| {
+                tracing::warn!("{}", e);
+                None
+            });
+        let craft_quality = roll_craft_quality(
+            &mut *self.outcome_rng.lock().unwrap(),
+            average_quality(&ingredient_lots),
+            workshop,
+            craft_skill,
+        );
+        tracing::info!(
+            "Craft completed (action {}): {} x item {} for units {:?}",
+            action_id,
+            result_qty,
+            recipe.result_item_id,
+            action_units
+        );
+        self.deliver_items(
+            action_info,
+            &action_units,
+            recipe.result_item_id,
+            result_qty,
+            craft_quality,
+        )
+        .await;
 
-                tracing::info!(
-                    "Action {} completed for player {} at chunk ({}, {}) cell ({}, {})",
-                    action_id,
-                    action_info.player_id,
-                    action_info.chunk_id.x,
-                    action_info.chunk_id.y,
-                    action_info.cell.q,
-                    action_info.cell.r
-                );
+        Ok(recipe
+            .required_skill
+            .or(default_skill)
+            .map(|skill| (skill, quantity.max(1) as i64)))
+    }
+
+    /// Recrée chez leurs unités les ingrédients prélevés par une fabrication avortée
+    async fn restore_ingredients(
+        &self,
+        action_id: u64,
+        consumed_lots: &[ConsumedLot],
+    ) {
+        for (unit_id, item_id, consumed) in consumed_lots {
+            for (_, quality) in consumed {
+                if let Err(e) = self
+                    .db_tables
+                    .resources
+                    .create_items_for_unit(*unit_id, *item_id, 1, *quality)
+                    .await
+                {
+                    tracing::error!(
+                        "Failed to restore ingredient {} to unit {} (action {}): {}",
+                        item_id,
+                        unit_id,
+                        action_id,
+                        e
+                    );
+                }
             }
         }
-
-        // Nettoyer les actions complétées du cache (optionnel)
-        active_actions.retain(|_, action| action.status != ActionStatusEnum::Completed);
     }
 
     /// Crée un bâtiment en construction pour une action BuildBuilding
@@ -1807,168 +2089,650 @@ impl ActionProcessor {
         }
     }
 
-    /// Régénère la SDF de route pour tous les chunks où un segment est visible
-    /// Utilisé quand on modifie un segment qui peut traverser plusieurs chunks
-    async fn regenerate_road_sdf_for_segment(&self, segment_id: i64) {
-        // Récupérer tous les chunks où ce segment est visible
-        let chunks = match self
+    /// Régénère la SDF de route pour tous les chunks où un segment est visible
+    /// Utilisé quand on modifie un segment qui peut traverser plusieurs chunks
+    async fn regenerate_road_sdf_for_segment(&self, segment_id: i64) {
+        // Récupérer tous les chunks où ce segment est visible
+        let chunks = match self
+            .db_tables
+            .road_segments
+            .get_chunks_for_segment(segment_id)
+            .await
+        {
+            Ok(chunks) => chunks,
+            Err(e) => {
+                tracing::error!("Failed to get chunks for segment {}: {}", segment_id, e);
+                return;
+            }
+        };
+
+        tracing::info!(
+            "Regenerating road SDF for segment {} across {} chunks: {:?}",
+            segment_id,
+            chunks.len(),
+            chunks
+        );
+
+        // Régénérer le SDF pour chaque chunk affecté
+        for (chunk_x, chunk_y) in chunks {
+            let chunk_id = shared::TerrainChunkId {
+                x: chunk_x,
+                y: chunk_y,
+            };
+            self.regenerate_and_send_road_sdf(&chunk_id).await;
+        }
+    }
+
+    /// Régénère la SDF de route pour un chunk et l'envoie à tous les joueurs
+    async fn regenerate_and_send_road_sdf(&self, chunk_id: &shared::TerrainChunkId) {
+        // Charger les segments visibles dans ce chunk via la table de visibilité
+        match self
+            .db_tables
+            .road_segments
+            .load_road_segments_by_chunk_new(chunk_id.x, chunk_id.y)
+            .await
+        {
+            Ok(road_segments) if !road_segments.is_empty() => {
+                tracing::info!(
+                    "Regenerating road SDF for chunk ({},{}) with {} segments",
+                    chunk_id.x,
+                    chunk_id.y,
+                    road_segments.len()
+                );
+
+                // Log détaillé de tous les segments
+                for (i, seg) in road_segments.iter().enumerate() {
+                    tracing::info!(
+                        "  Segment {}: id={}, cells={}, points={}, start=({},{}), end=({},{})",
+                        i,
+                        seg.id,
+                        seg.cell_path.len(),
+                        seg.points.len(),
+                        seg.start_cell.q,
+                        seg.start_cell.r,
+                        seg.end_cell.q,
+                        seg.end_cell.r
+                    );
+                }
+
+                use crate::road::{RoadConfig, compute_intersections, generate_road_sdf};
+
+                let config = RoadConfig::default();
+                let intersections = compute_intersections(&road_segments, &config);
+                let road_sdf = generate_road_sdf(
+                    &road_segments,
+                    &intersections,
+                    &config,
+                    chunk_id.x,
+                    chunk_id.y,
+                );
+
+                tracing::info!(
+                    "✓ Road SDF regenerated: {}x{} with {} intersections",
+                    config.sdf_resolution.x,
+                    config.sdf_resolution.y,
+                    intersections.len()
+                );
+
+                // Envoyer la mise à jour de la SDF à tous les joueurs du chunk
+                let road_update = shared::protocol::ServerMessage::RoadChunkSdfUpdate {
+                    terrain_name: "Gaulyia".to_string(),
+                    chunk_id: *chunk_id,
+                    road_sdf_data: road_sdf,
+                };
+
+                self.broadcast_to_chunk(chunk_id, road_update).await;
+            }
+            Ok(_) => {
+                tracing::debug!(
+                    "No road segments found for chunk ({},{})",
+                    chunk_id.x,
+                    chunk_id.y
+                );
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "Failed to load road segments for chunk ({},{}): {}",
+                    chunk_id.x,
+                    chunk_id.y,
+                    e
+                );
+            }
+        }
+    }
+
+    /// Donne l'XP d'une action terminée à chaque unité affectée
+    /// et notifie le joueur des montées de niveau
+    async fn award_action_xp(
+        &self,
+        player_id: u64,
+        action_type: ActionTypeEnum,
+        unit_ids: &[u64],
+        skill: SkillEnum,
+        multiplier: i64,
+    ) {
+        let Some(action_xp) = self.game_state.action_xp_for(action_type) else {
+            return;
+        };
+        let xp = action_xp.base_xp as i64 * multiplier;
+
+        for &unit_id in unit_ids {
+            match award_skill_xp(&self.db_tables, unit_id, skill, xp, &self.game_state.skill_curve)
+                .await
+            {
+                Ok(Some(unit_skill)) => {
+                    tracing::info!(
+                        "Unit {} reached level {} in {:?}",
+                        unit_id,
+                        unit_skill.level,
+                        skill
+                    );
+                    let msg = ServerMessage::SkillLevelUp {
+                        unit_id,
+                        skill,
+                        level: unit_skill.level,
+                    };
+                    self.send_message_to_player(player_id, msg).await;
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    tracing::error!("Failed to award {} XP to unit {}: {}", xp, unit_id, e);
+                }
+            }
+        }
+    }
+
+    /// Libère les unités affectées à une action
+    async fn release_units(&self, action_id: u64, player_id: u64) {
+        match self.db_tables.units.clear_units_working_on(action_id).await {
+            Ok(freed_unit_ids) => {
+                for uid in &freed_unit_ids {
+                    let msg = ServerMessage::UnitWorkStatusUpdate {
+                        unit_id: *uid,
+                        working_on_action_id: None,
+                    };
+                    self.send_message_to_player(player_id, msg).await;
+                    self.notify_unit_idle(player_id, *uid);
+                }
+                tracing::info!(
+                    "Freed {} units from action {}",
+                    freed_unit_ids.len(),
+                    action_id
+                );
+            }
+            Err(e) => {
+                tracing::error!("Failed to free units from action {}: {}", action_id, e);
+            }
+        }
+    }
+
+    /// Rend aux unités la part des matériaux réservés correspondant au travail non effectué
+    async fn refund_reservations(&self, action_id: u64, player_id: u64, remaining_fraction: f64) {
+        let reserved = match self.db_tables.actions.take_reservations(action_id).await {
+            Ok(reserved) => reserved,
+            Err(e) => {
+                tracing::error!("Failed to load reservations of action {}: {}", action_id, e);
+                return;
+            }
+        };
+
+        for (source, item_id, quantity, quality) in reserved {
+            let refund = refund_quantity(quantity, remaining_fraction);
+            if refund == 0 {
+                continue;
+            }
+            let unit_id = match source {
+                MaterialSource::Unit(unit_id) => unit_id,
+                MaterialSource::Treasury(organization_id) => {
+                    self.refund_to_treasury(action_id, organization_id, item_id, refund, quality)
+                        .await;
+                    continue;
+                }
+            };
+            match self
+                .db_tables
+                .resources
+                .create_items_for_unit(unit_id, item_id, refund, quality)
+                .await
+            {
+                Ok(_) => {
+                    let new_total = self
+                        .db_tables
+                        .resources
+                        .count_item_for_unit(unit_id, item_id)
+                        .await
+                        .unwrap_or(refund);
+                    let msg = ServerMessage::InventoryUpdate {
+                        unit_id,
+                        item_id,
+                        quantity_delta: refund,
+                        new_total,
+                    };
+                    self.send_message_to_player(player_id, msg).await;
+                }
+                Err(e) => {
+                    tracing::error!(
+                        "Failed to refund item {} to unit {} (action {}): {}",
+                        item_id,
+                        unit_id,
+                        action_id,
+                        e
+                    );
+                }
+            }
+        }
+    }
+
+    /// Rend au trésor d'une organisation des matériaux qu'une action y avait pris
+    async fn refund_to_treasury(
+        &self,
+        action_id: u64,
+        organization_id: u64,
+        item_id: i32,
+        quantity: i32,
+        quality: f32,
+    ) {
+        for _ in 0..quantity {
+            let refunded = async {
+                let instance_id = self
+                    .db_tables
+                    .resources
+                    .create_item_instance(item_id, quality, None, None)
+                    .await?;
+                self.db_tables
+                    .organizations
+                    .add_treasury_item(organization_id, instance_id, 1)
+                    .await
+            }
+            .await;
+            if let Err(e) = refunded {
+                tracing::error!(
+                    "Failed to refund item {} to the treasury of organization {} (action {}): {}",
+                    item_id,
+                    organization_id,
+                    action_id,
+                    e
+                );
+                return;
+            }
+        }
+    }
+
+    /// Part des matériaux réservés à rendre si l'action est annulée maintenant
+    /// (None si elle ne peut plus l'être)
+    fn cancellation_refund(action_info: &ActionInfo) -> Option<f64> {
+        match action_info.status {
+            ActionStatusEnum::Pending => Some(1.0),
+            ActionStatusEnum::InProgress => Some(remaining_fraction(
+                action_info.completion_time.saturating_sub(now_ms()),
+                action_info.duration_ms,
+            )),
+            ActionStatusEnum::Paused => Some(remaining_fraction(
+                action_info.paused_remaining_ms.unwrap_or(0),
+                action_info.duration_ms,
+            )),
+            _ => None,
+        }
+    }
+
+    /// Annule une action du joueur : les unités sont libérées et la part non
+    /// utilisée des matériaux réservés est rendue.
+    /// Une route ne peut plus être annulée une fois son segment tracé.
+    pub async fn cancel_action(&self, player_id: u64, action_id: u64) -> Result<(), ErrorCode> {
+        // Valider et marquer l'action sous le verrou, qui est relâché pendant l'écriture
+        let remaining_fraction = {
+            let mut active_actions = self.active_actions.write().await;
+            let action_info = active_actions
+                .get_mut(&action_id)
+                .filter(|a| a.player_id == player_id)
+                .ok_or(ErrorCode::ActionNotFound)?;
+
+            // Pas d'annulation pendant une autre transition (démarrage, pause, reprise)
+            if action_info.in_transition
+                || (action_info.action_type == ActionTypeEnum::BuildRoad
+                    && action_info.status != ActionStatusEnum::Pending)
+            {
+                return Err(ErrorCode::ActionNotCancellable);
+            }
+
+            let remaining_fraction =
+                Self::cancellation_refund(action_info).ok_or(ErrorCode::ActionNotCancellable)?;
+            action_info.in_transition = true;
+            remaining_fraction
+        };
+
+        if let Err(e) = self
+            .db_tables
+            .actions
+            .update_action_status(action_id, ActionStatusEnum::Cancelled)
+            .await
+        {
+            tracing::error!("Failed to cancel action {}: {}", action_id, e);
+            self.rollback_transition(action_id).await;
+            return Err(ErrorCode::InternalError);
+        }
+
+        let Some(action_info) = self.active_actions.write().await.remove(&action_id) else {
+            return Err(ErrorCode::ActionNotFound);
+        };
+        self.schedule.lock().unwrap().remove(action_id);
+
+        self.finish_cancellation(&action_info, remaining_fraction)
+            .await;
+
+        tracing::info!(
+            "Action {} cancelled by player {} ({:.0}% refunded)",
+            action_id,
+            player_id,
+            remaining_fraction * 100.0
+        );
+        Ok(())
+    }
+
+    /// Une transition n'a pas pu être écrite en base : l'action reprend son cours, et son
+    /// échéance est replanifiée au cas où le tick l'aurait sautée entre-temps
+    async fn rollback_transition(&self, action_id: u64) {
+        let due = {
+            let mut active_actions = self.active_actions.write().await;
+            let Some(action_info) = active_actions.get_mut(&action_id) else {
+                return;
+            };
+            action_info.in_transition = false;
+            (action_info.status != ActionStatusEnum::Paused).then(|| Self::due_time(action_info))
+        };
+        if let Some(due) = due {
+            self.schedule_at(due, action_id);
+        }
+    }
+
+    /// Annule d'office toutes les actions d'un joueur, par exemple avant la suppression
+    /// de son compte : elles quittent le cache et l'échéancier avant leurs lignes en base
+    pub async fn cancel_player_actions(&self, player_id: u64) {
+        self.cancel_actions_where(|action_info| action_info.player_id == player_id)
+            .await;
+    }
+
+    /// Retire une unité de l'action à laquelle elle travaille, avant qu'elle ne quitte
+    /// le monde : l'action est annulée si l'unité y était seule, sinon l'unité est libérée
+    pub async fn release_unit(&self, unit_id: u64) -> Result<(), String> {
+        let Some(action_id) = self.db_tables.units.load_unit_work(unit_id).await? else {
+            return Ok(());
+        };
+
+        let workers = self.db_tables.units.load_units_working_on(action_id).await?;
+        if workers.iter().all(|&worker_id| worker_id == unit_id) {
+            self.cancel_actions_where(|action_info| action_info.action_id == action_id)
+                .await;
+        }
+        self.db_tables.units.clear_unit_work(unit_id).await
+    }
+
+    /// Annule d'office les actions choisies, sans les restrictions d'une annulation
+    /// par le joueur. Une action en transition est annulée une fois celle-ci écrite.
+    async fn cancel_actions_where(&self, filter: impl Fn(&ActionInfo) -> bool) {
+        loop {
+            let (cancelled, waiting) = {
+                let mut active_actions = self.active_actions.write().await;
+                let matching: Vec<&ActionInfo> = active_actions
+                    .values()
+                    .filter(|action_info| filter(action_info))
+                    .filter(|action_info| Self::cancellation_refund(action_info).is_some())
+                    .collect();
+                let waiting = matching.iter().any(|action_info| action_info.in_transition);
+                let action_ids: Vec<u64> = matching
+                    .iter()
+                    .filter(|action_info| !action_info.in_transition)
+                    .map(|action_info| action_info.action_id)
+                    .collect();
+                let cancelled: Vec<(ActionInfo, f64)> = action_ids
+                    .iter()
+                    .filter_map(|action_id| active_actions.remove(action_id))
+                    .map(|action_info| {
+                        let refund = Self::cancellation_refund(&action_info).unwrap_or(1.0);
+                        (action_info, refund)
+                    })
+                    .collect();
+                (cancelled, waiting)
+            };
+
+            for (action_info, remaining_fraction) in cancelled {
+                let action_id = action_info.action_id;
+                self.schedule.lock().unwrap().remove(action_id);
+                if let Err(e) = self
+                    .db_tables
+                    .actions
+                    .update_action_status(action_id, ActionStatusEnum::Cancelled)
+                    .await
+                {
+                    tracing::error!("Failed to cancel action {}: {}", action_id, e);
+                }
+
+                self.finish_cancellation(&action_info, remaining_fraction)
+                    .await;
+                tracing::info!(
+                    "Action {} of player {} cancelled by the server",
+                    action_id,
+                    action_info.player_id
+                );
+            }
+
+            if !waiting {
+                break;
+            }
+            tokio::time::sleep(TRANSITION_WAIT).await;
+        }
+    }
+
+    /// Suites d'une annulation : matériaux rendus, bâtiment en chantier retiré,
+    /// unités libérées et joueur prévenu
+    async fn finish_cancellation(&self, action_info: &ActionInfo, remaining_fraction: f64) {
+        let action_id = action_info.action_id;
+        let player_id = action_info.player_id;
+
+        self.refund_reservations(action_id, player_id, remaining_fraction)
+            .await;
+
+        // Le bâtiment en construction disparaît avec l'action
+        if action_info.action_type == ActionTypeEnum::BuildBuilding
+            && action_info.status != ActionStatusEnum::Pending
+            && let Err(e) = self.db_tables.buildings.delete_building(action_id).await
+        {
+            tracing::error!(
+                "Failed to delete building {} after cancellation: {}",
+                action_id,
+                e
+            );
+        }
+
+        self.release_units(action_id, player_id).await;
+
+        let message = ServerMessage::ActionStatusUpdate {
+            action_id,
+            player_id,
+            chunk_id: action_info.chunk_id,
+            cell: action_info.cell,
+            status: ActionStatusEnum::Cancelled,
+            action_type: action_info.action_type,
+            completion_time: action_info.completion_time,
+            action_name: None,
+            unit_ids: vec![],
+        };
+        self.send_message_to_player(player_id, message).await;
+    }
+
+    /// Met en pause une action en cours ; ses unités restent affectées
+    pub async fn pause_action(&self, player_id: u64, action_id: u64) -> Result<(), ErrorCode> {
+        let (action_info, remaining_ms) = {
+            let mut active_actions = self.active_actions.write().await;
+            let action_info = active_actions
+                .get_mut(&action_id)
+                .filter(|a| a.player_id == player_id)
+                .ok_or(ErrorCode::ActionNotFound)?;
+
+            if action_info.status != ActionStatusEnum::InProgress || action_info.in_transition {
+                return Err(ErrorCode::ActionNotPausable);
+            }
+
+            action_info.in_transition = true;
+            let remaining_ms = action_info.completion_time.saturating_sub(now_ms());
+            (action_info.clone(), remaining_ms)
+        };
+
+        if let Err(e) = self
             .db_tables
-            .road_segments
-            .get_chunks_for_segment(segment_id)
+            .actions
+            .pause_action(action_id, remaining_ms)
             .await
         {
-            Ok(chunks) => chunks,
-            Err(e) => {
-                tracing::error!("Failed to get chunks for segment {}: {}", segment_id, e);
-                return;
-            }
+            tracing::error!("Failed to pause action {}: {}", action_id, e);
+            self.rollback_transition(action_id).await;
+            return Err(ErrorCode::InternalError);
+        }
+
+        // L'entrée de l'échéancier sera ignorée : l'action n'est plus InProgress
+        if let Some(action_info) = self.active_actions.write().await.get_mut(&action_id) {
+            action_info.status = ActionStatusEnum::Paused;
+            action_info.paused_remaining_ms = Some(remaining_ms);
+            action_info.in_transition = false;
+        }
+
+        let message = ServerMessage::ActionStatusUpdate {
+            action_id,
+            player_id,
+            chunk_id: action_info.chunk_id,
+            cell: action_info.cell,
+            status: ActionStatusEnum::Paused,
+            action_type: action_info.action_type,
+            completion_time: action_info.completion_time,
+            action_name: None,
+            unit_ids: vec![],
         };
+        self.send_message_to_player(player_id, message).await;
 
         tracing::info!(
-            "Regenerating road SDF for segment {} across {} chunks: {:?}",
-            segment_id,
-            chunks.len(),
-            chunks
+            "Action {} paused by player {} ({} ms remaining)",
+            action_id,
+            player_id,
+            remaining_ms
         );
-
-        // Régénérer le SDF pour chaque chunk affecté
-        for (chunk_x, chunk_y) in chunks {
-            let chunk_id = shared::TerrainChunkId {
-                x: chunk_x,
-                y: chunk_y,
-            };
-            self.regenerate_and_send_road_sdf(&chunk_id).await;
-        }
+        Ok(())
     }
 
-    /// Régénère la SDF de route pour un chunk et l'envoie à tous les joueurs
-    async fn regenerate_and_send_road_sdf(&self, chunk_id: &shared::TerrainChunkId) {
-        // Charger les segments visibles dans ce chunk via la table de visibilité
-        match self
+    /// Reprend une action en pause là où elle s'était arrêtée.
+    /// Début et fin sont décalés de la durée de la pause.
+    pub async fn resume_action(&self, player_id: u64, action_id: u64) -> Result<(), ErrorCode> {
+        let (action_info, start_time, completion_time) = {
+            let mut active_actions = self.active_actions.write().await;
+            let action_info = active_actions
+                .get_mut(&action_id)
+                .filter(|a| a.player_id == player_id)
+                .ok_or(ErrorCode::ActionNotFound)?;
+
+            if action_info.status != ActionStatusEnum::Paused || action_info.in_transition {
+                return Err(ErrorCode::ActionNotPaused);
+            }
+
+            action_info.in_transition = true;
+            let remaining_ms = action_info.paused_remaining_ms.unwrap_or(0);
+            let completion_time = now_ms() + remaining_ms;
+            let start_time = action_info.start_time
+                + completion_time.saturating_sub(action_info.completion_time);
+            (action_info.clone(), start_time, completion_time)
+        };
+
+        if let Err(e) = self
             .db_tables
-            .road_segments
-            .load_road_segments_by_chunk_new(chunk_id.x, chunk_id.y)
+            .actions
+            .resume_action(action_id, start_time, completion_time)
             .await
         {
-            Ok(road_segments) if !road_segments.is_empty() => {
-                tracing::info!(
-                    "Regenerating road SDF for chunk ({},{}) with {} segments",
-                    chunk_id.x,
-                    chunk_id.y,
-                    road_segments.len()
-                );
-
-                // Log détaillé de tous les segments
-                for (i, seg) in road_segments.iter().enumerate() {
-                    tracing::info!(
-                        "  Segment {}: id={}, cells={}, points={}, start=({},{}), end=({},{})",
-                        i,
-                        seg.id,
-                        seg.cell_path.len(),
-                        seg.points.len(),
-                        seg.start_cell.q,
-                        seg.start_cell.r,
-                        seg.end_cell.q,
-                        seg.end_cell.r
-                    );
-                }
-
-                use crate::road::{RoadConfig, compute_intersections, generate_road_sdf};
-
-                let config = RoadConfig::default();
-                let intersections = compute_intersections(&road_segments, &config);
-                let road_sdf = generate_road_sdf(
-                    &road_segments,
-                    &intersections,
-                    &config,
-                    chunk_id.x,
-                    chunk_id.y,
-                );
+            tracing::error!("Failed to resume action {}: {}", action_id, e);
+            self.rollback_transition(action_id).await;
+            return Err(ErrorCode::InternalError);
+        }
 
-                tracing::info!(
-                    "✓ Road SDF regenerated: {}x{} with {} intersections",
-                    config.sdf_resolution.x,
-                    config.sdf_resolution.y,
-                    intersections.len()
-                );
+        if let Some(action_info) = self.active_actions.write().await.get_mut(&action_id) {
+            action_info.status = ActionStatusEnum::InProgress;
+            action_info.start_time = start_time;
+            action_info.completion_time = completion_time;
+            action_info.paused_remaining_ms = None;
+            action_info.in_transition = false;
+        }
+        self.schedule_at(completion_time, action_id);
 
-                // Envoyer la mise à jour de la SDF à tous les joueurs du chunk
-                let road_update = shared::protocol::ServerMessage::RoadChunkSdfUpdate {
-                    terrain_name: "Gaulyia".to_string(),
-                    chunk_id: *chunk_id,
-                    road_sdf_data: road_sdf,
-                };
+        let message = ServerMessage::ActionStatusUpdate {
+            action_id,
+            player_id,
+            chunk_id: action_info.chunk_id,
+            cell: action_info.cell,
+            status: ActionStatusEnum::InProgress,
+            action_type: action_info.action_type,
+            completion_time,
+            action_name: None,
+            unit_ids: vec![],
+        };
+        self.send_message_to_player(player_id, message).await;
 
-                self.broadcast_to_chunk(chunk_id, road_update).await;
-            }
-            Ok(_) => {
-                tracing::debug!(
-                    "No road segments found for chunk ({},{})",
-                    chunk_id.x,
-                    chunk_id.y
-                );
-            }
-            Err(e) => {
-                tracing::warn!(
-                    "Failed to load road segments for chunk ({},{}): {}",
-                    chunk_id.x,
-                    chunk_id.y,
-                    e
-                );
-            }
-        }
+        tracing::info!("Action {} resumed by player {}", action_id, player_id);
+        Ok(())
     }
 
     /// Gère l'échec d'une action (supprime le bâtiment si nécessaire)
     pub async fn fail_action(&self, action_id: u64) -> Result<(), String> {
-        let mut active_actions = self.active_actions.write().await;
+        // L'action quitte le cache et l'échéancier, comme une annulation
+        let Some(action_info) = self.active_actions.write().await.remove(&action_id) else {
+            return Ok(());
+        };
+        self.schedule.lock().unwrap().remove(action_id);
 
-        if let Some(action_info) = active_actions.get_mut(&action_id) {
-            action_info.status = ActionStatusEnum::Failed;
+        // Les matériaux réservés sont rendus en totalité
+        self.refund_reservations(action_id, action_info.player_id, 1.0)
+            .await;
 
-            // Mettre à jour la DB
-            self.db_tables
-                .actions
-                .update_action_status(action_id, ActionStatusEnum::Failed)
-                .await?;
+        // Mettre à jour la DB
+        self.db_tables
+            .actions
+            .update_action_status(action_id, ActionStatusEnum::Failed)
+            .await?;
 
-            // Si c'est une action BuildBuilding qui était InProgress, supprimer le bâtiment
-            if action_info.action_type == ActionTypeEnum::BuildBuilding
-                && action_info.status != ActionStatusEnum::Pending
-            {
-                if let Err(e) = self.db_tables.buildings.delete_building(action_id).await {
-                    tracing::error!(
-                        "Failed to delete building {} after action failure: {}",
-                        action_id,
-                        e
-                    );
-                } else {
-                    tracing::info!("Building {} deleted after action failure", action_id);
-                }
+        // Si c'est une action BuildBuilding qui était InProgress, supprimer le bâtiment
+        if action_info.action_type == ActionTypeEnum::BuildBuilding
+            && action_info.status != ActionStatusEnum::Pending
+        {
+            if let Err(e) = self.db_tables.buildings.delete_building(action_id).await {
+                tracing::error!(
+                    "Failed to delete building {} after action failure: {}",
+                    action_id,
+                    e
+                );
+            } else {
+                tracing::info!("Building {} deleted after action failure", action_id);
             }
+        }
 
-            // Notifier le joueur
-            let message = ServerMessage::ActionStatusUpdate {
-                action_id,
-                player_id: action_info.player_id,
-                chunk_id: action_info.chunk_id.clone(),
-                cell: action_info.cell.clone(),
-                status: ActionStatusEnum::Failed,
-                action_type: action_info.action_type,
-                completion_time: action_info.completion_time,
-                action_name: None,
-                unit_ids: vec![],
-            };
+        // Notifier le joueur
+        let message = ServerMessage::ActionStatusUpdate {
+            action_id,
+            player_id: action_info.player_id,
+            chunk_id: action_info.chunk_id,
+            cell: action_info.cell,
+            status: ActionStatusEnum::Failed,
+            action_type: action_info.action_type,
+            completion_time: action_info.completion_time,
+            action_name: None,
+            unit_ids: vec![],
+        };
 
-            self.send_message_to_player(action_info.player_id, message)
-                .await;
+        self.send_message_to_player(action_info.player_id, message)
+            .await;
 
-            tracing::info!(
-                "Action {} failed for player {}",
-                action_id,
-                action_info.player_id
-            );
-        }
+        self.release_units(action_id, action_info.player_id).await;
+
+        tracing::info!(
+            "Action {} failed for player {}",
+            action_id,
+            action_info.player_id
+        );
 
         Ok(())
     }
@@ -1976,10 +2740,20 @@ impl ActionProcessor {
     /// Envoie un message à un joueur spécifique
     async fn send_message_to_player(&self, player_id: u64, message: ServerMessage) {
         let message_type = match &message {
+            ServerMessage::HelloAck { .. } => "HelloAck",
+            ServerMessage::HelloRejected { .. } => "HelloRejected",
+            ServerMessage::Sequenced { .. } => "Sequenced",
             ServerMessage::LoginSuccess { .. } => "LoginSuccess",
+            ServerMessage::SessionResumed { .. } => "SessionResumed",
+            ServerMessage::ResumeFailed { .. } => "ResumeFailed",
             ServerMessage::LoginError { .. } => "LoginError",
+            ServerMessage::NotAuthenticated => "NotAuthenticated",
+            ServerMessage::RateLimited { .. } => "RateLimited",
             ServerMessage::RegisterSuccess { .. } => "RegisterSuccess",
             ServerMessage::RegisterError { .. } => "RegisterError",
+            ServerMessage::PasswordChanged => "PasswordChanged",
+            ServerMessage::AccountDeleted => "AccountDeleted",
+            ServerMessage::AccountError { .. } => "AccountError",
             ServerMessage::LordData { .. } => "LordData",
             ServerMessage::LordCreated { .. } => "LordCreated",
             ServerMessage::LordCreateError { .. } => "LordCreateError",
@@ -2036,12 +2810,39 @@ impl ActionProcessor {
             ServerMessage::DebugUnitSpawned { .. } => "DebugUnitSpawned",
             ServerMessage::OrganizationAtCell { .. } => "OrganizationAtCell",
             ServerMessage::DebugError { .. } => "DebugError",
+            ServerMessage::ActionQueueUpdate { .. } => "ActionQueueUpdate",
+            ServerMessage::ActionQueueSkipped { .. } => "ActionQueueSkipped",
+            ServerMessage::UnitRoutines { .. } => "UnitRoutines",
+            ServerMessage::EquipmentChanged { .. } => "EquipmentChanged",
+            ServerMessage::GroundItemsUpdate { .. } => "GroundItemsUpdate",
+            ServerMessage::CellResourcesUpdate { .. } => "CellResourcesUpdate",
+            ServerMessage::BuildingRemoved { .. } => "BuildingRemoved",
+            ServerMessage::SkillLevelUp { .. } => "SkillLevelUp",
+            ServerMessage::TreasuryData { .. } => "TreasuryData",
             ServerMessage::UnitPositionUpdated { .. } => "UnitPositionUpdated",
             ServerMessage::UnitSlotUpdated { .. } => "UnitSlotUpdated",
             ServerMessage::UnitProfessionChanged { .. } => "UnitPorfessionChanged",
             ServerMessage::UnitWorkStatusUpdate { .. } => "UnitWorkStatusUpdate",
             ServerMessage::HamletFounded { .. } => "HamletFounded",
             ServerMessage::HamletFoundError { .. } => "HamletFoundError",
+            ServerMessage::OrganizationUpgraded { .. } => "OrganizationUpgraded",
+            ServerMessage::OrganizationUpgradeError { .. } => "OrganizationUpgradeError",
+            ServerMessage::VassalageOffered { .. } => "VassalageOffered",
+            ServerMessage::VassalageOfferClosed { .. } => "VassalageOfferClosed",
+            ServerMessage::VassalageChanged { .. } => "VassalageChanged",
+            ServerMessage::VassalageError { .. } => "VassalageError",
+            ServerMessage::OrganizationHierarchyData { .. } => "OrganizationHierarchyData",
+            ServerMessage::TributePaid { .. } => "TributePaid",
+            ServerMessage::DiplomacyData { .. } => "DiplomacyData",
+            ServerMessage::DiplomaticProposalReceived { .. } => "DiplomaticProposalReceived",
+            ServerMessage::DiplomaticProposalRejected { .. } => "DiplomaticProposalRejected",
+            ServerMessage::DiplomaticRelationChanged { .. } => "DiplomaticRelationChanged",
+            ServerMessage::OrganizationMembersData { .. } => "OrganizationMembersData",
+            ServerMessage::MembershipRequestReceived { .. } => "MembershipRequestReceived",
+            ServerMessage::MembershipRequestClosed { .. } => "MembershipRequestClosed",
+            ServerMessage::MembershipChanged { .. } => "MembershipChanged",
+            ServerMessage::OfficerChanged { .. } => "OfficerChanged",
+            ServerMessage::MembershipError { .. } => "MembershipError",
             ServerMessage::PlayerOrganizationData { .. } => "PlayerOrganizationData",
             ServerMessage::PopulationChanged { .. } => "PouplationChanged",
             ServerMessage::InventoryData { .. } => "InventoryData",
@@ -2066,6 +2867,100 @@ impl ActionProcessor {
         }
     }
 
+    /// Unités qui fournissent les matériaux et reçoivent le résultat d'une action :
+    /// celles qui y sont affectées, ou à défaut le Lord du joueur
+    async fn action_units(&self, player_id: u64, worker_ids: &[u64]) -> Result<Option<Vec<u64>>, String> {
+        if !worker_ids.is_empty() {
+            return Ok(Some(worker_ids.to_vec()));
+        }
+        Ok(self.find_lord_unit_id(player_id).await?.map(|lord_unit_id| vec![lord_unit_id]))
+    }
+
+    /// Remet le produit d'une action aux unités, réparti selon leur capacité de portage.
+    /// Ce qui ne rentre chez personne est déposé sur la cellule de l'action.
+    async fn deliver_items(
+        &self,
+        action_info: &ActionInfo,
+        recipients: &[u64],
+        item_id: i32,
+        quantity: i32,
+        quality: f32,
+    ) {
+        let item_weight = self
+            .game_state
+            .get_item_definition(item_id)
+            .map(|item| item.weight_kg)
+            .unwrap_or(0.0);
+
+        let mut free_kg = Vec::new();
+        for &unit_id in recipients {
+            match free_carry_capacity_kg(&self.db_tables, unit_id).await {
+                Ok(kg) => free_kg.push((unit_id, kg)),
+                Err(e) => tracing::warn!("Capacity of unit {} unknown: {}", unit_id, e),
+            }
+        }
+        let (split, overflow) = split_by_capacity(&free_kg, item_weight, quantity);
+
+        for (unit_id, share) in split {
+            if let Err(e) = self
+                .db_tables
+                .resources
+                .create_items_for_unit(unit_id, item_id, share, quality)
+                .await
+            {
+                tracing::error!("Failed to give {} x item {} to unit {}: {}", share, item_id, unit_id, e);
+                continue;
+            }
+            let msg = ServerMessage::InventoryUpdate {
+                unit_id,
+                item_id,
+                quantity_delta: share,
+                new_total: self
+                    .db_tables
+                    .resources
+                    .count_item_for_unit(unit_id, item_id)
+                    .await
+                    .unwrap_or(share),
+            };
+            self.send_message_to_player(action_info.player_id, msg).await;
+        }
+
+        if overflow > 0 {
+            let position = WorldPosition {
+                cell_q: action_info.cell.q,
+                cell_r: action_info.cell.r,
+                chunk_x: action_info.chunk_id.x,
+                chunk_y: action_info.chunk_id.y,
+            };
+            for _ in 0..overflow {
+                let placed = match self
+                    .db_tables
+                    .resources
+                    .create_item_instance(item_id, quality, None, None)
+                    .await
+                {
+                    Ok(instance_id) => {
+                        self.db_tables
+                            .resources
+                            .place_item_in_world(instance_id, position)
+                            .await
+                    }
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = placed {
+                    tracing::error!("Failed to stockpile item {}: {}", item_id, e);
+                }
+            }
+            tracing::info!(
+                "{} x item {} left on cell ({}, {}): carriers are full",
+                overflow,
+                item_id,
+                action_info.cell.q,
+                action_info.cell.r
+            );
+        }
+    }
+
     /// Trouve le Lord (unité principale) d'un joueur
     async fn find_lord_unit_id(&self, player_id: u64) -> Result<Option<u64>, String> {
         let row = sqlx::query_scalar::<_, i64>(
@@ -2079,17 +2974,135 @@ impl ActionProcessor {
         Ok(row.map(|id| id as u64))
     }
 
-    /// Broadcast un message à tous les joueurs qui ont chargé un chunk
-    async fn broadcast_to_chunk(&self, _chunk_id: &TerrainChunkId, message: ServerMessage) {
-        // TODO: Implémenter le broadcast aux joueurs d'un chunk spécifique
-        // Pour l'instant on broadcast à tous les joueurs
-        tracing::debug!(
-            "Broadcasting message to all players (chunk-specific broadcast not yet implemented)"
+    /// Ajoute au territoire les cellules encore libres que le trésor peut payer et qui
+    /// restent reliées au territoire, puis régénère les contours des seuls chunks touchés
+    async fn complete_claim(
+        &self,
+        player_id: u64,
+        unit_id: u64,
+        organization_id: u64,
+        cells: &[GridCell],
+    ) {
+        let mut territory: HashSet<GridCell> = match self
+            .db_tables
+            .organizations
+            .load_territory_cells(organization_id)
+            .await
+        {
+            Ok(territory) => territory.into_iter().collect(),
+            Err(e) => {
+                tracing::error!("{}", e);
+                return;
+            }
+        };
+
+        // Borner les cellules de proche en proche depuis le territoire actuel
+        let mut pending = cells.to_vec();
+        let mut claimed = Vec::new();
+        while let Some(index) = crate::world::territory::next_connected_cell(&territory, &pending)
+        {
+            let cell = pending.remove(index);
+            match self
+                .db_tables
+                .organizations
+                .claim_territory_cell(organization_id, &cell, shared::CLAIM_CELL_GOLD_COST)
+                .await
+            {
+                Ok(true) => {
+                    claimed.push(cell);
+                    territory.insert(cell);
+                }
+                // Prise entre-temps par une autre organisation, ou trésor vide
+                Ok(false) => tracing::info!(
+                    "Organization {} could not claim cell ({}, {})",
+                    organization_id,
+                    cell.q,
+                    cell.r
+                ),
+                Err(e) => tracing::error!("{}", e),
+            }
+        }
+        if !pending.is_empty() {
+            tracing::info!(
+                "Organization {} left {} claimed cells cut off from its territory",
+                organization_id,
+                pending.len()
+            );
+        }
+
+        tracing::info!(
+            "Organization {} claimed {}/{} cells",
+            organization_id,
+            claimed.len(),
+            cells.len()
         );
-        self.sessions.broadcast(message).await;
+        if claimed.is_empty() {
+            return;
+        }
+
+        match crate::world::territory::refresh_territory_contours(
+            &self.db_tables,
+            &self.grid_config,
+            organization_id,
+            &claimed,
+        )
+        .await
+        {
+            Ok(messages) => {
+                for msg in messages {
+                    if let ServerMessage::TerritoryContourUpdate { chunk_id, .. } = &msg {
+                        let chunk_id = *chunk_id;
+                        self.broadcast_to_chunk(&chunk_id, msg).await;
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to refresh contours of organization {}: {}",
+                    organization_id,
+                    e
+                );
+            }
+        }
+
+        // Le trésor a payé les cellules bornées
+        let treasury = async {
+            let organization = self
+                .db_tables
+                .organizations
+                .load_organization(organization_id)
+                .await?;
+            let officers = self
+                .db_tables
+                .organizations
+                .load_officers(organization_id)
+                .await?;
+            let items = self
+                .db_tables
+                .organizations
+                .load_treasury_stock(organization_id)
+                .await?;
+            // Un officier qui borne le territoire n'a pas forcément accès au trésor
+            Ok::<_, String>(ServerMessage::TreasuryData {
+                organization_id,
+                gold: organization.treasury_gold,
+                items,
+                can_withdraw: organization.can_withdraw_from_treasury(unit_id, &officers),
+            })
+        }
+        .await;
+        match treasury {
+            Ok(msg) => self.send_message_to_player(player_id, msg).await,
+            Err(e) => tracing::warn!("{}", e),
+        }
+    }
+
+    /// Broadcast un message à tous les joueurs qui ont chargé un chunk
+    async fn broadcast_to_chunk(&self, chunk_id: &TerrainChunkId, message: ServerMessage) {
+        self.sessions.broadcast_to_chunk(chunk_id, message).await;
     }
 
-    /// Count active (Pending + InProgress) production actions on a cell.
+    /// Count active (Pending + InProgress + Paused) production actions on a cell.
     pub async fn active_production_count_on_cell(&self, cell: &GridCell) -> usize {
         let active_actions = self.active_actions.read().await;
         active_actions
@@ -2098,7 +3111,9 @@ impl ActionProcessor {
                 a.cell == *cell
                     && matches!(
                         a.status,
-                        ActionStatusEnum::Pending | ActionStatusEnum::InProgress
+                        ActionStatusEnum::Pending
+                            | ActionStatusEnum::InProgress
+                            | ActionStatusEnum::Paused
                     )
                     && matches!(
                         a.action_type,
@@ -2111,14 +3126,74 @@ impl ActionProcessor {
     }
 }
 
-/// Démarre le processeur d'actions en arrière-plan
+/// Part du travail restant à effectuer, entre 0 et 1
+fn remaining_fraction(remaining_ms: u64, duration_ms: u64) -> f64 {
+    if duration_ms == 0 {
+        return 0.0;
+    }
+    (remaining_ms as f64 / duration_ms as f64).clamp(0.0, 1.0)
+}
+
+/// Quantité rendue pour une réservation, arrondie à l'unité inférieure
+fn refund_quantity(reserved: i32, remaining_fraction: f64) -> i32 {
+    (reserved as f64 * remaining_fraction.clamp(0.0, 1.0)).floor() as i32
+}
+
+/// Démarre le processeur d'actions en arrière-plan.
+/// Il dort jusqu'à la prochaine échéance, ou jusqu'à ce que `add_action` en ajoute une plus proche.
 pub fn start_action_processor(processor: Arc<ActionProcessor>) {
     tokio::task::spawn(async move {
-        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
-
         loop {
-            interval.tick().await;
             processor.tick().await;
+
+            match processor.time_until_next_due() {
+                Some(delay) => {
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = processor.wake.notified() => {}
+                    }
+                }
+                None => processor.wake.notified().await,
+            }
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_pops_due_actions_in_order() {
+        let mut schedule = ActionSchedule::default();
+        schedule.push(3_000, 3);
+        schedule.push(1_000, 1);
+        schedule.push(2_500, 2);
+
+        assert_eq!(schedule.next_due(), Some(1_000));
+        assert_eq!(schedule.pop_due(999), Vec::<u64>::new());
+        assert_eq!(schedule.pop_due(2_500), vec![1, 2]);
+        assert_eq!(schedule.next_due(), Some(3_000));
+    }
+
+    #[test]
+    fn test_removed_action_leaves_the_schedule() {
+        let mut schedule = ActionSchedule::default();
+        schedule.push(1_000, 1);
+        schedule.push(2_000, 2);
+        schedule.push(3_000, 1);
+
+        schedule.remove(1);
+        assert_eq!(schedule.next_due(), Some(2_000));
+        assert_eq!(schedule.pop_due(5_000), vec![2]);
+    }
+
+    #[test]
+    fn test_refund_is_proportional_to_remaining_work() {
+        assert_eq!(refund_quantity(10, remaining_fraction(0, 60_000)), 0);
+        assert_eq!(refund_quantity(10, remaining_fraction(30_000, 60_000)), 5);
+        assert_eq!(refund_quantity(3, remaining_fraction(30_000, 60_000)), 1);
+        assert_eq!(refund_quantity(7, remaining_fraction(90_000, 60_000)), 7);
+        assert_eq!(refund_quantity(7, 1.0), 7);
+    }
+}
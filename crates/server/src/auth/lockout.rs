@@ -0,0 +1,61 @@
+/// Lockout policy for password logins
+use std::time::Duration;
+
+/// Échecs tolérés sur un compte avant le premier verrouillage
+pub const ACCOUNT_FREE_ATTEMPTS: u32 = 5;
+
+/// Échecs tolérés depuis une même IP sur `IP_WINDOW` avant de refuser sans vérifier
+pub const IP_FREE_ATTEMPTS: u32 = 20;
+pub const IP_WINDOW: Duration = Duration::from_secs(15 * 60);
+
+const BASE_DELAY_SECS: u64 = 30;
+const MAX_DELAY_SECS: u64 = 60 * 60;
+
+/// Delay to wait after `failures` consecutive failures, doubling with each
+/// failure past `free_attempts` (30s, 1min, 2min... capped at one hour)
+pub fn lockout_delay(failures: u32, free_attempts: u32) -> Option<Duration> {
+    let excess = failures.checked_sub(free_attempts)?;
+    let delay = BASE_DELAY_SECS
+        .saturating_mul(1u64 << excess.min(32))
+        .min(MAX_DELAY_SECS);
+    Some(Duration::from_secs(delay))
+}
+
+/// Outcome stored in `game.login_attempts`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoginOutcome {
+    Success,
+    InvalidPassword,
+    UnknownAccount,
+    Locked,
+}
+
+impl LoginOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LoginOutcome::Success => "success",
+            LoginOutcome::InvalidPassword => "invalid_password",
+            LoginOutcome::UnknownAccount => "unknown_account",
+            LoginOutcome::Locked => "locked",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_no_lockout_before_threshold() {
+        assert_eq!(lockout_delay(0, 5), None);
+        assert_eq!(lockout_delay(4, 5), None);
+    }
+
+    #[test]
+    fn test_lockout_doubles_and_is_capped() {
+        assert_eq!(lockout_delay(5, 5), Some(Duration::from_secs(30)));
+        assert_eq!(lockout_delay(6, 5), Some(Duration::from_secs(60)));
+        assert_eq!(lockout_delay(8, 5), Some(Duration::from_secs(240)));
+        assert_eq!(lockout_delay(200, 5), Some(Duration::from_secs(3600)));
+    }
+}
@@ -1,5 +1,9 @@
 /// Server-side authentication module
 
+pub mod lockout;
 pub mod password;
+pub mod reset_code;
+pub mod resume_token;
 
 pub use password::{hash_password, verify_password};
+pub use resume_token::{RESUME_TOKEN_TTL_SECS, ResumeTokenSigner};
@@ -0,0 +1,40 @@
+/// One-time password reset codes issued by an administrator
+use rand::Rng;
+use std::time::Duration;
+
+/// Durée de validité d'un code de réinitialisation
+pub const RESET_CODE_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+const RESET_CODE_LENGTH: usize = 10;
+
+/// Sans 0/O ni 1/I/L : le code est recopié à la main par le joueur
+const RESET_CODE_ALPHABET: &[u8] = b"ABCDEFGHJKMNPQRSTUVWXYZ23456789";
+
+/// Generate a random code; only its hash is stored (see `hash_password`)
+pub fn generate_reset_code() -> String {
+    let mut rng = rand::rng();
+    (0..RESET_CODE_LENGTH)
+        .map(|_| RESET_CODE_ALPHABET[rng.random_range(0..RESET_CODE_ALPHABET.len())] as char)
+        .collect()
+}
+
+/// Codes are shown uppercase, but players may type them in lowercase or with spaces
+pub fn normalize_reset_code(code: &str) -> String {
+    code.chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .map(|c| c.to_ascii_uppercase())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_code_is_normalized() {
+        let code = generate_reset_code();
+        assert_eq!(code.len(), RESET_CODE_LENGTH);
+        assert_eq!(normalize_reset_code(&code), code);
+        assert_eq!(normalize_reset_code(" abcd-efgh "), "ABCDEFGH");
+    }
+}
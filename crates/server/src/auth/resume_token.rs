@@ -0,0 +1,157 @@
+/// Signed, expiring tokens used to resume a dropped session
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Durée de validité d'un jeton de reprise (et du buffer de messages d'un joueur déconnecté)
+pub const RESUME_TOKEN_TTL_SECS: u64 = 5 * 60;
+
+/// Signe et vérifie les jetons `player_id.generation.expires_at.signature`.
+///
+/// The secret is generated at startup: outboxes only live in memory,
+/// so a token has nothing to resume after a server restart anyway.
+/// Bumping a player's generation revokes every token issued before.
+pub struct ResumeTokenSigner {
+    secret: [u8; 32],
+    // player_id -> génération courante (absent = 0)
+    generations: Mutex<HashMap<u64, u32>>,
+}
+
+impl Default for ResumeTokenSigner {
+    fn default() -> Self {
+        Self::new(rand::random())
+    }
+}
+
+impl ResumeTokenSigner {
+    pub fn new(secret: [u8; 32]) -> Self {
+        Self {
+            secret,
+            generations: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Issue a token for `player_id`, valid for `RESUME_TOKEN_TTL_SECS` after `now`
+    pub fn issue(&self, player_id: u64, now: u64) -> String {
+        let payload = format!(
+            "{}.{}.{}",
+            player_id,
+            self.generation(player_id),
+            now + RESUME_TOKEN_TTL_SECS
+        );
+        let signature = self.mac(&payload).finalize().into_bytes();
+        format!("{}.{}", payload, to_hex(&signature))
+    }
+
+    /// Invalide tous les jetons déjà émis pour `player_id`
+    pub fn revoke(&self, player_id: u64) {
+        let mut generations = self.generations.lock().unwrap_or_else(|e| e.into_inner());
+        let generation = generations.entry(player_id).or_insert(0);
+        *generation = generation.wrapping_add(1);
+    }
+
+    /// Returns the player_id of a valid, non-expired, non-revoked token
+    pub fn verify(&self, token: &str, now: u64) -> Result<u64, String> {
+        let (payload, signature) = token
+            .rsplit_once('.')
+            .ok_or_else(|| "Malformed token".to_string())?;
+        let signature = from_hex(signature).ok_or_else(|| "Malformed signature".to_string())?;
+
+        self.mac(payload)
+            .verify_slice(&signature)
+            .map_err(|_| "Invalid signature".to_string())?;
+
+        let mut fields = payload.splitn(3, '.');
+        let (Some(player_id), Some(generation), Some(expires_at)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            return Err("Malformed token".to_string());
+        };
+        let player_id = player_id
+            .parse::<u64>()
+            .map_err(|e| format!("Invalid player id: {}", e))?;
+        let generation = generation
+            .parse::<u32>()
+            .map_err(|e| format!("Invalid generation: {}", e))?;
+        let expires_at = expires_at
+            .parse::<u64>()
+            .map_err(|e| format!("Invalid expiry: {}", e))?;
+
+        if now > expires_at {
+            return Err("Token expired".to_string());
+        }
+        if generation != self.generation(player_id) {
+            return Err("Token revoked".to_string());
+        }
+        Ok(player_id)
+    }
+
+    fn generation(&self, player_id: u64) -> u32 {
+        let generations = self.generations.lock().unwrap_or_else(|e| e.into_inner());
+        generations.get(&player_id).copied().unwrap_or(0)
+    }
+
+    fn mac(&self, payload: &str) -> HmacSha256 {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts keys of any size");
+        mac.update(payload.as_bytes());
+        mac
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn from_hex(s: &str) -> Option<Vec<u8>> {
+    if s.len() % 2 != 0 {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issued_token_verifies() {
+        let signer = ResumeTokenSigner::new([7; 32]);
+        let token = signer.issue(42, 1_000);
+        assert_eq!(signer.verify(&token, 1_000 + RESUME_TOKEN_TTL_SECS), Ok(42));
+    }
+
+    #[test]
+    fn test_expired_or_tampered_token_is_rejected() {
+        let signer = ResumeTokenSigner::new([7; 32]);
+        let token = signer.issue(42, 1_000);
+        assert!(signer.verify(&token, 1_001 + RESUME_TOKEN_TTL_SECS).is_err());
+
+        let tampered = token.replacen("42", "43", 1);
+        assert!(signer.verify(&tampered, 1_000).is_err());
+
+        let other_signer = ResumeTokenSigner::new([8; 32]);
+        assert!(other_signer.verify(&token, 1_000).is_err());
+    }
+
+    #[test]
+    fn test_revoke_invalidates_earlier_tokens_only() {
+        let signer = ResumeTokenSigner::new([7; 32]);
+        let old_token = signer.issue(42, 1_000);
+        let other_player = signer.issue(43, 1_000);
+
+        signer.revoke(42);
+        assert!(signer.verify(&old_token, 1_000).is_err());
+        assert_eq!(signer.verify(&other_player, 1_000), Ok(43));
+
+        let new_token = signer.issue(42, 1_000);
+        assert_eq!(signer.verify(&new_token, 1_000), Ok(42));
+    }
+}
@@ -0,0 +1,48 @@
+// Utilitaire d'administration : émet un code de réinitialisation de mot de passe
+// à usage unique pour un joueur. Le joueur l'utilise depuis l'écran de connexion
+// (ResetPasswordWithCode) pour choisir un nouveau mot de passe.
+//
+// Usage : issue_password_reset <family_name>
+
+use sqlx::PgPool;
+use std::env;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Load .env
+    dotenv::dotenv().ok();
+
+    let family_name = env::args()
+        .nth(1)
+        .ok_or("Usage: issue_password_reset <family_name>")?;
+
+    let database_url = env::var("DATABASE_URL")
+        .expect("DATABASE_URL must be set");
+
+    println!("Connecting to database...");
+    let pool = PgPool::connect(&database_url).await?;
+
+    use server::auth::{hash_password, reset_code};
+    use server::database::tables::AccountsTable;
+
+    let player = shared::types::game::methods::get_player_by_family_name(&pool, &family_name)
+        .await?
+        .ok_or_else(|| format!("No player named {}", family_name))?;
+
+    let code = reset_code::generate_reset_code();
+    let code_hash = hash_password(&code)?;
+
+    AccountsTable::new(pool.clone())
+        .issue_reset_code(player.id, &code_hash, reset_code::RESET_CODE_TTL)
+        .await?;
+
+    println!(
+        "✓ Reset code for {} (ID: {}), valid {} hours: {}",
+        player.family_name,
+        player.id,
+        reset_code::RESET_CODE_TTL.as_secs() / 3600,
+        code
+    );
+
+    Ok(())
+}
@@ -29,6 +29,9 @@ pub struct DatabaseTables {
     pub voronoi_zones: tables::VoronoiZonesTable,
     pub territory_contours: tables::TerritoryContoursTable,
     pub exploration: tables::ExplorationTable,
+    pub login_attempts: tables::LoginAttemptsTable,
+    pub accounts: tables::AccountsTable,
+    pub action_queue: tables::ActionQueueTable,
 }
 
 impl DatabaseClient {
@@ -93,6 +96,9 @@ impl DatabaseClient {
                 voronoi_zones: tables::VoronoiZonesTable::new(pool.clone()),
                 territory_contours: tables::TerritoryContoursTable::new(pool.clone()),
                 exploration: tables::ExplorationTable::new(pool.clone()),
+                login_attempts: tables::LoginAttemptsTable::new(pool.clone()),
+                accounts: tables::AccountsTable::new(pool.clone()),
+                action_queue: tables::ActionQueueTable::new(pool.clone()),
             },
             game_state,
         )
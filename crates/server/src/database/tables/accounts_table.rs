@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use shared::TerrainChunkId;
+use sqlx::{PgPool, Row};
+
+use crate::auth::password;
+
+/// Password reset codes and account deletion
+#[derive(Resource, Clone)]
+pub struct AccountsTable {
+    pool: PgPool,
+}
+
+impl AccountsTable {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Store a new reset code for the player. Codes issued earlier and not used yet are revoked.
+    pub async fn issue_reset_code(
+        &self,
+        player_id: i64,
+        code_hash: &str,
+        ttl: Duration,
+    ) -> Result<(), String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        sqlx::query(
+            r#"
+            UPDATE game.password_reset_codes
+            SET used_at = NOW()
+            WHERE player_id = $1 AND used_at IS NULL
+            "#,
+        )
+        .bind(player_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to revoke previous reset codes: {}", e))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO game.password_reset_codes (player_id, code_hash, expires_at)
+            VALUES ($1, $2, NOW() + make_interval(secs => $3))
+            "#,
+        )
+        .bind(player_id)
+        .bind(code_hash)
+        .bind(ttl.as_secs_f64())
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to store reset code: {}", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit reset code: {}", e))
+    }
+
+    /// Use a reset code: it is burned with every other pending code, and the account
+    /// lockout cleared. Returns false if no valid code matches.
+    pub async fn consume_reset_code(&self, player_id: i64, code: &str) -> Result<bool, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let pending = sqlx::query(
+            r#"
+            SELECT id, code_hash FROM game.password_reset_codes
+            WHERE player_id = $1 AND used_at IS NULL AND expires_at > NOW()
+            FOR UPDATE
+            "#,
+        )
+        .bind(player_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to load reset codes: {}", e))?;
+
+        let matching = pending.iter().find(|row| {
+            let code_hash: String = row.get("code_hash");
+            password::verify_password(code, &code_hash).unwrap_or(false)
+        });
+        if matching.is_none() {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE game.password_reset_codes
+            SET used_at = NOW()
+            WHERE player_id = $1 AND used_at IS NULL
+            "#,
+        )
+        .bind(player_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to burn reset code: {}", e))?;
+
+        sqlx::query(
+            r#"
+            UPDATE game.players
+            SET failed_login_count = 0, locked_until = NULL, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(player_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to clear account lockout: {}", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit reset code: {}", e))?;
+        Ok(true)
+    }
+
+    /// Delete a player and everything it owns, in one transaction:
+    /// - organizations led by its units (members, officers, territory, treasury,
+    ///   diplomacy follow by cascade; vassals become independent)
+    /// - its scheduled actions and the items carried by its units
+    /// - the player itself, which cascades to its characters and units
+    ///
+    /// Returns the ids of the deleted organizations, and the chunks where each of
+    /// them had a territory contour.
+    pub async fn delete_account(
+        &self,
+        player_id: i64,
+    ) -> Result<(Vec<u64>, Vec<(u64, TerrainChunkId)>), String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        // The contours go with their organizations: remember where they were drawn
+        let contour_chunks = sqlx::query(
+            r#"
+            SELECT organization_id, chunk_x, chunk_y
+            FROM organizations.territory_contours
+            WHERE organization_id IN (
+                SELECT o.id FROM organizations.organizations o
+                JOIN units.units u ON u.id = o.leader_unit_id
+                WHERE u.player_id = $1
+            )
+            "#,
+        )
+        .bind(player_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to load territory contours: {}", e))?
+        .iter()
+        .map(|row| {
+            let organization_id: i64 = row.get("organization_id");
+            let chunk_id = TerrainChunkId {
+                x: row.get("chunk_x"),
+                y: row.get("chunk_y"),
+            };
+            (organization_id as u64, chunk_id)
+        })
+        .collect();
+
+        let organization_ids = sqlx::query_scalar::<_, i64>(
+            r#"
+            DELETE FROM organizations.organizations
+            WHERE leader_unit_id IN (SELECT id FROM units.units WHERE player_id = $1)
+            RETURNING id
+            "#,
+        )
+        .bind(player_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete organizations: {}", e))?;
+
+        sqlx::query("DELETE FROM actions.scheduled_actions WHERE player_id = $1")
+            .bind(player_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete scheduled actions: {}", e))?;
+
+        sqlx::query(
+            r#"
+            DELETE FROM resources.item_instances
+            WHERE owner_unit_id IN (SELECT id FROM units.units WHERE player_id = $1)
+            "#,
+        )
+        .bind(player_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to delete unit items: {}", e))?;
+
+        let deleted = sqlx::query("DELETE FROM game.players WHERE id = $1")
+            .bind(player_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to delete player: {}", e))?;
+        if deleted.rows_affected() == 0 {
+            return Err(format!("Player {} not found", player_id));
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit account deletion: {}", e))?;
+
+        Ok((
+            organization_ids.into_iter().map(|id| id as u64).collect(),
+            contour_chunks,
+        ))
+    }
+}
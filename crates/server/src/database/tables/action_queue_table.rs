@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use shared::protocol::{PROTOCOL_VERSION, QueuedAction, QueuedActionEntry};
+use sqlx::{PgPool, Row};
+
+/// File d'actions de chaque unité
+#[derive(Resource, Clone)]
+pub struct ActionQueueTable {
+    pool: PgPool,
+}
+
+impl ActionQueueTable {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// L'encodage bincode dépend de la version du protocole : chaque entrée est
+    /// enregistrée avec `PROTOCOL_VERSION` (voir `purge_stale_entries`)
+    fn encode(action: &QueuedAction) -> Result<Vec<u8>, String> {
+        bincode::encode_to_vec(action, bincode::config::standard())
+            .map_err(|e| format!("Failed to encode queued action: {}", e))
+    }
+
+    fn decode(bytes: &[u8]) -> Result<QueuedAction, String> {
+        bincode::decode_from_slice(bytes, bincode::config::standard())
+            .map(|(action, _)| action)
+            .map_err(|e| format!("Failed to decode queued action: {}", e))
+    }
+
+    fn entry_from_row(row: &sqlx::postgres::PgRow) -> Result<QueuedActionEntry, String> {
+        let bytes: Vec<u8> = row.get("action_data");
+        Ok(QueuedActionEntry {
+            entry_id: row.get::<i64, &str>("id") as u64,
+            action: Self::decode(&bytes)?,
+        })
+    }
+
+    /// Ajoute une action en fin de file, renvoie l'id de l'entrée
+    pub async fn enqueue(&self, unit_id: u64, action: &QueuedAction) -> Result<u64, String> {
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO actions.unit_action_queue (unit_id, position, action_data, protocol_version)
+            SELECT $1, COALESCE(MAX(position) + 1, 0), $2, $3
+            FROM actions.unit_action_queue
+            WHERE unit_id = $1
+            RETURNING id
+            "#,
+        )
+        .bind(unit_id as i64)
+        .bind(Self::encode(action)?)
+        .bind(PROTOCOL_VERSION as i32)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to enqueue action: {}", e))?;
+
+        Ok(id as u64)
+    }
+
+    /// File d'une unité, dans l'ordre d'exécution
+    pub async fn load_queue(&self, unit_id: u64) -> Result<Vec<QueuedActionEntry>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, action_data FROM actions.unit_action_queue
+            WHERE unit_id = $1
+            ORDER BY position, id
+            "#,
+        )
+        .bind(unit_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load action queue: {}", e))?;
+
+        rows.iter().map(Self::entry_from_row).collect()
+    }
+
+    /// Retire et renvoie la première action de la file
+    pub async fn pop_front(&self, unit_id: u64) -> Result<Option<QueuedActionEntry>, String> {
+        let row = sqlx::query(
+            r#"
+            DELETE FROM actions.unit_action_queue
+            WHERE id = (
+                SELECT id FROM actions.unit_action_queue
+                WHERE unit_id = $1
+                ORDER BY position, id
+                LIMIT 1
+            )
+            RETURNING id, action_data
+            "#,
+        )
+        .bind(unit_id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to pop action queue: {}", e))?;
+
+        row.as_ref().map(Self::entry_from_row).transpose()
+    }
+
+    /// Réécrit l'ordre de la file ; `entry_ids` doit contenir toutes ses entrées
+    pub async fn reorder(&self, unit_id: u64, entry_ids: &[u64]) -> Result<(), String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        for (position, entry_id) in entry_ids.iter().enumerate() {
+            sqlx::query(
+                r#"
+                UPDATE actions.unit_action_queue
+                SET position = $1
+                WHERE id = $2 AND unit_id = $3
+                "#,
+            )
+            .bind(position as i32)
+            .bind(*entry_id as i64)
+            .bind(unit_id as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to reorder action queue: {}", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit queue order: {}", e))
+    }
+
+    /// Supprime les entrées écrites par une autre version du protocole, qu'on ne
+    /// saurait plus décoder. Renvoie le nombre d'entrées supprimées
+    pub async fn purge_stale_entries(&self) -> Result<u64, String> {
+        let result = sqlx::query(
+            "DELETE FROM actions.unit_action_queue WHERE protocol_version <> $1",
+        )
+        .bind(PROTOCOL_VERSION as i32)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to purge action queues: {}", e))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Vide la file d'une unité, renvoie le nombre d'entrées supprimées
+    pub async fn clear(&self, unit_id: u64) -> Result<u64, String> {
+        let result = sqlx::query("DELETE FROM actions.unit_action_queue WHERE unit_id = $1")
+            .bind(unit_id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to clear action queue: {}", e))?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -1,13 +1,23 @@
+use std::collections::HashSet;
+
 use bevy::prelude::*;
 
 use shared::{
     ActionBaseData, ActionData, ActionSpecificTypeEnum, ActionStatusEnum, ActionTypeEnum,
-    BuildBuildingAction, BuildRoadAction, BuildingTypeEnum, CraftResourceAction,
-    HarvestResourceAction, MoveUnitAction, ProfessionEnum, ResourceSpecificTypeEnum,
-    SendMessageAction, SpecificAction, TerrainChunkId, TrainUnitAction, grid::GridCell,
+    BuildBuildingAction, BuildRoadAction, BuildingTypeEnum, ClaimCellsAction, CraftResourceAction,
+    EnvoyMission, HarvestResourceAction, MoveUnitAction, ProfessionEnum, ResourceSpecificTypeEnum,
+    SendEnvoyAction, SendMessageAction, SpecificAction, TerrainChunkId, TrainUnitAction, grid::GridCell,
 };
 use sqlx::{PgPool, Row};
 
+/// D'où viennent les matériaux d'une action : l'inventaire d'une unité
+/// ou le trésor d'une organisation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MaterialSource {
+    Unit(u64),
+    Treasury(u64),
+}
+
 #[derive(Resource, Clone)]
 pub struct ScheduledActionsTable {
     pool: PgPool,
@@ -149,6 +159,61 @@ impl ScheduledActionsTable {
                 .await
                 .map_err(|e| format!("DB error: {}", e))?;
             }
+            SpecificAction::ClaimCells(a) => {
+                sqlx::query(
+                    "INSERT INTO actions.claim_cells_actions (action_id, unit_id, organization_id) VALUES ($1, $2, $3)"
+                )
+                .bind(action_id as i64)
+                .bind(a.unit_id as i64)
+                .bind(a.organization_id as i64)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| format!("DB error: {}", e))?;
+
+                for cell in &a.cells {
+                    sqlx::query(
+                        "INSERT INTO actions.claim_cells_action_cells (action_id, cell_q, cell_r) VALUES ($1, $2, $3)"
+                    )
+                    .bind(action_id as i64)
+                    .bind(cell.q)
+                    .bind(cell.r)
+                    .execute(&self.pool)
+                    .await
+                    .map_err(|e| format!("DB error: {}", e))?;
+                }
+            }
+            SpecificAction::SendEnvoy(a) => {
+                let (relation_type, duration_days, proposal_id) = match a.mission {
+                    EnvoyMission::Propose {
+                        relation_type,
+                        duration_days,
+                    } => (Some(relation_type.to_string()), duration_days, None),
+                    EnvoyMission::Accept { proposal_id } | EnvoyMission::Reject { proposal_id } => {
+                        (None, None, Some(proposal_id))
+                    }
+                };
+                sqlx::query(
+                    r#"
+                    INSERT INTO actions.send_envoy_actions
+                    (action_id, unit_id, organization_id, target_organization_id, target_q, target_r,
+                     mission, relation_type, duration_days, proposal_id)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                    "#,
+                )
+                .bind(action_id as i64)
+                .bind(a.unit_id as i64)
+                .bind(a.organization_id as i64)
+                .bind(a.target_organization_id as i64)
+                .bind(a.target_cell.q)
+                .bind(a.target_cell.r)
+                .bind(a.mission.kind())
+                .bind(relation_type)
+                .bind(duration_days.map(|days| days as i32))
+                .bind(proposal_id.map(|id| id as i64))
+                .execute(&self.pool)
+                .await
+                .map_err(|e| format!("DB error: {}", e))?;
+            }
             _ => {}
         }
 
@@ -381,6 +446,32 @@ impl ScheduledActionsTable {
                         target_profession,
                     })
                 }
+                ActionSpecificTypeEnum::ClaimCells => {
+                    let Ok(Some((unit_id, organization_id, cells))) =
+                        self.load_claim_cells_data(id as u64).await
+                    else {
+                        continue;
+                    };
+
+                    SpecificAction::ClaimCells(ClaimCellsAction {
+                        player_id,
+                        unit_id,
+                        organization_id,
+                        chunk_id: *chunk_id,
+                        cell,
+                        cells,
+                    })
+                }
+                ActionSpecificTypeEnum::SendEnvoy => {
+                    let Ok(Some(envoy)) = self
+                        .load_send_envoy_data(id as u64, player_id, *chunk_id, cell)
+                        .await
+                    else {
+                        continue;
+                    };
+
+                    SpecificAction::SendEnvoy(envoy)
+                }
             };
 
             actions.push(ActionData {
@@ -392,7 +483,7 @@ impl ScheduledActionsTable {
         Ok(actions)
     }
 
-    /// Charge toutes les actions actives (Pending ou InProgress)
+    /// Charge toutes les actions actives (Pending, InProgress ou Paused)
     pub async fn load_active_actions(
         &self,
     ) -> Result<
@@ -406,6 +497,7 @@ impl ScheduledActionsTable {
             u64,
             u64,
             u64,
+            Option<u64>,
         )>,
         String,
     > {
@@ -413,9 +505,10 @@ impl ScheduledActionsTable {
             r#"
             SELECT
                 id, player_id, chunk_x, chunk_y, cell_q, cell_r,
-                action_type_id, status_id, start_time, duration_ms, completion_time
+                action_type_id, status_id, start_time, duration_ms, completion_time,
+                paused_remaining_ms
             FROM actions.scheduled_actions
-            WHERE status_id IN (1, 2)
+            WHERE status_id IN (1, 2, 6)
             "#,
         )
         .fetch_all(&self.pool)
@@ -447,6 +540,9 @@ impl ScheduledActionsTable {
             let start_time = row.get::<i64, &str>("start_time") as u64;
             let duration_ms = row.get::<i64, &str>("duration_ms") as u64;
             let completion_time = row.get::<i64, &str>("completion_time") as u64;
+            let paused_remaining_ms = row
+                .get::<Option<i64>, &str>("paused_remaining_ms")
+                .map(|ms| ms.max(0) as u64);
 
             actions.push((
                 action_id,
@@ -458,6 +554,7 @@ impl ScheduledActionsTable {
                 start_time,
                 duration_ms,
                 completion_time,
+                paused_remaining_ms,
             ));
         }
 
@@ -491,6 +588,127 @@ impl ScheduledActionsTable {
         }))
     }
 
+    /// Charge les données d'une revendication : (unit_id, organization_id, cellules)
+    pub async fn load_claim_cells_data(
+        &self,
+        action_id: u64,
+    ) -> Result<Option<(u64, u64, Vec<GridCell>)>, String> {
+        let row = sqlx::query(
+            r#"
+            SELECT unit_id, organization_id
+            FROM actions.claim_cells_actions
+            WHERE action_id = $1
+            "#,
+        )
+        .bind(action_id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load claim_cells data: {}", e))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let cells = sqlx::query(
+            r#"
+            SELECT cell_q, cell_r
+            FROM actions.claim_cells_action_cells
+            WHERE action_id = $1
+            ORDER BY cell_q, cell_r
+            "#,
+        )
+        .bind(action_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load claim_cells cells: {}", e))?
+        .into_iter()
+        .map(|r| GridCell {
+            q: r.get("cell_q"),
+            r: r.get("cell_r"),
+        })
+        .collect();
+
+        Ok(Some((
+            row.get::<i64, &str>("unit_id") as u64,
+            row.get::<i64, &str>("organization_id") as u64,
+            cells,
+        )))
+    }
+
+    /// Charge la mission d'un émissaire ; le joueur et le siège de départ viennent
+    /// de `actions.scheduled_actions`
+    pub async fn load_send_envoy_data(
+        &self,
+        action_id: u64,
+        player_id: u64,
+        chunk_id: TerrainChunkId,
+        cell: GridCell,
+    ) -> Result<Option<SendEnvoyAction>, String> {
+        let row = sqlx::query(
+            r#"
+            SELECT unit_id, organization_id, target_organization_id, target_q, target_r,
+                   mission, relation_type, duration_days, proposal_id
+            FROM actions.send_envoy_actions
+            WHERE action_id = $1
+            "#,
+        )
+        .bind(action_id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load send_envoy data: {}", e))?;
+
+        let Some(row) = row else {
+            return Ok(None);
+        };
+
+        let relation_type: Option<String> = row.get("relation_type");
+        let Some(mission) = EnvoyMission::from_parts(
+            row.get("mission"),
+            relation_type.as_deref(),
+            row.get::<Option<i32>, &str>("duration_days").map(|days| days as u32),
+            row.get::<Option<i64>, &str>("proposal_id").map(|id| id as u64),
+        ) else {
+            return Err(format!("Envoy action {} has an unreadable mission", action_id));
+        };
+
+        Ok(Some(SendEnvoyAction {
+            player_id,
+            unit_id: row.get::<i64, &str>("unit_id") as u64,
+            organization_id: row.get::<i64, &str>("organization_id") as u64,
+            target_organization_id: row.get::<i64, &str>("target_organization_id") as u64,
+            chunk_id,
+            cell,
+            target_cell: GridCell {
+                q: row.get("target_q"),
+                r: row.get("target_r"),
+            },
+            mission,
+        }))
+    }
+
+    /// Cellules déjà visées par une revendication en cours (Pending, InProgress ou Paused)
+    pub async fn load_pending_claimed_cells(&self) -> Result<HashSet<GridCell>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT c.cell_q, c.cell_r
+            FROM actions.claim_cells_action_cells c
+            JOIN actions.scheduled_actions sa ON sa.id = c.action_id
+            WHERE sa.status_id IN (1, 2, 6)
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load pending claims: {}", e))?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| GridCell {
+                q: r.get("cell_q"),
+                r: r.get("cell_r"),
+            })
+            .collect())
+    }
+
     /// Charge les données spécifiques d'une action MoveUnit
     /// Table: actions.move_unit_actions (action_id, unit_id, target_q, target_r)
     /// Le chunk cible est dans la table parent scheduled_actions (chunk_x, chunk_y)
@@ -548,6 +766,168 @@ impl ScheduledActionsTable {
         Ok(())
     }
 
+    /// Met une action en pause en gardant le temps qu'il lui reste
+    pub async fn pause_action(&self, action_id: u64, remaining_ms: u64) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            UPDATE actions.scheduled_actions
+            SET status_id = $1, paused_remaining_ms = $2
+            WHERE id = $3
+            "#,
+        )
+        .bind(ActionStatusEnum::Paused.to_id())
+        .bind(remaining_ms as i64)
+        .bind(action_id as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to pause action: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Reprend une action en pause avec ses nouveaux horaires
+    pub async fn resume_action(
+        &self,
+        action_id: u64,
+        start_time: u64,
+        completion_time: u64,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            UPDATE actions.scheduled_actions
+            SET status_id = $1, start_time = $2, completion_time = $3, paused_remaining_ms = NULL
+            WHERE id = $4
+            "#,
+        )
+        .bind(ActionStatusEnum::InProgress.to_id())
+        .bind(start_time as i64)
+        .bind(completion_time as i64)
+        .bind(action_id as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to resume action: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Enregistre des matériaux retirés de l'inventaire d'une unité pour une action,
+    /// avec leur qualité moyenne
+    pub async fn add_reservation(
+        &self,
+        action_id: u64,
+        unit_id: u64,
+        item_id: i32,
+        quantity: i32,
+        quality: f32,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            INSERT INTO actions.action_reservations (action_id, unit_id, item_id, quantity, quality)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (action_id, unit_id, item_id)
+            DO UPDATE SET
+                quality = (actions.action_reservations.quality * actions.action_reservations.quantity
+                           + EXCLUDED.quality * EXCLUDED.quantity)
+                          / (actions.action_reservations.quantity + EXCLUDED.quantity),
+                quantity = actions.action_reservations.quantity + EXCLUDED.quantity
+            "#,
+        )
+        .bind(action_id as i64)
+        .bind(unit_id as i64)
+        .bind(item_id)
+        .bind(quantity)
+        .bind(quality)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to reserve materials: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Enregistre des matériaux pris dans le trésor d'une organisation pour une action
+    pub async fn add_treasury_reservation(
+        &self,
+        action_id: u64,
+        organization_id: u64,
+        item_id: i32,
+        quantity: i32,
+        quality: f32,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            INSERT INTO actions.action_treasury_reservations
+                (action_id, organization_id, item_id, quantity, quality)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (action_id, organization_id, item_id)
+            DO UPDATE SET
+                quality = (actions.action_treasury_reservations.quality * actions.action_treasury_reservations.quantity
+                           + EXCLUDED.quality * EXCLUDED.quantity)
+                          / (actions.action_treasury_reservations.quantity + EXCLUDED.quantity),
+                quantity = actions.action_treasury_reservations.quantity + EXCLUDED.quantity
+            "#,
+        )
+        .bind(action_id as i64)
+        .bind(organization_id as i64)
+        .bind(item_id)
+        .bind(quantity)
+        .bind(quality)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to reserve treasury materials: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Retire et renvoie les matériaux réservés par une action, qu'ils viennent
+    /// d'une unité ou d'un trésor : (source, item_id, quantity, quality)
+    pub async fn take_reservations(
+        &self,
+        action_id: u64,
+    ) -> Result<Vec<(MaterialSource, i32, i32, f32)>, String> {
+        let rows = sqlx::query(
+            r#"
+            DELETE FROM actions.action_reservations
+            WHERE action_id = $1
+            RETURNING unit_id, item_id, quantity, quality
+            "#,
+        )
+        .bind(action_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load reservations: {}", e))?;
+
+        let treasury_rows = sqlx::query(
+            r#"
+            DELETE FROM actions.action_treasury_reservations
+            WHERE action_id = $1
+            RETURNING organization_id, item_id, quantity, quality
+            "#,
+        )
+        .bind(action_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load treasury reservations: {}", e))?;
+
+        let unit_reservations = rows.iter().map(|r| {
+            (
+                MaterialSource::Unit(r.get::<i64, &str>("unit_id") as u64),
+                r.get::<i32, &str>("item_id"),
+                r.get::<i32, &str>("quantity"),
+                r.get::<f32, &str>("quality"),
+            )
+        });
+        let treasury_reservations = treasury_rows.iter().map(|r| {
+            (
+                MaterialSource::Treasury(r.get::<i64, &str>("organization_id") as u64),
+                r.get::<i32, &str>("item_id"),
+                r.get::<i32, &str>("quantity"),
+                r.get::<f32, &str>("quality"),
+            )
+        });
+
+        Ok(unit_reservations.chain(treasury_reservations).collect())
+    }
+
     /// Charge les données d'une action HarvestResource
     pub async fn load_harvest_data(
         &self,
@@ -762,6 +762,30 @@ impl BuildingsTable {
         Ok(buildings)
     }
 
+    /// Arbre d'une cellule : id du bâtiment et densité de la forêt
+    pub async fn find_tree_at_cell(&self, cell: &GridCell) -> Result<Option<(u64, f32)>, String> {
+        let result = sqlx::query(
+            r#"
+            SELECT b.id, t.density
+            FROM buildings.buildings_base b
+            INNER JOIN buildings.trees t ON t.building_id = b.id
+            WHERE b.cell_q = $1 AND b.cell_r = $2
+            "#,
+        )
+        .bind(cell.q)
+        .bind(cell.r)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to find tree at cell: {}", e))?;
+
+        Ok(result.map(|row| {
+            (
+                row.get::<i64, _>("id") as u64,
+                row.get::<i32, _>("density") as f32,
+            )
+        }))
+    }
+
     /// Get building type at a specific cell
     pub async fn get_building_type_at_cell(
         &self,
@@ -788,4 +812,30 @@ impl BuildingsTable {
             Ok(None)
         }
     }
+
+    /// Qualité et durabilité du bâtiment construit sur une cellule
+    pub async fn get_building_condition_at_cell(
+        &self,
+        cell: &GridCell,
+    ) -> Result<Option<(f32, f32)>, String> {
+        let result = sqlx::query(
+            r#"
+            SELECT b.quality, b.durability
+            FROM buildings.buildings_base b
+            WHERE b.cell_q = $1 AND b.cell_r = $2 AND b.is_built = true
+            "#,
+        )
+        .bind(cell.q)
+        .bind(cell.r)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to get building condition at cell: {}", e))?;
+
+        Ok(result.map(|row| {
+            (
+                row.get::<f64, _>("quality") as f32,
+                row.get::<f64, _>("durability") as f32,
+            )
+        }))
+    }
 }
@@ -0,0 +1,134 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use sqlx::PgPool;
+
+use crate::auth::lockout::{ACCOUNT_FREE_ATTEMPTS, LoginOutcome, lockout_delay};
+
+/// Compteurs d'échecs de connexion (par compte sur `game.players`, par IP via l'historique)
+#[derive(Resource, Clone)]
+pub struct LoginAttemptsTable {
+    pool: PgPool,
+}
+
+impl LoginAttemptsTable {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Record an attempt in the audit trail
+    pub async fn record_attempt(
+        &self,
+        player_id: Option<i64>,
+        family_name: &str,
+        ip_address: &str,
+        outcome: LoginOutcome,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            INSERT INTO game.login_attempts (player_id, family_name, ip_address, outcome)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(player_id)
+        .bind(family_name)
+        .bind(ip_address)
+        .bind(outcome.as_str())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to record login attempt: {}", e))?;
+        Ok(())
+    }
+
+    /// Failed attempts from this IP within `window`
+    pub async fn recent_failures_from_ip(
+        &self,
+        ip_address: &str,
+        window: Duration,
+    ) -> Result<u32, String> {
+        let count = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*) FROM game.login_attempts
+            WHERE ip_address = $1
+              AND outcome <> 'success'
+              AND attempted_at > NOW() - make_interval(secs => $2)
+            "#,
+        )
+        .bind(ip_address)
+        .bind(window.as_secs_f64())
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to count login failures: {}", e))?;
+        Ok(count as u32)
+    }
+
+    /// Remaining lockout of an account, if any
+    pub async fn account_lock_remaining(&self, player_id: i64) -> Result<Option<Duration>, String> {
+        let remaining = sqlx::query_scalar::<_, Option<f64>>(
+            r#"
+            SELECT EXTRACT(EPOCH FROM (locked_until - NOW()))::FLOAT8
+            FROM game.players
+            WHERE id = $1 AND locked_until > NOW()
+            "#,
+        )
+        .bind(player_id)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load account lockout: {}", e))?
+        .flatten();
+
+        Ok(remaining.map(|secs| Duration::from_secs_f64(secs.max(0.0))))
+    }
+
+    /// Increment the failure counter and lock the account if it went past the threshold.
+    /// Returns the lockout applied, if any.
+    pub async fn register_failure(&self, player_id: i64) -> Result<Option<Duration>, String> {
+        let failures = sqlx::query_scalar::<_, i32>(
+            r#"
+            UPDATE game.players
+            SET failed_login_count = failed_login_count + 1
+            WHERE id = $1
+            RETURNING failed_login_count
+            "#,
+        )
+        .bind(player_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to increment login failures: {}", e))?;
+
+        let Some(delay) = lockout_delay(failures.max(0) as u32, ACCOUNT_FREE_ATTEMPTS) else {
+            return Ok(None);
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE game.players
+            SET locked_until = NOW() + make_interval(secs => $2)
+            WHERE id = $1
+            "#,
+        )
+        .bind(player_id)
+        .bind(delay.as_secs_f64())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to lock account: {}", e))?;
+
+        Ok(Some(delay))
+    }
+
+    /// Successful login: the account starts over with a clean slate
+    pub async fn reset_failures(&self, player_id: i64) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            UPDATE game.players
+            SET failed_login_count = 0, locked_until = NULL
+            WHERE id = $1
+            "#,
+        )
+        .bind(player_id)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to reset login failures: {}", e))?;
+        Ok(())
+    }
+}
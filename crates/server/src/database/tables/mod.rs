@@ -1,8 +1,11 @@
+mod accounts_table;
+mod action_queue_table;
 mod actions_table;
 mod buildings_table;
 mod cells_table;
 mod exploration_table;
 mod lake_data_table;
+mod login_attempts_table;
 mod ocean_data_table;
 mod organizations_table;
 mod resources_table;
@@ -14,11 +17,14 @@ pub mod types;
 mod units_table;
 mod voronoi_zones_table;
 
+pub use accounts_table::*;
+pub use action_queue_table::*;
 pub use actions_table::*;
 pub use buildings_table::*;
 pub use cells_table::*;
 pub use exploration_table::*;
 pub use lake_data_table::*;
+pub use login_attempts_table::*;
 pub use ocean_data_table::*;
 pub use organizations_table::*;
 pub use resources_table::*;
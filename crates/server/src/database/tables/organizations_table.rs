@@ -1,9 +1,11 @@
 use sqlx::{PgPool, Row};
 use shared::{
-    grid::GridCell, CreateOrganizationRequest, DiplomaticRelation, DiplomaticRelationType,
-    FullOrganizationData, MembershipStatus, OrganizationBuilding, OrganizationData,
-    OrganizationMember, OrganizationOfficer, OrganizationSummary, OrganizationType,
-    OrganizationTreasuryItem, RoleType,
+    grid::GridCell, BuildingTypeEnum, CreateOrganizationRequest, DiplomaticProposal, DiplomaticRelation,
+    DiplomaticRelationType,
+    FullOrganizationData, MembershipRequest, MembershipRequestKind, MembershipStatus,
+    OrganizationBuilding, OrganizationData, OrganizationMember, OrganizationMemberSummary,
+    OrganizationOfficer, OrganizationSummary, OrganizationType,
+    OrganizationTreasuryItem, RoleType, VassalageOffer, protocol::TreasuryItemNet,
 };
 
 /// Database handler for organizations
@@ -197,6 +199,18 @@ impl OrganizationsTable {
         Ok(officers)
     }
 
+    /// Change the role of an existing officer
+    pub async fn update_officer_role(&self, officer_id: u64, role: RoleType) -> Result<(), String> {
+        sqlx::query("UPDATE organizations.officers SET role_type_id = $1 WHERE id = $2")
+            .bind(role.to_id())
+            .bind(officer_id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to update officer role: {}", e))?;
+
+        Ok(())
+    }
+
     /// Remove an officer
     pub async fn remove_officer(&self, officer_id: u64) -> Result<(), String> {
         sqlx::query("DELETE FROM organizations.officers WHERE id = $1")
@@ -302,6 +316,227 @@ impl OrganizationsTable {
         Ok(())
     }
 
+    /// Player lords who are active members, the leader first, with their officer role
+    pub async fn load_member_summaries(
+        &self,
+        organization_id: u64,
+    ) -> Result<Vec<OrganizationMemberSummary>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT m.unit_id, u.first_name || ' ' || u.last_name AS name,
+                   (o.leader_unit_id = m.unit_id) AS is_leader,
+                   officer.role_type_id,
+                   EXTRACT(EPOCH FROM m.joined_at)::BIGINT AS joined_at
+            FROM organizations.members m
+            JOIN organizations.organizations o ON o.id = m.organization_id
+            JOIN units.units u ON u.id = m.unit_id
+            LEFT JOIN LATERAL (
+                SELECT role_type_id FROM organizations.officers
+                WHERE organization_id = m.organization_id AND unit_id = m.unit_id
+                ORDER BY role_type_id ASC
+                LIMIT 1
+            ) officer ON TRUE
+            WHERE m.organization_id = $1
+              AND m.membership_status = 'active'
+              AND u.is_lord = true
+            ORDER BY is_leader DESC, m.joined_at ASC
+            "#,
+        )
+        .bind(organization_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load member summaries: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| OrganizationMemberSummary {
+                unit_id: row.get::<i64, _>("unit_id") as u64,
+                name: row.get("name"),
+                is_leader: row.get::<Option<bool>, _>("is_leader").unwrap_or(false),
+                role: row
+                    .get::<Option<i16>, _>("role_type_id")
+                    .map(RoleType::from_id),
+                joined_at: row.get::<Option<i64>, _>("joined_at").unwrap_or(0),
+            })
+            .collect())
+    }
+
+    /// Add the unit as an active member and drop its other pending invitations
+    /// and applications
+    pub async fn join_organization(
+        &self,
+        organization_id: u64,
+        unit_id: u64,
+        invited_by: Option<u64>,
+    ) -> Result<(), String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start joining organization: {}", e))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO organizations.members
+            (organization_id, unit_id, invited_by_unit_id, membership_status)
+            VALUES ($1, $2, $3, 'active')
+            ON CONFLICT (organization_id, unit_id)
+            DO UPDATE SET membership_status = 'active', joined_at = NOW()
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(unit_id as i64)
+        .bind(invited_by.map(|id| id as i64))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to add member: {}", e))?;
+
+        sqlx::query("DELETE FROM organizations.membership_requests WHERE unit_id = $1")
+            .bind(unit_id as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to clear membership requests: {}", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit joining organization: {}", e))
+    }
+
+    /// Remove a member and the officer roles it held. Returns false if it was not a member.
+    pub async fn leave_organization(&self, organization_id: u64, unit_id: u64) -> Result<bool, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start leaving organization: {}", e))?;
+
+        sqlx::query("DELETE FROM organizations.officers WHERE organization_id = $1 AND unit_id = $2")
+            .bind(organization_id as i64)
+            .bind(unit_id as i64)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to remove officer roles: {}", e))?;
+
+        let result =
+            sqlx::query("DELETE FROM organizations.members WHERE organization_id = $1 AND unit_id = $2")
+                .bind(organization_id as i64)
+                .bind(unit_id as i64)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| format!("Failed to remove member: {}", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit leaving organization: {}", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Give a member its (single) officer role, replacing the previous one
+    pub async fn appoint_officer(
+        &self,
+        organization_id: u64,
+        unit_id: u64,
+        role: RoleType,
+        appointed_by: u64,
+    ) -> Result<(), String> {
+        self.dismiss_officer(organization_id, unit_id).await?;
+        self.add_officer(organization_id, unit_id, role, Some(appointed_by))
+            .await
+            .map(|_| ())
+    }
+
+    /// Remove every officer role held by a unit. Returns false if it held none.
+    pub async fn dismiss_officer(&self, organization_id: u64, unit_id: u64) -> Result<bool, String> {
+        let result =
+            sqlx::query("DELETE FROM organizations.officers WHERE organization_id = $1 AND unit_id = $2")
+                .bind(organization_id as i64)
+                .bind(unit_id as i64)
+                .execute(&self.pool)
+                .await
+                .map_err(|e| format!("Failed to dismiss officer: {}", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ========================================================================
+    // MEMBERSHIP REQUESTS
+    // ========================================================================
+
+    /// Record an invitation or an application (a new one for the same pair replaces the old one)
+    pub async fn create_membership_request(
+        &self,
+        organization_id: u64,
+        unit_id: u64,
+        kind: MembershipRequestKind,
+        invited_by: Option<u64>,
+    ) -> Result<u64, String> {
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO organizations.membership_requests
+            (organization_id, unit_id, kind, invited_by_unit_id)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (organization_id, unit_id)
+            DO UPDATE SET kind = EXCLUDED.kind,
+                          invited_by_unit_id = EXCLUDED.invited_by_unit_id,
+                          created_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(unit_id as i64)
+        .bind(kind.to_string())
+        .bind(invited_by.map(|id| id as i64))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to create membership request: {}", e))?;
+
+        Ok(id as u64)
+    }
+
+    /// Pending requests addressed to or made by an organization, and those of a lord
+    pub async fn load_membership_requests(
+        &self,
+        organization_id: Option<u64>,
+        unit_id: u64,
+    ) -> Result<Vec<MembershipRequest>, String> {
+        let rows = sqlx::query(&format!(
+            "{} WHERE mr.organization_id = $1 OR mr.unit_id = $2 ORDER BY mr.created_at",
+            MEMBERSHIP_REQUEST_SELECT
+        ))
+        .bind(organization_id.map(|id| id as i64))
+        .bind(unit_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load membership requests: {}", e))?;
+
+        Ok(rows.iter().map(membership_request_from_row).collect())
+    }
+
+    pub async fn load_membership_request(
+        &self,
+        request_id: u64,
+    ) -> Result<Option<MembershipRequest>, String> {
+        let row = sqlx::query(&format!("{} WHERE mr.id = $1", MEMBERSHIP_REQUEST_SELECT))
+            .bind(request_id as i64)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to load membership request: {}", e))?;
+
+        Ok(row.as_ref().map(membership_request_from_row))
+    }
+
+    /// Delete a request once answered or withdrawn. Returns false if it was already gone.
+    pub async fn delete_membership_request(&self, request_id: u64) -> Result<bool, String> {
+        let result = sqlx::query("DELETE FROM organizations.membership_requests WHERE id = $1")
+            .bind(request_id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete membership request: {}", e))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
     // ========================================================================
     // TERRITORY
     // ========================================================================
@@ -357,6 +592,89 @@ impl OrganizationsTable {
         Ok(cells)
     }
 
+    /// Types of the finished buildings standing on the organization's territory
+    pub async fn load_territory_building_types(
+        &self,
+        organization_id: u64,
+    ) -> Result<Vec<BuildingTypeEnum>, String> {
+        let type_ids = sqlx::query_scalar::<_, i32>(
+            r#"
+            SELECT DISTINCT b.building_type_id
+            FROM buildings.buildings_base b
+            JOIN organizations.territory_cells t
+              ON t.cell_q = b.cell_q AND t.cell_r = b.cell_r
+            WHERE t.organization_id = $1 AND b.is_built
+            "#,
+        )
+        .bind(organization_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load territory buildings: {}", e))?;
+
+        Ok(type_ids
+            .into_iter()
+            .filter_map(|id| BuildingTypeEnum::from_id(id as i16))
+            .collect())
+    }
+
+    /// Claim a cell and pay for it from the treasury, in one transaction.
+    /// Returns false if the cell is already owned or the treasury can't pay.
+    pub async fn claim_territory_cell(
+        &self,
+        organization_id: u64,
+        cell: &GridCell,
+        cost_gold: i32,
+    ) -> Result<bool, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start claim: {}", e))?;
+
+        // UNIQUE (cell_q, cell_r) : une cellule n'a qu'un seul propriétaire
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO organizations.territory_cells
+            (organization_id, cell_q, cell_r)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(cell.q)
+        .bind(cell.r)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to claim territory cell: {}", e))?;
+
+        if inserted.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        let paid = sqlx::query(
+            r#"
+            UPDATE organizations.organizations
+            SET treasury_gold = treasury_gold - $2, updated_at = NOW()
+            WHERE id = $1 AND treasury_gold >= $2
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(cost_gold)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to pay territory cell: {}", e))?;
+
+        if paid.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit claim: {}", e))?;
+
+        Ok(true)
+    }
+
     /// Remove a territory cell
     pub async fn remove_territory_cell(
         &self,
@@ -478,6 +796,38 @@ impl OrganizationsTable {
         Ok(())
     }
 
+    /// Raise a settlement to its next tier, paying the charter from the treasury.
+    /// Returns false if the type or the gold changed since the requirements were checked.
+    pub async fn upgrade_settlement(
+        &self,
+        organization_id: u64,
+        from: OrganizationType,
+        to: OrganizationType,
+        name: &str,
+        cost_gold: i32,
+    ) -> Result<bool, String> {
+        let result = sqlx::query(
+            r#"
+            UPDATE organizations.organizations
+            SET organization_type_id = $3,
+                name = $4,
+                treasury_gold = treasury_gold - $5,
+                updated_at = NOW()
+            WHERE id = $1 AND organization_type_id = $2 AND treasury_gold >= $5
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(from.to_id())
+        .bind(to.to_id())
+        .bind(name)
+        .bind(cost_gold)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to upgrade settlement: {}", e))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
     /// Add item to treasury
     pub async fn add_treasury_item(
         &self,
@@ -537,6 +887,264 @@ impl OrganizationsTable {
         Ok(items)
     }
 
+    /// Contenu du trésor regroupé par item_id, avec la qualité moyenne
+    pub async fn load_treasury_stock(
+        &self,
+        organization_id: u64,
+    ) -> Result<Vec<TreasuryItemNet>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT ii.item_id, COUNT(*)::INT AS quantity, AVG(ii.quality)::float8 AS quality
+            FROM organizations.treasury_items ti
+            JOIN resources.item_instances ii ON ii.id = ti.item_instance_id
+            WHERE ti.organization_id = $1
+            GROUP BY ii.item_id
+            ORDER BY ii.item_id
+            "#,
+        )
+        .bind(organization_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load treasury stock: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| TreasuryItemNet {
+                item_id: row.get("item_id"),
+                quantity: row.get("quantity"),
+                quality: row.try_get::<f64, _>("quality").unwrap_or(1.0) as f32,
+            })
+            .collect())
+    }
+
+    /// Sort N instances d'un item_id du trésor (les plus anciennes d'abord)
+    /// Les instances ne sont pas détruites : renvoie (instance_id, qualité).
+    /// Tout ou rien : les lignes verrouillées par un autre retrait sont sautées,
+    /// et s'il en manque, rien n'est sorti.
+    pub async fn take_treasury_items(
+        &self,
+        organization_id: u64,
+        item_id: i32,
+        quantity: i32,
+    ) -> Result<Vec<(u64, f32)>, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let rows = sqlx::query(
+            r#"
+            WITH taken AS (
+                DELETE FROM organizations.treasury_items
+                WHERE id IN (
+                    SELECT ti.id
+                    FROM organizations.treasury_items ti
+                    JOIN resources.item_instances ii ON ii.id = ti.item_instance_id
+                    WHERE ti.organization_id = $1 AND ii.item_id = $2
+                    ORDER BY ti.stored_at ASC
+                    LIMIT $3
+                    FOR UPDATE OF ti SKIP LOCKED
+                )
+                RETURNING item_instance_id
+            )
+            SELECT taken.item_instance_id, ii.quality::float8 AS quality
+            FROM taken
+            JOIN resources.item_instances ii ON ii.id = taken.item_instance_id
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(item_id)
+        .bind(quantity as i64)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to remove treasury items: {}", e))?;
+
+        if rows.len() < quantity as usize {
+            // La transaction est annulée en sortant
+            return Err(format!(
+                "Not enough items in treasury: need {} of item_id {}, have {}",
+                quantity,
+                item_id,
+                rows.len()
+            ));
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit treasury withdrawal: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| {
+                (
+                    r.get::<i64, _>("item_instance_id") as u64,
+                    r.try_get::<f64, _>("quality").unwrap_or(1.0) as f32,
+                )
+            })
+            .collect())
+    }
+
+    /// Dépose au trésor N instances d'un item portées par une unité (les plus anciennes
+    /// d'abord) : elles quittent l'unité et entrent au trésor dans la même requête.
+    /// Tout ou rien : les instances verrouillées par un autre échange sont sautées, et
+    /// s'il en manque, rien n'est déposé. Retourne le nombre déposé (`quantity`, ou 0).
+    pub async fn deposit_unit_items(
+        &self,
+        organization_id: u64,
+        unit_id: u64,
+        item_id: i32,
+        quantity: i32,
+    ) -> Result<u64, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let deposited = sqlx::query(
+            r#"
+            WITH picked AS (
+                SELECT id FROM resources.item_instances
+                WHERE owner_unit_id = $2 AND item_id = $3
+                ORDER BY created_at ASC
+                LIMIT $4
+                FOR UPDATE SKIP LOCKED
+            ),
+            moved AS (
+                UPDATE resources.item_instances ii
+                SET owner_unit_id = NULL,
+                    world_cell_q = NULL,
+                    world_cell_r = NULL,
+                    world_chunk_x = NULL,
+                    world_chunk_y = NULL
+                FROM picked
+                WHERE ii.id = picked.id
+                RETURNING ii.id
+            )
+            INSERT INTO organizations.treasury_items (organization_id, item_instance_id, quantity)
+            SELECT $1, moved.id, 1 FROM moved
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(unit_id as i64)
+        .bind(item_id)
+        .bind(quantity as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to deposit treasury items: {}", e))?
+        .rows_affected();
+
+        if deposited < quantity as u64 {
+            // La transaction est annulée en sortant
+            return Ok(0);
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit treasury deposit: {}", e))?;
+        Ok(deposited)
+    }
+
+    /// Remet à une unité N instances d'un item du trésor (les plus anciennes d'abord) :
+    /// les lignes du trésor sont supprimées et les instances réattribuées ensemble.
+    /// Tout ou rien, comme `deposit_unit_items`. Retourne le nombre retiré (`quantity`, ou 0).
+    pub async fn withdraw_treasury_items(
+        &self,
+        organization_id: u64,
+        unit_id: u64,
+        item_id: i32,
+        quantity: i32,
+    ) -> Result<u64, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let withdrawn = sqlx::query(
+            r#"
+            WITH taken AS (
+                DELETE FROM organizations.treasury_items
+                WHERE id IN (
+                    SELECT ti.id
+                    FROM organizations.treasury_items ti
+                    JOIN resources.item_instances ii ON ii.id = ti.item_instance_id
+                    WHERE ti.organization_id = $1 AND ii.item_id = $3
+                    ORDER BY ti.stored_at ASC
+                    LIMIT $4
+                    FOR UPDATE SKIP LOCKED
+                )
+                RETURNING item_instance_id
+            )
+            UPDATE resources.item_instances ii
+            SET owner_unit_id = $2,
+                world_cell_q = NULL,
+                world_cell_r = NULL,
+                world_chunk_x = NULL,
+                world_chunk_y = NULL
+            FROM taken
+            WHERE ii.id = taken.item_instance_id
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(unit_id as i64)
+        .bind(item_id)
+        .bind(quantity as i64)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to withdraw treasury items: {}", e))?
+        .rows_affected();
+
+        if withdrawn < quantity as u64 {
+            return Ok(0);
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit treasury withdrawal: {}", e))?;
+        Ok(withdrawn)
+    }
+
+    /// Organisation d'une unité : celle qu'elle dirige, sinon celle dont elle est membre actif
+    pub async fn find_unit_organization(&self, unit_id: u64) -> Result<Option<u64>, String> {
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT id FROM (
+                SELECT id, 0 AS rank FROM organizations.organizations
+                WHERE leader_unit_id = $1
+                UNION ALL
+                SELECT organization_id AS id, 1 AS rank FROM organizations.members
+                WHERE unit_id = $1 AND membership_status = 'active'
+            ) candidates
+            ORDER BY rank ASC, id ASC
+            LIMIT 1
+            "#,
+        )
+        .bind(unit_id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to find unit organization: {}", e))?;
+
+        Ok(id.map(|id| id as u64))
+    }
+
+    /// Organisation qui contrôle une cellule, s'il y en a une
+    pub async fn find_cell_organization(&self, cell: &GridCell) -> Result<Option<u64>, String> {
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT organization_id FROM organizations.territory_cells
+            WHERE cell_q = $1 AND cell_r = $2
+            LIMIT 1
+            "#,
+        )
+        .bind(cell.q)
+        .bind(cell.r)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to find cell organization: {}", e))?;
+
+        Ok(id.map(|id| id as u64))
+    }
+
     // ========================================================================
     // DIPLOMATIC RELATIONS
     // ========================================================================
@@ -609,6 +1217,514 @@ impl OrganizationsTable {
         Ok(relations)
     }
 
+    /// Set the relation between two organizations, in both directions
+    /// (replaces whatever relation they had)
+    pub async fn establish_diplomatic_relation(
+        &self,
+        organization_id: u64,
+        target_organization_id: u64,
+        relation_type: DiplomaticRelationType,
+        established_by: Option<u64>,
+        expires_at: Option<i64>,
+    ) -> Result<(), String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start diplomatic relation: {}", e))?;
+
+        for (from, to) in [
+            (organization_id, target_organization_id),
+            (target_organization_id, organization_id),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO organizations.diplomatic_relations
+                (organization_id, target_organization_id, relation_type, established_by_unit_id, expires_at)
+                VALUES ($1, $2, $3, $4, to_timestamp($5))
+                ON CONFLICT (organization_id, target_organization_id)
+                DO UPDATE SET relation_type = EXCLUDED.relation_type,
+                              established_by_unit_id = EXCLUDED.established_by_unit_id,
+                              established_at = NOW(),
+                              expires_at = EXCLUDED.expires_at
+                "#,
+            )
+            .bind(from as i64)
+            .bind(to as i64)
+            .bind(relation_type.to_string())
+            .bind(established_by.map(|id| id as i64))
+            .bind(expires_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| format!("Failed to establish diplomatic relation: {}", e))?;
+        }
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit diplomatic relation: {}", e))
+    }
+
+    /// Relations that carry an expiry date, checked by the expiry tick
+    pub async fn load_expiring_diplomatic_relations(&self) -> Result<Vec<DiplomaticRelation>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, organization_id, target_organization_id, relation_type,
+                   EXTRACT(EPOCH FROM established_at)::BIGINT as established_at,
+                   established_by_unit_id,
+                   EXTRACT(EPOCH FROM expires_at)::BIGINT as expires_at
+            FROM organizations.diplomatic_relations
+            WHERE expires_at IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load expiring diplomatic relations: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DiplomaticRelation {
+                id: row.get::<i64, _>("id") as u64,
+                organization_id: row.get::<i64, _>("organization_id") as u64,
+                target_organization_id: row.get::<i64, _>("target_organization_id") as u64,
+                relation_type: DiplomaticRelationType::from_string(&row.get::<String, _>("relation_type")),
+                established_at: row.get("established_at"),
+                established_by_unit_id: row
+                    .try_get::<i64, _>("established_by_unit_id")
+                    .ok()
+                    .map(|id| id as u64),
+                expires_at: row.try_get("expires_at").ok(),
+            })
+            .collect())
+    }
+
+    /// Delete diplomatic relations by id
+    pub async fn delete_diplomatic_relations(&self, relation_ids: &[u64]) -> Result<(), String> {
+        let ids: Vec<i64> = relation_ids.iter().map(|id| *id as i64).collect();
+        sqlx::query("DELETE FROM organizations.diplomatic_relations WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete diplomatic relations: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Record a proposal delivered by an envoy (a new proposal between the same
+    /// pair replaces the old one). Returns its id.
+    pub async fn create_diplomatic_proposal(
+        &self,
+        organization_id: u64,
+        target_organization_id: u64,
+        relation_type: DiplomaticRelationType,
+        duration_days: Option<u32>,
+        proposed_by: Option<u64>,
+    ) -> Result<u64, String> {
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO organizations.diplomatic_proposals
+            (organization_id, target_organization_id, relation_type, duration_days, proposed_by_unit_id)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (organization_id, target_organization_id)
+            DO UPDATE SET relation_type = EXCLUDED.relation_type,
+                          duration_days = EXCLUDED.duration_days,
+                          proposed_by_unit_id = EXCLUDED.proposed_by_unit_id,
+                          created_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(target_organization_id as i64)
+        .bind(relation_type.to_string())
+        .bind(duration_days.map(|days| days as i32))
+        .bind(proposed_by.map(|id| id as i64))
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to create diplomatic proposal: {}", e))?;
+
+        Ok(id as u64)
+    }
+
+    /// Pending proposals made by or to the organization
+    pub async fn load_diplomatic_proposals(
+        &self,
+        organization_id: u64,
+    ) -> Result<Vec<DiplomaticProposal>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT dp.id, dp.organization_id, o.name AS organization_name,
+                   dp.target_organization_id, t.name AS target_name,
+                   dp.relation_type, dp.duration_days,
+                   EXTRACT(EPOCH FROM dp.created_at)::BIGINT AS created_at
+            FROM organizations.diplomatic_proposals dp
+            JOIN organizations.organizations o ON o.id = dp.organization_id
+            JOIN organizations.organizations t ON t.id = dp.target_organization_id
+            WHERE dp.organization_id = $1 OR dp.target_organization_id = $1
+            ORDER BY dp.created_at
+            "#,
+        )
+        .bind(organization_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load diplomatic proposals: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| DiplomaticProposal {
+                id: row.get::<i64, _>("id") as u64,
+                organization_id: row.get::<i64, _>("organization_id") as u64,
+                organization_name: row.get("organization_name"),
+                target_organization_id: row.get::<i64, _>("target_organization_id") as u64,
+                target_name: row.get("target_name"),
+                relation_type: DiplomaticRelationType::from_string(&row.get::<String, _>("relation_type")),
+                duration_days: row
+                    .get::<Option<i32>, _>("duration_days")
+                    .map(|days| days as u32),
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
+    /// Delete a proposal once answered. Returns false if it was already gone.
+    pub async fn delete_diplomatic_proposal(&self, proposal_id: u64) -> Result<bool, String> {
+        let result = sqlx::query("DELETE FROM organizations.diplomatic_proposals WHERE id = $1")
+            .bind(proposal_id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete diplomatic proposal: {}", e))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Players with a unit in the organization (active members and the leader)
+    pub async fn load_member_players(&self, organization_id: u64) -> Result<Vec<u64>, String> {
+        let ids = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT DISTINCT u.player_id
+            FROM units.units u
+            WHERE u.player_id IS NOT NULL
+              AND (u.id IN (
+                       SELECT unit_id FROM organizations.members
+                       WHERE organization_id = $1 AND membership_status = 'active'
+                   )
+                   OR u.id = (SELECT leader_unit_id FROM organizations.organizations WHERE id = $1))
+            "#,
+        )
+        .bind(organization_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load member players: {}", e))?;
+
+        Ok(ids.into_iter().map(|id| id as u64).collect())
+    }
+
+    // ========================================================================
+    // VASSALAGE
+    // ========================================================================
+
+    /// Record a vassalage offer (a repeated offer between the same pair replaces the old one)
+    pub async fn create_vassalage_offer(
+        &self,
+        liege_organization_id: u64,
+        vassal_organization_id: u64,
+        offered_by_organization_id: u64,
+    ) -> Result<u64, String> {
+        let id = sqlx::query_scalar::<_, i64>(
+            r#"
+            INSERT INTO organizations.vassalage_offers
+            (liege_organization_id, vassal_organization_id, offered_by_organization_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (liege_organization_id, vassal_organization_id)
+            DO UPDATE SET offered_by_organization_id = EXCLUDED.offered_by_organization_id,
+                          created_at = NOW()
+            RETURNING id
+            "#,
+        )
+        .bind(liege_organization_id as i64)
+        .bind(vassal_organization_id as i64)
+        .bind(offered_by_organization_id as i64)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to create vassalage offer: {}", e))?;
+
+        Ok(id as u64)
+    }
+
+    /// Pending offers where the organization is the liege or the vassal
+    pub async fn load_vassalage_offers(
+        &self,
+        organization_id: u64,
+    ) -> Result<Vec<VassalageOffer>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT vo.id, vo.liege_organization_id, l.name AS liege_name,
+                   vo.vassal_organization_id, v.name AS vassal_name,
+                   vo.offered_by_organization_id,
+                   EXTRACT(EPOCH FROM vo.created_at)::BIGINT AS created_at
+            FROM organizations.vassalage_offers vo
+            JOIN organizations.organizations l ON l.id = vo.liege_organization_id
+            JOIN organizations.organizations v ON v.id = vo.vassal_organization_id
+            WHERE vo.liege_organization_id = $1 OR vo.vassal_organization_id = $1
+            ORDER BY vo.created_at
+            "#,
+        )
+        .bind(organization_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load vassalage offers: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| VassalageOffer {
+                id: row.get::<i64, _>("id") as u64,
+                liege_organization_id: row.get::<i64, _>("liege_organization_id") as u64,
+                liege_name: row.get("liege_name"),
+                vassal_organization_id: row.get::<i64, _>("vassal_organization_id") as u64,
+                vassal_name: row.get("vassal_name"),
+                offered_by_organization_id: row.get::<i64, _>("offered_by_organization_id")
+                    as u64,
+                created_at: row.get("created_at"),
+            })
+            .collect())
+    }
+
+    /// Delete the pending offers between two organizations, whichever way round.
+    /// Returns the ids of the deleted offers.
+    pub async fn delete_vassalage_offers_between(
+        &self,
+        organization_id: u64,
+        other_organization_id: u64,
+    ) -> Result<Vec<u64>, String> {
+        let ids = sqlx::query_scalar::<_, i64>(
+            r#"
+            DELETE FROM organizations.vassalage_offers
+            WHERE (liege_organization_id = $1 AND vassal_organization_id = $2)
+               OR (liege_organization_id = $2 AND vassal_organization_id = $1)
+            RETURNING id
+            "#,
+        )
+        .bind(organization_id as i64)
+        .bind(other_organization_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to delete vassalage offers: {}", e))?;
+
+        Ok(ids.into_iter().map(|id| id as u64).collect())
+    }
+
+    /// Bind a vassal to its liege. Returns false if the vassal already has one.
+    pub async fn swear_fealty(
+        &self,
+        vassal_organization_id: u64,
+        liege_organization_id: u64,
+    ) -> Result<bool, String> {
+        let result = sqlx::query(
+            r#"
+            UPDATE organizations.organizations
+            SET parent_organization_id = $2
+            WHERE id = $1 AND parent_organization_id IS NULL
+            "#,
+        )
+        .bind(vassal_organization_id as i64)
+        .bind(liege_organization_id as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to swear fealty: {}", e))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Free a vassal from its liege. Returns false if they were not bound.
+    pub async fn release_vassal(
+        &self,
+        vassal_organization_id: u64,
+        liege_organization_id: u64,
+    ) -> Result<bool, String> {
+        let result = sqlx::query(
+            r#"
+            UPDATE organizations.organizations
+            SET parent_organization_id = NULL
+            WHERE id = $1 AND parent_organization_id = $2
+            "#,
+        )
+        .bind(vassal_organization_id as i64)
+        .bind(liege_organization_id as i64)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to release vassal: {}", e))?;
+
+        Ok(result.rows_affected() == 1)
+    }
+
+    /// Lieges of an organization, from its direct liege up to the top of the tree
+    pub async fn load_liege_chain(&self, organization_id: u64) -> Result<Vec<u64>, String> {
+        let ids = sqlx::query_scalar::<_, i64>(
+            r#"
+            WITH RECURSIVE chain AS (
+                SELECT parent_organization_id AS id, 1 AS depth
+                FROM organizations.organizations
+                WHERE id = $1 AND parent_organization_id IS NOT NULL
+                UNION ALL
+                SELECT o.parent_organization_id, c.depth + 1
+                FROM organizations.organizations o
+                JOIN chain c ON o.id = c.id
+                WHERE o.parent_organization_id IS NOT NULL AND c.depth < 32
+            )
+            SELECT id FROM chain ORDER BY depth
+            "#,
+        )
+        .bind(organization_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load liege chain: {}", e))?;
+
+        Ok(ids.into_iter().map(|id| id as u64).collect())
+    }
+
+    /// Every organization under `root_organization_id` (included), with its liege
+    pub async fn load_vassal_tree(
+        &self,
+        root_organization_id: u64,
+    ) -> Result<Vec<(u64, Option<u64>)>, String> {
+        let rows = sqlx::query(
+            r#"
+            WITH RECURSIVE tree AS (
+                SELECT id, parent_organization_id, 0 AS depth
+                FROM organizations.organizations
+                WHERE id = $1
+                UNION ALL
+                SELECT o.id, o.parent_organization_id, t.depth + 1
+                FROM organizations.organizations o
+                JOIN tree t ON o.parent_organization_id = t.id
+                WHERE t.depth < 32
+            )
+            SELECT id, parent_organization_id FROM tree ORDER BY depth, id
+            "#,
+        )
+        .bind(root_organization_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load vassal tree: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get::<i64, _>("id") as u64,
+                    row.try_get::<i64, _>("parent_organization_id")
+                        .ok()
+                        .map(|id| id as u64),
+                )
+            })
+            .collect())
+    }
+
+    /// Summaries of the given organizations
+    pub async fn load_organization_summaries(
+        &self,
+        organization_ids: &[u64],
+    ) -> Result<Vec<OrganizationSummary>, String> {
+        let ids: Vec<i64> = organization_ids.iter().map(|id| *id as i64).collect();
+        let rows = sqlx::query(
+            r#"
+            SELECT id, name, organization_type_id, leader_unit_id, population, emblem_url
+            FROM organizations.organizations
+            WHERE id = ANY($1)
+            "#,
+        )
+        .bind(&ids)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load organization summaries: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| OrganizationSummary {
+                id: row.get::<i64, _>("id") as u64,
+                name: row.get("name"),
+                organization_type: OrganizationType::from_id(row.get("organization_type_id")),
+                leader_unit_id: row
+                    .try_get::<i64, _>("leader_unit_id")
+                    .ok()
+                    .map(|id| id as u64),
+                population: row.get("population"),
+                emblem_url: row.try_get("emblem_url").ok(),
+            })
+            .collect())
+    }
+
+    /// Every vassal with its liege and its treasury gold, for the daily tribute
+    pub async fn load_vassal_treasuries(&self) -> Result<Vec<(u64, u64, i32)>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, parent_organization_id, treasury_gold
+            FROM organizations.organizations
+            WHERE parent_organization_id IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load vassal treasuries: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| {
+                (
+                    row.get::<i64, _>("id") as u64,
+                    row.get::<i64, _>("parent_organization_id") as u64,
+                    row.get::<Option<i32>, _>("treasury_gold").unwrap_or(0),
+                )
+            })
+            .collect())
+    }
+
+    /// Move tribute gold from a vassal's treasury to its liege's, in one transaction.
+    /// Returns false if the bond or the gold changed in the meantime.
+    pub async fn pay_tribute(
+        &self,
+        vassal_organization_id: u64,
+        liege_organization_id: u64,
+        gold: i32,
+    ) -> Result<bool, String> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to start tribute: {}", e))?;
+
+        let paid = sqlx::query(
+            r#"
+            UPDATE organizations.organizations
+            SET treasury_gold = treasury_gold - $3
+            WHERE id = $1 AND parent_organization_id = $2 AND treasury_gold >= $3
+            "#,
+        )
+        .bind(vassal_organization_id as i64)
+        .bind(liege_organization_id as i64)
+        .bind(gold)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to take tribute: {}", e))?;
+
+        if paid.rows_affected() == 0 {
+            return Ok(false);
+        }
+
+        sqlx::query(
+            "UPDATE organizations.organizations SET treasury_gold = treasury_gold + $2 WHERE id = $1",
+        )
+        .bind(liege_organization_id as i64)
+        .bind(gold)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to give tribute: {}", e))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit tribute: {}", e))?;
+
+        Ok(true)
+    }
+
     // ========================================================================
     // QUERIES
     // ========================================================================
@@ -665,3 +1781,24 @@ impl OrganizationsTable {
         Ok(rows.iter().map(|row| row.get::<i64, _>("id") as u64).collect())
     }
 }
+
+const MEMBERSHIP_REQUEST_SELECT: &str = r#"
+    SELECT mr.id, mr.organization_id, o.name AS organization_name,
+           mr.unit_id, u.first_name || ' ' || u.last_name AS unit_name,
+           mr.kind, EXTRACT(EPOCH FROM mr.created_at)::BIGINT AS created_at
+    FROM organizations.membership_requests mr
+    JOIN organizations.organizations o ON o.id = mr.organization_id
+    JOIN units.units u ON u.id = mr.unit_id
+"#;
+
+fn membership_request_from_row(row: &sqlx::postgres::PgRow) -> MembershipRequest {
+    MembershipRequest {
+        id: row.get::<i64, _>("id") as u64,
+        organization_id: row.get::<i64, _>("organization_id") as u64,
+        organization_name: row.get("organization_name"),
+        unit_id: row.get::<i64, _>("unit_id") as u64,
+        unit_name: row.get("unit_name"),
+        kind: MembershipRequestKind::from_string(&row.get::<String, _>("kind")),
+        created_at: row.get("created_at"),
+    }
+}
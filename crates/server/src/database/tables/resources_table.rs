@@ -1,11 +1,41 @@
 use bevy::prelude::*;
 use shared::{
-    EquipmentSlotEnum, FullItemData, ItemDefinition, ItemInstance, ItemTypeEnum, Recipe,
-    RecipeIngredient, ResourceCategoryEnum, SkillEnum, WorldPosition,
+    BiomeTypeEnum, BuildingTypeEnum, CellResourceStock, EquipmentSlotEnum, FullItemData, GroundItem, ItemDefinition, ItemInstance, ItemTypeEnum,
+    Recipe, RecipeIngredient, ResourceCategoryEnum, ResourceSpecificTypeEnum, SkillEnum, TerrainChunkId, WorldPosition, grid::GridCell,
 };
 use sqlx::{PgPool, Row, types::chrono};
 use std::collections::HashMap;
 
+/// Porteur d'objets : une unité, ou le sol d'une cellule
+#[derive(Debug, Clone, Copy)]
+pub enum ItemHolder {
+    Unit(u64),
+    Ground(WorldPosition),
+}
+
+/// Instance périssable à faire vieillir (voir `decay::DecaySystem`)
+#[derive(Debug, Clone)]
+pub struct PerishableInstance {
+    pub instance_id: u64,
+    pub item_id: i32,
+    pub owner_unit_id: Option<u64>,
+    pub owner_player_id: Option<u64>,
+    pub current_decay: f32,
+    pub decay_rate_per_day: f32,
+    /// Secondes écoulées depuis la dernière mise à jour du decay
+    pub elapsed_secs: f64,
+    /// Une glacière construite se trouve sur la cellule de l'item (ou de son porteur)
+    pub in_ice_house: bool,
+}
+
+/// Réserve entamée qui repousse (voir `resource_stocks::RegrowthSystem`)
+#[derive(Debug, Clone)]
+pub struct RegrowingStock {
+    pub chunk_id: TerrainChunkId,
+    pub biome: BiomeTypeEnum,
+    pub stock: CellResourceStock,
+}
+
 #[derive(Resource, Clone)]
 pub struct ResourcesTable {
     pool: PgPool,
@@ -225,6 +255,85 @@ impl ResourcesTable {
         Ok(())
     }
 
+    /// Enregistre le decay de plusieurs instances en une seule requête
+    pub async fn update_item_instances_decay(&self, decays: &[(u64, f32)]) -> Result<u64, String> {
+        let ids: Vec<i64> = decays.iter().map(|(id, _)| *id as i64).collect();
+        let values: Vec<f64> = decays.iter().map(|(_, decay)| *decay as f64).collect();
+        let result = sqlx::query(
+            r#"
+            UPDATE resources.item_instances ii
+            SET current_decay = d.current_decay, last_decay_update = NOW()
+            FROM unnest($1::bigint[], $2::float8[]) AS d(id, current_decay)
+            WHERE ii.id = d.id
+            "#,
+        )
+        .bind(&ids)
+        .bind(&values)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to update item decay: {}", e))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Charge toutes les instances d'items périssables avec leurs conditions de stockage
+    pub async fn load_perishable_instances(&self) -> Result<Vec<PerishableInstance>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT ii.id, ii.item_id, ii.owner_unit_id, u.player_id,
+                   ii.current_decay::float8 as current_decay,
+                   i.base_decay_rate_per_day::float8 as decay_rate,
+                   EXTRACT(EPOCH FROM (NOW() - COALESCE(ii.last_decay_update, ii.created_at)))::float8
+                       as elapsed_secs,
+                   EXISTS (
+                       SELECT 1 FROM buildings.buildings_base b
+                       WHERE b.building_type_id = $1
+                         AND b.is_built = true
+                         AND b.chunk_x = COALESCE(u.current_chunk_x, ii.world_chunk_x)
+                         AND b.chunk_y = COALESCE(u.current_chunk_y, ii.world_chunk_y)
+                         AND b.cell_q = COALESCE(u.current_cell_q, ii.world_cell_q)
+                         AND b.cell_r = COALESCE(u.current_cell_r, ii.world_cell_r)
+                   ) as in_ice_house
+            FROM resources.item_instances ii
+            JOIN resources.items i ON i.id = ii.item_id
+            LEFT JOIN units.units u ON u.id = ii.owner_unit_id
+            WHERE i.is_perishable = true
+            "#,
+        )
+        .bind(BuildingTypeEnum::IceHouse as i32)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load perishable items: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| PerishableInstance {
+                instance_id: row.get::<i64, _>("id") as u64,
+                item_id: row.get("item_id"),
+                owner_unit_id: row
+                    .get::<Option<i64>, _>("owner_unit_id")
+                    .map(|id| id as u64),
+                owner_player_id: row.get::<Option<i64>, _>("player_id").map(|id| id as u64),
+                current_decay: row.try_get::<f64, _>("current_decay").unwrap_or(0.0) as f32,
+                decay_rate_per_day: row.try_get::<f64, _>("decay_rate").unwrap_or(0.0) as f32,
+                elapsed_secs: row.try_get::<f64, _>("elapsed_secs").unwrap_or(0.0),
+                in_ice_house: row.get("in_ice_house"),
+            })
+            .collect())
+    }
+
+    /// Supprime des instances d'items (items pourris, ...)
+    pub async fn delete_item_instances(&self, instance_ids: &[u64]) -> Result<u64, String> {
+        let ids: Vec<i64> = instance_ids.iter().map(|id| *id as i64).collect();
+        let result = sqlx::query("DELETE FROM resources.item_instances WHERE id = ANY($1)")
+            .bind(&ids)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete item instances: {}", e))?;
+
+        Ok(result.rows_affected())
+    }
+
     /// Transfert une instance d'item à un propriétaire
     pub async fn transfer_item_instance(
         &self,
@@ -280,6 +389,437 @@ impl ResourcesTable {
         Ok(())
     }
 
+    /// Pose au sol tout ce que porte une unité (à sa mort par exemple)
+    pub async fn drop_all_unit_items(
+        &self,
+        unit_id: u64,
+        position: WorldPosition,
+    ) -> Result<u64, String> {
+        let result = sqlx::query(
+            r#"
+            UPDATE resources.item_instances
+            SET owner_unit_id = NULL,
+                world_cell_q = $2,
+                world_cell_r = $3,
+                world_chunk_x = $4,
+                world_chunk_y = $5
+            WHERE owner_unit_id = $1
+            "#,
+        )
+        .bind(unit_id as i64)
+        .bind(position.cell_q)
+        .bind(position.cell_r)
+        .bind(position.chunk_x)
+        .bind(position.chunk_y)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to drop items of unit {}: {}", unit_id, e))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Détruit tout ce que porte une unité (elle quitte le monde avec ses affaires)
+    pub async fn delete_all_unit_items(&self, unit_id: u64) -> Result<u64, String> {
+        let result = sqlx::query("DELETE FROM resources.item_instances WHERE owner_unit_id = $1")
+            .bind(unit_id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete items of unit {}: {}", unit_id, e))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Déplace `quantity` instances d'un item (les plus anciennes d'abord) d'un porteur
+    /// à un autre, en une seule requête. Les instances déjà verrouillées par un autre
+    /// échange sont ignorées ; s'il n'en reste pas assez, rien n'est déplacé.
+    /// Retourne le nombre d'instances déplacées (`quantity`, ou 0).
+    pub async fn move_item_instances(
+        &self,
+        from: ItemHolder,
+        to: ItemHolder,
+        item_id: i32,
+        quantity: i32,
+    ) -> Result<u64, String> {
+        let (from_unit, from_q, from_r) = match from {
+            ItemHolder::Unit(unit_id) => (Some(unit_id as i64), None, None),
+            ItemHolder::Ground(position) => (None, Some(position.cell_q), Some(position.cell_r)),
+        };
+        let (to_unit, to_position) = match to {
+            ItemHolder::Unit(unit_id) => (Some(unit_id as i64), None),
+            ItemHolder::Ground(position) => (None, Some(position)),
+        };
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| format!("Failed to begin transaction: {}", e))?;
+
+        let moved = sqlx::query(
+            r#"
+            WITH picked AS (
+                SELECT id FROM resources.item_instances
+                WHERE item_id = $1
+                  AND (owner_unit_id = $3
+                       OR ($3 IS NULL AND owner_unit_id IS NULL
+                           AND world_cell_q = $4 AND world_cell_r = $5))
+                ORDER BY created_at ASC
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            UPDATE resources.item_instances ii
+            SET owner_unit_id = $6,
+                world_cell_q = $7,
+                world_cell_r = $8,
+                world_chunk_x = $9,
+                world_chunk_y = $10
+            FROM picked
+            WHERE ii.id = picked.id
+            "#,
+        )
+        .bind(item_id)
+        .bind(quantity as i64)
+        .bind(from_unit)
+        .bind(from_q)
+        .bind(from_r)
+        .bind(to_unit)
+        .bind(to_position.map(|p| p.cell_q))
+        .bind(to_position.map(|p| p.cell_r))
+        .bind(to_position.map(|p| p.chunk_x))
+        .bind(to_position.map(|p| p.chunk_y))
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| format!("Failed to move item instances: {}", e))?
+        .rows_affected();
+
+        if moved < quantity as u64 {
+            // Transaction annulée au drop : tout ou rien
+            return Ok(0);
+        }
+        tx.commit()
+            .await
+            .map_err(|e| format!("Failed to commit item move: {}", e))?;
+        Ok(moved)
+    }
+
+    /// Instances d'un item posées au sol sur une cellule (les plus anciennes d'abord)
+    pub async fn find_ground_item_instances(
+        &self,
+        cell: &GridCell,
+        item_id: i32,
+        limit: i32,
+    ) -> Result<Vec<u64>, String> {
+        let ids = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT id FROM resources.item_instances
+            WHERE owner_unit_id IS NULL
+              AND world_cell_q = $1 AND world_cell_r = $2
+              AND item_id = $3
+            ORDER BY created_at ASC
+            LIMIT $4
+            "#,
+        )
+        .bind(cell.q)
+        .bind(cell.r)
+        .bind(item_id)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to find ground items: {}", e))?;
+
+        Ok(ids.into_iter().map(|id| id as u64).collect())
+    }
+
+    /// Objets au sol d'un chunk, regroupés par cellule et par item
+    pub async fn load_chunk_ground_items(
+        &self,
+        chunk_id: &TerrainChunkId,
+    ) -> Result<Vec<GroundItem>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT world_cell_q, world_cell_r, item_id, COUNT(*)::int AS qty
+            FROM resources.item_instances
+            WHERE owner_unit_id IS NULL
+              AND world_chunk_x = $1 AND world_chunk_y = $2
+              AND world_cell_q IS NOT NULL AND world_cell_r IS NOT NULL
+            GROUP BY world_cell_q, world_cell_r, item_id
+            "#,
+        )
+        .bind(chunk_id.x)
+        .bind(chunk_id.y)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load ground items: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| GroundItem {
+                cell: GridCell {
+                    q: row.get("world_cell_q"),
+                    r: row.get("world_cell_r"),
+                },
+                item_id: row.get("item_id"),
+                quantity: row.get("qty"),
+            })
+            .collect())
+    }
+
+    /// Objets au sol d'une cellule, regroupés par item
+    pub async fn load_cell_ground_items(&self, cell: &GridCell) -> Result<Vec<GroundItem>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT item_id, COUNT(*)::int AS qty
+            FROM resources.item_instances
+            WHERE owner_unit_id IS NULL
+              AND world_cell_q = $1 AND world_cell_r = $2
+            GROUP BY item_id
+            "#,
+        )
+        .bind(cell.q)
+        .bind(cell.r)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load cell ground items: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| GroundItem {
+                cell: *cell,
+                item_id: row.get("item_id"),
+                quantity: row.get("qty"),
+            })
+            .collect())
+    }
+
+    // ============ CELL RESOURCE STOCKS ============
+
+    /// Enregistre les réserves d'un chunk. Une réserve existante n'est pas touchée :
+    /// réensemencer une cellule ne rend pas ce qui a déjà été récolté.
+    pub async fn save_cell_resource_stocks(
+        &self,
+        chunk_id: &TerrainChunkId,
+        stocks: &[CellResourceStock],
+    ) -> Result<(), String> {
+        if stocks.is_empty() {
+            return Ok(());
+        }
+
+        let mut query_builder = sqlx::QueryBuilder::new(
+            "INSERT INTO resources.cell_resource_stocks (cell_q, cell_r, chunk_x, chunk_y, resource_specific_type_id, stock, max_stock)",
+        );
+        query_builder.push_values(stocks.iter(), |mut b, stock| {
+            b.push_bind(stock.cell.q)
+                .push_bind(stock.cell.r)
+                .push_bind(chunk_id.x)
+                .push_bind(chunk_id.y)
+                .push_bind(stock.resource_specific_type.to_id())
+                .push_bind(stock.stock)
+                .push_bind(stock.max_stock);
+        });
+        query_builder.push(" ON CONFLICT (cell_q, cell_r, resource_specific_type_id) DO NOTHING");
+
+        query_builder
+            .build()
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to save cell resource stocks: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Réserves naturelles des cellules d'un chunk
+    pub async fn load_chunk_resource_stocks(
+        &self,
+        chunk_id: &TerrainChunkId,
+    ) -> Result<Vec<CellResourceStock>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT cell_q, cell_r, resource_specific_type_id, stock, max_stock
+            FROM resources.cell_resource_stocks
+            WHERE chunk_x = $1 AND chunk_y = $2
+            "#,
+        )
+        .bind(chunk_id.x)
+        .bind(chunk_id.y)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load chunk resource stocks: {}", e))?;
+
+        Ok(rows.iter().filter_map(Self::row_to_cell_stock).collect())
+    }
+
+    /// Réserves naturelles d'une cellule
+    pub async fn load_cell_resource_stocks(
+        &self,
+        cell: &GridCell,
+    ) -> Result<Vec<CellResourceStock>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT cell_q, cell_r, resource_specific_type_id, stock, max_stock
+            FROM resources.cell_resource_stocks
+            WHERE cell_q = $1 AND cell_r = $2
+            "#,
+        )
+        .bind(cell.q)
+        .bind(cell.r)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load cell resource stocks: {}", e))?;
+
+        Ok(rows.iter().filter_map(Self::row_to_cell_stock).collect())
+    }
+
+    /// Puise jusqu'à `wanted` unités dans la réserve d'une cellule.
+    /// Renvoie la réserve avant et après le prélèvement (None si la cellule n'en a pas).
+    pub async fn draw_cell_resource_stock(
+        &self,
+        cell: &GridCell,
+        resource_specific_type: ResourceSpecificTypeEnum,
+        wanted: i32,
+    ) -> Result<Option<(CellResourceStock, CellResourceStock)>, String> {
+        let row = sqlx::query(
+            r#"
+            WITH previous AS (
+                SELECT cell_q, cell_r, resource_specific_type_id, stock
+                FROM resources.cell_resource_stocks
+                WHERE cell_q = $1 AND cell_r = $2 AND resource_specific_type_id = $3
+                FOR UPDATE
+            )
+            UPDATE resources.cell_resource_stocks s
+            SET stock = s.stock - LEAST(s.stock, $4)
+            FROM previous p
+            WHERE s.cell_q = p.cell_q AND s.cell_r = p.cell_r
+              AND s.resource_specific_type_id = p.resource_specific_type_id
+            RETURNING p.stock AS previous_stock, s.stock, s.max_stock
+            "#,
+        )
+        .bind(cell.q)
+        .bind(cell.r)
+        .bind(resource_specific_type.to_id())
+        .bind(wanted.max(0))
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to draw from cell resource stock: {}", e))?;
+
+        Ok(row.map(|row| {
+            let after = CellResourceStock {
+                cell: *cell,
+                resource_specific_type,
+                stock: row.get("stock"),
+                max_stock: row.get("max_stock"),
+            };
+            let before = CellResourceStock {
+                stock: row.get("previous_stock"),
+                ..after
+            };
+            (before, after)
+        }))
+    }
+
+    /// Supprime la réserve d'une cellule (arbre abattu)
+    pub async fn delete_cell_resource_stock(
+        &self,
+        cell: &GridCell,
+        resource_specific_type: ResourceSpecificTypeEnum,
+    ) -> Result<(), String> {
+        sqlx::query(
+            r#"
+            DELETE FROM resources.cell_resource_stocks
+            WHERE cell_q = $1 AND cell_r = $2 AND resource_specific_type_id = $3
+            "#,
+        )
+        .bind(cell.q)
+        .bind(cell.r)
+        .bind(resource_specific_type.to_id())
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to delete cell resource stock: {}", e))?;
+
+        Ok(())
+    }
+
+    /// Réserves entamées, avec le biome de leur cellule
+    pub async fn load_regrowing_stocks(&self) -> Result<Vec<RegrowingStock>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT s.cell_q, s.cell_r, s.chunk_x, s.chunk_y, s.resource_specific_type_id,
+                   s.stock, s.max_stock, c.biome_id
+            FROM resources.cell_resource_stocks s
+            JOIN terrain.cells c ON c.q = s.cell_q AND c.r = s.cell_r
+            WHERE s.stock < s.max_stock
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load regrowing stocks: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .filter_map(|row| {
+                Some(RegrowingStock {
+                    chunk_id: TerrainChunkId {
+                        x: row.get("chunk_x"),
+                        y: row.get("chunk_y"),
+                    },
+                    biome: BiomeTypeEnum::from_id(row.get("biome_id"))?,
+                    stock: Self::row_to_cell_stock(row)?,
+                })
+            })
+            .collect())
+    }
+
+    /// Met à jour le niveau de plusieurs réserves
+    pub async fn update_cell_resource_stocks(
+        &self,
+        stocks: &[CellResourceStock],
+    ) -> Result<(), String> {
+        if stocks.is_empty() {
+            return Ok(());
+        }
+
+        let qs: Vec<i32> = stocks.iter().map(|s| s.cell.q).collect();
+        let rs: Vec<i32> = stocks.iter().map(|s| s.cell.r).collect();
+        let resource_ids: Vec<i16> = stocks
+            .iter()
+            .map(|s| s.resource_specific_type.to_id())
+            .collect();
+        let levels: Vec<i32> = stocks.iter().map(|s| s.stock).collect();
+
+        sqlx::query(
+            r#"
+            UPDATE resources.cell_resource_stocks s
+            SET stock = LEAST(u.stock, s.max_stock)
+            FROM UNNEST($1::int[], $2::int[], $3::smallint[], $4::int[])
+                AS u(cell_q, cell_r, resource_specific_type_id, stock)
+            WHERE s.cell_q = u.cell_q AND s.cell_r = u.cell_r
+              AND s.resource_specific_type_id = u.resource_specific_type_id
+            "#,
+        )
+        .bind(&qs)
+        .bind(&rs)
+        .bind(&resource_ids)
+        .bind(&levels)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to update cell resource stocks: {}", e))?;
+
+        Ok(())
+    }
+
+    fn row_to_cell_stock(row: &sqlx::postgres::PgRow) -> Option<CellResourceStock> {
+        Some(CellResourceStock {
+            cell: GridCell {
+                q: row.get("cell_q"),
+                r: row.get("cell_r"),
+            },
+            resource_specific_type: ResourceSpecificTypeEnum::from_id(
+                row.get("resource_specific_type_id"),
+            )?,
+            stock: row.get("stock"),
+            max_stock: row.get("max_stock"),
+        })
+    }
+
     // ============ RECIPES ============
 
     /// Charge une recette par son ID
@@ -529,15 +1069,16 @@ impl ResourcesTable {
     }
 
     /// Consomme N instances d'un item_id pour une unité (FIFO : les plus anciens d'abord)
+    /// Renvoie (instance_id, qualité) des instances détruites
     pub async fn consume_items(
         &self,
         unit_id: u64,
         item_id: i32,
         quantity: i32,
-    ) -> Result<Vec<u64>, String> {
+    ) -> Result<Vec<(u64, f32)>, String> {
         let rows = sqlx::query(
             r#"
-            SELECT id FROM resources.item_instances
+            SELECT id, quality::float8 AS quality FROM resources.item_instances
             WHERE owner_unit_id = $1 AND item_id = $2
             ORDER BY created_at ASC
             LIMIT $3
@@ -559,18 +1100,19 @@ impl ResourcesTable {
             ));
         }
 
-        let mut deleted_ids = Vec::new();
+        let mut consumed = Vec::new();
         for row in &rows {
             let instance_id: i64 = row.get("id");
+            let quality: f64 = row.try_get("quality").unwrap_or(1.0);
             sqlx::query("DELETE FROM resources.item_instances WHERE id = $1")
                 .bind(instance_id)
                 .execute(&self.pool)
                 .await
                 .map_err(|e| format!("Failed to delete item instance {}: {}", instance_id, e))?;
-            deleted_ids.push(instance_id as u64);
+            consumed.push((instance_id as u64, quality as f32));
         }
 
-        Ok(deleted_ids)
+        Ok(consumed)
     }
 
     /// Crée N instances d'un item pour une unité (bulk)
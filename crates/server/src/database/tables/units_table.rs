@@ -1,7 +1,8 @@
 use bevy::prelude::*;
 use shared::{
-    EquipmentSlotEnum, EquippedItem, FullUnitData, InventoryItem, ProfessionEnum, SkillEnum,
-    TerrainChunkId, UnitBaseStats, UnitData, UnitDerivedStats, UnitSkill, grid::GridCell,
+    AutomatedAction, ConsumptionDemand, EquipmentSlotEnum, EquippedItem, FullUnitData, InventoryItem,
+    ProfessionEnum, SkillEnum, TerrainChunkId, UnitBaseStats, UnitData, UnitDerivedStats,
+    UnitSkill, grid::GridCell,
 };
 use sqlx::{PgPool, Row};
 use std::collections::HashMap;
@@ -700,8 +701,8 @@ impl UnitsTable {
         let skills = self.load_unit_skills(unit_id).await?;
         let inventory = self.load_inventory(unit_id).await?;
         let equipment = self.load_equipment(unit_id).await?;
-
-        // TODO: Load automated actions and consumption demands
+        let automated_actions = self.load_automated_actions(unit_id).await?;
+        let consumption_demands = self.load_consumption_demands(unit_id).await?;
 
         Ok(FullUnitData {
             unit,
@@ -710,11 +711,246 @@ impl UnitsTable {
             skills,
             inventory,
             equipment,
-            automated_actions: vec![],
-            consumption_demands: vec![],
+            automated_actions,
+            consumption_demands,
         })
     }
 
+    // ============ AUTOMATED ACTIONS ============
+
+    /// Routines d'une unité, dans l'ordre de création
+    pub async fn load_automated_actions(&self, unit_id: u64) -> Result<Vec<AutomatedAction>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT a.unit_id, a.id, a.action_type, a.is_enabled, p.key, p.value
+            FROM units.unit_automated_actions a
+            LEFT JOIN LATERAL jsonb_each_text(COALESCE(a.parameters, '{}'::jsonb)) p ON true
+            WHERE a.unit_id = $1
+            ORDER BY a.id ASC
+            "#,
+        )
+        .bind(unit_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load automated actions: {}", e))?;
+
+        Ok(Self::group_automated_actions(&rows)
+            .into_iter()
+            .map(|(_, action)| action)
+            .collect())
+    }
+
+    /// Routines actives de toutes les unités PNJ : (unit_id, routine)
+    pub async fn load_enabled_automated_actions(&self) -> Result<Vec<(u64, AutomatedAction)>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT a.unit_id, a.id, a.action_type, a.is_enabled, p.key, p.value
+            FROM units.unit_automated_actions a
+            INNER JOIN units.units u ON u.id = a.unit_id
+            LEFT JOIN LATERAL jsonb_each_text(COALESCE(a.parameters, '{}'::jsonb)) p ON true
+            WHERE a.is_enabled = true AND u.is_lord = false
+            ORDER BY a.unit_id ASC, a.id ASC
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load enabled automated actions: {}", e))?;
+
+        Ok(Self::group_automated_actions(&rows))
+    }
+
+    /// Joueur qui commande une unité : son propriétaire, ou le joueur dont le lord
+    /// dirige l'organisation à laquelle elle appartient
+    pub async fn load_controlling_player(&self, unit_id: u64) -> Result<Option<u64>, String> {
+        let player_id = sqlx::query_scalar::<_, Option<i64>>(
+            r#"
+            SELECT COALESCE(u.player_id, (
+                SELECT lord.player_id
+                FROM organizations.members om
+                JOIN organizations.organizations o ON o.id = om.organization_id
+                JOIN units.units lord ON lord.id = o.leader_unit_id
+                WHERE om.unit_id = u.id AND lord.is_lord = true AND lord.player_id IS NOT NULL
+                LIMIT 1
+            ))
+            FROM units.units u
+            WHERE u.id = $1
+            "#,
+        )
+        .bind(unit_id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load controlling player: {}", e))?;
+
+        Ok(player_id.flatten().map(|id| id as u64))
+    }
+
+    /// Une ligne par paramètre : regroupe les lignes (triées par routine) en routines
+    fn group_automated_actions(rows: &[sqlx::postgres::PgRow]) -> Vec<(u64, AutomatedAction)> {
+        let mut actions: Vec<(u64, AutomatedAction)> = Vec::new();
+        for row in rows {
+            let id = row.get::<i64, _>("id") as u64;
+            if actions.last().is_none_or(|(_, action)| action.id != id) {
+                actions.push((
+                    row.get::<i64, _>("unit_id") as u64,
+                    AutomatedAction {
+                        id,
+                        action_type: row.get("action_type"),
+                        is_enabled: row.get::<Option<bool>, _>("is_enabled").unwrap_or(true),
+                        parameters: HashMap::new(),
+                    },
+                ));
+            }
+            if let (Some(key), Some(value)) = (
+                row.get::<Option<String>, _>("key"),
+                row.get::<Option<String>, _>("value"),
+            ) && let Some((_, action)) = actions.last_mut()
+            {
+                action.parameters.insert(key, value);
+            }
+        }
+        actions
+    }
+
+    /// Crée (id 0) ou remplace une routine de l'unité. Renvoie son id.
+    pub async fn save_automated_action(&self, unit_id: u64, action: &AutomatedAction) -> Result<u64, String> {
+        let (keys, values): (Vec<String>, Vec<String>) = action
+            .parameters
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .unzip();
+
+        let id = if action.id == 0 {
+            sqlx::query_scalar::<_, i64>(
+                r#"
+                INSERT INTO units.unit_automated_actions (unit_id, action_type, is_enabled, parameters)
+                VALUES ($1, $2, $3, jsonb_object($4::text[], $5::text[]))
+                RETURNING id
+                "#,
+            )
+            .bind(unit_id as i64)
+            .bind(&action.action_type)
+            .bind(action.is_enabled)
+            .bind(&keys)
+            .bind(&values)
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to create automated action: {}", e))?
+        } else {
+            sqlx::query_scalar::<_, i64>(
+                r#"
+                UPDATE units.unit_automated_actions
+                SET action_type = $3, is_enabled = $4, parameters = jsonb_object($5::text[], $6::text[])
+                WHERE id = $1 AND unit_id = $2
+                RETURNING id
+                "#,
+            )
+            .bind(action.id as i64)
+            .bind(unit_id as i64)
+            .bind(&action.action_type)
+            .bind(action.is_enabled)
+            .bind(&keys)
+            .bind(&values)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to update automated action: {}", e))?
+            .ok_or_else(|| format!("Automated action {} not found for unit {}", action.id, unit_id))?
+        };
+
+        Ok(id as u64)
+    }
+
+    pub async fn delete_automated_action(&self, unit_id: u64, action_id: u64) -> Result<bool, String> {
+        let result = sqlx::query("DELETE FROM units.unit_automated_actions WHERE id = $1 AND unit_id = $2")
+            .bind(action_id as i64)
+            .bind(unit_id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete automated action: {}", e))?;
+
+        Ok(result.rows_affected() > 0)
+    }
+
+    // ============ CONSUMPTION ============
+
+    /// Besoins journaliers d'une unité, du plus important au moins important
+    pub async fn load_consumption_demands(
+        &self,
+        unit_id: u64,
+    ) -> Result<Vec<ConsumptionDemand>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT item_id, quantity_per_day::float4 AS quantity_per_day, priority
+            FROM units.unit_consumption_demands
+            WHERE unit_id = $1
+            ORDER BY priority ASC, item_id ASC
+            "#,
+        )
+        .bind(unit_id as i64)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load consumption demands: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|row| ConsumptionDemand {
+                item_id: row.get("item_id"),
+                quantity_per_day: row.get("quantity_per_day"),
+                priority: row.get("priority"),
+            })
+            .collect())
+    }
+
+    /// Donne les besoins par défaut (units.default_consumption_demands) aux
+    /// villageois qui n'en ont pas encore. Renvoie le nombre de besoins ajoutés.
+    pub async fn assign_default_consumption_demands(&self) -> Result<u64, String> {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO units.unit_consumption_demands (unit_id, item_id, quantity_per_day, priority)
+            SELECT u.id, d.item_id, d.quantity_per_day, d.priority
+            FROM units.units u
+            CROSS JOIN units.default_consumption_demands d
+            WHERE u.is_lord = false
+              AND NOT EXISTS (
+                  SELECT 1 FROM units.unit_consumption_demands c WHERE c.unit_id = u.id
+              )
+            "#,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to assign default consumption demands: {}", e))?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Compte un jour de plus sans les besoins vitaux, ou remet le compteur à zéro.
+    /// Renvoie le nombre de jours de famine consécutifs.
+    pub async fn record_starvation(&self, unit_id: u64, starving: bool) -> Result<i32, String> {
+        sqlx::query_scalar::<_, i32>(
+            r#"
+            UPDATE units.units
+            SET starving_days = CASE WHEN $2 THEN starving_days + 1 ELSE 0 END
+            WHERE id = $1
+            RETURNING starving_days
+            "#,
+        )
+        .bind(unit_id as i64)
+        .bind(starving)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to record starvation: {}", e))
+    }
+
+    /// Supprime une unité (stats, compétences, besoins et adhésions suivent en cascade)
+    pub async fn delete_unit(&self, unit_id: u64) -> Result<(), String> {
+        sqlx::query("DELETE FROM units.units WHERE id = $1")
+            .bind(unit_id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to delete unit {}: {}", unit_id, e))?;
+
+        Ok(())
+    }
+
     /// Update unit slot position
     pub async fn update_slot_position(
         &self,
@@ -829,6 +1065,40 @@ impl UnitsTable {
         Ok(())
     }
 
+    /// Units currently assigned to an action
+    pub async fn load_units_working_on(&self, action_id: u64) -> Result<Vec<u64>, String> {
+        let rows = sqlx::query("SELECT id FROM units.units WHERE working_on_action_id = $1")
+            .bind(action_id as i64)
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to load units working on action: {}", e))?;
+
+        Ok(rows.iter().map(|r| r.get::<i64, _>("id") as u64).collect())
+    }
+
+    /// Action a unit is working on, if any
+    pub async fn load_unit_work(&self, unit_id: u64) -> Result<Option<u64>, String> {
+        let action_id = sqlx::query_scalar::<_, Option<i64>>(
+            "SELECT working_on_action_id FROM units.units WHERE id = $1",
+        )
+        .bind(unit_id as i64)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| format!("Failed to load unit work: {}", e))?;
+
+        Ok(action_id.flatten().map(|id| id as u64))
+    }
+
+    /// Free a single unit from the action it works on
+    pub async fn clear_unit_work(&self, unit_id: u64) -> Result<(), String> {
+        sqlx::query("UPDATE units.units SET working_on_action_id = NULL WHERE id = $1")
+            .bind(unit_id as i64)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| format!("Failed to clear unit work: {}", e))?;
+        Ok(())
+    }
+
     /// Clear working_on for all units assigned to an action
     pub async fn clear_units_working_on(&self, action_id: u64) -> Result<Vec<u64>, String> {
         let rows = sqlx::query(
@@ -0,0 +1,3 @@
+mod systems;
+
+pub use systems::{DecaySystem, start_decay_tick};
@@ -0,0 +1,141 @@
+use shared::protocol::ServerMessage;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::database::client::DatabaseTables;
+use crate::database::tables::PerishableInstance;
+use crate::networking::Sessions;
+
+const SECONDS_PER_DAY: f64 = 86_400.0;
+
+/// Une glacière divise la vitesse de pourrissement par 4
+const ICE_HOUSE_DECAY_FACTOR: f32 = 0.25;
+
+/// Decay gagné pendant `elapsed_secs` selon le taux journalier et le stockage
+pub fn decay_increment(decay_rate_per_day: f32, elapsed_secs: f64, in_ice_house: bool) -> f32 {
+    let storage_factor = if in_ice_house {
+        ICE_HOUSE_DECAY_FACTOR
+    } else {
+        1.0
+    };
+    (decay_rate_per_day as f64 * elapsed_secs / SECONDS_PER_DAY) as f32 * storage_factor
+}
+
+pub struct DecaySystem {
+    db_tables: Arc<DatabaseTables>,
+    sessions: Sessions,
+}
+
+impl DecaySystem {
+    pub fn new(db_tables: Arc<DatabaseTables>, sessions: Sessions) -> Self {
+        Self {
+            db_tables,
+            sessions,
+        }
+    }
+
+    /// Tick principal — fait vieillir les items périssables, détruit ceux qui sont pourris
+    pub async fn tick(&self) {
+        let instances = match self.db_tables.resources.load_perishable_instances().await {
+            Ok(instances) => instances,
+            Err(e) => {
+                tracing::error!("Decay tick: failed to load perishable items: {}", e);
+                return;
+            }
+        };
+
+        let mut spoiled = Vec::new();
+        let mut decays = Vec::new();
+        for instance in instances {
+            let increment = decay_increment(
+                instance.decay_rate_per_day,
+                instance.elapsed_secs,
+                instance.in_ice_house,
+            );
+            let new_decay = (instance.current_decay + increment).min(1.0);
+
+            if new_decay >= 1.0 {
+                spoiled.push(instance);
+            } else if increment > 0.0 {
+                decays.push((instance.instance_id, new_decay));
+            }
+        }
+
+        if !decays.is_empty()
+            && let Err(e) = self.db_tables.resources.update_item_instances_decay(&decays).await
+        {
+            tracing::warn!("Decay tick: {}", e);
+        }
+
+        if !spoiled.is_empty() {
+            self.destroy_spoiled(spoiled).await;
+        }
+    }
+
+    /// Supprime les items pourris et prévient les joueurs qui les portaient
+    async fn destroy_spoiled(&self, spoiled: Vec<PerishableInstance>) {
+        let ids: Vec<u64> = spoiled.iter().map(|i| i.instance_id).collect();
+        match self.db_tables.resources.delete_item_instances(&ids).await {
+            Ok(count) => tracing::info!("Decay tick: {} items rotted away", count),
+            Err(e) => {
+                tracing::error!("Decay tick: {}", e);
+                return;
+            }
+        }
+
+        // (player, unit, item) -> nombre d'exemplaires perdus
+        let mut losses: HashMap<(u64, u64, i32), i32> = HashMap::new();
+        for instance in &spoiled {
+            if let (Some(player_id), Some(unit_id)) =
+                (instance.owner_player_id, instance.owner_unit_id)
+            {
+                *losses
+                    .entry((player_id, unit_id, instance.item_id))
+                    .or_insert(0) += 1;
+            }
+        }
+
+        for ((player_id, unit_id, item_id), lost) in losses {
+            let new_total = self
+                .db_tables
+                .resources
+                .count_item_for_unit(unit_id, item_id)
+                .await
+                .unwrap_or(0);
+            let msg = ServerMessage::InventoryUpdate {
+                unit_id,
+                item_id,
+                quantity_delta: -lost,
+                new_total,
+            };
+            if let Err(e) = self.sessions.send_to_player(player_id, msg).await {
+                tracing::debug!("Decay update not delivered to player {}: {}", player_id, e);
+            }
+        }
+    }
+}
+
+pub fn start_decay_tick(system: Arc<DecaySystem>) {
+    tokio::task::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(60));
+
+        loop {
+            interval.tick().await;
+            system.tick().await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ice_house_slows_decay() {
+        let outside = decay_increment(0.5, SECONDS_PER_DAY, false);
+        let inside = decay_increment(0.5, SECONDS_PER_DAY, true);
+        assert!((outside - 0.5).abs() < 1e-6);
+        assert!((inside - 0.125).abs() < 1e-6);
+        assert_eq!(decay_increment(0.0, SECONDS_PER_DAY, false), 0.0);
+    }
+}
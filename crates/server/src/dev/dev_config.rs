@@ -11,6 +11,8 @@ pub struct DevConfig {
     pub speed_factor: u64,
     /// Skip resource validation (allow crafting/building without ingredients)
     pub bypass_resources: bool,
+    /// Fixed seed for harvest/craft outcome rolls (reproducible results)
+    pub outcome_seed: Option<u64>,
 }
 
 impl DevConfig {
@@ -18,6 +20,7 @@ impl DevConfig {
     /// DEV_MODE=true enables dev mode
     /// DEV_SPEED_FACTOR=10 (default: 10 when dev mode, 1 otherwise)
     /// DEV_BYPASS_RESOURCES=true (default: same as DEV_MODE)
+    /// DEV_OUTCOME_SEED=42 (default: random seed)
     pub fn from_env() -> Self {
         let dev_mode = std::env::var("DEV_MODE")
             .map(|v| v == "true" || v == "1")
@@ -40,10 +43,19 @@ impl DevConfig {
             false
         };
 
+        let outcome_seed = if dev_mode {
+            std::env::var("DEV_OUTCOME_SEED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+        } else {
+            None
+        };
+
         Self {
             dev_mode,
             speed_factor,
             bypass_resources,
+            outcome_seed,
         }
     }
 
@@ -0,0 +1,3 @@
+mod systems;
+
+pub use systems::{DiplomacyExpirySystem, complete_envoy_mission, start_diplomacy_expiry_tick};
@@ -0,0 +1,290 @@
+use shared::protocol::ServerMessage;
+use shared::{DiplomaticRelation, EnvoyMission, SendEnvoyAction};
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use crate::action_processor::now_ms;
+use crate::database::client::DatabaseTables;
+use crate::dev::DevConfig;
+use crate::networking::Sessions;
+
+/// Les relations arrivées à échéance sont levées toutes les heures
+const EXPIRY_INTERVAL_SECS: u64 = 3_600;
+
+fn now_secs() -> i64 {
+    (now_ms() / 1000) as i64
+}
+
+/// Envoie un message à tous les joueurs qui ont une unité dans l'organisation
+async fn notify_members(
+    db_tables: &DatabaseTables,
+    sessions: &Sessions,
+    organization_id: u64,
+    msg: ServerMessage,
+) {
+    let players = match db_tables.organizations.load_member_players(organization_id).await {
+        Ok(players) => players,
+        Err(e) => {
+            tracing::warn!("{}", e);
+            return;
+        }
+    };
+    for player_id in players {
+        if let Err(e) = sessions.send_to_player(player_id, msg.clone()).await {
+            tracing::debug!("Diplomacy update not delivered to player {}: {}", player_id, e);
+        }
+    }
+}
+
+/// Prévient les membres des deux organisations
+async fn notify_both(
+    db_tables: &DatabaseTables,
+    sessions: &Sessions,
+    organization_id: u64,
+    target_organization_id: u64,
+    msg: ServerMessage,
+) {
+    notify_members(db_tables, sessions, organization_id, msg.clone()).await;
+    notify_members(db_tables, sessions, target_organization_id, msg).await;
+}
+
+/// L'émissaire est arrivé au siège de l'autre organisation : il remet sa proposition,
+/// ou la réponse à une proposition reçue
+pub async fn complete_envoy_mission(
+    db_tables: &DatabaseTables,
+    sessions: &Sessions,
+    envoy: &SendEnvoyAction,
+) {
+    let from = envoy.organization_id;
+    let to = envoy.target_organization_id;
+
+    match envoy.mission {
+        EnvoyMission::Propose {
+            relation_type,
+            duration_days,
+        } if !relation_type.needs_consent() => {
+            // Une déclaration d'hostilité ou de guerre n'attend pas de réponse
+            let expires_at = DiplomaticRelation::expiry(now_secs(), duration_days);
+            if let Err(e) = db_tables
+                .organizations
+                .establish_diplomatic_relation(from, to, relation_type, Some(envoy.unit_id), expires_at)
+                .await
+            {
+                tracing::error!("{}", e);
+                return;
+            }
+            tracing::info!("Organization {} declared {:?} to {}", from, relation_type, to);
+            notify_both(
+                db_tables,
+                sessions,
+                from,
+                to,
+                ServerMessage::DiplomaticRelationChanged {
+                    organization_id: from,
+                    target_organization_id: to,
+                    relation_type: Some(relation_type),
+                },
+            )
+            .await;
+        }
+
+        EnvoyMission::Propose {
+            relation_type,
+            duration_days,
+        } => {
+            let proposal = async {
+                let proposal_id = db_tables
+                    .organizations
+                    .create_diplomatic_proposal(from, to, relation_type, duration_days, Some(envoy.unit_id))
+                    .await?;
+                db_tables
+                    .organizations
+                    .load_diplomatic_proposals(from)
+                    .await?
+                    .into_iter()
+                    .find(|proposal| proposal.id == proposal_id)
+                    .ok_or_else(|| format!("Diplomatic proposal {} vanished", proposal_id))
+            }
+            .await;
+            let proposal = match proposal {
+                Ok(proposal) => proposal,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return;
+                }
+            };
+            tracing::info!(
+                "{} proposed {:?} to {}",
+                proposal.organization_name,
+                relation_type,
+                proposal.target_name
+            );
+            notify_both(
+                db_tables,
+                sessions,
+                from,
+                to,
+                ServerMessage::DiplomaticProposalReceived { proposal },
+            )
+            .await;
+        }
+
+        EnvoyMission::Accept { proposal_id } | EnvoyMission::Reject { proposal_id } => {
+            // La réponse porte sur une proposition de l'autre organisation à la nôtre
+            let proposal = match db_tables.organizations.load_diplomatic_proposals(from).await {
+                Ok(proposals) => proposals.into_iter().find(|proposal| {
+                    proposal.id == proposal_id
+                        && proposal.organization_id == to
+                        && proposal.target_organization_id == from
+                }),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return;
+                }
+            };
+            let proposal = match proposal {
+                Some(proposal) => proposal,
+                None => {
+                    tracing::info!("Proposal {} was withdrawn before the envoy arrived", proposal_id);
+                    return;
+                }
+            };
+            match db_tables.organizations.delete_diplomatic_proposal(proposal.id).await {
+                Ok(true) => {}
+                Ok(false) => return,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return;
+                }
+            }
+
+            if matches!(envoy.mission, EnvoyMission::Reject { .. }) {
+                tracing::info!("{} turned down {}", proposal.target_name, proposal.organization_name);
+                notify_both(
+                    db_tables,
+                    sessions,
+                    from,
+                    to,
+                    ServerMessage::DiplomaticProposalRejected { proposal },
+                )
+                .await;
+                return;
+            }
+
+            let expires_at = DiplomaticRelation::expiry(now_secs(), proposal.duration_days);
+            if let Err(e) = db_tables
+                .organizations
+                .establish_diplomatic_relation(
+                    proposal.organization_id,
+                    proposal.target_organization_id,
+                    proposal.relation_type,
+                    Some(envoy.unit_id),
+                    expires_at,
+                )
+                .await
+            {
+                tracing::error!("{}", e);
+                return;
+            }
+            tracing::info!(
+                "✓ {} and {} concluded {:?}",
+                proposal.organization_name,
+                proposal.target_name,
+                proposal.relation_type
+            );
+            notify_both(
+                db_tables,
+                sessions,
+                from,
+                to,
+                ServerMessage::DiplomaticRelationChanged {
+                    organization_id: proposal.organization_id,
+                    target_organization_id: proposal.target_organization_id,
+                    relation_type: Some(proposal.relation_type),
+                },
+            )
+            .await;
+        }
+    }
+}
+
+pub struct DiplomacyExpirySystem {
+    db_tables: Arc<DatabaseTables>,
+    sessions: Sessions,
+}
+
+impl DiplomacyExpirySystem {
+    pub fn new(db_tables: Arc<DatabaseTables>, sessions: Sessions) -> Self {
+        Self {
+            db_tables,
+            sessions,
+        }
+    }
+
+    /// Lève les relations arrivées à échéance et prévient les deux parties
+    pub async fn tick(&self) {
+        let relations = match self
+            .db_tables
+            .organizations
+            .load_expiring_diplomatic_relations()
+            .await
+        {
+            Ok(relations) => relations,
+            Err(e) => {
+                tracing::error!("Diplomacy expiry tick: {}", e);
+                return;
+            }
+        };
+
+        let now = now_secs();
+        let expired: Vec<&DiplomaticRelation> =
+            relations.iter().filter(|relation| relation.is_expired(now)).collect();
+        if expired.is_empty() {
+            return;
+        }
+
+        let ids: Vec<u64> = expired.iter().map(|relation| relation.id).collect();
+        if let Err(e) = self.db_tables.organizations.delete_diplomatic_relations(&ids).await {
+            tracing::error!("Diplomacy expiry tick: {}", e);
+            return;
+        }
+        tracing::info!("Diplomacy expiry tick: {} relations expired", ids.len());
+
+        // Une relation est stockée dans les deux sens : une seule annonce par paire
+        let mut announced = HashSet::new();
+        for relation in expired {
+            let pair = (
+                relation.organization_id.min(relation.target_organization_id),
+                relation.organization_id.max(relation.target_organization_id),
+            );
+            if !announced.insert(pair) {
+                continue;
+            }
+            notify_both(
+                &self.db_tables,
+                &self.sessions,
+                relation.organization_id,
+                relation.target_organization_id,
+                ServerMessage::DiplomaticRelationChanged {
+                    organization_id: relation.organization_id,
+                    target_organization_id: relation.target_organization_id,
+                    relation_type: None,
+                },
+            )
+            .await;
+        }
+    }
+}
+
+pub fn start_diplomacy_expiry_tick(system: Arc<DiplomacyExpirySystem>, dev_config: Arc<DevConfig>) {
+    tokio::task::spawn(async move {
+        let interval_ms = dev_config.apply_speed(EXPIRY_INTERVAL_SECS * 1000);
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(interval_ms));
+        // Le premier tick est immédiat : les traités échus pendant un arrêt du serveur sont levés
+
+        loop {
+            interval.tick().await;
+            system.tick().await;
+        }
+    });
+}
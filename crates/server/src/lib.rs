@@ -4,9 +4,14 @@
 pub mod action_processor;
 pub mod auth;
 pub mod database;
+pub mod decay;
+pub mod diplomacy;
 pub mod dev;
 pub mod networking;
+pub mod population;
+pub mod resource_stocks;
 pub mod road;
 pub mod units;
 pub mod utils;
+pub mod vassalage;
 pub mod world;
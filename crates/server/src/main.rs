@@ -6,12 +6,16 @@ use bevy::prelude::*;
 mod action_processor;
 mod auth;
 mod database;
+mod decay;
+mod diplomacy;
 mod dev;
 mod networking;
 mod population;
+mod resource_stocks;
 mod road;
 mod units;
 mod utils;
+mod vassalage;
 mod world;
 
 #[tokio::main]
@@ -129,6 +133,13 @@ async fn main() {
     if let Err(e) = action_processor.load_active_actions().await {
         tracing::error!("Failed to load active actions: {}", e);
     }
+    match db_tables_arc.action_queue.purge_stale_entries().await {
+        Ok(0) => {}
+        Ok(count) => {
+            tracing::warn!("Dropped {} queued actions from another protocol version", count)
+        }
+        Err(e) => tracing::error!("{}", e),
+    }
 
     // Initialiser le serveur réseau avec l'action_processor et name_generator
     networking::server::initialize_server(
@@ -153,6 +164,40 @@ async fn main() {
     ));
     population::start_population_tick(population_system);
 
+    // Consommation journalière des villageois (nourriture, bonheur, famine)
+    let consumption_system = Arc::new(population::ConsumptionSystem::new(
+        db_tables_arc.clone(),
+        sessions.clone(),
+        action_processor.clone(),
+    ));
+    population::start_consumption_tick(consumption_system, dev_config_arc.clone());
+
+    // Repousse journalière des ressources naturelles (bois, minerai, pierre)
+    let regrowth_system = Arc::new(resource_stocks::RegrowthSystem::new(
+        db_tables_arc.clone(),
+        sessions.clone(),
+        game_state_arc.clone(),
+    ));
+    resource_stocks::start_regrowth_tick(regrowth_system, dev_config_arc.clone());
+
+    // Tribut journalier des vassaux à leur suzerain
+    let tribute_system = Arc::new(vassalage::TributeSystem::new(
+        db_tables_arc.clone(),
+        sessions.clone(),
+    ));
+    vassalage::start_tribute_tick(tribute_system, dev_config_arc.clone());
+
+    // Fin des traités arrivés à échéance
+    let diplomacy_expiry_system = Arc::new(diplomacy::DiplomacyExpirySystem::new(
+        db_tables_arc.clone(),
+        sessions.clone(),
+    ));
+    diplomacy::start_diplomacy_expiry_tick(diplomacy_expiry_system, dev_config_arc.clone());
+
+    // Pourrissement des items périssables
+    let decay_system = Arc::new(decay::DecaySystem::new(db_tables_arc.clone(), sessions.clone()));
+    decay::start_decay_tick(decay_system);
+
     tokio::task::spawn_blocking(move || {
         App::new()
             .add_plugins(MinimalPlugins)
@@ -1,3 +1,5 @@
+mod outbox;
+mod rate_limit;
 pub mod server;
 mod session;
 
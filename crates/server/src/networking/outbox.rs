@@ -0,0 +1,167 @@
+use shared::TerrainChunkId;
+use shared::protocol::ServerMessage;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use super::session::ChunkInterest;
+
+/// Nombre de messages gardés par joueur pour pouvoir les rejouer après une reconnexion
+pub const OUTBOX_CAPACITY: usize = 512;
+
+/// Buffer borné des derniers messages poussés à un joueur, numérotés par `seq`.
+/// Survit à la déconnexion du joueur le temps que son jeton de reprise expire.
+#[derive(Debug)]
+pub struct PlayerOutbox {
+    next_seq: u64,
+    buffer: VecDeque<(u64, ServerMessage)>,
+    capacity: usize,
+    /// Set while no connection is bound to the player
+    disconnected_since: Option<Instant>,
+    /// Zone d'intérêt de la dernière session, restaurée à la reprise
+    interest: Option<ChunkInterest>,
+}
+
+impl Default for PlayerOutbox {
+    fn default() -> Self {
+        Self::with_capacity(OUTBOX_CAPACITY)
+    }
+}
+
+impl PlayerOutbox {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            next_seq: 1,
+            buffer: VecDeque::with_capacity(capacity.min(OUTBOX_CAPACITY)),
+            capacity: capacity.max(1),
+            disconnected_since: None,
+            interest: None,
+        }
+    }
+
+    /// Numérote un message et le garde pour un éventuel replay.
+    /// Returns the `Sequenced` message to send on the wire.
+    pub fn push(&mut self, message: ServerMessage) -> ServerMessage {
+        let seq = self.next_seq;
+        self.next_seq += 1;
+
+        if self.buffer.len() == self.capacity {
+            self.buffer.pop_front();
+        }
+        self.buffer.push_back((seq, message.clone()));
+
+        ServerMessage::Sequenced {
+            seq,
+            message: Box::new(message),
+        }
+    }
+
+    /// Messages to send again to a client that saw everything up to `last_seen_seq`.
+    /// `None` if some of them were already dropped from the buffer.
+    pub fn replay_after(&self, last_seen_seq: u64) -> Option<Vec<ServerMessage>> {
+        let oldest = self
+            .buffer
+            .front()
+            .map(|(seq, _)| *seq)
+            .unwrap_or(self.next_seq);
+        if last_seen_seq + 1 < oldest || last_seen_seq >= self.next_seq {
+            return None;
+        }
+
+        Some(
+            self.buffer
+                .iter()
+                .filter(|(seq, _)| *seq > last_seen_seq)
+                .map(|(seq, message)| ServerMessage::Sequenced {
+                    seq: *seq,
+                    message: Box::new(message.clone()),
+                })
+                .collect(),
+        )
+    }
+
+    pub fn mark_disconnected(&mut self, interest: ChunkInterest) {
+        self.disconnected_since = Some(Instant::now());
+        self.interest = Some(interest);
+    }
+
+    /// Rattache le buffer à une nouvelle connexion et rend la zone d'intérêt sauvegardée
+    pub fn mark_reconnected(&mut self) -> Option<ChunkInterest> {
+        self.disconnected_since = None;
+        self.interest.take()
+    }
+
+    /// Le joueur déconnecté regardait au moins un de ces chunks : leurs mises à jour
+    /// doivent l'attendre ici, sinon il garderait un état périmé après la reprise
+    pub fn watches_any(&self, chunk_ids: &[TerrainChunkId]) -> bool {
+        self.interest
+            .as_ref()
+            .is_some_and(|interest| chunk_ids.iter().any(|id| interest.covers(id)))
+    }
+
+    pub fn is_disconnected(&self) -> bool {
+        self.disconnected_since.is_some()
+    }
+
+    pub fn is_expired(&self, ttl: Duration) -> bool {
+        self.disconnected_since
+            .is_some_and(|since| since.elapsed() > ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seqs(messages: &[ServerMessage]) -> Vec<u64> {
+        messages
+            .iter()
+            .map(|m| match m {
+                ServerMessage::Sequenced { seq, .. } => *seq,
+                _ => panic!("replay must only contain sequenced messages"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_replay_returns_messages_after_last_seen() {
+        let mut outbox = PlayerOutbox::with_capacity(8);
+        for _ in 0..5 {
+            outbox.push(ServerMessage::Pong);
+        }
+
+        assert_eq!(seqs(&outbox.replay_after(2).unwrap()), vec![3, 4, 5]);
+        assert!(outbox.replay_after(5).unwrap().is_empty());
+        assert_eq!(seqs(&outbox.replay_after(0).unwrap()), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_replay_fails_when_messages_were_evicted() {
+        let mut outbox = PlayerOutbox::with_capacity(3);
+        for _ in 0..5 {
+            outbox.push(ServerMessage::Pong);
+        }
+
+        // 1 et 2 ont été évincés
+        assert!(outbox.replay_after(0).is_none());
+        assert_eq!(seqs(&outbox.replay_after(2).unwrap()), vec![3, 4, 5]);
+        // Un client ne peut pas avoir vu un message jamais envoyé
+        assert!(outbox.replay_after(9).is_none());
+    }
+
+    #[test]
+    fn test_disconnected_outbox_keeps_the_interest() {
+        let chunk = TerrainChunkId { x: 1, y: 2 };
+        let mut interest = ChunkInterest::default();
+        interest.mark_loaded(&[chunk]);
+
+        let mut outbox = PlayerOutbox::default();
+        assert!(!outbox.watches_any(&[chunk]));
+
+        outbox.mark_disconnected(interest);
+        assert!(outbox.watches_any(&[chunk]));
+        assert!(!outbox.watches_any(&[TerrainChunkId { x: 5, y: 5 }]));
+
+        outbox.mark_reconnected();
+        assert!(!outbox.watches_any(&[chunk]));
+    }
+}
@@ -0,0 +1,292 @@
+use shared::protocol::ClientMessage;
+use std::time::{Duration, Instant};
+
+/// Taille max d'une frame / d'un message WebSocket envoyé par un client.
+/// Les messages client sont petits : seules les réponses serveur transportent du terrain.
+pub const MAX_CLIENT_MESSAGE_SIZE: usize = 64 * 1024;
+
+/// Taille max d'un message client une fois décompressé
+pub const MAX_CLIENT_DECOMPRESSED_SIZE: usize = 256 * 1024;
+
+/// Catégories de messages client, chacune avec son propre budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageClass {
+    Auth,
+    Terrain,
+    Action,
+    Debug,
+    Other,
+}
+
+impl MessageClass {
+    pub fn of(msg: &ClientMessage) -> Self {
+        match msg {
+            ClientMessage::Hello { .. }
+            | ClientMessage::Login { .. }
+            | ClientMessage::RegisterAccount { .. }
+            | ClientMessage::LoginWithPassword { .. }
+            | ClientMessage::ResumeSession { .. }
+            | ClientMessage::ChangePassword { .. }
+            | ClientMessage::ResetPasswordWithCode { .. }
+            | ClientMessage::DeleteAccount { .. } => MessageClass::Auth,
+
+            ClientMessage::RequestTerrainChunks { .. }
+            | ClientMessage::RequestTerrains { .. }
+            | ClientMessage::RequestOceanData { .. }
+            | ClientMessage::RequestLakeData { .. }
+            | ClientMessage::RequestTerrainGlobalData { .. }
+            | ClientMessage::RequestExplorationMap { .. } => MessageClass::Terrain,
+
+            ClientMessage::ActionBuildBuilding { .. }
+            | ClientMessage::ActionBuildRoad { .. }
+            | ClientMessage::ActionMoveUnit { .. }
+            | ClientMessage::MoveUnitToSlot { .. }
+            | ClientMessage::AssignUnitToSlot { .. }
+            | ClientMessage::ActionSendMessage { .. }
+            | ClientMessage::ActionHarvestResource { .. }
+            | ClientMessage::ActionCraftResource { .. }
+            | ClientMessage::ActionTrainUnit { .. }
+            | ClientMessage::ActionClaimCells { .. }
+            | ClientMessage::ActionSendEnvoy { .. }
+            | ClientMessage::CreateLord { .. }
+            | ClientMessage::FoundHamlet
+            | ClientMessage::UpgradeOrganization
+            | ClientMessage::OfferVassalage { .. }
+            | ClientMessage::AcceptVassalage { .. }
+            | ClientMessage::RevokeVassalage { .. }
+            | ClientMessage::InviteToOrganization { .. }
+            | ClientMessage::ApplyToOrganization { .. }
+            | ClientMessage::AcceptMembershipRequest { .. }
+            | ClientMessage::DeclineMembershipRequest { .. }
+            | ClientMessage::ExpelMember { .. }
+            | ClientMessage::LeaveOrganization
+            | ClientMessage::AppointOfficer { .. }
+            | ClientMessage::DismissOfficer { .. }
+            | ClientMessage::ActionExplore { .. }
+            | ClientMessage::CancelAction { .. }
+            | ClientMessage::PauseAction { .. }
+            | ClientMessage::ResumeAction { .. }
+            | ClientMessage::EnqueueAction { .. }
+            | ClientMessage::ReorderActionQueue { .. }
+            | ClientMessage::ClearActionQueue { .. }
+            | ClientMessage::SaveUnitRoutine { .. }
+            | ClientMessage::DeleteUnitRoutine { .. }
+            | ClientMessage::EquipItem { .. }
+            | ClientMessage::UnequipItem { .. }
+            | ClientMessage::TransferItems { .. }
+            | ClientMessage::DropItems { .. }
+            | ClientMessage::PickUpItems { .. }
+            | ClientMessage::DepositToTreasury { .. }
+            | ClientMessage::WithdrawFromTreasury { .. } => MessageClass::Action,
+
+            ClientMessage::DebugCreateOrganization { .. }
+            | ClientMessage::DebugDeleteOrganization { .. }
+            | ClientMessage::DebugSpawnUnit { .. }
+            | ClientMessage::DebugRegenerateAllContours => MessageClass::Debug,
+
+            _ => MessageClass::Other,
+        }
+    }
+}
+
+/// Coût d'un message dans son budget : une demande de N chunks en coûte N
+pub fn message_cost(msg: &ClientMessage) -> f64 {
+    match msg {
+        ClientMessage::RequestTerrainChunks {
+            terrain_chunk_ids, ..
+        } => terrain_chunk_ids.len().max(1) as f64,
+        _ => 1.0,
+    }
+}
+
+/// Seau à jetons : `capacity` jetons au maximum, rechargés à `refill_per_sec`
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_per_sec: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(capacity: f64, refill_per_sec: f64, now: Instant) -> Self {
+        Self {
+            capacity,
+            refill_per_sec,
+            tokens: capacity,
+            last_refill: now,
+        }
+    }
+
+    /// Consume `cost` tokens if available (a cost above capacity needs a full bucket)
+    pub fn try_consume(&mut self, cost: f64, now: Instant) -> bool {
+        let cost = cost.min(self.capacity);
+        let elapsed = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.refill_per_sec).min(self.capacity);
+        self.last_refill = now;
+
+        if self.tokens >= cost {
+            self.tokens -= cost;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Time until `cost` tokens are available again
+    pub fn retry_after(&self, cost: f64) -> Duration {
+        let missing = (cost.min(self.capacity) - self.tokens).max(0.0);
+        Duration::from_secs_f64(missing / self.refill_per_sec)
+    }
+}
+
+/// Budgets par catégorie (capacité, recharge par seconde)
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    pub auth: (f64, f64),
+    pub terrain: (f64, f64),
+    pub action: (f64, f64),
+    pub debug: (f64, f64),
+    pub other: (f64, f64),
+    /// Dépassements tolérés avant déconnexion
+    pub max_strikes: u32,
+    /// Les dépassements plus anciens que ça sont oubliés
+    pub strike_window: Duration,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        Self {
+            auth: (5.0, 0.2),
+            // Le streaming client demande des chunks par paquets au déplacement de la caméra
+            terrain: (200.0, 40.0),
+            action: (20.0, 5.0),
+            debug: (10.0, 1.0),
+            other: (60.0, 20.0),
+            max_strikes: 10,
+            strike_window: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RateDecision {
+    Allowed,
+    Limited { retry_after: Duration },
+    Disconnect,
+}
+
+/// Limiteur d'une session : un seau par catégorie, et un compteur de dépassements
+pub struct SessionRateLimiter {
+    auth: TokenBucket,
+    terrain: TokenBucket,
+    action: TokenBucket,
+    debug: TokenBucket,
+    other: TokenBucket,
+    max_strikes: u32,
+    strike_window: Duration,
+    strikes: u32,
+    last_strike: Option<Instant>,
+}
+
+impl SessionRateLimiter {
+    pub fn new(config: &RateLimitConfig, now: Instant) -> Self {
+        let bucket = |(capacity, refill): (f64, f64)| TokenBucket::new(capacity, refill, now);
+        Self {
+            auth: bucket(config.auth),
+            terrain: bucket(config.terrain),
+            action: bucket(config.action),
+            debug: bucket(config.debug),
+            other: bucket(config.other),
+            max_strikes: config.max_strikes,
+            strike_window: config.strike_window,
+            strikes: 0,
+            last_strike: None,
+        }
+    }
+
+    pub fn check(&mut self, msg: &ClientMessage, now: Instant) -> RateDecision {
+        let cost = message_cost(msg);
+        let bucket = match MessageClass::of(msg) {
+            MessageClass::Auth => &mut self.auth,
+            MessageClass::Terrain => &mut self.terrain,
+            MessageClass::Action => &mut self.action,
+            MessageClass::Debug => &mut self.debug,
+            MessageClass::Other => &mut self.other,
+        };
+
+        if bucket.try_consume(cost, now) {
+            return RateDecision::Allowed;
+        }
+        let retry_after = bucket.retry_after(cost);
+
+        if self
+            .last_strike
+            .is_some_and(|last| now.saturating_duration_since(last) > self.strike_window)
+        {
+            self.strikes = 0;
+        }
+        self.strikes += 1;
+        self.last_strike = Some(now);
+
+        if self.strikes >= self.max_strikes {
+            RateDecision::Disconnect
+        } else {
+            RateDecision::Limited { retry_after }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_refills_over_time() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(2.0, 1.0, start);
+        assert!(bucket.try_consume(1.0, start));
+        assert!(bucket.try_consume(1.0, start));
+        assert!(!bucket.try_consume(1.0, start));
+        assert!(bucket.try_consume(1.0, start + Duration::from_millis(1_100)));
+    }
+
+    #[test]
+    fn test_classes_have_separate_budgets() {
+        let start = Instant::now();
+        let config = RateLimitConfig {
+            action: (1.0, 0.1),
+            ..Default::default()
+        };
+        let mut limiter = SessionRateLimiter::new(&config, start);
+        let explore = ClientMessage::ActionExplore {
+            cell: shared::grid::GridCell { q: 0, r: 0 },
+            radius: 1,
+        };
+
+        assert_eq!(limiter.check(&explore, start), RateDecision::Allowed);
+        assert!(matches!(
+            limiter.check(&explore, start),
+            RateDecision::Limited { .. }
+        ));
+        // Les autres catégories gardent leur budget
+        assert_eq!(limiter.check(&ClientMessage::Ping, start), RateDecision::Allowed);
+    }
+
+    #[test]
+    fn test_repeat_offender_is_disconnected() {
+        let start = Instant::now();
+        let config = RateLimitConfig {
+            debug: (1.0, 0.01),
+            max_strikes: 3,
+            ..Default::default()
+        };
+        let mut limiter = SessionRateLimiter::new(&config, start);
+        let msg = ClientMessage::DebugRegenerateAllContours;
+
+        assert_eq!(limiter.check(&msg, start), RateDecision::Allowed);
+        assert!(matches!(limiter.check(&msg, start), RateDecision::Limited { .. }));
+        assert!(matches!(limiter.check(&msg, start), RateDecision::Limited { .. }));
+        assert_eq!(limiter.check(&msg, start), RateDecision::Disconnect);
+    }
+}
@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use shared::GameState;
+use shared::grid::GridConfig;
+use shared::protocol::{ErrorCode, QueuedAction, ServerMessage};
+use tokio::sync::mpsc;
+
+use crate::action_processor::{ActionProcessor, IdleUnit};
+use crate::database::client::DatabaseTables;
+use crate::dev::DevConfig;
+use crate::units::NameGenerator;
+use crate::world::resources::WorldGlobalState;
+
+use super::super::Sessions;
+use super::handlers::{action_queue_update, dispatch_client_message};
+
+/// Nombre maximum d'actions en attente par unité
+pub const MAX_QUEUE_LENGTH: usize = 10;
+
+/// Les actions de file ne passent pas par une session : id fictif pour les logs
+const QUEUE_SESSION_ID: u64 = 0;
+
+/// Vrai si `requested` est un réordonnancement de `current` (mêmes entrées, chacune une fois)
+pub fn is_reordering(current: &[u64], requested: &[u64]) -> bool {
+    if current.len() != requested.len() {
+        return false;
+    }
+    let current: HashSet<u64> = current.iter().copied().collect();
+    let mut seen = HashSet::new();
+    requested
+        .iter()
+        .all(|id| current.contains(id) && seen.insert(*id))
+}
+
+/// Lance l'action suivante de la file d'une unité quand elle se libère
+struct ActionQueueRunner {
+    sessions: Sessions,
+    db_tables: Arc<DatabaseTables>,
+    action_processor: Arc<ActionProcessor>,
+    name_generator: Arc<NameGenerator>,
+    game_state: Arc<GameState>,
+    grid_config: Arc<GridConfig>,
+    dev_config: Arc<DevConfig>,
+    world_global_state: Arc<WorldGlobalState>,
+}
+
+impl ActionQueueRunner {
+    /// Dépile la file jusqu'à ce qu'une action démarre. Une action dont les conditions
+    /// ne sont plus remplies (position, inventaire, ligne de production...) est abandonnée.
+    async fn advance(&self, player_id: u64, unit_id: u64) {
+        let busy = self
+            .db_tables
+            .units
+            .get_busy_units(&[unit_id])
+            .await
+            .unwrap_or_default();
+        if !busy.is_empty() {
+            return;
+        }
+
+        let mut changed = false;
+        loop {
+            let entry = match self.db_tables.action_queue.pop_front(unit_id).await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::error!("Failed to advance queue of unit {}: {}", unit_id, e);
+                    break;
+                }
+            };
+            changed = true;
+
+            if let Err(code) = self.check_position(unit_id, &entry.action).await {
+                self.skip(player_id, unit_id, entry.action, code, None)
+                    .await;
+                continue;
+            }
+
+            let (responses, _, _) = dispatch_client_message(
+                entry.action.clone().into_client_message(unit_id),
+                QUEUE_SESSION_ID,
+                Some(player_id),
+                &self.sessions,
+                &self.db_tables,
+                &self.action_processor,
+                &self.name_generator,
+                &self.game_state,
+                &self.grid_config,
+                &self.dev_config,
+                &self.world_global_state,
+            )
+            .await;
+
+            let started = responses
+                .iter()
+                .any(|msg| matches!(msg, ServerMessage::ActionStatusUpdate { .. }));
+            if started {
+                tracing::info!(
+                    "Unit {} started queued {:?}",
+                    unit_id,
+                    entry.action.action_type()
+                );
+                for msg in responses {
+                    self.send(player_id, msg).await;
+                }
+                break;
+            }
+
+            let (code, detail) = responses
+                .into_iter()
+                .find_map(|msg| match msg {
+                    ServerMessage::ActionError { code, detail } => Some((code, detail)),
+                    _ => None,
+                })
+                .unwrap_or((ErrorCode::SchedulingFailed, None));
+            self.skip(player_id, unit_id, entry.action, code, detail)
+                .await;
+        }
+
+        if changed {
+            let update = action_queue_update(&self.db_tables, unit_id).await;
+            self.send(player_id, update).await;
+        }
+    }
+
+    /// Hors déplacement, une action de file a lieu là où se trouve l'unité
+    async fn check_position(&self, unit_id: u64, action: &QueuedAction) -> Result<(), ErrorCode> {
+        if matches!(action, QueuedAction::MoveUnit { .. }) {
+            return Ok(());
+        }
+        let unit = self
+            .db_tables
+            .units
+            .load_unit(unit_id)
+            .await
+            .map_err(|_| ErrorCode::UnitNotFound)?;
+        if unit.current_cell != action.cell() {
+            return Err(ErrorCode::UnitNotAtTarget);
+        }
+        Ok(())
+    }
+
+    async fn skip(
+        &self,
+        player_id: u64,
+        unit_id: u64,
+        action: QueuedAction,
+        code: ErrorCode,
+        detail: Option<String>,
+    ) {
+        tracing::info!(
+            "Skipping queued {:?} of unit {}: {:?}",
+            action.action_type(),
+            unit_id,
+            code
+        );
+        let msg = ServerMessage::ActionQueueSkipped {
+            unit_id,
+            action,
+            code,
+            detail,
+        };
+        self.send(player_id, msg).await;
+    }
+
+    async fn send(&self, player_id: u64, msg: ServerMessage) {
+        if let Err(e) = self.sessions.send_to_player(player_id, msg).await {
+            tracing::debug!("Queue update not delivered to player {}: {}", player_id, e);
+        }
+    }
+}
+
+/// Démarre le gestionnaire des files d'actions, alimenté par les unités que
+/// l'`ActionProcessor` libère
+pub fn start_action_queue_runner(
+    mut idle_units: mpsc::UnboundedReceiver<IdleUnit>,
+    sessions: Sessions,
+    db_tables: Arc<DatabaseTables>,
+    action_processor: Arc<ActionProcessor>,
+    name_generator: Arc<NameGenerator>,
+    game_state: Arc<GameState>,
+    grid_config: Arc<GridConfig>,
+    dev_config: Arc<DevConfig>,
+    world_global_state: Arc<WorldGlobalState>,
+) {
+    let runner = ActionQueueRunner {
+        sessions,
+        db_tables,
+        action_processor,
+        name_generator,
+        game_state,
+        grid_config,
+        dev_config,
+        world_global_state,
+    };
+
+    tokio::spawn(async move {
+        while let Some((player_id, unit_id)) = idle_units.recv().await {
+            runner.advance(player_id, unit_id).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reordering_must_list_every_entry_once() {
+        assert!(is_reordering(&[1, 2, 3], &[3, 1, 2]));
+        assert!(is_reordering(&[], &[]));
+        assert!(!is_reordering(&[1, 2, 3], &[1, 2]));
+        assert!(!is_reordering(&[1, 2, 3], &[1, 2, 2]));
+        assert!(!is_reordering(&[1, 2, 3], &[1, 2, 4]));
+    }
+}
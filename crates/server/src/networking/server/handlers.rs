@@ -1,28 +1,43 @@
 use futures::{SinkExt, StreamExt};
 use shared::grid::{GridCell, GridConfig};
 use shared::{
-    ActionBaseData, ActionContext, ActionData, ActionSpecificTypeEnum, ActionStatusEnum, ActionTypeEnum, BuildBuildingAction, BuildRoadAction, ContourSegmentData, CraftResourceAction, HarvestResourceAction, MoveUnitAction, SendMessageAction, SpecificAction, SpecificActionData, TerrainChunkData, TerrainChunkId, TrainUnitAction, UnitData, constants
+    ActionBaseData, ActionContext, ActionData, ActionSpecificTypeEnum, ActionStatusEnum, ActionTypeEnum, BuildBuildingAction, BuildRoadAction, ClaimCellsAction, CraftResourceAction, EnvoyMission, HarvestResourceAction, MoveUnitAction, ProfessionEnum, SendEnvoyAction, SendMessageAction, SpecificAction, SpecificActionData, TerrainChunkData, TerrainChunkId, TrainUnitAction, UnitData, WorldPosition, constants
 };
+use std::time::Instant;
 use std::{net::SocketAddr, sync::Arc};
 use tokio::net::TcpStream;
 use tokio_tungstenite::{
     accept_async_with_config, tungstenite::Message, tungstenite::protocol::WebSocketConfig,
 };
 
-use crate::action_processor::{ActionInfo, ActionProcessor};
+use crate::action_processor::{ActionInfo, ActionProcessor, now_ms};
+use crate::auth::lockout::{self, LoginOutcome};
 use crate::auth::password;
+use crate::auth::reset_code;
 use crate::database::client::DatabaseTables;
+use crate::database::tables::{ItemHolder, MaterialSource};
 use crate::dev::DevConfig;
-use crate::units::NameGenerator;
+use crate::units::{
+    NameGenerator, UnitCalculator, average_quality, load_unit_with_items, plan_material_draw,
+    skill_adjusted_duration_ms, would_be_overencumbered,
+};
 use crate::world::resources::WorldGlobalState;
 use crate::{utils, world};
-use shared::GameState;
+use shared::{
+    GameState, MembershipRequestKind, OrganizationHierarchy, OrganizationPermission, Routine,
+};
 use shared::protocol::{
-    ClientMessage, ColorData, ConstructionCostNet, GameDataPayload, HarvestYieldNet,
-    ItemDefinitionNet, RecipeIngredientNet, RecipeNet, ServerMessage, TranslationEntry,
+    ClientMessage, ColorData, ConstructionCostNet, ErrorCode, GameDataPayload, HarvestYieldNet,
+    ItemDefinitionNet, PROTOCOL_VERSION, RecipeIngredientNet, RecipeNet, ServerMessage,
+    TranslationEntry,
 };
 
 use super::super::Sessions;
+use super::action_queue;
+use super::super::rate_limit::{
+    MAX_CLIENT_DECOMPRESSED_SIZE, MAX_CLIENT_MESSAGE_SIZE, MessageClass, RateDecision,
+    RateLimitConfig, SessionRateLimiter,
+};
 
 /// Helper function to add action to both DB and cache
 async fn add_action_and_cache(
@@ -35,8 +50,6 @@ async fn add_action_and_cache(
     let action_id = action_table.add_scheduled_action(action_data).await?;
 
     // Add to cache
-    let completion_time =
-        action_data.base_data.start_time + (action_data.base_data.duration_ms / 1000);
     action_processor
         .add_action(ActionInfo {
             action_id,
@@ -47,38 +60,245 @@ async fn add_action_and_cache(
             status: ActionStatusEnum::Pending,
             start_time: action_data.base_data.start_time,
             duration_ms: action_data.base_data.duration_ms,
-            completion_time,
+            completion_time: action_data.base_data.completion_time,
+            paused_remaining_ms: None,
+            in_transition: false,
         })
         .await;
 
     Ok(action_id)
 }
 
-/// Claim une cellule et ses 6 voisins pour une organisation (fallback si pas de Voronoï)
+/// Marque une unité comme occupée par une action (libérée à sa fin)
+async fn assign_unit_to_action(
+    db_tables: &DatabaseTables,
+    unit_id: u64,
+    action_id: u64,
+    responses: &mut Vec<ServerMessage>,
+) {
+    match db_tables
+        .units
+        .set_units_working_on(&[unit_id], action_id)
+        .await
+    {
+        Ok(()) => responses.push(ServerMessage::UnitWorkStatusUpdate {
+            unit_id,
+            working_on_action_id: Some(action_id),
+        }),
+        Err(e) => tracing::error!("Failed to assign unit {} to action {}: {}", unit_id, action_id, e),
+    }
+}
+
+/// Unités dont l'inventaire paie les matériaux d'une action : celles qui y sont
+/// affectées, dans l'ordre donné, ou le lord si aucune unité n'est affectée
+/// (c'est le cas des constructions)
+fn material_payers(lord_unit_id: u64, unit_ids: &[u64]) -> Vec<u64> {
+    if unit_ids.is_empty() {
+        vec![lord_unit_id]
+    } else {
+        unit_ids.to_vec()
+    }
+}
+
+/// Répartit les matériaux (item_id, quantité) d'une action sur les inventaires des
+/// payeurs, puis sur le trésor de l'organisation s'il est utilisable (voir `usable_treasury`).
+/// Renvoie les prélèvements (source, item_id, quantité), ou l'erreur à envoyer au
+/// client si les inventaires réunis ne suffisent pas
+async fn plan_materials(
+    db_tables: &DatabaseTables,
+    game_state: &GameState,
+    payers: &[u64],
+    treasury: Option<u64>,
+    materials: &[(i32, i32)],
+) -> Result<Vec<(MaterialSource, i32, i32)>, ServerMessage> {
+    let mut holdings = Vec::new();
+    for &unit_id in payers {
+        match db_tables.resources.load_inventory_summary(unit_id).await {
+            Ok(inventory) => holdings.push((MaterialSource::Unit(unit_id), inventory)),
+            Err(e) => {
+                tracing::error!("Failed to load inventory: {}", e);
+                return Err(ServerMessage::ActionError {
+                    code: ErrorCode::InventoryUnavailable,
+                    detail: None,
+                });
+            }
+        }
+    }
+    // Le trésor ne paie que ce que les unités n'ont pas
+    if let Some(organization_id) = treasury {
+        match db_tables.organizations.load_treasury_stock(organization_id).await {
+            Ok(stock) => holdings.push((
+                MaterialSource::Treasury(organization_id),
+                stock
+                    .iter()
+                    .map(|item| (item.item_id, item.quantity))
+                    .collect::<std::collections::HashMap<i32, i32>>(),
+            )),
+            Err(e) => tracing::error!("Failed to load treasury of organization {}: {}", organization_id, e),
+        }
+    }
+
+    plan_material_draw(&holdings, materials).map_err(|missing| {
+        let missing: Vec<String> = missing
+            .iter()
+            .map(|(item_id, needed, have)| {
+                format!(
+                    "{} (besoin: {}, possédé: {})",
+                    game_state.item_name(*item_id, 1),
+                    needed,
+                    have
+                )
+            })
+            .collect();
+        ServerMessage::ActionError {
+            code: ErrorCode::MissingResources,
+            detail: Some(missing.join(", ")),
+        }
+    })
+}
+
+/// Retire des inventaires (et du trésor) les matériaux d'une action planifiée et les
+/// enregistre comme réservés, pour pouvoir les rendre si l'action est annulée.
+/// `draws` : (source, item_id, quantité), voir `plan_materials`.
+/// `lord_unit_id` sert à calculer les droits affichés dans le TreasuryData renvoyé.
+async fn reserve_materials(
+    db_tables: &DatabaseTables,
+    action_id: u64,
+    lord_unit_id: u64,
+    draws: &[(MaterialSource, i32, i32)],
+) -> Result<Vec<ServerMessage>, String> {
+    let mut responses = Vec::new();
+    let mut touched_treasuries = Vec::new();
+    for &(source, item_id, quantity) in draws {
+        if quantity <= 0 {
+            continue;
+        }
+        match source {
+            MaterialSource::Unit(unit_id) => {
+                let consumed = db_tables
+                    .resources
+                    .consume_items(unit_id, item_id, quantity)
+                    .await?;
+                let quality = average_quality(
+                    &consumed
+                        .iter()
+                        .map(|(_, quality)| (1, *quality))
+                        .collect::<Vec<_>>(),
+                );
+                db_tables
+                    .actions
+                    .add_reservation(action_id, unit_id, item_id, quantity, quality)
+                    .await?;
+
+                responses.push(inventory_changed(db_tables, unit_id, item_id, -quantity).await);
+            }
+            MaterialSource::Treasury(organization_id) => {
+                let taken = db_tables
+                    .organizations
+                    .take_treasury_items(organization_id, item_id, quantity)
+                    .await?;
+                let instance_ids: Vec<u64> = taken.iter().map(|(id, _)| *id).collect();
+                db_tables.resources.delete_item_instances(&instance_ids).await?;
+                let quality = average_quality(
+                    &taken
+                        .iter()
+                        .map(|(_, quality)| (1, *quality))
+                        .collect::<Vec<_>>(),
+                );
+                db_tables
+                    .actions
+                    .add_treasury_reservation(action_id, organization_id, item_id, quantity, quality)
+                    .await?;
+
+                if !touched_treasuries.contains(&organization_id) {
+                    touched_treasuries.push(organization_id);
+                }
+            }
+        }
+    }
+    for organization_id in touched_treasuries {
+        responses.push(treasury_data(db_tables, organization_id, lord_unit_id).await?);
+    }
+    Ok(responses)
+}
+
+/// Trésor dans lequel une action sur `cell` peut puiser : celui de l'organisation qui
+/// contrôle la cellule, si le lord a le droit d'y retirer des objets
+async fn usable_treasury(db_tables: &DatabaseTables, lord_unit_id: u64, cell: &GridCell) -> Option<u64> {
+    let organization_id = match db_tables.organizations.find_cell_organization(cell).await {
+        Ok(Some(id)) => id,
+        Ok(None) => return None,
+        Err(e) => {
+            tracing::error!("{}", e);
+            return None;
+        }
+    };
+    let allowed = has_organization_permission(
+        db_tables,
+        organization_id,
+        lord_unit_id,
+        OrganizationPermission::WithdrawTreasury,
+    )
+    .await;
+    match allowed {
+        Ok(true) => Some(organization_id),
+        Ok(false) => None,
+        Err(e) => {
+            tracing::error!("{}", e);
+            None
+        }
+    }
+}
+
+/// Le chef a tous les droits sur son organisation, un officier ceux de son rang
+async fn has_organization_permission(
+    db_tables: &DatabaseTables,
+    organization_id: u64,
+    unit_id: u64,
+    permission: OrganizationPermission,
+) -> Result<bool, String> {
+    let organization = db_tables.organizations.load_organization(organization_id).await?;
+    let officers = db_tables.organizations.load_officers(organization_id).await?;
+    Ok(organization.has_permission(unit_id, &officers, permission))
+}
+
+/// Contenu du trésor d'une organisation, tel que le voit le joueur qui contrôle `unit_id`
+async fn treasury_data(
+    db_tables: &DatabaseTables,
+    organization_id: u64,
+    unit_id: u64,
+) -> Result<ServerMessage, String> {
+    let organization = db_tables.organizations.load_organization(organization_id).await?;
+    let officers = db_tables.organizations.load_officers(organization_id).await?;
+    let items = db_tables.organizations.load_treasury_stock(organization_id).await?;
+
+    Ok(ServerMessage::TreasuryData {
+        organization_id,
+        gold: organization.treasury_gold,
+        items,
+        can_withdraw: organization.can_withdraw_from_treasury(unit_id, &officers),
+    })
+}
+
+/// Claim une cellule et ses voisins jusqu'à `radius` pour une organisation
+/// (fallback si pas de Voronoï, et extension du territoire quand l'établissement grandit).
+/// Les cellules déjà prises, par elle ou par une autre organisation, sont ignorées.
 async fn claim_cell_and_neighbors(
     db_tables: &DatabaseTables,
     org_id: u64,
     center: &shared::grid::GridCell,
+    radius: u32,
     claimed_cells: &mut Vec<shared::grid::GridCell>,
 ) {
-    // Cellule centrale
-    if db_tables
-        .organizations
-        .add_territory_cell(org_id, center)
-        .await
-        .is_ok()
-    {
-        claimed_cells.push(*center);
-    }
+    for hex in center.to_hex().range(radius) {
+        let cell = shared::grid::GridCell::from_hex(&hex);
 
-    // 6 voisins directs
-    for neighbor in center.neighbors() {
-        // Vérifier que le voisin n'est pas déjà pris
+        // Vérifier que la cellule n'est pas déjà prise
         let already_taken = sqlx::query_scalar::<_, i64>(
             "SELECT organization_id FROM organizations.territory_cells WHERE cell_q = $1 AND cell_r = $2"
         )
-        .bind(neighbor.q)
-        .bind(neighbor.r)
+        .bind(cell.q)
+        .bind(cell.r)
         .fetch_optional(&db_tables.pool)
         .await
         .ok()
@@ -88,16 +308,70 @@ async fn claim_cell_and_neighbors(
         if !already_taken {
             if db_tables
                 .organizations
-                .add_territory_cell(org_id, &neighbor)
+                .add_territory_cell(org_id, &cell)
                 .await
                 .is_ok()
             {
-                claimed_cells.push(neighbor);
+                claimed_cells.push(cell);
             }
         }
     }
 }
 
+/// Recalcule les contours d'une organisation à partir de son territoire, les stocke
+/// et prépare un TerritoryContourUpdate par chunk traversé
+async fn territory_contour_messages(
+    db_tables: &DatabaseTables,
+    grid_config: &GridConfig,
+    org_id: u64,
+) -> Vec<ServerMessage> {
+    let mut contour_messages = Vec::new();
+
+    let territory_cells = match db_tables.organizations.load_territory_cells(org_id).await {
+        Ok(cells) if !cells.is_empty() => cells,
+        _ => return contour_messages,
+    };
+
+    use hexx::Hex;
+    let territory_hex: std::collections::HashSet<Hex> =
+        territory_cells.iter().map(|c| c.to_hex()).collect();
+
+    let contour_points = &world::territory::build_contour(
+        &grid_config.layout,
+        &territory_hex,
+        0.0,
+        org_id,
+    );
+
+    let contour_chunks = utils::chunks::split_contour_into_chunks(contour_points);
+
+    tracing::info!(
+        "Generated {} contour chunks for organization {}",
+        contour_chunks.len(),
+        org_id
+    );
+
+    // Stocker les contours en DB
+    for (chunk_id, contour_segments) in &contour_chunks {
+        let _ = db_tables
+            .territory_contours
+            .store_contour(org_id, chunk_id.x, chunk_id.y, contour_segments)
+            .await;
+    }
+
+    // Préparer les messages TerritoryContourUpdate à envoyer au client
+    // Regrouper par chunk_id
+    for (chunk_id, contour_segments) in &contour_chunks {
+        contour_messages.push(world::territory::contour_update_message(
+            org_id,
+            *chunk_id,
+            contour_segments,
+        ));
+    }
+
+    contour_messages
+}
+
 /// Build the GameDataPayload from the cached GameState
 fn build_game_data_payload(game_state: &GameState, dev_config: &DevConfig) -> GameDataPayload {
     let items = game_state
@@ -199,12 +473,15 @@ async fn player_controls_unit(db_tables: &DatabaseTables, player_id: u64, unit_i
     }
 
     // 2. Organization membership — unit belongs to an org led by the player's lord
+    //    (the lords of other players who joined the org stay under their own control)
     let result = sqlx::query(
         r#"
         SELECT 1 FROM organizations.members om
         JOIN organizations.organizations o ON o.id = om.organization_id
         JOIN units.units lord ON lord.id = o.leader_unit_id
+        JOIN units.units member ON member.id = om.unit_id
         WHERE om.unit_id = $1 AND lord.player_id = $2 AND lord.is_lord = true
+          AND member.player_id IS NULL
         LIMIT 1
         "#,
     )
@@ -216,6 +493,452 @@ async fn player_controls_unit(db_tables: &DatabaseTables, player_id: u64, unit_i
     matches!(result, Ok(Some(_)))
 }
 
+/// Check that every unit in `unit_ids` is controlled by the player.
+async fn player_controls_units(db_tables: &DatabaseTables, player_id: u64, unit_ids: &[u64]) -> bool {
+    for &unit_id in unit_ids {
+        if !player_controls_unit(db_tables, player_id, unit_id).await {
+            return false;
+        }
+    }
+    true
+}
+
+/// IP du client d'une session, pour les logs et l'historique des connexions
+async fn client_ip(sessions: &Sessions, session_id: u64) -> String {
+    sessions
+        .get_addr(session_id)
+        .await
+        .map(|addr| addr.ip().to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// Enregistre une tentative de connexion dans `game.login_attempts`
+async fn audit_login(
+    db_tables: &DatabaseTables,
+    player_id: Option<i64>,
+    family_name: &str,
+    ip_address: &str,
+    outcome: LoginOutcome,
+) {
+    if let Err(e) = db_tables
+        .login_attempts
+        .record_attempt(player_id, family_name, ip_address, outcome)
+        .await
+    {
+        tracing::error!("{}", e);
+    }
+}
+
+/// État courant de la file d'actions d'une unité
+pub(super) async fn action_queue_update(db_tables: &DatabaseTables, unit_id: u64) -> ServerMessage {
+    let entries = db_tables
+        .action_queue
+        .load_queue(unit_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("{}", e);
+            vec![]
+        });
+    ServerMessage::ActionQueueUpdate { unit_id, entries }
+}
+
+/// Routines configurées pour une unité
+async fn unit_routines(db_tables: &DatabaseTables, unit_id: u64) -> ServerMessage {
+    let routines = db_tables
+        .units
+        .load_automated_actions(unit_id)
+        .await
+        .unwrap_or_else(|e| {
+            tracing::error!("{}", e);
+            vec![]
+        });
+    ServerMessage::UnitRoutines { unit_id, routines }
+}
+
+/// Recalcule et sauvegarde les stats dérivées après un changement d'équipement
+async fn equipment_changed(db_tables: &DatabaseTables, unit_id: u64) -> Result<ServerMessage, String> {
+    let (unit, lookup) = load_unit_with_items(db_tables, unit_id).await?;
+    let derived_stats = UnitCalculator::recalculate_derived_stats(&unit, &lookup);
+    db_tables
+        .units
+        .update_derived_stats(unit_id, &derived_stats)
+        .await?;
+
+    Ok(ServerMessage::EquipmentChanged {
+        unit_id,
+        equipment: unit.equipment,
+        derived_stats,
+    })
+}
+
+/// Réponse d'erreur d'une commande de gameplay
+fn action_error(
+    code: ErrorCode,
+    detail: Option<String>,
+) -> (Vec<ServerMessage>, Vec<TerrainChunkId>, Option<String>) {
+    (vec![ServerMessage::ActionError { code, detail }], vec![], None)
+}
+
+/// Nombre d'exemplaires d'un objet qu'une unité peut céder : ceux qu'elle porte,
+/// moins ceux qu'elle a équipés
+async fn movable_item_count(db_tables: &DatabaseTables, unit_id: u64, item_id: i32) -> Result<i32, String> {
+    let owned = db_tables.resources.count_item_for_unit(unit_id, item_id).await?;
+    let equipped = db_tables
+        .units
+        .load_equipment(unit_id)
+        .await?
+        .iter()
+        .filter(|equipped| equipped.item_id == item_id)
+        .count() as i32;
+    Ok((owned - equipped).max(0))
+}
+
+/// Mise à jour d'inventaire d'une unité après un échange ou un dépôt d'objets
+async fn inventory_changed(
+    db_tables: &DatabaseTables,
+    unit_id: u64,
+    item_id: i32,
+    quantity_delta: i32,
+) -> ServerMessage {
+    let new_total = db_tables
+        .resources
+        .count_item_for_unit(unit_id, item_id)
+        .await
+        .unwrap_or(0);
+    ServerMessage::InventoryUpdate {
+        unit_id,
+        item_id,
+        quantity_delta,
+        new_total,
+    }
+}
+
+/// Envoie les objets au sol d'une cellule aux joueurs qui voient son chunk
+async fn broadcast_ground_items(
+    db_tables: &DatabaseTables,
+    sessions: &Sessions,
+    cell: &GridCell,
+    chunk_id: &TerrainChunkId,
+) {
+    match db_tables.resources.load_cell_ground_items(cell).await {
+        Ok(items) => {
+            let msg = ServerMessage::GroundItemsUpdate {
+                chunk_id: *chunk_id,
+                cell: *cell,
+                items,
+            };
+            broadcast_message(sessions, chunk_id, msg).await;
+        }
+        Err(e) => tracing::error!("Failed to load ground items of cell ({},{}): {}", cell.q, cell.r, e),
+    }
+}
+
+/// Réponse d'erreur d'une montée de rang de l'établissement
+fn upgrade_error(
+    code: ErrorCode,
+    detail: Option<String>,
+) -> (Vec<ServerMessage>, Vec<TerrainChunkId>, Option<String>) {
+    (vec![ServerMessage::OrganizationUpgradeError { code, detail }], vec![], None)
+}
+
+fn vassalage_error(
+    code: ErrorCode,
+    detail: Option<String>,
+) -> (Vec<ServerMessage>, Vec<TerrainChunkId>, Option<String>) {
+    (vec![ServerMessage::VassalageError { code, detail }], vec![], None)
+}
+
+/// Organisation dont le seigneur du joueur est le chef
+async fn led_organization(
+    db_tables: &DatabaseTables,
+    player_id: u64,
+) -> Result<shared::OrganizationData, ErrorCode> {
+    let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+        return Err(ErrorCode::LordNotFound);
+    };
+    let organization = match db_tables.organizations.find_unit_organization(lord.id).await {
+        Ok(Some(id)) => db_tables.organizations.load_organization(id).await,
+        Ok(None) => return Err(ErrorCode::NoOrganization),
+        Err(e) => Err(e),
+    };
+    match organization {
+        Ok(organization) if organization.leader_unit_id == Some(lord.id) => Ok(organization),
+        Ok(_) => Err(ErrorCode::InsufficientAuthority),
+        Err(e) => {
+            tracing::error!("{}", e);
+            Err(ErrorCode::DatabaseError)
+        }
+    }
+}
+
+fn membership_error(
+    code: ErrorCode,
+    detail: Option<String>,
+) -> (Vec<ServerMessage>, Vec<TerrainChunkId>, Option<String>) {
+    (vec![ServerMessage::MembershipError { code, detail }], vec![], None)
+}
+
+/// Organisation du seigneur du joueur, si son rang lui y donne `permission`.
+/// Renvoie aussi l'id du seigneur.
+async fn authorized_organization(
+    db_tables: &DatabaseTables,
+    player_id: u64,
+    permission: OrganizationPermission,
+) -> Result<(shared::OrganizationData, u64), ErrorCode> {
+    let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+        return Err(ErrorCode::LordNotFound);
+    };
+    let allowed = async {
+        let Some(id) = db_tables.organizations.find_unit_organization(lord.id).await? else {
+            return Ok(None);
+        };
+        let organization = db_tables.organizations.load_organization(id).await?;
+        let officers = db_tables.organizations.load_officers(id).await?;
+        let allowed = organization.has_permission(lord.id, &officers, permission);
+        Ok::<_, String>(Some((organization, allowed)))
+    }
+    .await;
+    match allowed {
+        Ok(Some((organization, true))) => Ok((organization, lord.id)),
+        Ok(Some((_, false))) => Err(ErrorCode::InsufficientAuthority),
+        Ok(None) => Err(ErrorCode::NoOrganization),
+        Err(e) => {
+            tracing::error!("{}", e);
+            Err(ErrorCode::DatabaseError)
+        }
+    }
+}
+
+/// Organisation (éventuelle) d'un seigneur, telle qu'envoyée à son joueur
+async fn player_organization_data(db_tables: &DatabaseTables, lord_id: u64) -> ServerMessage {
+    let organization = match db_tables.organizations.find_unit_organization(lord_id).await {
+        Ok(Some(id)) => db_tables.organizations.load_organization(id).await.map(Some),
+        Ok(None) => Ok(None),
+        Err(e) => Err(e),
+    };
+    let organization = organization.unwrap_or_else(|e| {
+        tracing::error!("{}", e);
+        None
+    });
+    ServerMessage::PlayerOrganizationData {
+        organization: organization.map(|organization| organization.summary()),
+    }
+}
+
+/// Membres, charges et demandes en attente tels que les voit le seigneur `lord_id`
+async fn members_data(db_tables: &DatabaseTables, lord_id: u64) -> Result<ServerMessage, String> {
+    let organization_id = db_tables.organizations.find_unit_organization(lord_id).await?;
+    let (members, permissions) = match organization_id {
+        Some(id) => {
+            let organization = db_tables.organizations.load_organization(id).await?;
+            let officers = db_tables.organizations.load_officers(id).await?;
+            let permissions = OrganizationPermission::ALL
+                .into_iter()
+                .filter(|permission| organization.has_permission(lord_id, &officers, *permission))
+                .collect();
+            (db_tables.organizations.load_member_summaries(id).await?, permissions)
+        }
+        None => (vec![], vec![]),
+    };
+    let can_manage = permissions.contains(&OrganizationPermission::ManageMembers);
+    // Les demandes de l'organisation ne regardent que ceux qui gèrent les membres
+    let requests = db_tables
+        .organizations
+        .load_membership_requests(organization_id, lord_id)
+        .await?
+        .into_iter()
+        .filter(|request| request.unit_id == lord_id || can_manage)
+        .collect();
+
+    Ok(ServerMessage::OrganizationMembersData {
+        organization_id,
+        members,
+        requests,
+        permissions,
+    })
+}
+
+/// Envoie un message à tous les joueurs qui ont une unité dans l'organisation
+async fn notify_organization_members(
+    sessions: &Sessions,
+    db_tables: &DatabaseTables,
+    organization_id: u64,
+    msg: ServerMessage,
+) {
+    let players = match db_tables.organizations.load_member_players(organization_id).await {
+        Ok(players) => players,
+        Err(e) => {
+            tracing::warn!("{}", e);
+            return;
+        }
+    };
+    for player_id in players {
+        if let Err(e) = sessions.send_to_player(player_id, msg.clone()).await {
+            tracing::debug!("Membership update not delivered to player {}: {}", player_id, e);
+        }
+    }
+}
+
+/// Envoie un message au joueur d'un seigneur, s'il est connecté
+async fn notify_lord_player(sessions: &Sessions, db_tables: &DatabaseTables, lord_id: u64, msg: ServerMessage) {
+    if let Ok(Some(player_id)) = db_tables.units.load_controlling_player(lord_id).await
+        && let Err(e) = sessions.send_to_player(player_id, msg).await
+    {
+        tracing::debug!("Membership update not delivered to player {}: {}", player_id, e);
+    }
+}
+
+/// Vérifie qu'un lien de vassalité peut se nouer : rangs compatibles, vassal libre
+/// et pas de boucle (le suzerain ne doit pas déjà dépendre du vassal)
+async fn check_vassalage(
+    db_tables: &DatabaseTables,
+    liege: &shared::OrganizationData,
+    vassal: &shared::OrganizationData,
+) -> Result<(), ErrorCode> {
+    if vassal.parent_organization_id.is_some() {
+        return Err(ErrorCode::AlreadyVassal);
+    }
+    if !vassal.can_swear_fealty_to(liege) {
+        return Err(ErrorCode::InvalidVassalage);
+    }
+    match db_tables.organizations.load_liege_chain(liege.id).await {
+        Ok(chain) if chain.contains(&vassal.id) => Err(ErrorCode::InvalidVassalage),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            tracing::error!("{}", e);
+            Err(ErrorCode::DatabaseError)
+        }
+    }
+}
+
+/// Envoie un message au joueur qui contrôle le chef d'une organisation, s'il est connecté
+async fn notify_organization_leader(
+    sessions: &Sessions,
+    db_tables: &DatabaseTables,
+    organization_id: u64,
+    msg: ServerMessage,
+) {
+    let leader = match db_tables.organizations.load_organization(organization_id).await {
+        Ok(organization) => organization.leader_unit_id,
+        Err(e) => {
+            tracing::warn!("{}", e);
+            return;
+        }
+    };
+    let Some(leader) = leader else {
+        return;
+    };
+    if let Ok(Some(player_id)) = db_tables.units.load_controlling_player(leader).await
+        && let Err(e) = sessions.send_to_player(player_id, msg).await
+    {
+        tracing::debug!("Vassalage update not delivered to player {}: {}", player_id, e);
+    }
+}
+
+/// Réponse renvoyée quand une session non authentifiée envoie un message de jeu
+fn not_authenticated() -> (Vec<ServerMessage>, Vec<TerrainChunkId>, Option<String>) {
+    (vec![ServerMessage::NotAuthenticated], vec![], None)
+}
+
+fn account_error(
+    code: ErrorCode,
+    detail: Option<String>,
+) -> (Vec<ServerMessage>, Vec<TerrainChunkId>, Option<String>) {
+    (vec![ServerMessage::AccountError { code, detail }], vec![], None)
+}
+
+/// Refuse une vérification de mot de passe ou de code de réinitialisation quand
+/// l'IP ou le compte a accumulé trop d'échecs, comme pour un login
+async fn credential_attempt_blocked(
+    db_tables: &DatabaseTables,
+    player_id: Option<i64>,
+    family_name: &str,
+    ip_address: &str,
+) -> Option<ErrorCode> {
+    match db_tables
+        .login_attempts
+        .recent_failures_from_ip(ip_address, lockout::IP_WINDOW)
+        .await
+    {
+        Ok(failures) if failures >= lockout::IP_FREE_ATTEMPTS => {
+            return Some(ErrorCode::TooManyAttempts);
+        }
+        Ok(_) => {}
+        Err(e) => tracing::error!("{}", e),
+    }
+
+    let player_id = player_id?;
+    match db_tables.login_attempts.account_lock_remaining(player_id).await {
+        Ok(Some(_)) => {
+            audit_login(db_tables, Some(player_id), family_name, ip_address, LoginOutcome::Locked).await;
+            Some(ErrorCode::AccountLocked)
+        }
+        Ok(None) => None,
+        Err(e) => {
+            tracing::error!("{}", e);
+            None
+        }
+    }
+}
+
+/// Un mot de passe ou un code faux compte pour le verrouillage du compte et de l'IP
+async fn register_credential_failure(
+    db_tables: &DatabaseTables,
+    player_id: Option<i64>,
+    family_name: &str,
+    ip_address: &str,
+) {
+    let Some(player_id) = player_id else {
+        audit_login(db_tables, None, family_name, ip_address, LoginOutcome::UnknownAccount).await;
+        return;
+    };
+    if let Err(e) = db_tables.login_attempts.register_failure(player_id).await {
+        tracing::error!("{}", e);
+    }
+    audit_login(db_tables, Some(player_id), family_name, ip_address, LoginOutcome::InvalidPassword).await;
+}
+
+/// Vérifie le mot de passe actuel d'un joueur connecté (changement de mot de passe, suppression).
+/// Les échecs comptent pour le verrouillage, comme ceux d'un login.
+async fn check_account_password(
+    db_tables: &DatabaseTables,
+    player_id: u64,
+    password: &str,
+    ip_address: &str,
+) -> Result<(), ErrorCode> {
+    let player = shared::types::game::methods::get_player_by_id(&db_tables.pool, player_id as i64)
+        .await
+        .map_err(|e| {
+            tracing::error!("Failed to load player {}: {}", player_id, e);
+            ErrorCode::DatabaseError
+        })?
+        .ok_or(ErrorCode::PlayerNotFound)?;
+
+    if let Some(code) =
+        credential_attempt_blocked(db_tables, Some(player.id), &player.family_name, ip_address).await
+    {
+        return Err(code);
+    }
+
+    let password_hash = player
+        .password_hash
+        .as_deref()
+        .ok_or(ErrorCode::PasswordMigrationRequired)?;
+
+    match password::verify_password(password, password_hash) {
+        Ok(true) => Ok(()),
+        Ok(false) => {
+            register_credential_failure(db_tables, Some(player.id), &player.family_name, ip_address)
+                .await;
+            Err(ErrorCode::InvalidCredentials)
+        }
+        Err(e) => {
+            tracing::error!("Password verification error for player {}: {}", player_id, e);
+            Err(ErrorCode::InternalError)
+        }
+    }
+}
+
 async fn ensure_spawn_explored(lord: Option<UnitData>, db_tables: &DatabaseTables, player_id: i64) {
     if let Some(ref lord) = lord {
         let mut spawn_chunks = Vec::new();
@@ -245,9 +968,10 @@ pub async fn handle_connection(
 ) {
     tracing::info!("New connection from {}", addr);
 
+    // Limites côté lecture uniquement : les réponses serveur (terrain) ne sont pas concernées
     let mut ws_config = WebSocketConfig::default();
-    ws_config.max_message_size = Some(64 * 1024 * 1024); // 64 MB
-    ws_config.max_frame_size = Some(64 * 1024 * 1024);
+    ws_config.max_message_size = Some(MAX_CLIENT_MESSAGE_SIZE);
+    ws_config.max_frame_size = Some(MAX_CLIENT_MESSAGE_SIZE);
 
     let ws_stream = match accept_async_with_config(stream, Some(ws_config)).await {
         Ok(ws) => ws,
@@ -265,6 +989,8 @@ pub async fn handle_connection(
     let tx_for_bg = tx.clone();
     sessions.insert(session_id, addr, tx).await;
 
+    let mut rate_limiter = SessionRateLimiter::new(&RateLimitConfig::default(), Instant::now());
+
     let generation_semaphore = Arc::new(tokio::sync::Semaphore::new(1));
     let active_prefetch = Arc::new(std::sync::Mutex::new(std::collections::HashSet::<TerrainChunkId>::new()));
 
@@ -276,7 +1002,7 @@ pub async fn handle_connection(
                 match msg {
                     Some(Ok(Message::Binary(data))) => {
                         tracing::info!("Received message from {}: {} bytes", addr, data.len());
-                        let decompressed = match shared::protocol::compression::decompress(&data[..]) {
+                        let decompressed = match shared::protocol::compression::decompress_with_limit(&data[..], MAX_CLIENT_DECOMPRESSED_SIZE) {
                             Ok(d) => d,
                             Err(e) => {
                                 tracing::warn!("Decompression failed from {}: {}", addr, e);
@@ -286,9 +1012,39 @@ pub async fn handle_connection(
                         match bincode::decode_from_slice(&decompressed[..], bincode::config::standard()) {
                             Ok((client_msg, _)) => {
                                 tracing::debug!("Received: {:?}", client_msg);
+
+                                match rate_limiter.check(&client_msg, Instant::now()) {
+                                    RateDecision::Allowed => {}
+                                    RateDecision::Limited { retry_after } => {
+                                        tracing::warn!(
+                                            "Session {} ({}) rate limited on {:?}",
+                                            session_id,
+                                            addr,
+                                            MessageClass::of(&client_msg)
+                                        );
+                                        let _ = tx_for_bg.send(ServerMessage::RateLimited {
+                                            retry_after_ms: retry_after.as_millis() as u64,
+                                        });
+                                        continue;
+                                    }
+                                    RateDecision::Disconnect => {
+                                        tracing::warn!(
+                                            "Session {} ({}) keeps exceeding its rate limits, disconnecting",
+                                            session_id,
+                                            addr
+                                        );
+                                        let _ = write.send(Message::Close(None)).await;
+                                        break;
+                                    }
+                                }
                                 let (responses, missing_chunks, terrain_name_opt) =
                                     handle_client_message(client_msg, session_id, &sessions, &db_tables, &action_processor, &name_generator, &game_state, &grid_config, &dev_config, &world_global_state).await;
 
+                                // Un Hello refusé termine la connexion une fois la réponse envoyée
+                                let handshake_rejected = responses
+                                    .iter()
+                                    .any(|r| matches!(r, ServerMessage::HelloRejected { .. }));
+
                                 // Send DB-cached responses immediately
                                 for response in responses {
                                     let raw = bincode::encode_to_vec(&response, bincode::config::standard())
@@ -300,6 +1056,11 @@ pub async fn handle_connection(
                                     }
                                 }
 
+                                if handshake_rejected {
+                                    let _ = write.send(Message::Close(None)).await;
+                                    break;
+                                }
+
                                 // Generate missing chunks and send via async channel
                                 if !missing_chunks.is_empty() {
                                     spawn_generate_and_send(
@@ -330,11 +1091,25 @@ pub async fn handle_connection(
             // Messages asynchrones depuis action_processor
             Some(async_message) = rx.recv() => {
                 // Log le type de message sans afficher les données complètes (évite de logger des MB de SDF)
-                let message_type = match &async_message {
+                let logged_message = match &async_message {
+                    ServerMessage::Sequenced { message, .. } => message.as_ref(),
+                    other => other,
+                };
+                let message_type = match logged_message {
+                    ServerMessage::HelloAck { .. } => "HelloAck",
+                    ServerMessage::HelloRejected { .. } => "HelloRejected",
+                    ServerMessage::Sequenced { .. } => "Sequenced",
                     ServerMessage::LoginSuccess { .. } => "LoginSuccess",
+                    ServerMessage::SessionResumed { .. } => "SessionResumed",
+                    ServerMessage::ResumeFailed { .. } => "ResumeFailed",
                     ServerMessage::LoginError { .. } => "LoginError",
+                    ServerMessage::NotAuthenticated => "NotAuthenticated",
+                    ServerMessage::RateLimited { .. } => "RateLimited",
                     ServerMessage::RegisterSuccess{ .. } => "RegisterSuccess",
                     ServerMessage::RegisterError{ .. } => "RegisterError",
+                    ServerMessage::PasswordChanged => "PasswordChanged",
+                    ServerMessage::AccountDeleted => "AccountDeleted",
+                    ServerMessage::AccountError { .. } => "AccountError",
                     ServerMessage::LordData { .. } => "LordData",
                     ServerMessage::LordCreated { .. } => "LordCreated",
                     ServerMessage::LordCreateError { .. } => "LordCreateError",
@@ -367,12 +1142,39 @@ pub async fn handle_connection(
                     ServerMessage::DebugUnitSpawned { .. } => "DebugUnitSpawned",
                     ServerMessage::OrganizationAtCell { .. } => "OrganizationAtCell",
                     ServerMessage::DebugError { .. } => "DebugError",
+                    ServerMessage::ActionQueueUpdate { .. } => "ActionQueueUpdate",
+                    ServerMessage::ActionQueueSkipped { .. } => "ActionQueueSkipped",
+                    ServerMessage::UnitRoutines { .. } => "UnitRoutines",
+                    ServerMessage::EquipmentChanged { .. } => "EquipmentChanged",
+                    ServerMessage::GroundItemsUpdate { .. } => "GroundItemsUpdate",
+                    ServerMessage::CellResourcesUpdate { .. } => "CellResourcesUpdate",
+                    ServerMessage::BuildingRemoved { .. } => "BuildingRemoved",
+                    ServerMessage::SkillLevelUp { .. } => "SkillLevelUp",
+                    ServerMessage::TreasuryData { .. } => "TreasuryData",
                     ServerMessage::UnitPositionUpdated { .. } => "UnitPositionUpdated",
                     ServerMessage::UnitSlotUpdated { .. } => "UnitSlotUpdated",
                     ServerMessage::UnitProfessionChanged { .. } => "UnitProfessionChanged",
                     ServerMessage::UnitWorkStatusUpdate { .. } => "UnitWorkStatusUpdate",
                     ServerMessage::HamletFounded { .. } => "HamletFounded",
                     ServerMessage::HamletFoundError { .. } => "HamletFoundError",
+                    ServerMessage::OrganizationUpgraded { .. } => "OrganizationUpgraded",
+                    ServerMessage::OrganizationUpgradeError { .. } => "OrganizationUpgradeError",
+                    ServerMessage::VassalageOffered { .. } => "VassalageOffered",
+                    ServerMessage::VassalageOfferClosed { .. } => "VassalageOfferClosed",
+                    ServerMessage::VassalageChanged { .. } => "VassalageChanged",
+                    ServerMessage::VassalageError { .. } => "VassalageError",
+                    ServerMessage::OrganizationHierarchyData { .. } => "OrganizationHierarchyData",
+                    ServerMessage::TributePaid { .. } => "TributePaid",
+                    ServerMessage::DiplomacyData { .. } => "DiplomacyData",
+                    ServerMessage::DiplomaticProposalReceived { .. } => "DiplomaticProposalReceived",
+                    ServerMessage::DiplomaticProposalRejected { .. } => "DiplomaticProposalRejected",
+                    ServerMessage::DiplomaticRelationChanged { .. } => "DiplomaticRelationChanged",
+                    ServerMessage::OrganizationMembersData { .. } => "OrganizationMembersData",
+                    ServerMessage::MembershipRequestReceived { .. } => "MembershipRequestReceived",
+                    ServerMessage::MembershipRequestClosed { .. } => "MembershipRequestClosed",
+                    ServerMessage::MembershipChanged { .. } => "MembershipChanged",
+                    ServerMessage::OfficerChanged { .. } => "OfficerChanged",
+                    ServerMessage::MembershipError { .. } => "MembershipError",
                     ServerMessage::PlayerOrganizationData { .. } => "PlayerOrganizationData",
                     ServerMessage::PopulationChanged { .. } => "PouplationChanged",
                     ServerMessage::InventoryData { .. } => "InventoryData",
@@ -383,7 +1185,7 @@ pub async fn handle_connection(
                     ServerMessage::Pong => "Pong",
                 };
 
-                if !matches!(async_message, ServerMessage::RoadChunkSdfUpdate { .. }) {
+                if !matches!(logged_message, ServerMessage::RoadChunkSdfUpdate { .. }) {
                     tracing::debug!("Sending async {} to session {}", message_type, session_id);
                 }
 
@@ -413,20 +1215,173 @@ async fn handle_client_message(
     dev_config: &DevConfig,
     world_global_state: &WorldGlobalState,
 ) -> (Vec<ServerMessage>, Vec<TerrainChunkId>, Option<String>) {
-    match msg {
-        ClientMessage::Login { username } => {
-            tracing::info!(
-                "Session {} attempting to log in as {}",
-                session_id,
-                username
-            );
+    // Le joueur qui agit est toujours celui de la session, jamais celui annoncé par le client
+    let session_player_id = sessions.get_player_id(session_id).await;
+    if session_player_id.is_none() && msg.requires_authentication() {
+        tracing::warn!(
+            "Session {} sent a gameplay message before authenticating, rejecting",
+            session_id
+        );
+        return not_authenticated();
+    }
 
-            // Try to get or create the player in the database
-            match shared::types::game::methods::get_or_create_player(
-                &db_tables.pool,
-                &username,
-                1,                  // Default language_id (could be configurable)
-                "default_location", // Default origin location
+    // Pas de login tant que le client n'a pas annoncé une version compatible
+    if matches!(
+        msg,
+        ClientMessage::Login { .. }
+            | ClientMessage::LoginWithPassword { .. }
+            | ClientMessage::RegisterAccount { .. }
+            | ClientMessage::ResumeSession { .. }
+            | ClientMessage::ResetPasswordWithCode { .. }
+    ) && !sessions.has_completed_handshake(session_id).await
+    {
+        tracing::warn!("Session {} tried to log in before Hello, rejecting", session_id);
+        let response = match msg {
+            ClientMessage::RegisterAccount { .. } => ServerMessage::RegisterError {
+                reason: ErrorCode::HandshakeRequired.message().to_string(),
+            },
+            ClientMessage::ResumeSession { .. } => ServerMessage::ResumeFailed {
+                code: ErrorCode::HandshakeRequired,
+            },
+            ClientMessage::ResetPasswordWithCode { .. } => ServerMessage::AccountError {
+                code: ErrorCode::HandshakeRequired,
+                detail: None,
+            },
+            _ => ServerMessage::LoginError {
+                code: ErrorCode::HandshakeRequired,
+                detail: None,
+            },
+        };
+        return (vec![response], vec![], None);
+    }
+
+    dispatch_client_message(
+        msg,
+        session_id,
+        session_player_id,
+        sessions,
+        db_tables,
+        action_processor,
+        name_generator,
+        game_state,
+        grid_config,
+        dev_config,
+        world_global_state,
+    )
+    .await
+}
+
+/// Traite un message pour le joueur `session_player_id`, une fois la session vérifiée.
+/// Aussi appelé par les files d'actions (`action_queue`), sans session réelle.
+pub(super) async fn dispatch_client_message(
+    msg: ClientMessage,
+    session_id: u64,
+    session_player_id: Option<u64>,
+    sessions: &Sessions,
+    db_tables: &DatabaseTables,
+    action_processor: &ActionProcessor,
+    name_generator: &NameGenerator,
+    game_state: &GameState,
+    grid_config: &GridConfig,
+    dev_config: &DevConfig,
+    world_global_state: &WorldGlobalState,
+) -> (Vec<ServerMessage>, Vec<TerrainChunkId>, Option<String>) {
+    match msg {
+        ClientMessage::Hello {
+            protocol_version,
+            client_build,
+        } => {
+            if protocol_version != PROTOCOL_VERSION {
+                tracing::warn!(
+                    "Session {} uses protocol v{} (client build {}), server expects v{}",
+                    session_id,
+                    protocol_version,
+                    client_build,
+                    PROTOCOL_VERSION
+                );
+                return (
+                    vec![ServerMessage::HelloRejected {
+                        code: ErrorCode::IncompatibleProtocol,
+                        server_protocol_version: PROTOCOL_VERSION,
+                    }],
+                    vec![],
+                    None,
+                );
+            }
+
+            tracing::info!(
+                "Session {} handshake OK (protocol v{}, client build {})",
+                session_id,
+                protocol_version,
+                client_build
+            );
+            sessions.complete_handshake(session_id, protocol_version).await;
+            (
+                vec![ServerMessage::HelloAck {
+                    protocol_version: PROTOCOL_VERSION,
+                    server_build: env!("CARGO_PKG_VERSION").to_string(),
+                }],
+                vec![],
+                None,
+            )
+        }
+
+        ClientMessage::ResumeSession {
+            token,
+            last_seen_seq,
+        } => match sessions
+            .resume_session(session_id, &token, last_seen_seq)
+            .await
+        {
+            Ok((player_id, replayed)) => (
+                vec![ServerMessage::SessionResumed {
+                    resume_token: sessions.issue_resume_token(player_id),
+                    replayed,
+                }],
+                vec![],
+                None,
+            ),
+            Err(code) => {
+                tracing::info!(
+                    "Session {} could not resume ({:?}), client must log in again",
+                    session_id,
+                    code
+                );
+                (vec![ServerMessage::ResumeFailed { code }], vec![], None)
+            }
+        },
+
+        ClientMessage::Login { username } => {
+            // Connexion sans mot de passe : réservée au développement
+            if !dev_config.dev_mode {
+                tracing::warn!(
+                    "Session {} ({}) tried legacy passwordless login as {}, refused",
+                    session_id,
+                    client_ip(sessions, session_id).await,
+                    username
+                );
+                return (
+                    vec![ServerMessage::LoginError {
+                        code: ErrorCode::LegacyLoginDisabled,
+                        detail: None,
+                    }],
+                    vec![],
+                    None,
+                );
+            }
+
+            tracing::info!(
+                "Session {} attempting to log in as {}",
+                session_id,
+                username
+            );
+
+            // Try to get or create the player in the database
+            match shared::types::game::methods::get_or_create_player(
+                &db_tables.pool,
+                &username,
+                1,                  // Default language_id (could be configurable)
+                "default_location", // Default origin location
                 None,               // No motto by default
             )
             .await
@@ -477,6 +1432,7 @@ async fn handle_client_message(
                     let login_response = vec![ServerMessage::LoginSuccess {
                         player: player_data,
                         character: character_data,
+                        resume_token: sessions.issue_resume_token(player.id as u64),
                     }];
 
                     // Puis charger et envoyer le lord
@@ -511,7 +1467,8 @@ async fn handle_client_message(
                     tracing::error!("Failed to get/create player {}: {}", username, e);
                     (
                         vec![ServerMessage::LoginError {
-                            reason: format!("Database error: {}", e),
+                            code: ErrorCode::DatabaseError,
+                            detail: Some(e.to_string()),
                         }], vec![], None)
                 }
             }
@@ -622,12 +1579,40 @@ async fn handle_client_message(
             family_name,
             password,
         } => {
+            let ip_address = client_ip(sessions, session_id).await;
             tracing::info!(
-                "Session {} attempting to log in with password as {}",
+                "Session {} ({}) attempting to log in with password as {}",
                 session_id,
+                ip_address,
                 family_name
             );
 
+            // 0. Trop d'échecs récents depuis cette IP : refuser sans lancer Argon2
+            match db_tables
+                .login_attempts
+                .recent_failures_from_ip(&ip_address, lockout::IP_WINDOW)
+                .await
+            {
+                Ok(failures) if failures >= lockout::IP_FREE_ATTEMPTS => {
+                    tracing::warn!(
+                        "Login as {} from {} refused: {} failures from this IP recently",
+                        family_name,
+                        ip_address,
+                        failures
+                    );
+                    return (
+                        vec![ServerMessage::LoginError {
+                            code: ErrorCode::TooManyAttempts,
+                            detail: None,
+                        }],
+                        vec![],
+                        None,
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => tracing::error!("{}", e),
+            }
+
             // 1. Fetch player by family name
             match shared::types::game::methods::get_player_by_family_name(
                 &db_tables.pool,
@@ -636,7 +1621,44 @@ async fn handle_client_message(
             .await
             {
                 Ok(Some(player)) => {
-                    // 2. Check if password_hash exists
+                    // 2. Compte verrouillé (trop d'échecs, ou par un administrateur)
+                    let admin_locked = matches!(
+                        player.account_status.as_deref(),
+                        Some("locked") | Some("suspended")
+                    );
+                    let lock_remaining = match db_tables
+                        .login_attempts
+                        .account_lock_remaining(player.id)
+                        .await
+                    {
+                        Ok(remaining) => remaining,
+                        Err(e) => {
+                            tracing::error!("{}", e);
+                            None
+                        }
+                    };
+                    if admin_locked || lock_remaining.is_some() {
+                        tracing::warn!(
+                            "Login as {} from {} refused: account locked{}",
+                            family_name,
+                            ip_address,
+                            lock_remaining
+                                .map(|remaining| format!(" for {}s", remaining.as_secs().max(1)))
+                                .unwrap_or_default()
+                        );
+                        audit_login(db_tables, Some(player.id), &family_name, &ip_address, LoginOutcome::Locked).await;
+                        // Même réponse qu'un compte inconnu : le verrouillage reste dans l'audit
+                        return (
+                            vec![ServerMessage::LoginError {
+                                code: ErrorCode::InvalidCredentials,
+                                detail: None,
+                            }],
+                            vec![],
+                            None,
+                        );
+                    }
+
+                    // 3. Check if password_hash exists
                     let password_hash = match &player.password_hash {
                         Some(hash) => hash,
                         None => {
@@ -645,20 +1667,27 @@ async fn handle_client_message(
                                 family_name
                             );
                             return (vec![ServerMessage::LoginError {
-                                reason: "Ce compte nécessite une migration de mot de passe. Veuillez contacter un administrateur.".to_string(),
+                                code: ErrorCode::PasswordMigrationRequired,
+                                detail: None,
                             }], vec![], None);
                         }
                     };
 
-                    // 3. Verify password
+                    // 4. Verify password
                     match password::verify_password(&password, password_hash) {
                         Ok(true) => {
                             // Password correct, proceed with login
                             tracing::info!(
-                                "Player {} logged in successfully with password authentication",
-                                family_name
+                                "Player {} logged in successfully with password authentication from {}",
+                                family_name,
+                                ip_address
                             );
 
+                            if let Err(e) = db_tables.login_attempts.reset_failures(player.id).await {
+                                tracing::error!("{}", e);
+                            }
+                            audit_login(db_tables, Some(player.id), &family_name, &ip_address, LoginOutcome::Success).await;
+
                             // Associate session with player_id
                             sessions
                                 .authenticate_session(session_id, player.id as u64)
@@ -713,6 +1742,7 @@ async fn handle_client_message(
                             let login_response = vec![ServerMessage::LoginSuccess {
                                 player: player_data,
                                 character: character_data,
+                                resume_token: sessions.issue_resume_token(player.id as u64),
                             }];
 
                             // Puis charger et envoyer le lord
@@ -738,42 +1768,12 @@ async fn handle_client_message(
                                 )
                                 .await;
 
-                            // Chercher l'organisation du joueur (via le lord)
+                            // Chercher l'organisation du joueur (dirigée ou rejointe par le lord)
                             if let Some(ref lord) = lord {
-                                let player_org = sqlx::query_as::<_, (i64, String, i16, Option<i64>, i32, Option<String>)>(
-                                    r#"
-                                    SELECT o.id, o.name, o.organization_type_id, o.leader_unit_id, o.population, o.emblem_url
-                                    FROM organizations.organizations o
-                                    WHERE o.leader_unit_id = $1
-                                    LIMIT 1
-                                    "#,
-                                )
-                                .bind(lord.id as i64)
-                                .fetch_optional(&db_tables.pool)
-                                .await;
-
-                                let org_summary = match player_org {
-                                    Ok(Some((id, name, type_id, leader_id, pop, emblem))) => {
-                                        Some(shared::OrganizationSummary {
-                                            id: id as u64,
-                                            name,
-                                            organization_type: shared::OrganizationType::from_id(
-                                                type_id,
-                                            ),
-                                            leader_unit_id: leader_id.map(|l| l as u64),
-                                            population: pop,
-                                            emblem_url: emblem,
-                                        })
-                                    }
-                                    _ => None,
-                                };
-
                                 let _ = sessions
                                     .send_to_player(
                                         player_id_u64,
-                                        ServerMessage::PlayerOrganizationData {
-                                            organization: org_summary,
-                                        },
+                                        player_organization_data(db_tables, lord.id).await,
                                     )
                                     .await;
                             }
@@ -787,13 +1787,27 @@ async fn handle_client_message(
                             (login_response, vec![], None)
                         }
                         Ok(false) => {
+                            let lockout = db_tables
+                                .login_attempts
+                                .register_failure(player.id)
+                                .await
+                                .unwrap_or_else(|e| {
+                                    tracing::error!("{}", e);
+                                    None
+                                });
                             tracing::warn!(
-                                "Failed login attempt for {}: invalid password",
-                                family_name
+                                "Failed login attempt for {} from {}: invalid password{}",
+                                family_name,
+                                ip_address,
+                                lockout
+                                    .map(|d| format!(", account locked for {}s", d.as_secs()))
+                                    .unwrap_or_default()
                             );
+                            audit_login(db_tables, Some(player.id), &family_name, &ip_address, LoginOutcome::InvalidPassword).await;
                             (
                                 vec![ServerMessage::LoginError {
-                                    reason: "Identifiants invalides".to_string(),
+                                    code: ErrorCode::InvalidCredentials,
+                                    detail: None,
                                 }], vec![], None)
                         }
                         Err(e) => {
@@ -804,32 +1818,236 @@ async fn handle_client_message(
                             );
                             (
                                 vec![ServerMessage::LoginError {
-                                    reason: "Erreur lors de l'authentification".to_string(),
+                                    code: ErrorCode::InternalError,
+                                    detail: None,
                                 }], vec![], None)
                         }
                     }
                 }
                 Ok(None) => {
                     tracing::warn!(
-                        "Login attempt with non-existent family name: {}",
-                        family_name
+                        "Login attempt with non-existent family name {} from {}",
+                        family_name,
+                        ip_address
                     );
+                    audit_login(db_tables, None, &family_name, &ip_address, LoginOutcome::UnknownAccount).await;
                     // Don't reveal that the account doesn't exist (security)
                     (
                         vec![ServerMessage::LoginError {
-                            reason: "Identifiants invalides".to_string(),
+                            code: ErrorCode::InvalidCredentials,
+                            detail: None,
                         }], vec![], None)
                 }
                 Err(e) => {
                     tracing::error!("Database error during login for {}: {}", family_name, e);
                     (
                         vec![ServerMessage::LoginError {
-                            reason: "Erreur de base de données".to_string(),
+                            code: ErrorCode::DatabaseError,
+                            detail: None,
                         }], vec![], None)
                 }
             }
         }
 
+        ClientMessage::ChangePassword {
+            current_password,
+            new_password,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            tracing::info!("Player {} requesting a password change", player_id);
+
+            let requirements = shared::auth::PasswordRequirements::default();
+            if let Err(e) = shared::auth::validate_password(&new_password, &requirements) {
+                return account_error(ErrorCode::WeakPassword, Some(e));
+            }
+
+            let ip_address = client_ip(sessions, session_id).await;
+            if let Err(code) =
+                check_account_password(db_tables, player_id, &current_password, &ip_address).await
+            {
+                tracing::warn!("Password change refused for player {}: {:?}", player_id, code);
+                return account_error(code, None);
+            }
+
+            let password_hash = match password::hash_password(&new_password) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    tracing::error!("Failed to hash password: {}", e);
+                    return account_error(ErrorCode::InternalError, None);
+                }
+            };
+
+            match shared::types::game::methods::update_password_hash(
+                &db_tables.pool,
+                player_id as i64,
+                &password_hash,
+            )
+            .await
+            {
+                Ok(()) => {
+                    tracing::info!("Player {} changed their password", player_id);
+                    // Un jeton volé ne doit pas survivre au changement de mot de passe
+                    sessions.revoke_resume_tokens(player_id);
+                    (vec![ServerMessage::PasswordChanged], vec![], None)
+                }
+                Err(e) => {
+                    tracing::error!("Failed to update password of player {}: {}", player_id, e);
+                    account_error(ErrorCode::DatabaseError, None)
+                }
+            }
+        }
+
+        ClientMessage::ResetPasswordWithCode {
+            family_name,
+            reset_code,
+            new_password,
+        } => {
+            let ip_address = client_ip(sessions, session_id).await;
+            tracing::info!(
+                "Session {} ({}) attempting a password reset for {}",
+                session_id,
+                ip_address,
+                family_name
+            );
+
+            let requirements = shared::auth::PasswordRequirements::default();
+            if let Err(e) = shared::auth::validate_password(&new_password, &requirements) {
+                return account_error(ErrorCode::WeakPassword, Some(e));
+            }
+
+            let player = match shared::types::game::methods::get_player_by_family_name(
+                &db_tables.pool,
+                &family_name,
+            )
+            .await
+            {
+                Ok(player) => player,
+                Err(e) => {
+                    tracing::error!("Database error during password reset for {}: {}", family_name, e);
+                    return account_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            let player_id = player.as_ref().map(|player| player.id);
+
+            // Les codes faux comptent pour le verrouillage, comme des mots de passe faux
+            if let Some(code) =
+                credential_attempt_blocked(db_tables, player_id, &family_name, &ip_address).await
+            {
+                tracing::warn!(
+                    "Password reset for {} from {} refused: {:?}",
+                    family_name,
+                    ip_address,
+                    code
+                );
+                // Un compte verrouillé répond comme un compte inconnu
+                let code = match code {
+                    ErrorCode::AccountLocked => ErrorCode::ResetCodeInvalid,
+                    code => code,
+                };
+                return account_error(code, None);
+            }
+
+            // 1. Valider et brûler le code avant de hacher le nouveau mot de passe
+            let code = reset_code::normalize_reset_code(&reset_code);
+            let redeemed = match player_id {
+                Some(player_id) => db_tables.accounts.consume_reset_code(player_id, &code).await,
+                None => Ok(false),
+            };
+            let player_id = match (redeemed, player_id) {
+                (Ok(true), Some(player_id)) => player_id,
+                (Ok(_), _) => {
+                    tracing::warn!(
+                        "Invalid password reset code for {} from {}",
+                        family_name,
+                        ip_address
+                    );
+                    register_credential_failure(db_tables, player_id, &family_name, &ip_address)
+                        .await;
+                    // Même réponse pour un compte inconnu : ne pas révéler son existence
+                    return account_error(ErrorCode::ResetCodeInvalid, None);
+                }
+                (Err(e), _) => {
+                    tracing::error!("{}", e);
+                    return account_error(ErrorCode::DatabaseError, None);
+                }
+            };
+
+            // 2. Enregistrer le nouveau mot de passe
+            let password_hash = match password::hash_password(&new_password) {
+                Ok(hash) => hash,
+                Err(e) => {
+                    tracing::error!("Failed to hash password: {}", e);
+                    return account_error(ErrorCode::InternalError, None);
+                }
+            };
+
+            match shared::types::game::methods::update_password_hash(
+                &db_tables.pool,
+                player_id,
+                &password_hash,
+            )
+            .await
+            {
+                Ok(()) => {
+                    tracing::info!(
+                        "Password of {} reset with a one-time code from {}",
+                        family_name,
+                        ip_address
+                    );
+                    sessions.revoke_resume_tokens(player_id as u64);
+                    (vec![ServerMessage::PasswordChanged], vec![], None)
+                }
+                Err(e) => {
+                    tracing::error!("Failed to update password of player {}: {}", player_id, e);
+                    account_error(ErrorCode::DatabaseError, None)
+                }
+            }
+        }
+
+        ClientMessage::DeleteAccount { password } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            tracing::info!("Player {} requesting account deletion", player_id);
+
+            let ip_address = client_ip(sessions, session_id).await;
+            if let Err(code) =
+                check_account_password(db_tables, player_id, &password, &ip_address).await
+            {
+                tracing::warn!("Account deletion refused for player {}: {:?}", player_id, code);
+                return account_error(code, None);
+            }
+
+            // Les actions en cours disparaissent avec le compte : les sortir du processeur d'abord
+            action_processor.cancel_player_actions(player_id).await;
+
+            match db_tables.accounts.delete_account(player_id as i64).await {
+                Ok((organization_ids, contour_chunks)) => {
+                    tracing::info!(
+                        "Player {} deleted their account ({} organization(s) removed: {:?})",
+                        player_id,
+                        organization_ids.len(),
+                        organization_ids
+                    );
+                    // Effacer les frontières des organisations dissoutes
+                    for (organization_id, chunk_id) in contour_chunks {
+                        let msg =
+                            world::territory::contour_update_message(organization_id, chunk_id, &[]);
+                        broadcast_message(sessions, &chunk_id, msg).await;
+                    }
+                    sessions.revoke_resume_tokens(player_id);
+                    sessions.logout(session_id).await;
+                    (vec![ServerMessage::AccountDeleted], vec![], None)
+                }
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    account_error(ErrorCode::DatabaseError, None)
+                }
+            }
+        }
+
         ClientMessage::RequestTerrainChunks {
             terrain_name,
             terrain_chunk_ids,
@@ -838,6 +2056,11 @@ async fn handle_client_message(
             let terrain_name_ref = &terrain_name;
             let mut missing_chunks = Vec::new();
 
+            // Le client va garder ces chunks jusqu'à ce qu'ils sortent de son rayon de déchargement
+            sessions
+                .mark_chunks_loaded(session_id, &terrain_chunk_ids)
+                .await;
+
             for terrain_chunk_id in terrain_chunk_ids.iter() {
                 // 1. Try to load terrain from DB — no generation
                 let (terrain_chunk_data, biome_chunk_data) = match db_tables
@@ -901,13 +2124,43 @@ async fn handle_client_message(
                         vec![]
                     });
 
-                // 3. Send terrain + cells + buildings + units
+                let ground_items = db_tables
+                    .resources
+                    .load_chunk_ground_items(terrain_chunk_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!(
+                            "Failed to load ground items for chunk ({},{}): {}",
+                            terrain_chunk_id.x,
+                            terrain_chunk_id.y,
+                            e
+                        );
+                        vec![]
+                    });
+
+                let resource_stocks = db_tables
+                    .resources
+                    .load_chunk_resource_stocks(terrain_chunk_id)
+                    .await
+                    .unwrap_or_else(|e| {
+                        tracing::warn!(
+                            "Failed to load resource stocks for chunk ({},{}): {}",
+                            terrain_chunk_id.x,
+                            terrain_chunk_id.y,
+                            e
+                        );
+                        vec![]
+                    });
+
+                // 3. Send terrain + cells + buildings + units + ground items + resource stocks
                 responses.push(ServerMessage::TerrainChunkData {
                     terrain_chunk_data: terrain_chunk_data.clone(),
                     biome_chunk_data,
                     cell_data,
                     building_data,
                     unit_data,
+                    ground_items,
+                    resource_stocks,
                 });
 
                 // 4. Roads
@@ -981,12 +2234,24 @@ async fn handle_client_message(
             (responses, missing_chunks, Some(terrain_name))
         }
 
+        ClientMessage::UpdateInterestArea {
+            center_chunk,
+            unload_distance,
+        } => {
+            sessions
+                .update_interest_area(session_id, center_chunk, unload_distance)
+                .await;
+            (vec![], vec![], None)
+        }
+
         ClientMessage::ActionBuildBuilding {
-            player_id,
             chunk_id: _,
             cell,
             building_type,
         } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
             let chunk_id = cell.to_chunk_id(&grid_config.layout);
             let building_specific_type = building_type.to_specific_type();
             tracing::info!(
@@ -1008,14 +2273,16 @@ async fn handle_client_message(
                 Ok(None) => {
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: "Aucun seigneur trouvé".to_string(),
+                            code: ErrorCode::LordNotFound,
+                            detail: None,
                         }], vec![], None);
                 }
                 Err(e) => {
                     tracing::error!("Failed to load lord: {}", e);
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: "Erreur serveur".to_string(),
+                            code: ErrorCode::InternalError,
+                            detail: None,
                         }],
                         vec![],
                         None,
@@ -1023,50 +2290,24 @@ async fn handle_client_message(
                 }
             };
 
-            // 2. Check construction costs
-            if !dev_config.skip_resource_check() {
-                let bt_id = building_type.to_id() as i32;
-                let costs = game_state.building_costs(bt_id);
-
-                if !costs.is_empty() {
-                    let inventory = match db_tables
-                        .resources
-                        .load_inventory_summary(lord_unit_id)
-                        .await
-                    {
-                        Ok(inv) => inv,
-                        Err(e) => {
-                            tracing::error!("Failed to load inventory: {}", e);
-                            return (
-                                vec![ServerMessage::ActionError {
-                                    reason: "Erreur de chargement de l'inventaire".to_string(),
-                                }], vec![], None);
-                        }
-                    };
-
-                    let mut missing = Vec::new();
-                    for cost in costs {
-                        let have = inventory.get(&cost.item_id).copied().unwrap_or(0);
-                        if have < cost.quantity {
-                            let item_name = game_state.item_name(cost.item_id, 1);
-                            missing.push(format!(
-                                "{} (besoin: {}, possédé: {})",
-                                item_name, cost.quantity, have
-                            ));
-                        }
-                    }
-
-                    if !missing.is_empty() {
-                        return (
-                            vec![ServerMessage::ActionError {
-                                reason: format!(
-                                    "Matériaux de construction manquants : {}",
-                                    missing.join(", ")
-                                ),
-                            }], vec![], None);
-                    }
+            // 2. Check construction costs (paid by the lord, then the treasury of the
+            //    organization owning the cell, see `material_payers` and `usable_treasury`)
+            let bt_id = building_type.to_id() as i32;
+            let draws = if dev_config.skip_resource_check() {
+                vec![]
+            } else {
+                let materials: Vec<(i32, i32)> = game_state
+                    .building_costs(bt_id)
+                    .iter()
+                    .map(|cost| (cost.item_id, cost.quantity))
+                    .collect();
+                let payers = material_payers(lord_unit_id, &[]);
+                let treasury = usable_treasury(db_tables, lord_unit_id, &cell).await;
+                match plan_materials(db_tables, game_state, &payers, treasury, &materials).await {
+                    Ok(draws) => draws,
+                    Err(error) => return (vec![error], vec![], None),
                 }
-            }
+            };
 
             // ── Schedule ────────────────────────────────────
 
@@ -1080,12 +2321,8 @@ async fn handle_client_message(
                 building_specific_type,
             });
 
-            let start_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
+            let start_time = now_ms();
             // Duration from DB
-            let bt_id = building_type.to_id() as i32;
             let duration_ms =
                 dev_config.apply_speed((game_state.building_duration_seconds(bt_id) as u64) * 1000);
 
@@ -1098,7 +2335,7 @@ async fn handle_client_message(
                     action_specific_type: ActionSpecificTypeEnum::BuildBuilding,
                     start_time,
                     duration_ms,
-                    completion_time: start_time + (duration_ms / 1000),
+                    completion_time: start_time + duration_ms,
                     status: ActionStatusEnum::Pending,
                 },
                 specific_data,
@@ -1115,22 +2352,47 @@ async fn handle_client_message(
                 Ok(action_id) => {
                     tracing::info!("Scheduled build building action with ID {}", action_id);
 
-                    responses.push(ServerMessage::ActionStatusUpdate {
-                        action_id,
-                        player_id,
-                        chunk_id,
-                        cell,
-                        status: ActionStatusEnum::Pending,
-                        action_type: ActionTypeEnum::BuildBuilding,
-                        completion_time: start_time + (duration_ms / 1000),
-                        action_name: None, // TODO: building is the same as a production
-                        unit_ids: vec![],
-                    });
+                    if !draws.is_empty() {
+                        match reserve_materials(db_tables, action_id, lord_unit_id, &draws).await {
+                            Ok(updates) => responses.extend(updates),
+                            Err(e) => {
+                                tracing::warn!(
+                                    "Failed to reserve materials for action {}: {}",
+                                    action_id,
+                                    e
+                                );
+                                if let Err(e) = action_processor.fail_action(action_id).await {
+                                    tracing::error!("{}", e);
+                                }
+                                return (
+                                    vec![ServerMessage::ActionError {
+                                        code: ErrorCode::MissingResources,
+                                        detail: None,
+                                    }],
+                                    vec![],
+                                    None,
+                                );
+                            }
+                        }
+                    }
+
+                    responses.push(ServerMessage::ActionStatusUpdate {
+                        action_id,
+                        player_id,
+                        chunk_id,
+                        cell,
+                        status: ActionStatusEnum::Pending,
+                        action_type: ActionTypeEnum::BuildBuilding,
+                        completion_time: start_time + duration_ms,
+                        action_name: None, // TODO: building is the same as a production
+                        unit_ids: vec![],
+                    });
                 }
                 Err(e) => {
                     tracing::error!("Failed to schedule action: {}", e);
                     responses.push(ServerMessage::ActionError {
-                        reason: format!("Failed to schedule action: {}", e),
+                        code: ErrorCode::SchedulingFailed,
+                        detail: Some(e.to_string()),
                     });
                 }
             }
@@ -1138,10 +2400,12 @@ async fn handle_client_message(
             (responses, vec![], None)
         }
         ClientMessage::ActionBuildRoad {
-            player_id,
             start_cell,
             end_cell,
         } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
             tracing::info!(
                 "Player {} requested to build road from ({},{}) to ({},{})",
                 player_id,
@@ -1162,10 +2426,7 @@ async fn handle_client_message(
             use crate::database::tables::RoadSegmentsTable;
             let chunk_id = RoadSegmentsTable::cell_to_chunk_id(&start_cell);
 
-            let start_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
+            let start_time = now_ms();
             let duration_ms = dev_config.apply_speed(specific_data.duration_ms(&ActionContext {
                 player_id,
                 grid_cell: start_cell,
@@ -1180,7 +2441,7 @@ async fn handle_client_message(
                     action_specific_type: ActionSpecificTypeEnum::BuildRoad,
                     start_time,
                     duration_ms,
-                    completion_time: start_time + (duration_ms / 1000),
+                    completion_time: start_time + duration_ms,
                     status: ActionStatusEnum::Pending,
                 },
                 specific_data,
@@ -1204,7 +2465,7 @@ async fn handle_client_message(
                         cell: start_cell,
                         status: ActionStatusEnum::Pending,
                         action_type: ActionTypeEnum::BuildRoad,
-                        completion_time: start_time + (duration_ms / 1000),
+                        completion_time: start_time + duration_ms,
                         action_name: None, // TODO: building is the same as a production
                         unit_ids: vec![],
                     });
@@ -1212,7 +2473,8 @@ async fn handle_client_message(
                 Err(e) => {
                     tracing::error!("Failed to schedule action: {}", e);
                     responses.push(ServerMessage::ActionError {
-                        reason: format!("Failed to schedule action: {}", e),
+                        code: ErrorCode::SchedulingFailed,
+                        detail: Some(e.to_string()),
                     });
                 }
             }
@@ -1220,13 +2482,15 @@ async fn handle_client_message(
             (responses, vec![], None)
         }
         ClientMessage::ActionCraftResource {
-            player_id,
             chunk_id,
             cell,
             recipe_id,
             quantity,
             unit_ids,
         } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
             // ── Validation ──────────────────────────────────
 
             // 1. Find the recipe (by numeric ID or slug)
@@ -1240,7 +2504,8 @@ async fn handle_client_message(
                     );
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: format!("Recette inconnue : {}", recipe_id),
+                            code: ErrorCode::UnknownRecipe,
+                            detail: Some(recipe_id.to_string()),
                         }], vec![], None);
                 }
             };
@@ -1251,57 +2516,21 @@ async fn handle_client_message(
                 Ok(None) => {
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: "Aucun seigneur trouvé".to_string(),
+                            code: ErrorCode::LordNotFound,
+                            detail: None,
                         }], vec![], None);
                 }
                 Err(e) => {
                     tracing::error!("Failed to load lord for player {}: {}", player_id, e);
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: "Erreur serveur".to_string(),
+                            code: ErrorCode::InternalError,
+                            detail: None,
                         }], vec![], None);
                 }
             };
 
-            // 3. Check ingredients
-            if !dev_config.skip_resource_check() && !recipe.ingredients.is_empty() {
-                let inventory = match db_tables
-                    .resources
-                    .load_inventory_summary(lord_unit_id)
-                    .await
-                {
-                    Ok(inv) => inv,
-                    Err(e) => {
-                        tracing::error!("Failed to load inventory: {}", e);
-                        return (
-                            vec![ServerMessage::ActionError {
-                                reason: "Erreur de chargement de l'inventaire".to_string(),
-                            }], vec![], None);
-                    }
-                };
-
-                let mut missing = Vec::new();
-                for ingredient in &recipe.ingredients {
-                    let needed = ingredient.quantity * quantity as i32;
-                    let have = inventory.get(&ingredient.item_id).copied().unwrap_or(0);
-                    if have < needed {
-                        let item_name = game_state.item_name(ingredient.item_id, 1).clone();
-                        missing.push(format!(
-                            "{} (besoin: {}, possédé: {})",
-                            item_name, needed, have
-                        ));
-                    }
-                }
-
-                if !missing.is_empty() {
-                    return (
-                        vec![ServerMessage::ActionError {
-                            reason: format!("Ressources manquantes : {}", missing.join(", ")),
-                        }], vec![], None);
-                }
-            }
-
-            // 4. Check production line capacity
+            // 3. Check production line capacity
             let building_type = db_tables
                 .buildings
                 .get_building_type_at_cell(&cell)
@@ -1317,15 +2546,20 @@ async fn handle_client_message(
                 if active_count >= max_lines {
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: format!(
-                                "Toutes les lignes de production sont occupées ({}/{})",
-                                active_count, max_lines
-                            ),
+                            code: ErrorCode::ProductionLinesFull,
+                            detail: Some(format!("{}/{}", active_count, max_lines)),
                         }], vec![], None);
                 }
             }
 
-            // 5. Validate units aren't already busy
+            // 4. Validate units belong to the player and aren't already busy
+            if !player_controls_units(db_tables, player_id, &unit_ids).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
             if !unit_ids.is_empty() {
                 let busy = db_tables
                     .units
@@ -1335,11 +2569,30 @@ async fn handle_client_message(
                 if !busy.is_empty() {
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: format!("Certaines unités sont déjà occupées : {:?}", busy),
+                            code: ErrorCode::UnitBusy,
+                            detail: Some(format!("{:?}", busy)),
                         }], vec![], None);
                 }
             }
 
+            // 5. Check ingredients across the inventories of the assigned units, then
+            //    the treasury of the organization owning the workshop cell
+            let draws = if dev_config.skip_resource_check() {
+                vec![]
+            } else {
+                let materials: Vec<(i32, i32)> = recipe
+                    .ingredients
+                    .iter()
+                    .map(|ingredient| (ingredient.item_id, ingredient.quantity * quantity as i32))
+                    .collect();
+                let payers = material_payers(lord_unit_id, &unit_ids);
+                let treasury = usable_treasury(db_tables, lord_unit_id, &cell).await;
+                match plan_materials(db_tables, game_state, &payers, treasury, &materials).await {
+                    Ok(draws) => draws,
+                    Err(error) => return (vec![error], vec![], None),
+                }
+            };
+
             // ── Schedule the action ─────────────────────────
 
             let mut responses = Vec::new();
@@ -1354,14 +2607,20 @@ async fn handle_client_message(
                 quantity,
             });
 
-            let start_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-
-            // Duration from DB recipe instead of hardcoded
-            let duration_ms = dev_config
-                .apply_speed((recipe.craft_duration_seconds as u64) * 1000 * (quantity as u64));
+            let start_time = now_ms();
+
+            // Duration from DB recipe, shortened by the crafters' skill
+            let base_ms = (recipe.craft_duration_seconds as u64) * 1000 * (quantity as u64);
+            let duration_ms = dev_config.apply_speed(
+                skill_adjusted_duration_ms(
+                    db_tables,
+                    &unit_ids,
+                    recipe.required_skill,
+                    &game_state.profession_skill_bonuses,
+                    base_ms,
+                )
+                .await,
+            );
 
             let action_data = ActionData {
                 base_data: ActionBaseData {
@@ -1372,7 +2631,7 @@ async fn handle_client_message(
                     action_specific_type: ActionSpecificTypeEnum::CraftResource,
                     start_time,
                     duration_ms,
-                    completion_time: start_time + (duration_ms / 1000),
+                    completion_time: start_time + duration_ms,
                     status: ActionStatusEnum::Pending,
                 },
                 specific_data,
@@ -1387,6 +2646,30 @@ async fn handle_client_message(
             .await
             {
                 Ok(action_id) => {
+                    if !draws.is_empty() {
+                        match reserve_materials(db_tables, action_id, lord_unit_id, &draws).await {
+                            Ok(updates) => responses.extend(updates),
+                            Err(e) => {
+                                tracing::warn!(
+                                    "Failed to reserve ingredients for action {}: {}",
+                                    action_id,
+                                    e
+                                );
+                                if let Err(e) = action_processor.fail_action(action_id).await {
+                                    tracing::error!("{}", e);
+                                }
+                                return (
+                                    vec![ServerMessage::ActionError {
+                                        code: ErrorCode::MissingResources,
+                                        detail: None,
+                                    }],
+                                    vec![],
+                                    None,
+                                );
+                            }
+                        }
+                    }
+
                     // Assign units to this action
                     if !unit_ids.is_empty() {
                         if let Err(e) = db_tables
@@ -1425,7 +2708,7 @@ async fn handle_client_message(
                         cell,
                         status: ActionStatusEnum::Pending,
                         action_type: ActionTypeEnum::CraftResource,
-                        completion_time: start_time + (duration_ms / 1000),
+                        completion_time: start_time + duration_ms,
                         action_name: Some(recipe.name.clone()),
                         unit_ids: unit_ids.clone(),
                     });
@@ -1433,7 +2716,8 @@ async fn handle_client_message(
                 Err(e) => {
                     tracing::error!("Failed to schedule craft action: {}", e);
                     responses.push(ServerMessage::ActionError {
-                        reason: format!("Erreur lors de la planification : {}", e),
+                        code: ErrorCode::SchedulingFailed,
+                        detail: Some(e.to_string()),
                     });
                 }
             }
@@ -1441,12 +2725,14 @@ async fn handle_client_message(
             (responses, vec![], None)
         }
         ClientMessage::ActionHarvestResource {
-            player_id,
             chunk_id,
             cell,
             resource_specific_type,
             unit_ids,
         } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
             // ── Validation ──────────────────────────────────
 
             // 1. Check harvest yields exist for this resource type
@@ -1455,27 +2741,55 @@ async fn handle_client_message(
             if yields.is_empty() {
                 return (
                     vec![ServerMessage::ActionError {
-                        reason: format!(
-                            "Aucun rendement de récolte défini pour ce type de ressource ({:?})",
-                            resource_specific_type
-                        ),
+                        code: ErrorCode::NoHarvestYield,
+                        detail: Some(format!("{:?}", resource_specific_type)),
                     }], vec![], None);
             }
 
+            // 1b. The cell must still hold some of this resource
+            match crate::resource_stocks::cell_resource_stock(
+                db_tables,
+                game_state,
+                &chunk_id,
+                &cell,
+                resource_specific_type,
+            )
+            .await
+            {
+                Ok(Some(stock)) if !stock.is_depleted() => {}
+                Ok(_) => {
+                    return (
+                        vec![ServerMessage::ActionError {
+                            code: ErrorCode::ResourceDepleted,
+                            detail: Some(format!("{:?}", resource_specific_type)),
+                        }], vec![], None);
+                }
+                Err(e) => {
+                    tracing::error!("Failed to load resource stock: {}", e);
+                    return (
+                        vec![ServerMessage::ActionError {
+                            code: ErrorCode::InternalError,
+                            detail: None,
+                        }], vec![], None);
+                }
+            }
+
             // 2. Find the lord
             match db_tables.units.load_lord_for_player(player_id).await {
                 Ok(Some(_)) => {} // Lord exists, good
                 Ok(None) => {
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: "Aucun seigneur trouvé".to_string(),
+                            code: ErrorCode::LordNotFound,
+                            detail: None,
                         }], vec![], None);
                 }
                 Err(e) => {
                     tracing::error!("Failed to load lord: {}", e);
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: "Erreur serveur".to_string(),
+                            code: ErrorCode::InternalError,
+                            detail: None,
                         }], vec![], None);
                 }
             }
@@ -1496,15 +2810,20 @@ async fn handle_client_message(
                 if active_count >= max_lines {
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: format!(
-                                "Toutes les lignes de production sont occupées ({}/{})",
-                                active_count, max_lines
-                            ),
+                            code: ErrorCode::ProductionLinesFull,
+                            detail: Some(format!("{}/{}", active_count, max_lines)),
                         }], vec![], None);
                 }
             }
 
-            // 4. Validate units aren't already busy
+            // 4. Validate units belong to the player and aren't already busy
+            if !player_controls_units(db_tables, player_id, &unit_ids).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
             if !unit_ids.is_empty() {
                 let busy = db_tables
                     .units
@@ -1514,7 +2833,8 @@ async fn handle_client_message(
                 if !busy.is_empty() {
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: format!("Certaines unités sont déjà occupées : {:?}", busy),
+                            code: ErrorCode::UnitBusy,
+                            detail: Some(format!("{:?}", busy)),
                         }], vec![], None);
                 }
             }
@@ -1530,13 +2850,21 @@ async fn handle_client_message(
                 resource_specific_type,
             });
 
-            let start_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-
-            // Duration from DB harvest yield (use the first yield's duration)
-            let duration_ms = dev_config.apply_speed((yields[0].duration_seconds as u64) * 1000);
+            let start_time = now_ms();
+
+            // Duration from DB harvest yield (use the first yield's duration),
+            // shortened by the harvesters' skill
+            let base_ms = (yields[0].duration_seconds as u64) * 1000;
+            let duration_ms = dev_config.apply_speed(
+                skill_adjusted_duration_ms(
+                    db_tables,
+                    &unit_ids,
+                    yields[0].skill,
+                    &game_state.profession_skill_bonuses,
+                    base_ms,
+                )
+                .await,
+            );
 
             let action_data = ActionData {
                 base_data: ActionBaseData {
@@ -1547,7 +2875,7 @@ async fn handle_client_message(
                     action_specific_type: ActionSpecificTypeEnum::HarvestResource,
                     start_time,
                     duration_ms,
-                    completion_time: start_time + (duration_ms / 1000),
+                    completion_time: start_time + duration_ms,
                     status: ActionStatusEnum::Pending,
                 },
                 specific_data,
@@ -1591,7 +2919,7 @@ async fn handle_client_message(
                         cell,
                         status: ActionStatusEnum::Pending,
                         action_type: ActionTypeEnum::HarvestResource,
-                        completion_time: start_time + (duration_ms / 1000),
+                        completion_time: start_time + duration_ms,
                         action_name: None, //TODO: Should have something here
                         unit_ids: unit_ids.clone(),
                     });
@@ -1599,7 +2927,8 @@ async fn handle_client_message(
                 Err(e) => {
                     tracing::error!("Failed to schedule harvest action: {}", e);
                     responses.push(ServerMessage::ActionError {
-                        reason: format!("Erreur : {}", e),
+                        code: ErrorCode::SchedulingFailed,
+                        detail: Some(e.to_string()),
                     });
                 }
             }
@@ -1607,11 +2936,13 @@ async fn handle_client_message(
             (responses, vec![], None)
         }
         ClientMessage::ActionMoveUnit {
-            player_id,
             unit_id,
             chunk_id,
             cell,
         } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
             let mut responses = Vec::new();
             let action_table = &db_tables.actions;
 
@@ -1621,7 +2952,8 @@ async fn handle_client_message(
                 Err(e) => {
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: format!("Unité introuvable: {}", e),
+                            code: ErrorCode::UnitNotFound,
+                            detail: Some(e.to_string()),
                         }], vec![], None);
                 }
             };
@@ -1630,7 +2962,21 @@ async fn handle_client_message(
             if !player_controls_unit(&db_tables, player_id, unit_id).await {
                 return (
                     vec![ServerMessage::ActionError {
-                        reason: "Cette unité ne vous appartient pas".to_string(),
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
+            if !db_tables
+                .units
+                .get_busy_units(&[unit_id])
+                .await
+                .unwrap_or_default()
+                .is_empty()
+            {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitBusy,
+                        detail: None,
                     }], vec![], None);
             }
 
@@ -1644,7 +2990,8 @@ async fn handle_client_message(
             if hex_distance == 0 {
                 return (
                     vec![ServerMessage::ActionError {
-                        reason: "L'unité est déjà sur cette cellule".to_string(),
+                        code: ErrorCode::UnitAlreadyAtTarget,
+                        detail: None,
                     }], vec![], None);
             }
 
@@ -1658,10 +3005,7 @@ async fn handle_client_message(
                 cell,
             });
 
-            let start_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
+            let start_time = now_ms();
 
             let action_data = ActionData {
                 base_data: ActionBaseData {
@@ -1672,7 +3016,7 @@ async fn handle_client_message(
                     action_specific_type: ActionSpecificTypeEnum::MoveUnit,
                     start_time,
                     duration_ms,
-                    completion_time: start_time + (duration_ms / 1000),
+                    completion_time: start_time + duration_ms,
                     status: ActionStatusEnum::Pending,
                 },
                 specific_data,
@@ -1698,6 +3042,7 @@ async fn handle_client_message(
                         duration_ms,
                         action_id
                     );
+                    assign_unit_to_action(db_tables, unit_id, action_id, &mut responses).await;
                     responses.push(ServerMessage::ActionStatusUpdate {
                         action_id,
                         player_id,
@@ -1705,7 +3050,7 @@ async fn handle_client_message(
                         cell,
                         status: ActionStatusEnum::Pending,
                         action_type: ActionTypeEnum::MoveUnit,
-                        completion_time: start_time + (duration_ms / 1000),
+                        completion_time: start_time + duration_ms,
                         action_name: None, // TODO: movement should also be something but not in slots
                         unit_ids: vec![],
                     });
@@ -1713,7 +3058,8 @@ async fn handle_client_message(
                 Err(e) => {
                     tracing::error!("Failed to schedule move action: {}", e);
                     responses.push(ServerMessage::ActionError {
-                        reason: format!("Échec de la planification: {}", e),
+                        code: ErrorCode::SchedulingFailed,
+                        detail: Some(e.to_string()),
                     });
                 }
             }
@@ -1721,14 +3067,37 @@ async fn handle_client_message(
             (responses, vec![], None)
         }
         ClientMessage::ActionTrainUnit {
-            player_id,
             unit_id,
             chunk_id,
             cell,
             target_profession,
         } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
             let mut responses = Vec::new();
 
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
+            if !db_tables
+                .units
+                .get_busy_units(&[unit_id])
+                .await
+                .unwrap_or_default()
+                .is_empty()
+            {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitBusy,
+                        detail: None,
+                    }], vec![], None);
+            }
+
             // 1. Check production line capacity
             let building_type = db_tables
                 .buildings
@@ -1745,10 +3114,8 @@ async fn handle_client_message(
                 if active_count >= max_lines {
                     return (
                         vec![ServerMessage::ActionError {
-                            reason: format!(
-                                "Toutes les lignes de production sont occupées ({}/{})",
-                                active_count, max_lines
-                            ),
+                            code: ErrorCode::ProductionLinesFull,
+                            detail: Some(format!("{}/{}", active_count, max_lines)),
                         }], vec![], None);
                 }
             }
@@ -1762,10 +3129,7 @@ async fn handle_client_message(
                 target_profession,
             });
 
-            let start_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
+            let start_time = now_ms();
             let duration_ms = dev_config.apply_speed(specific_data.duration_ms(&ActionContext {
                 player_id,
                 grid_cell: cell,
@@ -1780,7 +3144,7 @@ async fn handle_client_message(
                     action_specific_type: ActionSpecificTypeEnum::TrainUnit,
                     start_time,
                     duration_ms,
-                    completion_time: start_time + (duration_ms / 1000),
+                    completion_time: start_time + duration_ms,
                     status: ActionStatusEnum::Pending,
                 },
                 specific_data,
@@ -1801,6 +3165,7 @@ async fn handle_client_message(
                         target_profession,
                         action_id
                     );
+                    assign_unit_to_action(db_tables, unit_id, action_id, &mut responses).await;
                     responses.push(ServerMessage::ActionStatusUpdate {
                         action_id,
                         player_id,
@@ -1808,7 +3173,7 @@ async fn handle_client_message(
                         cell,
                         status: ActionStatusEnum::Pending,
                         action_type: ActionTypeEnum::TrainUnit,
-                        completion_time: start_time + (duration_ms / 1000),
+                        completion_time: start_time + duration_ms,
                         action_name: None, // TODO: Training should display something
                         unit_ids: vec![],
                     });
@@ -1816,144 +3181,145 @@ async fn handle_client_message(
                 Err(e) => {
                     tracing::error!("Failed to schedule training: {}", e);
                     responses.push(ServerMessage::ActionError {
-                        reason: format!("Failed to schedule training: {}", e),
+                        code: ErrorCode::SchedulingFailed,
+                        detail: Some(e.to_string()),
                     });
                 }
             }
 
             (responses, vec![], None)
         }
-        ClientMessage::MoveUnitToSlot {
-            unit_id,
-            cell,
-            from_slot,
-            to_slot,
-        } => {
+        ClientMessage::ActionClaimCells { unit_id, cells } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
             let mut responses = Vec::new();
 
-            tracing::info!(
-                "Moving unit {} from slot {:?} to {:?} at cell {:?}",
-                unit_id,
-                from_slot,
-                to_slot,
-                cell
-            );
-
-            // Convert SlotPosition to DB format
-            let slot_type_str = match to_slot.slot_type {
-                shared::SlotType::Interior => "interior",
-                shared::SlotType::Exterior => "exterior",
+            // Le seigneur arpente lui-même, s'il dirige l'établissement ou en a la charge
+            let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+                return action_error(ErrorCode::LordNotFound, None);
             };
-            let slot_index = to_slot.index as i32;
-
-            // Update database
-            match db_tables
+            if lord.id != unit_id {
+                return action_error(ErrorCode::InsufficientAuthority, None);
+            }
+            if !db_tables
                 .units
-                .update_slot_position(unit_id, Some(slot_type_str.to_string()), Some(slot_index))
+                .get_busy_units(&[unit_id])
                 .await
+                .unwrap_or_default()
+                .is_empty()
             {
-                Ok(_) => {
-                    tracing::info!("Unit {} slot updated in database", unit_id);
+                return action_error(ErrorCode::UnitBusy, None);
+            }
 
-                    // Broadcast success to all clients
-                    responses.push(ServerMessage::UnitSlotUpdated {
-                        unit_id,
-                        cell,
-                        slot_position: Some(to_slot),
-                    });
+            let organization = match db_tables.organizations.find_unit_organization(lord.id).await {
+                Ok(Some(id)) => db_tables.organizations.load_organization(id).await,
+                Ok(None) => return action_error(ErrorCode::NoOrganization, None),
+                Err(e) => Err(e),
+            };
+            let organization = match organization {
+                Ok(organization) => organization,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
                 }
+            };
+            if !organization.is_territorial() {
+                return action_error(ErrorCode::InsufficientAuthority, None);
+            }
+            match has_organization_permission(
+                db_tables,
+                organization.id,
+                lord.id,
+                OrganizationPermission::ManageTerritory,
+            )
+            .await
+            {
+                Ok(true) => {}
+                Ok(false) => return action_error(ErrorCode::InsufficientAuthority, None),
                 Err(e) => {
-                    tracing::error!("Failed to update unit slot in database: {}", e);
-                    responses.push(ServerMessage::ActionError {
-                        reason: format!("Failed to update slot position: {}", e),
-                    });
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
                 }
             }
 
-            (responses, vec![], None)
-        }
-        ClientMessage::AssignUnitToSlot {
-            unit_id,
-            cell,
-            slot,
-        } => {
-            let mut responses = Vec::new();
-
-            tracing::info!(
-                "Assigning unit {} to slot {:?}:{} at cell {:?}",
-                unit_id,
-                slot.slot_type,
-                slot.index,
-                cell
-            );
-
-            // Convert SlotPosition to DB format
-            let slot_type_str = match slot.slot_type {
-                shared::SlotType::Interior => "interior",
-                shared::SlotType::Exterior => "exterior",
-            };
-            let slot_index = slot.index as i32;
-
-            // Update database
-            match db_tables
-                .units
-                .update_slot_position(unit_id, Some(slot_type_str.to_string()), Some(slot_index))
+            // 1. Le seigneur arpente depuis son territoire
+            let territory: std::collections::HashSet<shared::grid::GridCell> = match db_tables
+                .organizations
+                .load_territory_cells(organization.id)
                 .await
             {
-                Ok(_) => {
-                    tracing::info!("Unit {} slot assigned in database", unit_id);
-
-                    // Broadcast success to all clients
-                    responses.push(ServerMessage::UnitSlotUpdated {
-                        unit_id,
-                        cell,
-                        slot_position: Some(slot),
-                    });
-                }
+                Ok(cells) => cells.into_iter().collect(),
                 Err(e) => {
-                    tracing::error!("Failed to assign unit slot in database: {}", e);
-                    responses.push(ServerMessage::ActionError {
-                        reason: format!("Failed to assign slot position: {}", e),
-                    });
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
                 }
+            };
+            if !territory.contains(&lord.current_cell) {
+                return action_error(ErrorCode::OutsideTerritory, None);
+            }
+
+            // 2. Les cellules doivent être libres, y compris des revendications en cours
+            let pending = db_tables
+                .actions
+                .load_pending_claimed_cells()
+                .await
+                .unwrap_or_default();
+            for cell in &cells {
+                let owner = db_tables
+                    .organizations
+                    .find_cell_organization(cell)
+                    .await
+                    .unwrap_or(None);
+                if owner.is_some() || pending.contains(cell) {
+                    return action_error(
+                        ErrorCode::CellAlreadyClaimed,
+                        Some(format!("({}, {})", cell.q, cell.r)),
+                    );
+                }
+            }
+            if !world::territory::is_contiguous_claim(&territory, &cells) {
+                return action_error(ErrorCode::CellNotAdjacent, None);
+            }
+
+            // 3. Le trésor paiera chaque cellule une fois bornée
+            let cost = shared::CLAIM_CELL_GOLD_COST * cells.len() as i32;
+            if organization.treasury_gold < cost {
+                return action_error(
+                    ErrorCode::NotEnoughGold,
+                    Some(format!("{}/{}", organization.treasury_gold, cost)),
+                );
             }
 
-            (responses, vec![], None)
-        }
-        ClientMessage::ActionSendMessage {
-            player_id,
-            chunk_id,
-            cell,
-            receivers,
-            content,
-        } => {
-            let mut responses = Vec::new();
             let action_table = &db_tables.actions;
-            let specific_data = SpecificAction::SendMessage(SendMessageAction {
+            let chunk_id = lord.current_chunk;
+            let cell = lord.current_cell;
+            let cell_count = cells.len();
+            let specific_data = SpecificAction::ClaimCells(ClaimCellsAction {
                 player_id,
-                receivers,
-                content,
+                unit_id,
+                organization_id: organization.id,
+                chunk_id,
+                cell,
+                cells,
             });
 
-            let start_time = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs();
-            let duration_ms = specific_data.duration_ms(&ActionContext {
+            let start_time = now_ms();
+            let duration_ms = dev_config.apply_speed(specific_data.duration_ms(&ActionContext {
                 player_id,
                 grid_cell: cell,
-            });
+            }));
 
             let action_data = ActionData {
                 base_data: ActionBaseData {
                     player_id,
                     chunk: chunk_id,
                     cell,
-                    action_type: ActionTypeEnum::SendMessage,
-                    action_specific_type: ActionSpecificTypeEnum::SendMessage,
+                    action_type: ActionTypeEnum::ClaimCells,
+                    action_specific_type: ActionSpecificTypeEnum::ClaimCells,
                     start_time,
                     duration_ms,
-                    completion_time: start_time + (duration_ms / 1000),
+                    completion_time: start_time + duration_ms,
                     status: ActionStatusEnum::Pending,
                 },
                 specific_data,
@@ -1963,44 +3329,381 @@ async fn handle_client_message(
                 action_table,
                 action_processor,
                 &action_data,
-                ActionTypeEnum::SendMessage,
+                ActionTypeEnum::ClaimCells,
             )
             .await
             {
                 Ok(action_id) => {
+                    tracing::info!(
+                        "{} claims {} cells (action {})",
+                        organization.name,
+                        cell_count,
+                        action_id
+                    );
+                    assign_unit_to_action(db_tables, unit_id, action_id, &mut responses).await;
                     responses.push(ServerMessage::ActionStatusUpdate {
                         action_id,
                         player_id,
                         chunk_id,
                         cell,
                         status: ActionStatusEnum::Pending,
-                        action_type: ActionTypeEnum::SendMessage,
-                        completion_time: start_time + (duration_ms / 1000),
+                        action_type: ActionTypeEnum::ClaimCells,
+                        completion_time: start_time + duration_ms,
                         action_name: None,
-                        unit_ids: vec![],
+                        unit_ids: vec![unit_id],
                     });
                 }
                 Err(e) => {
-                    tracing::error!("Failed to schedule action: {}", e);
+                    tracing::error!("Failed to schedule claim: {}", e);
                     responses.push(ServerMessage::ActionError {
-                        reason: format!("Failed to schedule action: {}", e),
+                        code: ErrorCode::SchedulingFailed,
+                        detail: Some(e.to_string()),
                     });
                 }
             }
 
             (responses, vec![], None)
         }
-        ClientMessage::RequestOceanData { world_name } => {
-            tracing::info!("=== CLIENT REQUESTED OCEAN DATA ===");
-            tracing::info!("World name: {}", world_name);
+        ClientMessage::ActionSendEnvoy {
+            unit_id,
+            target_organization_id,
+            mission,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let mut responses = Vec::new();
 
-            match db_tables.ocean_data.load_ocean_data(&world_name).await {
-                Ok(Some(ocean_data)) => {
-                    tracing::info!("Ocean data loaded from database:");
-                    tracing::info!("  - width: {}", ocean_data.width);
-                    tracing::info!("  - height: {}", ocean_data.height);
-                    tracing::info!("  - sdf_values: {} bytes", ocean_data.sdf_values.len());
-                    tracing::info!(
+            // La diplomatie est l'affaire du chef de l'organisation
+            let organization = match led_organization(db_tables, player_id).await {
+                Ok(organization) => organization,
+                Err(code) => return action_error(code, None),
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return action_error(ErrorCode::UnitNotOwned, None);
+            }
+            let envoy = match db_tables.units.load_unit(unit_id).await {
+                Ok(unit) => unit,
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    return action_error(ErrorCode::UnitNotFound, None);
+                }
+            };
+            if !envoy.is_lord
+                && !matches!(envoy.profession, ProfessionEnum::Merchant | ProfessionEnum::Scholar)
+            {
+                return action_error(ErrorCode::NotAnEnvoy, None);
+            }
+            if !db_tables
+                .units
+                .get_busy_units(&[unit_id])
+                .await
+                .unwrap_or_default()
+                .is_empty()
+            {
+                return action_error(ErrorCode::UnitBusy, None);
+            }
+
+            let target = match db_tables.organizations.load_organization(target_organization_id).await {
+                Ok(target) if target.id != organization.id => target,
+                Ok(_) => return action_error(ErrorCode::InvalidEnvoyTarget, None),
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    return action_error(ErrorCode::OrganizationNotFound, None);
+                }
+            };
+            let (Some(headquarters), Some(target_headquarters)) =
+                (organization.headquarters_cell, target.headquarters_cell)
+            else {
+                return action_error(ErrorCode::NoHeadquarters, None);
+            };
+
+            // La mission doit encore avoir un sens au départ de l'émissaire
+            match mission {
+                EnvoyMission::Propose { relation_type, .. } => {
+                    let current = db_tables
+                        .organizations
+                        .load_diplomatic_relations(organization.id)
+                        .await
+                        .unwrap_or_default();
+                    if current.iter().any(|relation| {
+                        relation.target_organization_id == target.id
+                            && relation.relation_type == relation_type
+                    }) {
+                        return action_error(ErrorCode::RelationAlreadyExists, None);
+                    }
+                }
+                EnvoyMission::Accept { proposal_id } | EnvoyMission::Reject { proposal_id } => {
+                    let proposals = db_tables
+                        .organizations
+                        .load_diplomatic_proposals(organization.id)
+                        .await
+                        .unwrap_or_default();
+                    if !proposals.iter().any(|proposal| {
+                        proposal.id == proposal_id
+                            && proposal.organization_id == target.id
+                            && proposal.target_organization_id == organization.id
+                    }) {
+                        return action_error(ErrorCode::DiplomaticProposalNotFound, None);
+                    }
+                }
+            }
+
+            let action_table = &db_tables.actions;
+            let cell = headquarters;
+            let chunk_id = cell.to_chunk_id(&grid_config.layout);
+            let specific_data = SpecificAction::SendEnvoy(SendEnvoyAction {
+                player_id,
+                unit_id,
+                organization_id: organization.id,
+                target_organization_id: target.id,
+                chunk_id,
+                cell,
+                target_cell: target_headquarters,
+                mission,
+            });
+
+            let start_time = now_ms();
+            let duration_ms = dev_config.apply_speed(specific_data.duration_ms(&ActionContext {
+                player_id,
+                grid_cell: cell,
+            }));
+
+            let action_data = ActionData {
+                base_data: ActionBaseData {
+                    player_id,
+                    chunk: chunk_id,
+                    cell,
+                    action_type: ActionTypeEnum::SendEnvoy,
+                    action_specific_type: ActionSpecificTypeEnum::SendEnvoy,
+                    start_time,
+                    duration_ms,
+                    completion_time: start_time + duration_ms,
+                    status: ActionStatusEnum::Pending,
+                },
+                specific_data,
+            };
+
+            match add_action_and_cache(
+                action_table,
+                action_processor,
+                &action_data,
+                ActionTypeEnum::SendEnvoy,
+            )
+            .await
+            {
+                Ok(action_id) => {
+                    tracing::info!(
+                        "{} sends an envoy to {} ({}, action {})",
+                        organization.name,
+                        target.name,
+                        mission.kind(),
+                        action_id
+                    );
+                    assign_unit_to_action(db_tables, unit_id, action_id, &mut responses).await;
+                    responses.push(ServerMessage::ActionStatusUpdate {
+                        action_id,
+                        player_id,
+                        chunk_id,
+                        cell,
+                        status: ActionStatusEnum::Pending,
+                        action_type: ActionTypeEnum::SendEnvoy,
+                        completion_time: start_time + duration_ms,
+                        action_name: None,
+                        unit_ids: vec![unit_id],
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Failed to schedule envoy: {}", e);
+                    responses.push(ServerMessage::ActionError {
+                        code: ErrorCode::SchedulingFailed,
+                        detail: Some(e.to_string()),
+                    });
+                }
+            }
+
+            (responses, vec![], None)
+        }
+        ClientMessage::MoveUnitToSlot {
+            unit_id,
+            cell,
+            from_slot,
+            to_slot,
+        } => {
+            let mut responses = Vec::new();
+
+            tracing::info!(
+                "Moving unit {} from slot {:?} to {:?} at cell {:?}",
+                unit_id,
+                from_slot,
+                to_slot,
+                cell
+            );
+
+            // Convert SlotPosition to DB format
+            let slot_type_str = match to_slot.slot_type {
+                shared::SlotType::Interior => "interior",
+                shared::SlotType::Exterior => "exterior",
+            };
+            let slot_index = to_slot.index as i32;
+
+            // Update database
+            match db_tables
+                .units
+                .update_slot_position(unit_id, Some(slot_type_str.to_string()), Some(slot_index))
+                .await
+            {
+                Ok(_) => {
+                    tracing::info!("Unit {} slot updated in database", unit_id);
+
+                    // Broadcast success to all clients
+                    responses.push(ServerMessage::UnitSlotUpdated {
+                        unit_id,
+                        cell,
+                        slot_position: Some(to_slot),
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Failed to update unit slot in database: {}", e);
+                    responses.push(ServerMessage::ActionError {
+                        code: ErrorCode::DatabaseError,
+                        detail: Some(e.to_string()),
+                    });
+                }
+            }
+
+            (responses, vec![], None)
+        }
+        ClientMessage::AssignUnitToSlot {
+            unit_id,
+            cell,
+            slot,
+        } => {
+            let mut responses = Vec::new();
+
+            tracing::info!(
+                "Assigning unit {} to slot {:?}:{} at cell {:?}",
+                unit_id,
+                slot.slot_type,
+                slot.index,
+                cell
+            );
+
+            // Convert SlotPosition to DB format
+            let slot_type_str = match slot.slot_type {
+                shared::SlotType::Interior => "interior",
+                shared::SlotType::Exterior => "exterior",
+            };
+            let slot_index = slot.index as i32;
+
+            // Update database
+            match db_tables
+                .units
+                .update_slot_position(unit_id, Some(slot_type_str.to_string()), Some(slot_index))
+                .await
+            {
+                Ok(_) => {
+                    tracing::info!("Unit {} slot assigned in database", unit_id);
+
+                    // Broadcast success to all clients
+                    responses.push(ServerMessage::UnitSlotUpdated {
+                        unit_id,
+                        cell,
+                        slot_position: Some(slot),
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Failed to assign unit slot in database: {}", e);
+                    responses.push(ServerMessage::ActionError {
+                        code: ErrorCode::DatabaseError,
+                        detail: Some(e.to_string()),
+                    });
+                }
+            }
+
+            (responses, vec![], None)
+        }
+        ClientMessage::ActionSendMessage {
+            chunk_id,
+            cell,
+            receivers,
+            content,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let mut responses = Vec::new();
+            let action_table = &db_tables.actions;
+            let specific_data = SpecificAction::SendMessage(SendMessageAction {
+                player_id,
+                receivers,
+                content,
+            });
+
+            let start_time = now_ms();
+            let duration_ms = specific_data.duration_ms(&ActionContext {
+                player_id,
+                grid_cell: cell,
+            });
+
+            let action_data = ActionData {
+                base_data: ActionBaseData {
+                    player_id,
+                    chunk: chunk_id,
+                    cell,
+                    action_type: ActionTypeEnum::SendMessage,
+                    action_specific_type: ActionSpecificTypeEnum::SendMessage,
+                    start_time,
+                    duration_ms,
+                    completion_time: start_time + duration_ms,
+                    status: ActionStatusEnum::Pending,
+                },
+                specific_data,
+            };
+
+            match add_action_and_cache(
+                action_table,
+                action_processor,
+                &action_data,
+                ActionTypeEnum::SendMessage,
+            )
+            .await
+            {
+                Ok(action_id) => {
+                    responses.push(ServerMessage::ActionStatusUpdate {
+                        action_id,
+                        player_id,
+                        chunk_id,
+                        cell,
+                        status: ActionStatusEnum::Pending,
+                        action_type: ActionTypeEnum::SendMessage,
+                        completion_time: start_time + duration_ms,
+                        action_name: None,
+                        unit_ids: vec![],
+                    });
+                }
+                Err(e) => {
+                    tracing::error!("Failed to schedule action: {}", e);
+                    responses.push(ServerMessage::ActionError {
+                        code: ErrorCode::SchedulingFailed,
+                        detail: Some(e.to_string()),
+                    });
+                }
+            }
+
+            (responses, vec![], None)
+        }
+        ClientMessage::RequestOceanData { world_name } => {
+            tracing::info!("=== CLIENT REQUESTED OCEAN DATA ===");
+            tracing::info!("World name: {}", world_name);
+
+            match db_tables.ocean_data.load_ocean_data(&world_name).await {
+                Ok(Some(ocean_data)) => {
+                    tracing::info!("Ocean data loaded from database:");
+                    tracing::info!("  - width: {}", ocean_data.width);
+                    tracing::info!("  - height: {}", ocean_data.height);
+                    tracing::info!("  - sdf_values: {} bytes", ocean_data.sdf_values.len());
+                    tracing::info!(
                         "  - heightmap_values: {} bytes",
                         ocean_data.heightmap_values.len()
                     );
@@ -2107,7 +3810,8 @@ async fn handle_client_message(
                 None => {
                     return (
                         vec![ServerMessage::LordCreateError {
-                            reason: "Non authentifié".to_string(),
+                            code: ErrorCode::NotAuthenticated,
+                            detail: None,
                         }], vec![], None);
                 }
             };
@@ -2122,14 +3826,16 @@ async fn handle_client_message(
                     );
                     return (
                         vec![ServerMessage::LordCreateError {
-                            reason: "Vous avez déjà un Lord/Lady".to_string(),
+                            code: ErrorCode::LordAlreadyExists,
+                            detail: None,
                         }], vec![], None);
                 }
                 Ok(None) => { /* OK, pas de lord existant */ }
                 Err(e) => {
                     return (
                         vec![ServerMessage::LordCreateError {
-                            reason: format!("Erreur: {}", e),
+                            code: ErrorCode::DatabaseError,
+                            detail: Some(e.to_string()),
                         }], vec![], None);
                 }
             }
@@ -2145,13 +3851,15 @@ async fn handle_client_message(
                 Ok(None) => {
                     return (
                         vec![ServerMessage::LordCreateError {
-                            reason: "Joueur introuvable".to_string(),
+                            code: ErrorCode::PlayerNotFound,
+                            detail: None,
                         }], vec![], None);
                 }
                 Err(e) => {
                     return (
                         vec![ServerMessage::LordCreateError {
-                            reason: format!("Erreur DB: {}", e),
+                            code: ErrorCode::DatabaseError,
+                            detail: Some(e.to_string()),
                         }], vec![], None);
                 }
             };
@@ -2217,7 +3925,8 @@ async fn handle_client_message(
                             tracing::error!("Lord created but failed to reload: {}", e);
                             (
                                 vec![ServerMessage::LordCreateError {
-                                    reason: format!("Lord créé mais erreur au chargement: {}", e),
+                                    code: ErrorCode::InternalError,
+                                    detail: Some(format!("Lord créé mais erreur au chargement: {}", e)),
                                 }], vec![], None)
                         }
                     }
@@ -2226,7 +3935,8 @@ async fn handle_client_message(
                     tracing::error!("Failed to create lord: {}", e);
                     (
                         vec![ServerMessage::LordCreateError {
-                            reason: format!("Erreur lors de la création: {}", e),
+                            code: ErrorCode::DatabaseError,
+                            detail: Some(e.to_string()),
                         }], vec![], None)
                 }
             }
@@ -2244,7 +3954,8 @@ async fn handle_client_message(
                 None => {
                     return (
                         vec![ServerMessage::HamletFoundError {
-                            reason: "Non authentifié".to_string(),
+                            code: ErrorCode::NotAuthenticated,
+                            detail: None,
                         }],
                         vec![],
                         None,
@@ -2258,13 +3969,15 @@ async fn handle_client_message(
                 Ok(None) => {
                     return (
                         vec![ServerMessage::HamletFoundError {
-                            reason: "Vous n'avez pas de Lord/Lady".to_string(),
+                            code: ErrorCode::LordNotFound,
+                            detail: None,
                         }], vec![], None);
                 }
                 Err(e) => {
                     return (
                         vec![ServerMessage::HamletFoundError {
-                            reason: format!("Erreur: {}", e),
+                            code: ErrorCode::DatabaseError,
+                            detail: Some(e.to_string()),
                         }], vec![], None);
                 }
             };
@@ -2282,13 +3995,15 @@ async fn handle_client_message(
             {
                 Ok(Some(_)) => {
                     return (vec![ServerMessage::HamletFoundError {
-                        reason: "Cette cellule appartient déjà à un territoire".to_string(),
+                        code: ErrorCode::CellAlreadyClaimed,
+                        detail: None,
                     }], vec![], None);
                 }
                 Ok(None) => { /* libre, on continue */ }
                 Err(e) => {
                     return (vec![ServerMessage::HamletFoundError {
-                        reason: format!("Erreur DB: {}", e),
+                        code: ErrorCode::DatabaseError,
+                        detail: Some(e.to_string()),
                     }], vec![], None);
                 }
             }
@@ -2305,17 +4020,16 @@ async fn handle_client_message(
                 Ok(Some(existing_org_id)) => {
                     return (
                         vec![ServerMessage::HamletFoundError {
-                            reason: format!(
-                                "Vous avez déjà une organisation (ID: {})",
-                                existing_org_id
-                            ),
+                            code: ErrorCode::OrganizationAlreadyExists,
+                            detail: Some(existing_org_id.to_string()),
                         }], vec![], None);
                 }
                 Ok(None) => { /* OK */ }
                 Err(e) => {
                     return (
                         vec![ServerMessage::HamletFoundError {
-                            reason: format!("Erreur DB: {}", e),
+                            code: ErrorCode::DatabaseError,
+                            detail: Some(e.to_string()),
                         }], vec![], None);
                 }
             }
@@ -2348,7 +4062,8 @@ async fn handle_client_message(
                     tracing::error!("Failed to create hamlet: {}", e);
                     return (
                         vec![ServerMessage::HamletFoundError {
-                            reason: format!("Échec de la création: {}", e),
+                            code: ErrorCode::DatabaseError,
+                            detail: Some(e.to_string()),
                         }], vec![], None);
                 }
             };
@@ -2360,7 +4075,21 @@ async fn handle_client_message(
                 player_id
             );
 
+            // 6b. Le seigneur prend le titre de chef du hameau
+            let hamlet_tier = game_state.settlement_tier(shared::OrganizationType::Hamlet);
+            let leader_role = hamlet_tier
+                .map(|tier| tier.leader_role)
+                .unwrap_or(shared::RoleType::Headman);
+            if let Err(e) = db_tables
+                .organizations
+                .add_officer(org_id, lord.id, leader_role, None)
+                .await
+            {
+                tracing::warn!("Hamlet {}: could not name lord {} {:?}: {}", org_id, lord.id, leader_role, e);
+            }
+
             // 7. Claim territoire — Voronoï si disponible, sinon voisins
+            let claim_radius = hamlet_tier.map(|tier| tier.claim_radius).unwrap_or(1);
             let mut claimed_cells = Vec::new();
 
             match db_tables.voronoi_zones.get_zone_at_cell(cell).await {
@@ -2404,6 +4133,7 @@ async fn handle_client_message(
                                         db_tables,
                                         org_id,
                                         &cell,
+                                        claim_radius,
                                         &mut claimed_cells,
                                     )
                                     .await;
@@ -2415,14 +4145,21 @@ async fn handle_client_message(
                                 "Voronoi zone {} not available, using fallback",
                                 zone_id
                             );
-                            claim_cell_and_neighbors(db_tables, org_id, &cell, &mut claimed_cells)
-                                .await;
-                        }
+                            claim_cell_and_neighbors(
+                                db_tables,
+                                org_id,
+                                &cell,
+                                claim_radius,
+                                &mut claimed_cells,
+                            )
+                            .await;
+                        }
                     }
                 }
                 _ => {
                     tracing::info!("No Voronoi zone at cell, using fallback (cell + neighbors)");
-                    claim_cell_and_neighbors(db_tables, org_id, &cell, &mut claimed_cells).await;
+                    claim_cell_and_neighbors(db_tables, org_id, &cell, claim_radius, &mut claimed_cells)
+                        .await;
                 }
             }
 
@@ -2433,61 +4170,9 @@ async fn handle_client_message(
             );
 
             // 8. Générer les contours territoriaux
-            let mut contour_messages = Vec::new();
-
-            match db_tables.organizations.load_territory_cells(org_id).await {
-                Ok(territory_cells) if !territory_cells.is_empty() => {
-                    use hexx::Hex;
-                    let territory_hex: std::collections::HashSet<Hex> =
-                        territory_cells.iter().map(|c| c.to_hex()).collect();
-
-                    let contour_points = &world::territory::build_contour(
-                        &grid_config.layout,
-                        &territory_hex,
-                        0.0,
-                        org_id as u64,
-                    );
-
-                    let contour_chunks = utils::chunks::split_contour_into_chunks(contour_points);
-
-                    tracing::info!(
-                        "Generated {} contour chunks for hamlet {}",
-                        contour_chunks.len(),
-                        org_id
-                    );
-
-                    // Stocker les contours en DB
-                    for (chunk_id, contour_segments) in &contour_chunks {
-                        let _ = db_tables
-                            .territory_contours
-                            .store_contour(org_id, chunk_id.x, chunk_id.y, contour_segments)
-                            .await;
-                    }
-
-                    // Préparer les messages TerritoryContourUpdate à envoyer au client
-                    // Regrouper par chunk_id
-                    for (chunk_id, contour_segments) in &contour_chunks {
-                        let (border_color, fill_color) =
-                            world::territory::generate_org_colors(org_id);
-
-                        contour_messages.push(ServerMessage::TerritoryContourUpdate {
-                            chunk_id: *chunk_id,
-                            contours: vec![shared::protocol::TerritoryContourChunkData {
-                                organization_id: org_id,
-                                chunk_id: *chunk_id,
-                                segments: contour_segments
-                                    .iter()
-                                    .map(ContourSegmentData::from_contour_segment)
-                                    .collect(),
-                                border_color: ColorData::from_array(border_color),
-                                fill_color: ColorData::from_array(fill_color),
-                            }],
-                        });
-                    }
-                }
-                _ => {
-                    tracing::warn!("No territory cells after founding hamlet {}", org_id);
-                }
+            let contour_messages = territory_contour_messages(db_tables, grid_config, org_id).await;
+            if contour_messages.is_empty() {
+                tracing::warn!("No territory cells after founding hamlet {}", org_id);
             }
 
             // 9. Envoyer les réponses
@@ -2504,152 +4189,1112 @@ async fn handle_client_message(
             (responses, vec![], None)
         }
 
-        // ====================================================================
-        // DEBUG COMMANDS
-        // ====================================================================
-        ClientMessage::DebugCreateOrganization {
-            name,
-            organization_type,
-            cell,
-            parent_organization_id,
-        } => {
+        ClientMessage::UpgradeOrganization => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+                return upgrade_error(ErrorCode::LordNotFound, None);
+            };
+            let organization = match db_tables.organizations.find_unit_organization(lord.id).await {
+                Ok(Some(id)) => db_tables.organizations.load_organization(id).await,
+                Ok(None) => return upgrade_error(ErrorCode::NoOrganization, None),
+                Err(e) => Err(e),
+            };
+            let organization = match organization {
+                Ok(organization) => organization,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return upgrade_error(ErrorCode::DatabaseError, None);
+                }
+            };
+
+            // Le chef, ou un officier chargé du territoire, demande la charte
+            match has_organization_permission(
+                db_tables,
+                organization.id,
+                lord.id,
+                OrganizationPermission::ManageTerritory,
+            )
+            .await
+            {
+                Ok(true) => {}
+                Ok(false) => return upgrade_error(ErrorCode::InsufficientAuthority, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return upgrade_error(ErrorCode::DatabaseError, None);
+                }
+            }
+
+            let (Some(current_tier), Some(next_tier)) = (
+                game_state.settlement_tier(organization.organization_type),
+                game_state.next_settlement_tier(organization.organization_type),
+            ) else {
+                return upgrade_error(ErrorCode::NoSettlementTier, None);
+            };
+
+            // 1. Vérifier population, territoire, bâtiments et trésor
+            let stats = async {
+                Ok::<_, String>(shared::SettlementStats {
+                    population: organization.population,
+                    territory_cells: db_tables
+                        .organizations
+                        .load_territory_cells(organization.id)
+                        .await?
+                        .len() as i32,
+                    buildings: db_tables
+                        .organizations
+                        .load_territory_building_types(organization.id)
+                        .await?,
+                    treasury_gold: organization.treasury_gold,
+                })
+            }
+            .await;
+            let stats = match stats {
+                Ok(stats) => stats,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return upgrade_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            let missing = next_tier.missing_requirements(&stats);
+            if !missing.is_empty() {
+                return upgrade_error(ErrorCode::SettlementRequirementsNotMet, Some(missing.join(", ")));
+            }
+
+            // 2. Changer de type et payer la charte
+            let name = next_tier.rename(&organization.name, current_tier);
+            match db_tables
+                .organizations
+                .upgrade_settlement(
+                    organization.id,
+                    organization.organization_type,
+                    next_tier.organization_type,
+                    &name,
+                    next_tier.cost_gold,
+                )
+                .await
+            {
+                Ok(true) => {}
+                // Le trésor a bougé entre la vérification et la mise à jour
+                Ok(false) => return upgrade_error(ErrorCode::SettlementRequirementsNotMet, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return upgrade_error(ErrorCode::DatabaseError, Some(e));
+                }
+            }
+
             tracing::info!(
-                "DEBUG: Creating organization '{}' of type {:?} at {:?}",
+                "✓ {} ({}) rose from {:?} to {:?}",
                 name,
-                organization_type,
-                cell
+                organization.id,
+                organization.organization_type,
+                next_tier.organization_type
             );
 
-            // First, create a leader unit for the organization
-            let (first_name, last_name, gender, portrait_variant_id, avatar_url) = {
-                use crate::units::PortraitGenerator;
-                use rand::Rng;
-                let mut rng = rand::rng();
+            // 3. Le chef prend le titre du nouveau palier (pas l'officier qui a demandé la charte)
+            let leader_role = next_tier.leader_role;
+            if let Some(leader_unit_id) = organization.leader_unit_id {
+                let titled = async {
+                    let officers = db_tables.organizations.load_officers(organization.id).await?;
+                    match officers
+                        .iter()
+                        .find(|o| o.unit_id == leader_unit_id && o.role.is_territorial_leader())
+                    {
+                        Some(officer) if officer.role == leader_role => Ok(()),
+                        Some(officer) => {
+                            db_tables
+                                .organizations
+                                .update_officer_role(officer.id, leader_role)
+                                .await
+                        }
+                        None => db_tables
+                            .organizations
+                            .add_officer(organization.id, leader_unit_id, leader_role, None)
+                            .await
+                            .map(|_| ()),
+                    }
+                }
+                .await;
+                if let Err(e) = titled {
+                    tracing::warn!(
+                        "{}: could not name leader {} {:?}: {}",
+                        name,
+                        leader_unit_id,
+                        leader_role,
+                        e
+                    );
+                }
+            }
 
-                // Generate random gender
-                let is_male = rng.random_bool(0.5);
-                let gender_str = if is_male { "male" } else { "female" };
+            // 4. Étendre le territoire autour du siège
+            let headquarters = organization.headquarters_cell.unwrap_or(lord.current_cell);
+            let mut claimed_cells = Vec::new();
+            claim_cell_and_neighbors(
+                db_tables,
+                organization.id,
+                &headquarters,
+                next_tier.claim_radius,
+                &mut claimed_cells,
+            )
+            .await;
+            tracing::info!("{} claimed {} more territory cells", name, claimed_cells.len());
+
+            // 5. Diffuser les nouveaux contours et le nouveau palier
+            for msg in territory_contour_messages(db_tables, grid_config, organization.id).await {
+                if let ServerMessage::TerritoryContourUpdate { chunk_id, .. } = &msg {
+                    let chunk_id = *chunk_id;
+                    broadcast_message(sessions, &chunk_id, msg).await;
+                }
+            }
+            sessions
+                .broadcast(ServerMessage::OrganizationUpgraded {
+                    organization_id: organization.id,
+                    organization_type: next_tier.organization_type,
+                    name,
+                })
+                .await;
 
-                // Use NameGenerator to get realistic names based on gender
-                let (first_name, last_name) = name_generator.generate_random_name(Some(is_male));
+            // Le trésor du chef a payé la charte
+            match treasury_data(db_tables, organization.id, lord.id).await {
+                Ok(msg) => (vec![msg], vec![], None),
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    (vec![], vec![], None)
+                }
+            }
+        }
 
-                // Generate portrait for Merchant profession
-                let (variant_id, avatar_url) = PortraitGenerator::generate_variant_and_url(
-                    gender_str,
-                    shared::ProfessionEnum::Merchant,
-                );
+        ClientMessage::OfferVassalage {
+            liege_organization_id,
+            vassal_organization_id,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let own = match led_organization(db_tables, player_id).await {
+                Ok(organization) => organization,
+                Err(code) => return vassalage_error(code, None),
+            };
 
-                (
-                    first_name,
-                    last_name,
-                    gender_str.to_string(),
-                    variant_id,
-                    avatar_url,
-                )
+            // On propose pour sa propre organisation, comme suzerain ou comme vassal
+            let other_id = if own.id == liege_organization_id {
+                vassal_organization_id
+            } else if own.id == vassal_organization_id {
+                liege_organization_id
+            } else {
+                return vassalage_error(ErrorCode::InsufficientAuthority, None);
+            };
+            let other = match db_tables.organizations.load_organization(other_id).await {
+                Ok(organization) => organization,
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    return vassalage_error(ErrorCode::OrganizationNotFound, None);
+                }
+            };
+            let (liege, vassal) = if own.id == liege_organization_id {
+                (&own, &other)
+            } else {
+                (&other, &own)
             };
+            if let Err(code) = check_vassalage(db_tables, liege, vassal).await {
+                return vassalage_error(code, None);
+            }
 
-            // Create the leader unit
-            let founder_unit_result = db_tables
-                .units
-                .create_unit(
-                    None,
-                    first_name.clone(),
-                    last_name.clone(),
-                    gender.clone(),
-                    portrait_variant_id,
-                    avatar_url,
-                    cell,
-                    shared::TerrainChunkId { x: 0, y: 0 },
-                    shared::ProfessionEnum::Merchant,
-                    false,
-                    None,
-                )
-                .await;
+            let offer = async {
+                let offer_id = db_tables
+                    .organizations
+                    .create_vassalage_offer(liege.id, vassal.id, own.id)
+                    .await?;
+                db_tables
+                    .organizations
+                    .load_vassalage_offers(own.id)
+                    .await?
+                    .into_iter()
+                    .find(|offer| offer.id == offer_id)
+                    .ok_or_else(|| format!("Vassalage offer {} vanished", offer_id))
+            }
+            .await;
+            let offer = match offer {
+                Ok(offer) => offer,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return vassalage_error(ErrorCode::DatabaseError, None);
+                }
+            };
 
-            match founder_unit_result {
-                Ok(founder_unit_id) => {
-                    let request = shared::CreateOrganizationRequest {
-                        name: name.clone(),
-                        organization_type,
-                        headquarters_cell: Some(cell),
-                        parent_organization_id,
-                        founder_unit_id,
-                    };
+            tracing::info!(
+                "{} offers {} to become the vassal of {}",
+                own.name,
+                vassal.name,
+                liege.name
+            );
+            notify_organization_leader(
+                sessions,
+                db_tables,
+                other.id,
+                ServerMessage::VassalageOffered { offer: offer.clone() },
+            )
+            .await;
+            (vec![ServerMessage::VassalageOffered { offer }], vec![], None)
+        }
 
-                    match db_tables.organizations.create_organization(request).await {
-                        Ok(org_id) => {
-                            tracing::info!("✓ Organization created with ID {}", org_id);
+        ClientMessage::AcceptVassalage { offer_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let own = match led_organization(db_tables, player_id).await {
+                Ok(organization) => organization,
+                Err(code) => return vassalage_error(code, None),
+            };
+            let offer = match db_tables.organizations.load_vassalage_offers(own.id).await {
+                Ok(offers) => offers.into_iter().find(|offer| offer.id == offer_id),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return vassalage_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            let Some(offer) = offer else {
+                return vassalage_error(ErrorCode::VassalageOfferNotFound, None);
+            };
+            // On n'accepte pas sa propre proposition
+            if offer.offered_by_organization_id == own.id {
+                return vassalage_error(ErrorCode::InvalidVassalage, None);
+            }
 
-                            // Try to claim entire Voronoi zone
-                            let mut claimed_count = 0;
+            // Les rangs ou les liens ont pu changer depuis la proposition
+            let parties = async {
+                Ok::<_, String>((
+                    db_tables.organizations.load_organization(offer.liege_organization_id).await?,
+                    db_tables.organizations.load_organization(offer.vassal_organization_id).await?,
+                ))
+            }
+            .await;
+            let (liege, vassal) = match parties {
+                Ok(parties) => parties,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return vassalage_error(ErrorCode::OrganizationNotFound, None);
+                }
+            };
+            if let Err(code) = check_vassalage(db_tables, &liege, &vassal).await {
+                return vassalage_error(code, None);
+            }
 
-                            match db_tables.voronoi_zones.get_zone_at_cell(cell).await {
-                                Ok(Some(zone_id)) => {
-                                    tracing::info!(
-                                        "Found Voronoi zone {} at cell {:?}",
-                                        zone_id,
-                                        cell
-                                    );
+            match db_tables.organizations.swear_fealty(vassal.id, liege.id).await {
+                Ok(true) => {}
+                Ok(false) => return vassalage_error(ErrorCode::AlreadyVassal, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return vassalage_error(ErrorCode::DatabaseError, None);
+                }
+            }
+            if let Err(e) = db_tables
+                .organizations
+                .delete_vassalage_offers_between(liege.id, vassal.id)
+                .await
+            {
+                tracing::warn!("{}", e);
+            }
 
-                                    // Check if zone is available
-                                    match db_tables.voronoi_zones.is_zone_available(zone_id).await {
-                                        Ok(true) => {
-                                            // Zone is available, claim all cells
-                                            match db_tables
-                                                .voronoi_zones
-                                                .get_zone_cells(zone_id)
-                                                .await
-                                            {
-                                                Ok(zone_cells) => {
-                                                    tracing::info!(
-                                                        "Claiming {} cells from Voronoi zone {}",
-                                                        zone_cells.len(),
-                                                        zone_id
-                                                    );
+            tracing::info!("✓ {} swore fealty to {}", vassal.name, liege.name);
 
-                                                    // Add all cells to territory
-                                                    for zone_cell in &zone_cells {
-                                                        if let Err(e) = db_tables
-                                                            .organizations
-                                                            .add_territory_cell(org_id, zone_cell)
-                                                            .await
-                                                        {
-                                                            tracing::warn!(
-                                                                "Failed to add cell {:?}: {}",
-                                                                zone_cell,
-                                                                e
-                                                            );
-                                                        } else {
-                                                            claimed_count += 1;
-                                                        }
-                                                    }
+            let changed = ServerMessage::VassalageChanged {
+                vassal_organization_id: vassal.id,
+                liege_organization_id: Some(liege.id),
+            };
+            let other_id = if own.id == liege.id { vassal.id } else { liege.id };
+            notify_organization_leader(sessions, db_tables, other_id, changed.clone()).await;
+            (vec![changed], vec![], None)
+        }
 
-                                                    // Link organization to zone
-                                                    let link_result = sqlx::query(
-                                                        "UPDATE organizations.organizations SET voronoi_zone_id = $1 WHERE id = $2"
-                                                    )
-                                                    .bind(zone_id)
-                                                    .bind(org_id as i64)
-                                                    .execute(&db_tables.pool)
-                                                    .await;
+        ClientMessage::RevokeVassalage { organization_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let own = match led_organization(db_tables, player_id).await {
+                Ok(organization) => organization,
+                Err(code) => return vassalage_error(code, None),
+            };
 
-                                                    if let Err(e) = link_result {
-                                                        tracing::warn!(
-                                                            "Failed to link zone to organization: {}",
-                                                            e
-                                                        );
-                                                    }
+            // Rompre avec son suzerain, affranchir un vassal, ou retirer une proposition
+            let released = async {
+                if db_tables.organizations.release_vassal(own.id, organization_id).await? {
+                    return Ok::<_, String>(Some((own.id, organization_id)));
+                }
+                if db_tables.organizations.release_vassal(organization_id, own.id).await? {
+                    return Ok(Some((organization_id, own.id)));
+                }
+                Ok(None)
+            }
+            .await;
+            let released = match released {
+                Ok(released) => released,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return vassalage_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            let closed_offers = match db_tables
+                .organizations
+                .delete_vassalage_offers_between(own.id, organization_id)
+                .await
+            {
+                Ok(ids) => ids,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return vassalage_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            if released.is_none() && closed_offers.is_empty() {
+                return vassalage_error(ErrorCode::VassalageOfferNotFound, None);
+            }
 
-                                                    tracing::info!(
-                                                        "✓ Organization {} claimed {} cells from Voronoi zone {}",
-                                                        org_id,
-                                                        claimed_count,
-                                                        zone_id
-                                                    );
-                                                }
-                                                Err(e) => {
-                                                    tracing::error!(
-                                                        "Failed to get zone cells: {}",
+            let mut responses: Vec<ServerMessage> = closed_offers
+                .into_iter()
+                .map(|offer_id| ServerMessage::VassalageOfferClosed { offer_id })
+                .collect();
+            if let Some((vassal_id, liege_id)) = released {
+                tracing::info!("Organization {} is no longer the vassal of {}", vassal_id, liege_id);
+                responses.push(ServerMessage::VassalageChanged {
+                    vassal_organization_id: vassal_id,
+                    liege_organization_id: None,
+                });
+            }
+            for msg in &responses {
+                notify_organization_leader(sessions, db_tables, organization_id, msg.clone()).await;
+            }
+            (responses, vec![], None)
+        }
+
+        ClientMessage::RequestDiplomacy => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+                return action_error(ErrorCode::LordNotFound, None);
+            };
+            let organization_id = match db_tables.organizations.find_unit_organization(lord.id).await {
+                Ok(Some(id)) => id,
+                Ok(None) => return action_error(ErrorCode::NoOrganization, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
+                }
+            };
+
+            let diplomacy = async {
+                // Une relation échue mais pas encore levée par le tick n'est plus en vigueur
+                let now = (now_ms() / 1000) as i64;
+                let relations: Vec<shared::DiplomaticRelation> = db_tables
+                    .organizations
+                    .load_diplomatic_relations(organization_id)
+                    .await?
+                    .into_iter()
+                    .filter(|relation| !relation.is_expired(now))
+                    .collect();
+                let proposals = db_tables
+                    .organizations
+                    .load_diplomatic_proposals(organization_id)
+                    .await?;
+                let ids: Vec<u64> = relations
+                    .iter()
+                    .map(|relation| relation.target_organization_id)
+                    .chain(proposals.iter().flat_map(|proposal| {
+                        [proposal.organization_id, proposal.target_organization_id]
+                    }))
+                    .filter(|id| *id != organization_id)
+                    .collect();
+                let organizations = db_tables.organizations.load_organization_summaries(&ids).await?;
+                Ok::<_, String>((relations, proposals, organizations))
+            }
+            .await;
+
+            match diplomacy {
+                Ok((relations, proposals, organizations)) => (
+                    vec![ServerMessage::DiplomacyData {
+                        organization_id,
+                        relations,
+                        proposals,
+                        organizations,
+                    }],
+                    vec![],
+                    None,
+                ),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    action_error(ErrorCode::DatabaseError, None)
+                }
+            }
+        }
+
+        ClientMessage::InviteToOrganization { unit_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let (organization, lord_id) = match authorized_organization(
+                db_tables,
+                player_id,
+                OrganizationPermission::ManageMembers,
+            )
+            .await
+            {
+                Ok(authorized) => authorized,
+                Err(code) => return membership_error(code, None),
+            };
+            // On n'invite que le seigneur d'un autre joueur
+            match db_tables.units.load_unit(unit_id).await {
+                Ok(unit) if unit.is_lord && unit.player_id.is_some() => {}
+                Ok(_) => return membership_error(ErrorCode::LordNotFound, None),
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    return membership_error(ErrorCode::UnitNotFound, None);
+                }
+            }
+            match db_tables.organizations.find_unit_organization(unit_id).await {
+                Ok(None) => {}
+                Ok(Some(_)) => return membership_error(ErrorCode::AlreadyMember, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            }
+
+            let request = async {
+                let id = db_tables
+                    .organizations
+                    .create_membership_request(
+                        organization.id,
+                        unit_id,
+                        MembershipRequestKind::Invitation,
+                        Some(lord_id),
+                    )
+                    .await?;
+                db_tables.organizations.load_membership_request(id).await
+            }
+            .await;
+            let request = match request {
+                Ok(Some(request)) => request,
+                Ok(None) => return membership_error(ErrorCode::MembershipRequestNotFound, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            tracing::info!(
+                "Organization {} invited lord {} (request {})",
+                organization.id,
+                unit_id,
+                request.id
+            );
+
+            let msg = ServerMessage::MembershipRequestReceived { request };
+            notify_lord_player(sessions, db_tables, unit_id, msg.clone()).await;
+            (vec![msg], vec![], None)
+        }
+
+        ClientMessage::ApplyToOrganization { organization_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+                return membership_error(ErrorCode::LordNotFound, None);
+            };
+            match db_tables.organizations.find_unit_organization(lord.id).await {
+                Ok(None) => {}
+                Ok(Some(_)) => return membership_error(ErrorCode::AlreadyMember, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            }
+            if db_tables.organizations.load_organization(organization_id).await.is_err() {
+                return membership_error(ErrorCode::OrganizationNotFound, None);
+            }
+
+            let request = async {
+                let id = db_tables
+                    .organizations
+                    .create_membership_request(
+                        organization_id,
+                        lord.id,
+                        MembershipRequestKind::Application,
+                        None,
+                    )
+                    .await?;
+                db_tables.organizations.load_membership_request(id).await
+            }
+            .await;
+            let request = match request {
+                Ok(Some(request)) => request,
+                Ok(None) => return membership_error(ErrorCode::MembershipRequestNotFound, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            tracing::info!(
+                "Lord {} applied to organization {} (request {})",
+                lord.id,
+                organization_id,
+                request.id
+            );
+
+            let msg = ServerMessage::MembershipRequestReceived { request };
+            notify_organization_leader(sessions, db_tables, organization_id, msg.clone()).await;
+            (vec![msg], vec![], None)
+        }
+
+        ClientMessage::AcceptMembershipRequest { request_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let request = match db_tables.organizations.load_membership_request(request_id).await {
+                Ok(Some(request)) => request,
+                Ok(None) => return membership_error(ErrorCode::MembershipRequestNotFound, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            // Une invitation se règle par le seigneur invité, une candidature par l'organisation
+            let invited_by = match request.kind {
+                MembershipRequestKind::Invitation => {
+                    match db_tables.units.load_lord_for_player(player_id).await {
+                        Ok(Some(lord)) if lord.id == request.unit_id => None,
+                        Ok(_) => return membership_error(ErrorCode::MembershipRequestNotFound, None),
+                        Err(e) => {
+                            tracing::error!("{}", e);
+                            return membership_error(ErrorCode::DatabaseError, None);
+                        }
+                    }
+                }
+                MembershipRequestKind::Application => match authorized_organization(
+                    db_tables,
+                    player_id,
+                    OrganizationPermission::ManageMembers,
+                )
+                .await
+                {
+                    Ok((organization, lord_id)) if organization.id == request.organization_id => {
+                        Some(lord_id)
+                    }
+                    Ok(_) => return membership_error(ErrorCode::InsufficientAuthority, None),
+                    Err(code) => return membership_error(code, None),
+                },
+            };
+            match db_tables.organizations.find_unit_organization(request.unit_id).await {
+                Ok(None) => {}
+                Ok(Some(_)) => {
+                    let _ = db_tables.organizations.delete_membership_request(request.id).await;
+                    return membership_error(ErrorCode::AlreadyMember, None);
+                }
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            }
+
+            if let Err(e) = db_tables
+                .organizations
+                .join_organization(request.organization_id, request.unit_id, invited_by)
+                .await
+            {
+                tracing::error!("{}", e);
+                return membership_error(ErrorCode::DatabaseError, None);
+            }
+            tracing::info!(
+                "Lord {} joined organization {}",
+                request.unit_id,
+                request.organization_id
+            );
+
+            let closed = ServerMessage::MembershipRequestClosed { request_id: request.id };
+            notify_lord_player(sessions, db_tables, request.unit_id, closed.clone()).await;
+            notify_organization_leader(sessions, db_tables, request.organization_id, closed).await;
+            notify_lord_player(
+                sessions,
+                db_tables,
+                request.unit_id,
+                player_organization_data(db_tables, request.unit_id).await,
+            )
+            .await;
+            notify_organization_members(
+                sessions,
+                db_tables,
+                request.organization_id,
+                ServerMessage::MembershipChanged {
+                    organization_id: request.organization_id,
+                    unit_id: request.unit_id,
+                    member: true,
+                },
+            )
+            .await;
+            (vec![], vec![], None)
+        }
+
+        ClientMessage::DeclineMembershipRequest { request_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let request = match db_tables.organizations.load_membership_request(request_id).await {
+                Ok(Some(request)) => request,
+                Ok(None) => return membership_error(ErrorCode::MembershipRequestNotFound, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            // Le seigneur concerné ou quelqu'un qui gère les membres de l'organisation
+            let is_lord = matches!(
+                db_tables.units.load_lord_for_player(player_id).await,
+                Ok(Some(lord)) if lord.id == request.unit_id
+            );
+            if !is_lord {
+                match authorized_organization(db_tables, player_id, OrganizationPermission::ManageMembers)
+                    .await
+                {
+                    Ok((organization, _)) if organization.id == request.organization_id => {}
+                    Ok(_) => return membership_error(ErrorCode::InsufficientAuthority, None),
+                    Err(code) => return membership_error(code, None),
+                }
+            }
+            match db_tables.organizations.delete_membership_request(request.id).await {
+                Ok(true) => {}
+                Ok(false) => return membership_error(ErrorCode::MembershipRequestNotFound, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            }
+
+            let closed = ServerMessage::MembershipRequestClosed { request_id: request.id };
+            if is_lord {
+                notify_organization_leader(sessions, db_tables, request.organization_id, closed.clone())
+                    .await;
+            } else {
+                notify_lord_player(sessions, db_tables, request.unit_id, closed.clone()).await;
+            }
+            (vec![closed], vec![], None)
+        }
+
+        ClientMessage::ExpelMember { unit_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let (organization, actor_unit_id) = match authorized_organization(
+                db_tables,
+                player_id,
+                OrganizationPermission::ManageMembers,
+            )
+            .await
+            {
+                Ok(authorized) => authorized,
+                Err(code) => return membership_error(code, None),
+            };
+            if organization.leader_unit_id == Some(unit_id) {
+                return membership_error(ErrorCode::InsufficientAuthority, None);
+            }
+            // Un officier n'exclut que les membres de rang inférieur au sien
+            match db_tables.organizations.load_officers(organization.id).await {
+                Ok(officers) if organization.outranks(actor_unit_id, unit_id, &officers) => {}
+                Ok(_) => return membership_error(ErrorCode::InsufficientAuthority, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            }
+            // Seuls les seigneurs s'excluent : les habitants suivent l'établissement
+            match db_tables.units.load_unit(unit_id).await {
+                Ok(unit) if unit.is_lord => {}
+                Ok(_) => return membership_error(ErrorCode::NotAMember, None),
+                Err(e) => {
+                    tracing::warn!("{}", e);
+                    return membership_error(ErrorCode::UnitNotFound, None);
+                }
+            }
+            match db_tables.organizations.leave_organization(organization.id, unit_id).await {
+                Ok(true) => {}
+                Ok(false) => return membership_error(ErrorCode::NotAMember, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            }
+            tracing::info!("Lord {} was expelled from organization {}", unit_id, organization.id);
+
+            // Le départ est enregistré : prévenir les membres restants, puis l'exclu
+            let changed = ServerMessage::MembershipChanged {
+                organization_id: organization.id,
+                unit_id,
+                member: false,
+            };
+            notify_organization_members(sessions, db_tables, organization.id, changed.clone()).await;
+            notify_lord_player(sessions, db_tables, unit_id, changed).await;
+            notify_lord_player(
+                sessions,
+                db_tables,
+                unit_id,
+                player_organization_data(db_tables, unit_id).await,
+            )
+            .await;
+            (vec![], vec![], None)
+        }
+
+        ClientMessage::LeaveOrganization => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+                return membership_error(ErrorCode::LordNotFound, None);
+            };
+            let organization = match db_tables.organizations.find_unit_organization(lord.id).await {
+                Ok(Some(id)) => db_tables.organizations.load_organization(id).await,
+                Ok(None) => return membership_error(ErrorCode::NoOrganization, None),
+                Err(e) => Err(e),
+            };
+            let organization = match organization {
+                Ok(organization) => organization,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            if organization.leader_unit_id == Some(lord.id) {
+                return membership_error(ErrorCode::LeaderCannotLeave, None);
+            }
+            if let Err(e) = db_tables.organizations.leave_organization(organization.id, lord.id).await {
+                tracing::error!("{}", e);
+                return membership_error(ErrorCode::DatabaseError, None);
+            }
+            tracing::info!("Lord {} left organization {}", lord.id, organization.id);
+
+            let changed = ServerMessage::MembershipChanged {
+                organization_id: organization.id,
+                unit_id: lord.id,
+                member: false,
+            };
+            notify_organization_members(sessions, db_tables, organization.id, changed.clone()).await;
+            (
+                vec![changed, player_organization_data(db_tables, lord.id).await],
+                vec![],
+                None,
+            )
+        }
+
+        ClientMessage::AppointOfficer { unit_id, role } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            // Seul le chef distribue les charges
+            let organization = match led_organization(db_tables, player_id).await {
+                Ok(organization) => organization,
+                Err(code) => return membership_error(code, None),
+            };
+            if !role.is_appointable() || organization.leader_unit_id == Some(unit_id) {
+                return membership_error(ErrorCode::InvalidOfficerRole, None);
+            }
+            match db_tables.organizations.load_member_summaries(organization.id).await {
+                Ok(members) if members.iter().any(|member| member.unit_id == unit_id) => {}
+                Ok(_) => return membership_error(ErrorCode::NotAMember, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            }
+            if let Err(e) = db_tables
+                .organizations
+                .appoint_officer(
+                    organization.id,
+                    unit_id,
+                    role,
+                    organization.leader_unit_id.unwrap_or_default(),
+                )
+                .await
+            {
+                tracing::error!("{}", e);
+                return membership_error(ErrorCode::DatabaseError, None);
+            }
+            tracing::info!(
+                "Lord {} appointed {} of organization {}",
+                unit_id,
+                role.to_string(),
+                organization.id
+            );
+
+            notify_organization_members(
+                sessions,
+                db_tables,
+                organization.id,
+                ServerMessage::OfficerChanged {
+                    organization_id: organization.id,
+                    unit_id,
+                    role: Some(role),
+                },
+            )
+            .await;
+            (vec![], vec![], None)
+        }
+
+        ClientMessage::DismissOfficer { unit_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let organization = match led_organization(db_tables, player_id).await {
+                Ok(organization) => organization,
+                Err(code) => return membership_error(code, None),
+            };
+            // Le chef garde son titre : il ne le perd qu'en perdant la tête de l'organisation
+            if organization.leader_unit_id == Some(unit_id) {
+                return membership_error(ErrorCode::InvalidOfficerRole, None);
+            }
+            match db_tables.organizations.dismiss_officer(organization.id, unit_id).await {
+                Ok(true) => {}
+                Ok(false) => return membership_error(ErrorCode::NotAMember, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return membership_error(ErrorCode::DatabaseError, None);
+                }
+            }
+            tracing::info!("Lord {} dismissed from office in organization {}", unit_id, organization.id);
+
+            notify_organization_members(
+                sessions,
+                db_tables,
+                organization.id,
+                ServerMessage::OfficerChanged {
+                    organization_id: organization.id,
+                    unit_id,
+                    role: None,
+                },
+            )
+            .await;
+            (vec![], vec![], None)
+        }
+
+        ClientMessage::RequestMembers => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+                return membership_error(ErrorCode::LordNotFound, None);
+            };
+            match members_data(db_tables, lord.id).await {
+                Ok(msg) => (vec![msg], vec![], None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    membership_error(ErrorCode::DatabaseError, None)
+                }
+            }
+        }
+
+        ClientMessage::RequestOrganizationHierarchy { organization_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+
+            let hierarchy = async {
+                // L'arbre complet part du plus haut suzerain
+                let root_id = db_tables
+                    .organizations
+                    .load_liege_chain(organization_id)
+                    .await?
+                    .last()
+                    .copied()
+                    .unwrap_or(organization_id);
+                let links = db_tables.organizations.load_vassal_tree(root_id).await?;
+                let ids: Vec<u64> = links.iter().map(|(id, _)| *id).collect();
+                let organizations = db_tables.organizations.load_organization_summaries(&ids).await?;
+                Ok::<_, String>((root_id, organizations, OrganizationHierarchy::from_links(&links)))
+            }
+            .await;
+            let (root_organization_id, organizations, hierarchy) = match hierarchy {
+                Ok(hierarchy) => hierarchy,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return vassalage_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            if organizations.is_empty() {
+                return vassalage_error(ErrorCode::OrganizationNotFound, None);
+            }
+
+            // Les propositions en cours ne regardent que les membres de l'organisation
+            let is_member = match db_tables.units.load_lord_for_player(player_id).await {
+                Ok(Some(lord)) => matches!(
+                    db_tables.organizations.find_unit_organization(lord.id).await,
+                    Ok(Some(id)) if id == organization_id
+                ),
+                _ => false,
+            };
+            let offers = if is_member {
+                db_tables
+                    .organizations
+                    .load_vassalage_offers(organization_id)
+                    .await
+                    .unwrap_or_default()
+            } else {
+                vec![]
+            };
+
+            (
+                vec![ServerMessage::OrganizationHierarchyData {
+                    root_organization_id,
+                    organizations,
+                    hierarchy,
+                    offers,
+                }],
+                vec![],
+                None,
+            )
+        }
+
+        // ====================================================================
+        // DEBUG COMMANDS
+        // ====================================================================
+        ClientMessage::DebugCreateOrganization {
+            name,
+            organization_type,
+            cell,
+            parent_organization_id,
+        } => {
+            tracing::info!(
+                "DEBUG: Creating organization '{}' of type {:?} at {:?}",
+                name,
+                organization_type,
+                cell
+            );
+
+            // First, create a leader unit for the organization
+            let (first_name, last_name, gender, portrait_variant_id, avatar_url) = {
+                use crate::units::PortraitGenerator;
+                use rand::Rng;
+                let mut rng = rand::rng();
+
+                // Generate random gender
+                let is_male = rng.random_bool(0.5);
+                let gender_str = if is_male { "male" } else { "female" };
+
+                // Use NameGenerator to get realistic names based on gender
+                let (first_name, last_name) = name_generator.generate_random_name(Some(is_male));
+
+                // Generate portrait for Merchant profession
+                let (variant_id, avatar_url) = PortraitGenerator::generate_variant_and_url(
+                    gender_str,
+                    shared::ProfessionEnum::Merchant,
+                );
+
+                (
+                    first_name,
+                    last_name,
+                    gender_str.to_string(),
+                    variant_id,
+                    avatar_url,
+                )
+            };
+
+            // Create the leader unit
+            let founder_unit_result = db_tables
+                .units
+                .create_unit(
+                    None,
+                    first_name.clone(),
+                    last_name.clone(),
+                    gender.clone(),
+                    portrait_variant_id,
+                    avatar_url,
+                    cell,
+                    shared::TerrainChunkId { x: 0, y: 0 },
+                    shared::ProfessionEnum::Merchant,
+                    false,
+                    None,
+                )
+                .await;
+
+            match founder_unit_result {
+                Ok(founder_unit_id) => {
+                    let request = shared::CreateOrganizationRequest {
+                        name: name.clone(),
+                        organization_type,
+                        headquarters_cell: Some(cell),
+                        parent_organization_id,
+                        founder_unit_id,
+                    };
+
+                    match db_tables.organizations.create_organization(request).await {
+                        Ok(org_id) => {
+                            tracing::info!("✓ Organization created with ID {}", org_id);
+
+                            // Try to claim entire Voronoi zone
+                            let mut claimed_count = 0;
+
+                            match db_tables.voronoi_zones.get_zone_at_cell(cell).await {
+                                Ok(Some(zone_id)) => {
+                                    tracing::info!(
+                                        "Found Voronoi zone {} at cell {:?}",
+                                        zone_id,
+                                        cell
+                                    );
+
+                                    // Check if zone is available
+                                    match db_tables.voronoi_zones.is_zone_available(zone_id).await {
+                                        Ok(true) => {
+                                            // Zone is available, claim all cells
+                                            match db_tables
+                                                .voronoi_zones
+                                                .get_zone_cells(zone_id)
+                                                .await
+                                            {
+                                                Ok(zone_cells) => {
+                                                    tracing::info!(
+                                                        "Claiming {} cells from Voronoi zone {}",
+                                                        zone_cells.len(),
+                                                        zone_id
+                                                    );
+
+                                                    // Add all cells to territory
+                                                    for zone_cell in &zone_cells {
+                                                        if let Err(e) = db_tables
+                                                            .organizations
+                                                            .add_territory_cell(org_id, zone_cell)
+                                                            .await
+                                                        {
+                                                            tracing::warn!(
+                                                                "Failed to add cell {:?}: {}",
+                                                                zone_cell,
+                                                                e
+                                                            );
+                                                        } else {
+                                                            claimed_count += 1;
+                                                        }
+                                                    }
+
+                                                    // Link organization to zone
+                                                    let link_result = sqlx::query(
+                                                        "UPDATE organizations.organizations SET voronoi_zone_id = $1 WHERE id = $2"
+                                                    )
+                                                    .bind(zone_id)
+                                                    .bind(org_id as i64)
+                                                    .execute(&db_tables.pool)
+                                                    .await;
+
+                                                    if let Err(e) = link_result {
+                                                        tracing::warn!(
+                                                            "Failed to link zone to organization: {}",
+                                                            e
+                                                        );
+                                                    }
+
+                                                    tracing::info!(
+                                                        "✓ Organization {} claimed {} cells from Voronoi zone {}",
+                                                        org_id,
+                                                        claimed_count,
+                                                        zone_id
+                                                    );
+                                                }
+                                                Err(e) => {
+                                                    tracing::error!(
+                                                        "Failed to get zone cells: {}",
                                                         e
                                                     );
                                                 }
@@ -2695,656 +5340,1497 @@ async fn handle_client_message(
                                         }
                                     }
                                 }
-                                Ok(None) => {
-                                    tracing::warn!(
-                                        "No Voronoi zone found at cell {:?}, using fallback",
-                                        cell
+                                Ok(None) => {
+                                    tracing::warn!(
+                                        "No Voronoi zone found at cell {:?}, using fallback",
+                                        cell
+                                    );
+                                    // Fallback: just claim HQ and neighbors
+                                    for neighbor in cell.neighbors() {
+                                        let _ = db_tables
+                                            .organizations
+                                            .add_territory_cell(org_id, &neighbor)
+                                            .await;
+                                        claimed_count += 1;
+                                    }
+                                    let _ = db_tables
+                                        .organizations
+                                        .add_territory_cell(org_id, &cell)
+                                        .await;
+                                    claimed_count += 1;
+                                }
+                                Err(e) => {
+                                    tracing::error!("Failed to query Voronoi zone: {}", e);
+                                    // Fallback
+                                    for neighbor in cell.neighbors() {
+                                        let _ = db_tables
+                                            .organizations
+                                            .add_territory_cell(org_id, &neighbor)
+                                            .await;
+                                        claimed_count += 1;
+                                    }
+                                    let _ = db_tables
+                                        .organizations
+                                        .add_territory_cell(org_id, &cell)
+                                        .await;
+                                    claimed_count += 1;
+                                }
+                            }
+
+                            tracing::info!(
+                                "✓ Organization {} claimed {} total cells",
+                                org_id,
+                                claimed_count
+                            );
+
+                            // Generate territory contours and split by chunks
+                            tracing::info!(
+                                "Generating territory contours for organization {}...",
+                                org_id
+                            );
+                            match db_tables.organizations.load_territory_cells(org_id).await {
+                                Ok(territory_cells) if !territory_cells.is_empty() => {
+                                    tracing::info!(
+                                        "Loaded {} territory cells for organization {}",
+                                        territory_cells.len(),
+                                        org_id
+                                    );
+
+                                    // Convert GridCell to Hex
+                                    use hexx::Hex;
+                                    let territory_hex: std::collections::HashSet<Hex> =
+                                        territory_cells.iter().map(|cell| cell.to_hex()).collect();
+
+                                    let contour_points = &world::territory::build_contour(
+                                        &grid_config.layout,
+                                        &territory_hex,
+                                        0.0,
+                                        12345,
+                                    );
+
+                                    // Generate and split contours
+                                    let contour_chunks =
+                                        utils::chunks::split_contour_into_chunks(contour_points);
+                                    // let contour_chunks = crate::world::territory::generate_and_split_contour(
+                                    //     &territory_hex,
+                                    //     &grid_config.layout,
+                                    //     2.0,    // jitter amplitude
+                                    //     org_id, // jitter seed (ensures consistency)
+                                    // );
+
+                                    tracing::info!(
+                                        "Generated {} contour chunks for organization {}",
+                                        contour_chunks.len(),
+                                        org_id
+                                    );
+
+                                    // Store contours in database
+                                    let mut stored_count = 0;
+                                    for (chunk_id, contour_segments) in contour_chunks {
+                                        match db_tables
+                                            .territory_contours
+                                            .store_contour(
+                                                org_id,
+                                                chunk_id.x,
+                                                chunk_id.y,
+                                                &contour_segments,
+                                            )
+                                            .await
+                                        {
+                                            Ok(_) => {
+                                                stored_count += 1;
+                                                tracing::debug!(
+                                                    "Stored contour for org {} in chunk ({},{})",
+                                                    org_id,
+                                                    chunk_id.x,
+                                                    chunk_id.y
+                                                );
+                                            }
+                                            Err(e) => {
+                                                tracing::warn!(
+                                                    "Failed to store contour for chunk ({},{}): {}",
+                                                    chunk_id.x,
+                                                    chunk_id.y,
+                                                    e
+                                                );
+                                            }
+                                        }
+                                    }
+
+                                    tracing::info!(
+                                        "✓ Stored {} territory contour chunks for organization {}",
+                                        stored_count,
+                                        org_id
+                                    );
+                                }
+                                Ok(_) => {
+                                    tracing::warn!(
+                                        "No territory cells found for organization {}",
+                                        org_id
+                                    );
+                                }
+                                Err(e) => {
+                                    tracing::error!(
+                                        "Failed to load territory cells for organization {}: {}",
+                                        org_id,
+                                        e
+                                    );
+                                }
+                            }
+
+                            // Get territory border cells for debugging
+                            let mut response_messages =
+                                vec![ServerMessage::DebugOrganizationCreated {
+                                    organization_id: org_id,
+                                    name: name.clone(),
+                                }];
+
+                            // Get border cells (cells at the frontier of the territory)
+                            tracing::info!(
+                                "Getting territory border cells for organization {}...",
+                                org_id
+                            );
+                            match crate::world::territory::get_territory_border_cells(
+                                &db_tables.pool,
+                                org_id,
+                            )
+                            .await
+                            {
+                                Ok(border_cells) => {
+                                    tracing::info!(
+                                        "Organization {}: {} border cells identified",
+                                        org_id,
+                                        border_cells.len()
                                     );
-                                    // Fallback: just claim HQ and neighbors
-                                    for neighbor in cell.neighbors() {
-                                        let _ = db_tables
-                                            .organizations
-                                            .add_territory_cell(org_id, &neighbor)
-                                            .await;
-                                        claimed_count += 1;
-                                    }
-                                    let _ = db_tables
-                                        .organizations
-                                        .add_territory_cell(org_id, &cell)
-                                        .await;
-                                    claimed_count += 1;
+
+                                    // Send border cells to client for debug visualization
+                                    response_messages.push(ServerMessage::TerritoryBorderCells {
+                                        organization_id: org_id,
+                                        border_cells,
+                                    });
                                 }
                                 Err(e) => {
-                                    tracing::error!("Failed to query Voronoi zone: {}", e);
-                                    // Fallback
-                                    for neighbor in cell.neighbors() {
-                                        let _ = db_tables
-                                            .organizations
-                                            .add_territory_cell(org_id, &neighbor)
-                                            .await;
-                                        claimed_count += 1;
-                                    }
-                                    let _ = db_tables
-                                        .organizations
-                                        .add_territory_cell(org_id, &cell)
-                                        .await;
-                                    claimed_count += 1;
+                                    tracing::error!("Failed to get territory border cells: {}", e);
                                 }
                             }
 
-                            tracing::info!(
-                                "✓ Organization {} claimed {} total cells",
-                                org_id,
-                                claimed_count
-                            );
+                            (response_messages, vec![], None)
+                        }
+                        Err(e) => {
+                            tracing::error!("✗ Failed to create organization: {}", e);
+                            (
+                                vec![ServerMessage::DebugError {
+                                    code: ErrorCode::InternalError,
+                                    detail: Some(format!("Failed to create organization: {}", e)),
+                                }], vec![], None)
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("✗ Failed to create leader unit: {}", e);
+                    (
+                        vec![ServerMessage::DebugError {
+                            code: ErrorCode::InternalError,
+                            detail: Some(format!("Failed to create leader unit: {}", e)),
+                        }], vec![], None)
+                }
+            }
+        }
+
+        ClientMessage::DebugDeleteOrganization { organization_id } => {
+            tracing::info!("DEBUG: Deleting organization ID {}", organization_id);
+
+            match sqlx::query("DELETE FROM organizations.organizations WHERE id = $1")
+                .bind(organization_id as i64)
+                .execute(&db_tables.pool)
+                .await
+            {
+                Ok(_) => {
+                    tracing::info!("✓ Organization {} deleted", organization_id);
+                    (
+                        vec![ServerMessage::DebugOrganizationDeleted { organization_id }], vec![], None)
+                }
+                Err(e) => {
+                    tracing::error!("✗ Failed to delete organization: {}", e);
+                    (
+                        vec![ServerMessage::DebugError {
+                            code: ErrorCode::InternalError,
+                            detail: Some(format!("Failed to delete organization: {}", e)),
+                        }], vec![], None)
+                }
+            }
+        }
+
+        ClientMessage::DebugSpawnUnit { cell } => {
+            tracing::info!("DEBUG: Spawning random unit at {:?}", cell);
+
+            // Check slot availability before spawning
+            let default_chunk = shared::TerrainChunkId { x: 0, y: 0 };
+
+            // Get occupied slots on the cell
+            let occupied_slots = match db_tables
+                .units
+                .get_occupied_slots_on_cell(&cell, &default_chunk)
+                .await
+            {
+                Ok(slots) => slots,
+                Err(e) => {
+                    tracing::error!("✗ Failed to get occupied slots: {}", e);
+                    return (
+                        vec![ServerMessage::DebugError {
+                            code: ErrorCode::DatabaseError,
+                            detail: Some(format!("Failed to get occupied slots: {}", e)),
+                        }], vec![], None);
+                }
+            };
+
+            // Determine slot configuration based on building type or terrain biome
+            let slot_config = {
+                use shared::SlotConfiguration;
+
+                // Try to get building type first
+                match db_tables.buildings.get_building_type_at_cell(&cell).await {
+                    Ok(Some(building_type)) => SlotConfiguration::for_building_type(building_type),
+                    Ok(None) => {
+                        // No building, check terrain biome
+                        match db_tables.cells.get_biome_at_cell(&cell).await {
+                            Ok(Some(biome)) => SlotConfiguration::for_terrain_type(biome),
+                            Ok(None) => {
+                                tracing::warn!(
+                                    "No biome found for cell {:?}, using default config",
+                                    cell
+                                );
+                                SlotConfiguration::default()
+                            }
+                            Err(e) => {
+                                tracing::error!("✗ Failed to get biome: {}", e);
+                                return (
+                                    vec![ServerMessage::DebugError {
+                                        code: ErrorCode::DatabaseError,
+                                        detail: Some(format!("Failed to get biome: {}", e)),
+                                    }],
+                                    vec![],
+                                    None,
+                                );
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!("✗ Failed to get building type: {}", e);
+                        return (
+                            vec![ServerMessage::DebugError {
+                                code: ErrorCode::DatabaseError,
+                                detail: Some(format!("Failed to get building type: {}", e)),
+                            }],
+                            vec![],
+                            None,
+                        );
+                    }
+                }
+            };
+
+            let total_slots = slot_config.total_slots();
+
+            // Generate list of all available slots
+            let mut all_slots = Vec::new();
+
+            // Add all interior slots
+            for i in 0..slot_config.interior_slots() {
+                all_slots.push(shared::SlotPosition {
+                    slot_type: shared::SlotType::Interior,
+                    index: i,
+                });
+            }
+
+            // Add all exterior slots
+            for i in 0..slot_config.exterior_slots() {
+                all_slots.push(shared::SlotPosition {
+                    slot_type: shared::SlotType::Exterior,
+                    index: i,
+                });
+            }
+
+            // Filter out occupied slots to get available slots
+            let available_slots: Vec<_> = all_slots
+                .into_iter()
+                .filter(|slot| !occupied_slots.contains(slot))
+                .collect();
+
+            // Check if there's space available
+            if available_slots.is_empty() {
+                tracing::warn!(
+                    "✗ Cannot spawn unit: cell {:?} is full ({}/{} slots occupied)",
+                    cell,
+                    occupied_slots.len(),
+                    total_slots
+                );
+                return (
+                    vec![ServerMessage::DebugError {
+                        code: ErrorCode::CellFull,
+                        detail: Some(format!(
+                            "Cell is full ({}/{} slots occupied)",
+                            occupied_slots.len(),
+                            total_slots
+                        )),
+                    }], vec![], None);
+            }
+
+            tracing::info!(
+                "Cell has available slots: {}/{} occupied",
+                occupied_slots.len(),
+                total_slots
+            );
+
+            // Generate random unit data using NameGenerator and PortraitGenerator
+            let (first_name, last_name, gender, profession, portrait_variant_id, avatar_url) = {
+                use crate::units::PortraitGenerator;
+                use rand::Rng;
+                let mut rng = rand::rng();
+
+                // Generate random gender (true = male, false = female)
+                let is_male = rng.random_bool(0.5);
+                let gender_str = if is_male { "male" } else { "female" };
+
+                // Use NameGenerator to get realistic names based on gender
+                let (first_name, last_name) = name_generator.generate_random_name(Some(is_male));
+
+                // Generate random profession
+                let profession_id: i16 = rng.random_range(1..=16);
+                let profession = shared::ProfessionEnum::from_id(profession_id)
+                    .unwrap_or(shared::ProfessionEnum::Farmer);
+
+                // Generate portrait variant and avatar URL
+                let (variant_id, avatar_url) =
+                    PortraitGenerator::generate_variant_and_url(gender_str, profession);
+
+                (
+                    first_name,
+                    last_name,
+                    gender_str.to_string(),
+                    profession,
+                    variant_id,
+                    avatar_url,
+                )
+            };
+
+            // Choose a random available slot
+            let chosen_slot = {
+                use rand::Rng;
+                let mut rng = rand::rng();
+                let index = rng.random_range(0..available_slots.len());
+                available_slots[index]
+            };
+
+            match db_tables
+                .units
+                .create_unit(
+                    None, // No player
+                    first_name.clone(),
+                    last_name.clone(),
+                    gender.clone(),
+                    portrait_variant_id.clone(),
+                    avatar_url.clone(),
+                    cell,
+                    default_chunk,
+                    profession,
+                    false,
+                    None,
+                )
+                .await
+            {
+                Ok(unit_id) => {
+                    // Assign the chosen slot to the unit
+                    let slot_type_str = match chosen_slot.slot_type {
+                        shared::SlotType::Interior => "interior",
+                        shared::SlotType::Exterior => "exterior",
+                    };
 
-                            // Generate territory contours and split by chunks
+                    match db_tables
+                        .units
+                        .update_slot_position(
+                            unit_id,
+                            Some(slot_type_str.to_string()),
+                            Some(chosen_slot.index as i32),
+                        )
+                        .await
+                    {
+                        Ok(_) => {
                             tracing::info!(
-                                "Generating territory contours for organization {}...",
-                                org_id
+                                "✓ Unit spawned: {} {} ({}, {}, ID: {}) - Assigned to slot {:?} {} - {}/{} slots occupied",
+                                first_name,
+                                last_name,
+                                gender,
+                                avatar_url,
+                                unit_id,
+                                slot_type_str,
+                                chosen_slot.index,
+                                occupied_slots.len() + 1,
+                                total_slots
                             );
-                            match db_tables.organizations.load_territory_cells(org_id).await {
-                                Ok(territory_cells) if !territory_cells.is_empty() => {
-                                    tracing::info!(
-                                        "Loaded {} territory cells for organization {}",
-                                        territory_cells.len(),
-                                        org_id
+
+                            // Load the full unit data to send to the client
+                            match db_tables.units.load_unit(unit_id).await {
+                                Ok(unit_data) => (
+                                    vec![ServerMessage::DebugUnitSpawned { unit_data }],
+                                    vec![],
+                                    None,
+                                ),
+                                Err(e) => {
+                                    tracing::error!(
+                                        "✗ Failed to load unit data after spawn: {}",
+                                        e
                                     );
+                                    (
+                                        vec![ServerMessage::DebugError {
+                                            code: ErrorCode::InternalError,
+                                            detail: Some(format!(
+                                                "Unit created but failed to load data: {}",
+                                                e
+                                            )),
+                                        }], vec![], None)
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            tracing::error!("✗ Failed to assign slot to unit: {}", e);
+                            // Unit was created but slot assignment failed
+                            // We could delete the unit here, or just warn
+                            (
+                                vec![ServerMessage::DebugError {
+                                    code: ErrorCode::InternalError,
+                                    detail: Some(format!(
+                                        "Unit created but slot assignment failed: {}",
+                                        e
+                                    )),
+                                }], vec![], None)
+                        }
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("✗ Failed to spawn unit: {}", e);
+                    (
+                        vec![ServerMessage::DebugError {
+                            code: ErrorCode::InternalError,
+                            detail: Some(format!("Failed to spawn unit: {}", e)),
+                        }], vec![], None)
+                }
+            }
+        }
+
+        ClientMessage::RequestOrganizationAtCell { cell } => {
+            tracing::debug!("Checking organization at cell {:?}", cell);
+
+            // Query to find which organization owns this cell
+            match sqlx::query_as::<_, (i64,)>(
+                "SELECT organization_id FROM organizations.territory_cells WHERE cell_q = $1 AND cell_r = $2"
+            )
+            .bind(cell.q)
+            .bind(cell.r)
+            .fetch_optional(&db_tables.pool)
+            .await
+            {
+                Ok(Some((org_id,))) => {
+                    // Load organization summary
+                    match db_tables.organizations.load_organization(org_id as u64).await {
+                        Ok(org_data) => {
+                            let summary = shared::OrganizationSummary {
+                                id: org_data.id,
+                                name: org_data.name,
+                                organization_type: org_data.organization_type,
+                                leader_unit_id: org_data.leader_unit_id,
+                                population: org_data.population,
+                                emblem_url: org_data.emblem_url,
+                            };
+                            (vec![ServerMessage::OrganizationAtCell {
+                                cell,
+                                organization: Some(summary),
+                            }], vec![], None)
+                        }
+                        Err(e) => {
+                            tracing::error!("Failed to load organization: {}", e);
+                            (vec![ServerMessage::OrganizationAtCell {
+                                cell,
+                                organization: None,
+                            }], vec![], None)
+                        }
+                    }
+                }
+                Ok(None) => {
+                    // No organization at this cell
+                    (vec![ServerMessage::OrganizationAtCell {
+                        cell,
+                        organization: None,
+                    }], vec![], None)
+                }
+                Err(e) => {
+                    tracing::error!("Database error checking organization: {}", e);
+                    (vec![ServerMessage::OrganizationAtCell {
+                        cell,
+                        organization: None,
+                    }], vec![], None)
+                }
+            }
+        }
+
+        ClientMessage::RequestInventory { unit_id } => {
+            use shared::protocol::InventoryItemData;
+
+            match db_tables.resources.load_items_for_unit(unit_id).await {
+                Ok(full_items) => {
+                    // Grouper par item_id pour l'affichage
+                    let mut grouped: std::collections::HashMap<
+                        i32,
+                        (String, shared::ItemTypeEnum, f32, f32, i32),
+                    > = std::collections::HashMap::new();
+
+                    for item in &full_items {
+                        let entry = grouped.entry(item.definition.id).or_insert((
+                            item.definition.name.clone(),
+                            item.definition.item_type,
+                            item.definition.weight_kg,
+                            item.instance.quality,
+                            0,
+                        ));
+                        entry.4 += 1;
+                    }
+
+                    let equipment = db_tables
+                        .units
+                        .load_equipment(unit_id)
+                        .await
+                        .unwrap_or_default();
+
+                    let items: Vec<InventoryItemData> = grouped
+                        .into_iter()
+                        .map(|(item_id, (name, item_type, weight, quality, qty))| {
+                            let equipped = equipment.iter().find(|e| e.item_id == item_id);
+                            InventoryItemData {
+                                instance_id: 0,
+                                item_id,
+                                name,
+                                item_type,
+                                quality,
+                                weight_kg: weight,
+                                quantity: qty,
+                                is_equipped: equipped.is_some(),
+                                equipment_slot: equipped.map(|e| e.slot),
+                            }
+                        })
+                        .collect();
+
+                    let mut responses = vec![ServerMessage::InventoryData { unit_id, items }];
+                    if let Ok(derived_stats) = db_tables.units.load_derived_stats(unit_id).await {
+                        responses.push(ServerMessage::EquipmentChanged {
+                            unit_id,
+                            equipment,
+                            derived_stats,
+                        });
+                    }
+
+                    (responses, vec![], None)
+                }
+                Err(e) => {
+                    tracing::error!("Failed to load inventory for unit {}: {}", unit_id, e);
+                    (
+                        vec![ServerMessage::ActionError {
+                            code: ErrorCode::InventoryUnavailable,
+                            detail: Some(e.to_string()),
+                        }], vec![], None)
+                }
+            }
+        }
+
+        ClientMessage::RequestExplorationMap { terrain_name } => {
+            let n_chunk_x = world_global_state.n_chunk_x;
+            let n_chunk_y = world_global_state.n_chunk_y;
+
+            match db_tables.exploration.load_exploration_map(n_chunk_x, n_chunk_y).await {
+                Ok(data) => {
+                    tracing::info!(
+                        "Sending exploration map {}x{} ({} explored chunks)",
+                        n_chunk_x, n_chunk_y,
+                        data.iter().filter(|&&v| v > 0).count()
+                    );
+                    (vec![ServerMessage::ExplorationMap {
+                        width: n_chunk_x,
+                        height: n_chunk_y,
+                        data,
+                    }], vec![], None)
+                }
+                Err(e) => {
+                    tracing::error!("Failed to load exploration map: {}", e);
+                    (vec![], vec![], None)
+                }
+            }
+        }
+
+        ClientMessage::ActionExplore { cell, radius } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let chunk_id = cell.to_chunk_id(&grid_config.layout);
+
+            // Collect chunks in radius
+            let mut chunks_to_explore = Vec::new();
+            for dx in -radius..=radius {
+                for dy in -radius..=radius {
+                    let candidate = TerrainChunkId {
+                        x: chunk_id.x + dx,
+                        y: chunk_id.y + dy,
+                    };
+                    if candidate.x >= 0 && candidate.y >= 0
+                        && candidate.x < world_global_state.n_chunk_x
+                        && candidate.y < world_global_state.n_chunk_y
+                    {
+                        chunks_to_explore.push(candidate);
+                    }
+                }
+            }
+
+            match db_tables.exploration.mark_explored(&chunks_to_explore, player_id as i64).await {
+                Ok(newly_explored) => {
+                    if !newly_explored.is_empty() {
+                        tracing::info!(
+                            "Player {} explored {} new chunks around ({},{})",
+                            player_id, newly_explored.len(), chunk_id.x, chunk_id.y
+                        );
 
-                                    // Convert GridCell to Hex
-                                    use hexx::Hex;
-                                    let territory_hex: std::collections::HashSet<Hex> =
-                                        territory_cells.iter().map(|cell| cell.to_hex()).collect();
+                        // Broadcast to all connected clients
+                        let update = ServerMessage::ExplorationUpdate {
+                            chunks: newly_explored.clone(),
+                        };
+                        sessions.broadcast(update).await;
+                    }
+                    (vec![], vec![], None)
+                }
+                Err(e) => {
+                    tracing::error!("Failed to mark exploration: {}", e);
+                    (vec![], vec![], None)
+                }
+            }
+        }
 
-                                    let contour_points = &world::territory::build_contour(
-                                        &grid_config.layout,
-                                        &territory_hex,
-                                        0.0,
-                                        12345,
-                                    );
+        ClientMessage::CancelAction { action_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            match action_processor.cancel_action(player_id, action_id).await {
+                Ok(()) => (vec![], vec![], None),
+                Err(code) => (
+                    vec![ServerMessage::ActionError { code, detail: None }],
+                    vec![],
+                    None,
+                ),
+            }
+        }
+        ClientMessage::PauseAction { action_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            match action_processor.pause_action(player_id, action_id).await {
+                Ok(()) => (vec![], vec![], None),
+                Err(code) => (
+                    vec![ServerMessage::ActionError { code, detail: None }],
+                    vec![],
+                    None,
+                ),
+            }
+        }
+        ClientMessage::ResumeAction { action_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            match action_processor.resume_action(player_id, action_id).await {
+                Ok(()) => (vec![], vec![], None),
+                Err(code) => (
+                    vec![ServerMessage::ActionError { code, detail: None }],
+                    vec![],
+                    None,
+                ),
+            }
+        }
+        ClientMessage::EnqueueAction { unit_id, action } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
 
-                                    // Generate and split contours
-                                    let contour_chunks =
-                                        utils::chunks::split_contour_into_chunks(contour_points);
-                                    // let contour_chunks = crate::world::territory::generate_and_split_contour(
-                                    //     &territory_hex,
-                                    //     &grid_config.layout,
-                                    //     2.0,    // jitter amplitude
-                                    //     org_id, // jitter seed (ensures consistency)
-                                    // );
+            let queue = db_tables
+                .action_queue
+                .load_queue(unit_id)
+                .await
+                .unwrap_or_default();
+            if queue.len() >= action_queue::MAX_QUEUE_LENGTH {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::ActionQueueFull,
+                        detail: Some(format!("{}", action_queue::MAX_QUEUE_LENGTH)),
+                    }], vec![], None);
+            }
 
-                                    tracing::info!(
-                                        "Generated {} contour chunks for organization {}",
-                                        contour_chunks.len(),
-                                        org_id
-                                    );
+            if let Err(e) = db_tables.action_queue.enqueue(unit_id, &action).await {
+                tracing::error!("{}", e);
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::DatabaseError,
+                        detail: None,
+                    }], vec![], None);
+            }
+            tracing::info!(
+                "Player {} queued {:?} for unit {}",
+                player_id,
+                action.action_type(),
+                unit_id
+            );
 
-                                    // Store contours in database
-                                    let mut stored_count = 0;
-                                    for (chunk_id, contour_segments) in contour_chunks {
-                                        match db_tables
-                                            .territory_contours
-                                            .store_contour(
-                                                org_id,
-                                                chunk_id.x,
-                                                chunk_id.y,
-                                                &contour_segments,
-                                            )
-                                            .await
-                                        {
-                                            Ok(_) => {
-                                                stored_count += 1;
-                                                tracing::debug!(
-                                                    "Stored contour for org {} in chunk ({},{})",
-                                                    org_id,
-                                                    chunk_id.x,
-                                                    chunk_id.y
-                                                );
-                                            }
-                                            Err(e) => {
-                                                tracing::warn!(
-                                                    "Failed to store contour for chunk ({},{}): {}",
-                                                    chunk_id.x,
-                                                    chunk_id.y,
-                                                    e
-                                                );
-                                            }
-                                        }
-                                    }
+            // Une unité libre lance tout de suite sa file
+            let idle = db_tables
+                .units
+                .get_busy_units(&[unit_id])
+                .await
+                .is_ok_and(|busy| busy.is_empty());
+            if idle {
+                action_processor.notify_unit_idle(player_id, unit_id);
+            }
 
-                                    tracing::info!(
-                                        "✓ Stored {} territory contour chunks for organization {}",
-                                        stored_count,
-                                        org_id
-                                    );
-                                }
-                                Ok(_) => {
-                                    tracing::warn!(
-                                        "No territory cells found for organization {}",
-                                        org_id
-                                    );
-                                }
-                                Err(e) => {
-                                    tracing::error!(
-                                        "Failed to load territory cells for organization {}: {}",
-                                        org_id,
-                                        e
-                                    );
-                                }
-                            }
+            (vec![action_queue_update(db_tables, unit_id).await], vec![], None)
+        }
+        ClientMessage::ReorderActionQueue { unit_id, entry_ids } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
 
-                            // Get territory border cells for debugging
-                            let mut response_messages =
-                                vec![ServerMessage::DebugOrganizationCreated {
-                                    organization_id: org_id,
-                                    name: name.clone(),
-                                }];
+            let current: Vec<u64> = db_tables
+                .action_queue
+                .load_queue(unit_id)
+                .await
+                .unwrap_or_default()
+                .iter()
+                .map(|entry| entry.entry_id)
+                .collect();
+            if !action_queue::is_reordering(&current, &entry_ids) {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::InvalidQueueOrder,
+                        detail: None,
+                    }], vec![], None);
+            }
 
-                            // Get border cells (cells at the frontier of the territory)
-                            tracing::info!(
-                                "Getting territory border cells for organization {}...",
-                                org_id
-                            );
-                            match crate::world::territory::get_territory_border_cells(
-                                &db_tables.pool,
-                                org_id,
-                            )
-                            .await
-                            {
-                                Ok(border_cells) => {
-                                    tracing::info!(
-                                        "Organization {}: {} border cells identified",
-                                        org_id,
-                                        border_cells.len()
-                                    );
+            if let Err(e) = db_tables.action_queue.reorder(unit_id, &entry_ids).await {
+                tracing::error!("{}", e);
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::DatabaseError,
+                        detail: None,
+                    }], vec![], None);
+            }
 
-                                    // Send border cells to client for debug visualization
-                                    response_messages.push(ServerMessage::TerritoryBorderCells {
-                                        organization_id: org_id,
-                                        border_cells,
-                                    });
-                                }
-                                Err(e) => {
-                                    tracing::error!("Failed to get territory border cells: {}", e);
-                                }
-                            }
+            (vec![action_queue_update(db_tables, unit_id).await], vec![], None)
+        }
+        ClientMessage::ClearActionQueue { unit_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
 
-                            (response_messages, vec![], None)
-                        }
-                        Err(e) => {
-                            tracing::error!("✗ Failed to create organization: {}", e);
-                            (
-                                vec![ServerMessage::DebugError {
-                                    reason: format!("Failed to create organization: {}", e),
-                                }], vec![], None)
-                        }
-                    }
-                }
-                Err(e) => {
-                    tracing::error!("✗ Failed to create leader unit: {}", e);
-                    (
-                        vec![ServerMessage::DebugError {
-                            reason: format!("Failed to create leader unit: {}", e),
-                        }], vec![], None)
+            match db_tables.action_queue.clear(unit_id).await {
+                Ok(cleared) => {
+                    tracing::info!("Cleared {} queued actions of unit {}", cleared, unit_id)
                 }
+                Err(e) => tracing::error!("{}", e),
             }
+
+            (vec![action_queue_update(db_tables, unit_id).await], vec![], None)
         }
+        ClientMessage::RequestActionQueue { unit_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
 
-        ClientMessage::DebugDeleteOrganization { organization_id } => {
-            tracing::info!("DEBUG: Deleting organization ID {}", organization_id);
+            (vec![action_queue_update(db_tables, unit_id).await], vec![], None)
+        }
 
-            match sqlx::query("DELETE FROM organizations.organizations WHERE id = $1")
-                .bind(organization_id as i64)
-                .execute(&db_tables.pool)
+        ClientMessage::SaveUnitRoutine { unit_id, routine } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
+
+            // Seuls les PNJ suivent des routines ; le lord reste aux ordres du joueur
+            let is_lord = db_tables
+                .units
+                .load_unit(unit_id)
                 .await
-            {
-                Ok(_) => {
-                    tracing::info!("✓ Organization {} deleted", organization_id);
-                    (
-                        vec![ServerMessage::DebugOrganizationDeleted { organization_id }], vec![], None)
+                .is_ok_and(|unit| unit.is_lord);
+            let valid = match Routine::from_automated_action(&routine) {
+                Some(Routine::Harvest {
+                    resource_specific_type,
+                    max_stock,
+                }) => {
+                    max_stock > 0
+                        && !game_state
+                            .harvest_yields_for(resource_specific_type.to_id())
+                            .is_empty()
                 }
+                Some(Routine::Craft {
+                    ref recipe_id,
+                    max_stock,
+                }) => max_stock > 0 && game_state.find_recipe(recipe_id).is_some(),
+                None => false,
+            };
+            if is_lord || !valid {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::InvalidRoutine,
+                        detail: Some(routine.action_type),
+                    }], vec![], None);
+            }
+
+            match db_tables.units.save_automated_action(unit_id, &routine).await {
+                Ok(routine_id) => tracing::info!(
+                    "Player {} saved routine {} ({}) of unit {}",
+                    player_id,
+                    routine_id,
+                    routine.action_type,
+                    unit_id
+                ),
                 Err(e) => {
-                    tracing::error!("✗ Failed to delete organization: {}", e);
-                    (
-                        vec![ServerMessage::DebugError {
-                            reason: format!("Failed to delete organization: {}", e),
-                        }], vec![], None)
+                    tracing::error!("{}", e);
+                    return (
+                        vec![ServerMessage::ActionError {
+                            code: ErrorCode::DatabaseError,
+                            detail: None,
+                        }], vec![], None);
                 }
             }
+
+            (vec![unit_routines(db_tables, unit_id).await], vec![], None)
+        }
+        ClientMessage::DeleteUnitRoutine { unit_id, routine_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
+
+            if let Err(e) = db_tables.units.delete_automated_action(unit_id, routine_id).await {
+                tracing::error!("{}", e);
+            }
+
+            (vec![unit_routines(db_tables, unit_id).await], vec![], None)
         }
+        ClientMessage::RequestUnitRoutines { unit_id } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
 
-        ClientMessage::DebugSpawnUnit { cell } => {
-            tracing::info!("DEBUG: Spawning random unit at {:?}", cell);
+            (vec![unit_routines(db_tables, unit_id).await], vec![], None)
+        }
 
-            // Check slot availability before spawning
-            let default_chunk = shared::TerrainChunkId { x: 0, y: 0 };
+        ClientMessage::EquipItem {
+            unit_id,
+            item_id,
+            slot,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
+                    }], vec![], None);
+            }
 
-            // Get occupied slots on the cell
-            let occupied_slots = match db_tables
-                .units
-                .get_occupied_slots_on_cell(&cell, &default_chunk)
-                .await
-            {
-                Ok(slots) => slots,
+            let (unit, lookup) = match load_unit_with_items(db_tables, unit_id).await {
+                Ok(loaded) => loaded,
                 Err(e) => {
-                    tracing::error!("✗ Failed to get occupied slots: {}", e);
+                    tracing::error!("Failed to load unit {} for equipment: {}", unit_id, e);
                     return (
-                        vec![ServerMessage::DebugError {
-                            reason: format!("Failed to get occupied slots: {}", e),
+                        vec![ServerMessage::ActionError {
+                            code: ErrorCode::InventoryUnavailable,
+                            detail: None,
                         }], vec![], None);
                 }
             };
 
-            // Determine slot configuration based on building type or terrain biome
-            let slot_config = {
-                use shared::SlotConfiguration;
-
-                // Try to get building type first
-                match db_tables.buildings.get_building_type_at_cell(&cell).await {
-                    Ok(Some(building_type)) => SlotConfiguration::for_building_type(building_type),
-                    Ok(None) => {
-                        // No building, check terrain biome
-                        match db_tables.cells.get_biome_at_cell(&cell).await {
-                            Ok(Some(biome)) => SlotConfiguration::for_terrain_type(biome),
-                            Ok(None) => {
-                                tracing::warn!(
-                                    "No biome found for cell {:?}, using default config",
-                                    cell
-                                );
-                                SlotConfiguration::default()
-                            }
-                            Err(e) => {
-                                tracing::error!("✗ Failed to get biome: {}", e);
-                                return (
-                                    vec![ServerMessage::DebugError {
-                                        reason: format!("Failed to get biome: {}", e),
-                                    }],
-                                    vec![],
-                                    None,
-                                );
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        tracing::error!("✗ Failed to get building type: {}", e);
-                        return (
-                            vec![ServerMessage::DebugError {
-                                reason: format!("Failed to get building type: {}", e),
-                            }],
-                            vec![],
-                            None,
-                        );
-                    }
-                }
+            // Seuls les objets de l'inventaire de l'unité sont dans le catalogue
+            let Some(item) = lookup.get(item_id) else {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::ItemNotFound,
+                        detail: None,
+                    }], vec![], None);
             };
 
-            let total_slots = slot_config.total_slots();
-
-            // Generate list of all available slots
-            let mut all_slots = Vec::new();
-
-            // Add all interior slots
-            for i in 0..slot_config.interior_slots() {
-                all_slots.push(shared::SlotPosition {
-                    slot_type: shared::SlotType::Interior,
-                    index: i,
-                });
+            if let Err(reason) = UnitCalculator::can_equip_item(&unit, item, slot) {
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::CannotEquip,
+                        detail: Some(reason),
+                    }], vec![], None);
             }
 
-            // Add all exterior slots
-            for i in 0..slot_config.exterior_slots() {
-                all_slots.push(shared::SlotPosition {
-                    slot_type: shared::SlotType::Exterior,
-                    index: i,
-                });
+            if let Err(e) = db_tables.units.equip_item(unit_id, slot, item_id).await {
+                tracing::error!("{}", e);
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::DatabaseError,
+                        detail: None,
+                    }], vec![], None);
             }
+            tracing::info!("Unit {} equipped item {} in {}", unit_id, item_id, slot.to_name());
 
-            // Filter out occupied slots to get available slots
-            let available_slots: Vec<_> = all_slots
-                .into_iter()
-                .filter(|slot| !occupied_slots.contains(slot))
-                .collect();
+            match equipment_changed(db_tables, unit_id).await {
+                Ok(msg) => (vec![msg], vec![], None),
+                Err(e) => {
+                    tracing::error!("Failed to recalculate stats of unit {}: {}", unit_id, e);
+                    (
+                        vec![ServerMessage::ActionError {
+                            code: ErrorCode::DatabaseError,
+                            detail: None,
+                        }], vec![], None)
+                }
+            }
+        }
 
-            // Check if there's space available
-            if available_slots.is_empty() {
-                tracing::warn!(
-                    "✗ Cannot spawn unit: cell {:?} is full ({}/{} slots occupied)",
-                    cell,
-                    occupied_slots.len(),
-                    total_slots
-                );
+        ClientMessage::UnequipItem { unit_id, slot } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
                 return (
-                    vec![ServerMessage::DebugError {
-                        reason: format!(
-                            "Cell is full ({}/{} slots occupied)",
-                            occupied_slots.len(),
-                            total_slots
-                        ),
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::UnitNotOwned,
+                        detail: None,
                     }], vec![], None);
             }
 
-            tracing::info!(
-                "Cell has available slots: {}/{} occupied",
-                occupied_slots.len(),
-                total_slots
-            );
-
-            // Generate random unit data using NameGenerator and PortraitGenerator
-            let (first_name, last_name, gender, profession, portrait_variant_id, avatar_url) = {
-                use crate::units::PortraitGenerator;
-                use rand::Rng;
-                let mut rng = rand::rng();
-
-                // Generate random gender (true = male, false = female)
-                let is_male = rng.random_bool(0.5);
-                let gender_str = if is_male { "male" } else { "female" };
-
-                // Use NameGenerator to get realistic names based on gender
-                let (first_name, last_name) = name_generator.generate_random_name(Some(is_male));
+            if let Err(e) = db_tables.units.unequip_item(unit_id, slot).await {
+                tracing::error!("{}", e);
+                return (
+                    vec![ServerMessage::ActionError {
+                        code: ErrorCode::DatabaseError,
+                        detail: None,
+                    }], vec![], None);
+            }
+            tracing::info!("Unit {} unequipped {}", unit_id, slot.to_name());
 
-                // Generate random profession
-                let profession_id: i16 = rng.random_range(1..=16);
-                let profession = shared::ProfessionEnum::from_id(profession_id)
-                    .unwrap_or(shared::ProfessionEnum::Farmer);
+            match equipment_changed(db_tables, unit_id).await {
+                Ok(msg) => (vec![msg], vec![], None),
+                Err(e) => {
+                    tracing::error!("Failed to recalculate stats of unit {}: {}", unit_id, e);
+                    (
+                        vec![ServerMessage::ActionError {
+                            code: ErrorCode::DatabaseError,
+                            detail: None,
+                        }], vec![], None)
+                }
+            }
+        }
 
-                // Generate portrait variant and avatar URL
-                let (variant_id, avatar_url) =
-                    PortraitGenerator::generate_variant_and_url(gender_str, profession);
+        ClientMessage::TransferItems {
+            from_unit,
+            to_unit,
+            item_id,
+            quantity,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if quantity <= 0 || from_unit == to_unit {
+                return (vec![], vec![], None);
+            }
+            if !player_controls_units(db_tables, player_id, &[from_unit, to_unit]).await {
+                return action_error(ErrorCode::UnitNotOwned, None);
+            }
 
-                (
-                    first_name,
-                    last_name,
-                    gender_str.to_string(),
-                    profession,
-                    variant_id,
-                    avatar_url,
-                )
+            let (giver, receiver) = match (
+                db_tables.units.load_unit(from_unit).await,
+                db_tables.units.load_unit(to_unit).await,
+            ) {
+                (Ok(giver), Ok(receiver)) => (giver, receiver),
+                _ => return action_error(ErrorCode::UnitNotFound, None),
             };
+            if giver.current_cell != receiver.current_cell {
+                return action_error(ErrorCode::UnitsNotOnSameCell, None);
+            }
 
-            // Choose a random available slot
-            let chosen_slot = {
-                use rand::Rng;
-                let mut rng = rand::rng();
-                let index = rng.random_range(0..available_slots.len());
-                available_slots[index]
+            match movable_item_count(db_tables, from_unit, item_id).await {
+                Ok(available) if available >= quantity => {}
+                Ok(available) => {
+                    return action_error(
+                        ErrorCode::NotEnoughItems,
+                        Some(format!("{} / {}", available, quantity)),
+                    );
+                }
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::InventoryUnavailable, None);
+                }
+            }
+
+            let Some(item) = game_state.get_item_definition(item_id) else {
+                return action_error(ErrorCode::ItemNotFound, None);
             };
+            match would_be_overencumbered(db_tables, to_unit, item.weight_kg * quantity as f32).await {
+                Ok(false) => {}
+                Ok(true) => return action_error(ErrorCode::Overencumbered, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::InventoryUnavailable, None);
+                }
+            }
 
             match db_tables
-                .units
-                .create_unit(
-                    None, // No player
-                    first_name.clone(),
-                    last_name.clone(),
-                    gender.clone(),
-                    portrait_variant_id.clone(),
-                    avatar_url.clone(),
-                    cell,
-                    default_chunk,
-                    profession,
-                    false,
-                    None,
+                .resources
+                .move_item_instances(
+                    ItemHolder::Unit(from_unit),
+                    ItemHolder::Unit(to_unit),
+                    item_id,
+                    quantity,
                 )
                 .await
             {
-                Ok(unit_id) => {
-                    // Assign the chosen slot to the unit
-                    let slot_type_str = match chosen_slot.slot_type {
-                        shared::SlotType::Interior => "interior",
-                        shared::SlotType::Exterior => "exterior",
-                    };
+                Ok(moved) if moved == quantity as u64 => {}
+                // Les objets sont partis entre la vérification et le transfert
+                Ok(_) => return action_error(ErrorCode::NotEnoughItems, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
+                }
+            }
+            tracing::info!(
+                "Unit {} gave {} x item {} to unit {}",
+                from_unit,
+                quantity,
+                item_id,
+                to_unit
+            );
 
-                    match db_tables
-                        .units
-                        .update_slot_position(
-                            unit_id,
-                            Some(slot_type_str.to_string()),
-                            Some(chosen_slot.index as i32),
-                        )
-                        .await
-                    {
-                        Ok(_) => {
-                            tracing::info!(
-                                "✓ Unit spawned: {} {} ({}, {}, ID: {}) - Assigned to slot {:?} {} - {}/{} slots occupied",
-                                first_name,
-                                last_name,
-                                gender,
-                                avatar_url,
-                                unit_id,
-                                slot_type_str,
-                                chosen_slot.index,
-                                occupied_slots.len() + 1,
-                                total_slots
-                            );
+            (
+                vec![
+                    inventory_changed(db_tables, from_unit, item_id, -quantity).await,
+                    inventory_changed(db_tables, to_unit, item_id, quantity).await,
+                ],
+                vec![],
+                None,
+            )
+        }
 
-                            // Load the full unit data to send to the client
-                            match db_tables.units.load_unit(unit_id).await {
-                                Ok(unit_data) => (
-                                    vec![ServerMessage::DebugUnitSpawned { unit_data }],
-                                    vec![],
-                                    None,
-                                ),
-                                Err(e) => {
-                                    tracing::error!(
-                                        "✗ Failed to load unit data after spawn: {}",
-                                        e
-                                    );
-                                    (
-                                        vec![ServerMessage::DebugError {
-                                            reason: format!(
-                                                "Unit created but failed to load data: {}",
-                                                e
-                                            ),
-                                        }], vec![], None)
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            tracing::error!("✗ Failed to assign slot to unit: {}", e);
-                            // Unit was created but slot assignment failed
-                            // We could delete the unit here, or just warn
-                            (
-                                vec![ServerMessage::DebugError {
-                                    reason: format!(
-                                        "Unit created but slot assignment failed: {}",
-                                        e
-                                    ),
-                                }], vec![], None)
-                        }
-                    }
+        ClientMessage::DropItems {
+            unit_id,
+            item_id,
+            quantity,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if quantity <= 0 {
+                return (vec![], vec![], None);
+            }
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return action_error(ErrorCode::UnitNotOwned, None);
+            }
+            let Ok(unit) = db_tables.units.load_unit(unit_id).await else {
+                return action_error(ErrorCode::UnitNotFound, None);
+            };
+
+            match movable_item_count(db_tables, unit_id, item_id).await {
+                Ok(available) if available >= quantity => {}
+                Ok(available) => {
+                    return action_error(
+                        ErrorCode::NotEnoughItems,
+                        Some(format!("{} / {}", available, quantity)),
+                    );
                 }
                 Err(e) => {
-                    tracing::error!("✗ Failed to spawn unit: {}", e);
-                    (
-                        vec![ServerMessage::DebugError {
-                            reason: format!("Failed to spawn unit: {}", e),
-                        }], vec![], None)
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::InventoryUnavailable, None);
                 }
             }
-        }
-
-        ClientMessage::RequestOrganizationAtCell { cell } => {
-            tracing::debug!("Checking organization at cell {:?}", cell);
 
-            // Query to find which organization owns this cell
-            match sqlx::query_as::<_, (i64,)>(
-                "SELECT organization_id FROM organizations.territory_cells WHERE cell_q = $1 AND cell_r = $2"
-            )
-            .bind(cell.q)
-            .bind(cell.r)
-            .fetch_optional(&db_tables.pool)
-            .await
+            let position = WorldPosition {
+                cell_q: unit.current_cell.q,
+                cell_r: unit.current_cell.r,
+                chunk_x: unit.current_chunk.x,
+                chunk_y: unit.current_chunk.y,
+            };
+            match db_tables
+                .resources
+                .move_item_instances(
+                    ItemHolder::Unit(unit_id),
+                    ItemHolder::Ground(position),
+                    item_id,
+                    quantity,
+                )
+                .await
             {
-                Ok(Some((org_id,))) => {
-                    // Load organization summary
-                    match db_tables.organizations.load_organization(org_id as u64).await {
-                        Ok(org_data) => {
-                            let summary = shared::OrganizationSummary {
-                                id: org_data.id,
-                                name: org_data.name,
-                                organization_type: org_data.organization_type,
-                                leader_unit_id: org_data.leader_unit_id,
-                                population: org_data.population,
-                                emblem_url: org_data.emblem_url,
-                            };
-                            (vec![ServerMessage::OrganizationAtCell {
-                                cell,
-                                organization: Some(summary),
-                            }], vec![], None)
-                        }
-                        Err(e) => {
-                            tracing::error!("Failed to load organization: {}", e);
-                            (vec![ServerMessage::OrganizationAtCell {
-                                cell,
-                                organization: None,
-                            }], vec![], None)
-                        }
-                    }
-                }
-                Ok(None) => {
-                    // No organization at this cell
-                    (vec![ServerMessage::OrganizationAtCell {
-                        cell,
-                        organization: None,
-                    }], vec![], None)
-                }
+                Ok(moved) if moved == quantity as u64 => {}
+                Ok(_) => return action_error(ErrorCode::NotEnoughItems, None),
                 Err(e) => {
-                    tracing::error!("Database error checking organization: {}", e);
-                    (vec![ServerMessage::OrganizationAtCell {
-                        cell,
-                        organization: None,
-                    }], vec![], None)
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
                 }
             }
+            tracing::info!(
+                "Unit {} dropped {} x item {} on ({},{})",
+                unit_id,
+                quantity,
+                item_id,
+                unit.current_cell.q,
+                unit.current_cell.r
+            );
+
+            broadcast_ground_items(db_tables, sessions, &unit.current_cell, &unit.current_chunk).await;
+            (
+                vec![inventory_changed(db_tables, unit_id, item_id, -quantity).await],
+                vec![],
+                None,
+            )
         }
 
-        ClientMessage::RequestInventory { unit_id } => {
-            use shared::protocol::InventoryItemData;
+        ClientMessage::PickUpItems {
+            unit_id,
+            item_id,
+            quantity,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if quantity <= 0 {
+                return (vec![], vec![], None);
+            }
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return action_error(ErrorCode::UnitNotOwned, None);
+            }
+            let Ok(unit) = db_tables.units.load_unit(unit_id).await else {
+                return action_error(ErrorCode::UnitNotFound, None);
+            };
 
-            match db_tables.resources.load_items_for_unit(unit_id).await {
-                Ok(full_items) => {
-                    // Grouper par item_id pour l'affichage
-                    let mut grouped: std::collections::HashMap<
-                        i32,
-                        (String, shared::ItemTypeEnum, f32, f32, i32),
-                    > = std::collections::HashMap::new();
+            // Seuls les objets posés sur la cellule de l'unité peuvent être ramassés
+            let available = match db_tables
+                .resources
+                .find_ground_item_instances(&unit.current_cell, item_id, quantity)
+                .await
+            {
+                Ok(ids) => ids,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            if available.len() < quantity as usize {
+                return action_error(
+                    ErrorCode::NotEnoughItems,
+                    Some(format!("{} / {}", available.len(), quantity)),
+                );
+            }
 
-                    for item in &full_items {
-                        let entry = grouped.entry(item.definition.id).or_insert((
-                            item.definition.name.clone(),
-                            item.definition.item_type,
-                            item.definition.weight_kg,
-                            item.instance.quality,
-                            0,
-                        ));
-                        entry.4 += 1;
-                    }
+            let Some(item) = game_state.get_item_definition(item_id) else {
+                return action_error(ErrorCode::ItemNotFound, None);
+            };
+            match would_be_overencumbered(db_tables, unit_id, item.weight_kg * quantity as f32).await {
+                Ok(false) => {}
+                Ok(true) => return action_error(ErrorCode::Overencumbered, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::InventoryUnavailable, None);
+                }
+            }
 
-                    let items: Vec<InventoryItemData> = grouped
-                        .into_iter()
-                        .map(|(item_id, (name, item_type, weight, quality, qty))| {
-                            InventoryItemData {
-                                instance_id: 0,
-                                item_id,
-                                name,
-                                item_type,
-                                quality,
-                                weight_kg: weight,
-                                quantity: qty,
-                                is_equipped: false,
-                                equipment_slot: None,
-                            }
-                        })
-                        .collect();
+            let position = WorldPosition {
+                cell_q: unit.current_cell.q,
+                cell_r: unit.current_cell.r,
+                chunk_x: unit.current_chunk.x,
+                chunk_y: unit.current_chunk.y,
+            };
+            match db_tables
+                .resources
+                .move_item_instances(
+                    ItemHolder::Ground(position),
+                    ItemHolder::Unit(unit_id),
+                    item_id,
+                    quantity,
+                )
+                .await
+            {
+                Ok(moved) if moved == quantity as u64 => {}
+                // Un autre joueur a ramassé les objets entre-temps
+                Ok(_) => return action_error(ErrorCode::NotEnoughItems, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
+                }
+            }
+            tracing::info!(
+                "Unit {} picked up {} x item {} on ({},{})",
+                unit_id,
+                quantity,
+                item_id,
+                unit.current_cell.q,
+                unit.current_cell.r
+            );
 
-                    (
-                        vec![ServerMessage::InventoryData { unit_id, items }], vec![], None)
+            broadcast_ground_items(db_tables, sessions, &unit.current_cell, &unit.current_chunk).await;
+            (
+                vec![inventory_changed(db_tables, unit_id, item_id, quantity).await],
+                vec![],
+                None,
+            )
+        }
+        ClientMessage::RequestTreasury => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+                return action_error(ErrorCode::LordNotFound, None);
+            };
+            let organization_id = match db_tables.organizations.find_unit_organization(lord.id).await {
+                Ok(Some(id)) => id,
+                Ok(None) => return action_error(ErrorCode::NoOrganization, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
                 }
+            };
+            match treasury_data(db_tables, organization_id, lord.id).await {
+                Ok(msg) => (vec![msg], vec![], None),
                 Err(e) => {
-                    tracing::error!("Failed to load inventory for unit {}: {}", unit_id, e);
-                    (
-                        vec![ServerMessage::ActionError {
-                            reason: format!("Failed to load inventory: {}", e),
-                        }], vec![], None)
+                    tracing::error!("{}", e);
+                    action_error(ErrorCode::DatabaseError, None)
                 }
             }
         }
 
-        ClientMessage::RequestExplorationMap { terrain_name } => {
-            let n_chunk_x = world_global_state.n_chunk_x;
-            let n_chunk_y = world_global_state.n_chunk_y;
+        ClientMessage::DepositToTreasury {
+            unit_id,
+            item_id,
+            quantity,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if quantity <= 0 {
+                return (vec![], vec![], None);
+            }
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return action_error(ErrorCode::UnitNotOwned, None);
+            }
+            let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+                return action_error(ErrorCode::LordNotFound, None);
+            };
+            let Ok(unit) = db_tables.units.load_unit(unit_id).await else {
+                return action_error(ErrorCode::UnitNotFound, None);
+            };
+            let organization_id = match db_tables.organizations.find_unit_organization(lord.id).await {
+                Ok(Some(id)) => id,
+                Ok(None) => return action_error(ErrorCode::NoOrganization, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
+                }
+            };
+            // L'unité doit se trouver sur le territoire de l'organisation
+            match db_tables.organizations.find_cell_organization(&unit.current_cell).await {
+                Ok(Some(owner)) if owner == organization_id => {}
+                Ok(_) => return action_error(ErrorCode::OutsideTerritory, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
+                }
+            }
 
-            match db_tables.exploration.load_exploration_map(n_chunk_x, n_chunk_y).await {
-                Ok(data) => {
-                    tracing::info!(
-                        "Sending exploration map {}x{} ({} explored chunks)",
-                        n_chunk_x, n_chunk_y,
-                        data.iter().filter(|&&v| v > 0).count()
+            match movable_item_count(db_tables, unit_id, item_id).await {
+                Ok(available) if available >= quantity => {}
+                Ok(available) => {
+                    return action_error(
+                        ErrorCode::NotEnoughItems,
+                        Some(format!("{} / {}", available, quantity)),
                     );
-                    (vec![ServerMessage::ExplorationMap {
-                        width: n_chunk_x,
-                        height: n_chunk_y,
-                        data,
-                    }], vec![], None)
                 }
                 Err(e) => {
-                    tracing::error!("Failed to load exploration map: {}", e);
-                    (vec![], vec![], None)
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::InventoryUnavailable, None);
+                }
+            }
+
+            match db_tables
+                .organizations
+                .deposit_unit_items(organization_id, unit_id, item_id, quantity)
+                .await
+            {
+                Ok(deposited) if deposited == quantity as u64 => {}
+                // Les objets sont partis entre la vérification et le dépôt
+                Ok(_) => return action_error(ErrorCode::NotEnoughItems, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
                 }
             }
+            tracing::info!(
+                "Unit {} deposited {} x item {} into the treasury of organization {}",
+                unit_id,
+                quantity,
+                item_id,
+                organization_id
+            );
+
+            let mut responses = vec![inventory_changed(db_tables, unit_id, item_id, -quantity).await];
+            match treasury_data(db_tables, organization_id, lord.id).await {
+                Ok(msg) => responses.push(msg),
+                Err(e) => tracing::error!("{}", e),
+            }
+            (responses, vec![], None)
         }
 
-        ClientMessage::ActionExplore { player_id, cell, radius } => {
-            let chunk_id = cell.to_chunk_id(&grid_config.layout);
+        ClientMessage::WithdrawFromTreasury {
+            unit_id,
+            item_id,
+            quantity,
+        } => {
+            let Some(player_id) = session_player_id else {
+                return not_authenticated();
+            };
+            if quantity <= 0 {
+                return (vec![], vec![], None);
+            }
+            if !player_controls_unit(db_tables, player_id, unit_id).await {
+                return action_error(ErrorCode::UnitNotOwned, None);
+            }
+            let Ok(Some(lord)) = db_tables.units.load_lord_for_player(player_id).await else {
+                return action_error(ErrorCode::LordNotFound, None);
+            };
+            let Ok(unit) = db_tables.units.load_unit(unit_id).await else {
+                return action_error(ErrorCode::UnitNotFound, None);
+            };
+            let organization_id = match db_tables.organizations.find_unit_organization(lord.id).await {
+                Ok(Some(id)) => id,
+                Ok(None) => return action_error(ErrorCode::NoOrganization, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
+                }
+            };
 
-            // Collect chunks in radius
-            let mut chunks_to_explore = Vec::new();
-            for dx in -radius..=radius {
-                for dy in -radius..=radius {
-                    let candidate = TerrainChunkId {
-                        x: chunk_id.x + dx,
-                        y: chunk_id.y + dy,
-                    };
-                    if candidate.x >= 0 && candidate.y >= 0
-                        && candidate.x < world_global_state.n_chunk_x
-                        && candidate.y < world_global_state.n_chunk_y
-                    {
-                        chunks_to_explore.push(candidate);
-                    }
+            // Seuls le chef et les officiers de rang suffisant peuvent retirer
+            match has_organization_permission(
+                db_tables,
+                organization_id,
+                lord.id,
+                OrganizationPermission::WithdrawTreasury,
+            )
+            .await
+            {
+                Ok(true) => {}
+                Ok(false) => return action_error(ErrorCode::InsufficientAuthority, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
                 }
             }
 
-            match db_tables.exploration.mark_explored(&chunks_to_explore, player_id).await {
-                Ok(newly_explored) => {
-                    if !newly_explored.is_empty() {
-                        tracing::info!(
-                            "Player {} explored {} new chunks around ({},{})",
-                            player_id, newly_explored.len(), chunk_id.x, chunk_id.y
-                        );
+            match db_tables.organizations.find_cell_organization(&unit.current_cell).await {
+                Ok(Some(owner)) if owner == organization_id => {}
+                Ok(_) => return action_error(ErrorCode::OutsideTerritory, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
+                }
+            }
 
-                        // Broadcast to all connected clients
-                        let update = ServerMessage::ExplorationUpdate {
-                            chunks: newly_explored.clone(),
-                        };
-                        sessions.broadcast(update).await;
-                    }
-                    (vec![], vec![], None)
+            let Some(item) = game_state.get_item_definition(item_id) else {
+                return action_error(ErrorCode::ItemNotFound, None);
+            };
+            match would_be_overencumbered(db_tables, unit_id, item.weight_kg * quantity as f32).await {
+                Ok(false) => {}
+                Ok(true) => return action_error(ErrorCode::Overencumbered, None),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::InventoryUnavailable, None);
                 }
+            }
+
+            match db_tables
+                .organizations
+                .withdraw_treasury_items(organization_id, unit_id, item_id, quantity)
+                .await
+            {
+                Ok(withdrawn) if withdrawn == quantity as u64 => {}
+                Ok(_) => return action_error(ErrorCode::NotEnoughItems, None),
                 Err(e) => {
-                    tracing::error!("Failed to mark exploration: {}", e);
-                    (vec![], vec![], None)
+                    tracing::error!("{}", e);
+                    return action_error(ErrorCode::DatabaseError, None);
                 }
             }
-        }
+            tracing::info!(
+                "Unit {} withdrew {} x item {} from the treasury of organization {}",
+                unit_id,
+                quantity,
+                item_id,
+                organization_id
+            );
 
+            let mut responses = vec![inventory_changed(db_tables, unit_id, item_id, quantity).await];
+            match treasury_data(db_tables, organization_id, lord.id).await {
+                Ok(msg) => responses.push(msg),
+                Err(e) => tracing::error!("{}", e),
+            }
+            (responses, vec![], None)
+        }
         ClientMessage::Ping => (vec![ServerMessage::Pong], vec![], None),
         _ => (vec![ServerMessage::Pong], vec![], None),
     }
 }
 
-pub async fn broadcast_message(sessions: Sessions, msg: ServerMessage) {
-    let count = sessions.count().await;
-    tracing::debug!("Broadcasting message to {} sessions: {:?}", count, msg);
-    // TODO: implement proper broadcasting
+/// Broadcast un message aux sessions dont la zone d'intérêt couvre le chunk
+pub async fn broadcast_message(sessions: &Sessions, chunk_id: &TerrainChunkId, msg: ServerMessage) {
+    sessions.broadcast_to_chunk(chunk_id, msg).await;
 }
 
 fn spawn_generate_and_send(
@@ -3374,6 +6860,8 @@ fn spawn_generate_and_send(
                     let cell_data = db_tables.cells.load_chunk_cells(chunk_id).await.unwrap_or_default();
                     let building_data = db_tables.buildings.load_chunk_buildings(chunk_id).await.unwrap_or_default();
                     let unit_data = db_tables.units.load_chunk_units(*chunk_id).await.unwrap_or_default();
+                    let ground_items = db_tables.resources.load_chunk_ground_items(chunk_id).await.unwrap_or_default();
+                    let resource_stocks = db_tables.resources.load_chunk_resource_stocks(chunk_id).await.unwrap_or_default();
                     let (terrain_data, biome_data) = db_tables.terrains
                         .load_terrain(&terrain_name, chunk_id).await
                         .map(|(t, b)| (t.unwrap_or_default(), b.unwrap_or_default()))
@@ -3385,6 +6873,8 @@ fn spawn_generate_and_send(
                         cell_data,
                         building_data,
                         unit_data,
+                        ground_items,
+                        resource_stocks,
                     });
                     continue;
                 }
@@ -3403,7 +6893,7 @@ fn spawn_generate_and_send(
                 _ => {}
             }
 
-            let (terrain_data, cell_data, building_data) =
+            let (terrain_data, cell_data, building_data, resource_stocks) =
                 crate::world::systems::generate_chunk_data(
                     chunk_id,
                     &world_global_state,
@@ -3422,6 +6912,8 @@ fn spawn_generate_and_send(
                 cell_data,
                 building_data,
                 unit_data,
+                ground_items: vec![],
+                resource_stocks,
             }).is_err() {
                 tracing::debug!("Client disconnected");
                 return;
@@ -1,4 +1,6 @@
+mod action_queue;
 mod handlers;
 mod network_server;
+mod routines;
 
 pub use network_server::{NetworkServer, initialize_server};
\ No newline at end of file
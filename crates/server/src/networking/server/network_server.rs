@@ -11,7 +11,7 @@ use crate::units::NameGenerator;
 use crate::world::resources::WorldGlobalState;
 
 use super::super::Sessions;
-use super::handlers;
+use super::{action_queue, handlers, routines};
 
 pub struct NetworkServer {
     pub address: String,
@@ -92,6 +92,34 @@ pub fn initialize_server(
         .parse()
         .unwrap_or(9001);
 
+    // Files d'actions : lancées à chaque unité libérée par le processeur d'actions
+    match action_processor.take_idle_units_receiver() {
+        Some(idle_units) => action_queue::start_action_queue_runner(
+            idle_units,
+            sessions.clone(),
+            db_tables.clone(),
+            action_processor.clone(),
+            name_generator.clone(),
+            game_state.clone(),
+            grid_config.clone(),
+            dev_config.clone(),
+            world_global_state.clone(),
+        ),
+        None => tracing::error!("Action queue runner already started"),
+    }
+
+    // Routines des PNJ : relancées périodiquement tant que l'unité est libre
+    routines::start_routine_runner(
+        sessions.clone(),
+        db_tables.clone(),
+        action_processor.clone(),
+        name_generator.clone(),
+        game_state.clone(),
+        grid_config.clone(),
+        dev_config.clone(),
+        world_global_state.clone(),
+    );
+
     let sessions_clone = sessions.clone();
     let db_tables_clone = db_tables.clone();
     let action_processor_clone = action_processor.clone();
@@ -0,0 +1,263 @@
+use std::sync::Arc;
+
+use shared::grid::{GridConfig, GridCell};
+use shared::protocol::{QueuedAction, ServerMessage};
+use shared::{GameState, Routine, TerrainChunkId};
+
+use crate::action_processor::ActionProcessor;
+use crate::database::client::DatabaseTables;
+use crate::dev::DevConfig;
+use crate::units::NameGenerator;
+use crate::world::resources::WorldGlobalState;
+
+use super::super::Sessions;
+use super::handlers::dispatch_client_message;
+
+/// Fréquence à laquelle les unités libres reprennent leurs routines
+const ROUTINE_INTERVAL_SECS: u64 = 30;
+
+/// Les routines ne passent pas par une session : id fictif pour les logs
+const ROUTINE_SESSION_ID: u64 = 0;
+
+/// L'action qu'une routine lance là où se trouve l'unité
+pub fn routine_action(routine: &Routine, chunk_id: TerrainChunkId, cell: GridCell) -> QueuedAction {
+    match routine {
+        Routine::Harvest {
+            resource_specific_type,
+            ..
+        } => QueuedAction::HarvestResource {
+            chunk_id,
+            cell,
+            resource_specific_type: *resource_specific_type,
+        },
+        Routine::Craft { recipe_id, .. } => QueuedAction::CraftResource {
+            chunk_id,
+            cell,
+            recipe_id: recipe_id.clone(),
+            quantity: 1,
+        },
+    }
+}
+
+/// Objet produit par la routine, dont le stock décide si elle tourne encore
+pub fn routine_product(routine: &Routine, game_state: &GameState) -> Option<i32> {
+    match routine {
+        Routine::Harvest {
+            resource_specific_type,
+            ..
+        } => game_state
+            .harvest_yields_for(resource_specific_type.to_id())
+            .first()
+            .map(|y| y.result_item_id),
+        Routine::Craft { recipe_id, .. } => game_state.find_recipe(recipe_id).map(|r| r.result_item_id),
+    }
+}
+
+/// Occupe les PNJ désœuvrés avec leurs routines
+struct RoutineRunner {
+    sessions: Sessions,
+    db_tables: Arc<DatabaseTables>,
+    action_processor: Arc<ActionProcessor>,
+    name_generator: Arc<NameGenerator>,
+    game_state: Arc<GameState>,
+    grid_config: Arc<GridConfig>,
+    dev_config: Arc<DevConfig>,
+    world_global_state: Arc<WorldGlobalState>,
+}
+
+impl RoutineRunner {
+    async fn tick(&self) {
+        let automations = match self.db_tables.units.load_enabled_automated_actions().await {
+            Ok(automations) => automations,
+            Err(e) => {
+                tracing::error!("Routine tick: {}", e);
+                return;
+            }
+        };
+
+        // Les routines d'une même unité se suivent : on essaie dans l'ordre jusqu'à ce qu'une démarre
+        let mut started_unit = None;
+        for (unit_id, automation) in automations {
+            if started_unit == Some(unit_id) {
+                continue;
+            }
+            let Some(routine) = Routine::from_automated_action(&automation) else {
+                tracing::warn!(
+                    "Unit {} has an unreadable routine {} ({})",
+                    unit_id,
+                    automation.id,
+                    automation.action_type
+                );
+                continue;
+            };
+            if self.run(unit_id, &routine).await {
+                started_unit = Some(unit_id);
+            }
+        }
+    }
+
+    /// Lance la routine si l'unité est libre et que le stock n'est pas atteint.
+    /// Renvoie vrai si une action a démarré.
+    async fn run(&self, unit_id: u64, routine: &Routine) -> bool {
+        // Une unité occupée, ou à qui le joueur a donné une file, ne suit pas sa routine
+        let busy = self
+            .db_tables
+            .units
+            .get_busy_units(&[unit_id])
+            .await
+            .unwrap_or_default();
+        if !busy.is_empty() {
+            return false;
+        }
+        let queued = self
+            .db_tables
+            .action_queue
+            .load_queue(unit_id)
+            .await
+            .unwrap_or_default();
+        if !queued.is_empty() {
+            return false;
+        }
+
+        let Ok(Some(player_id)) = self.db_tables.units.load_controlling_player(unit_id).await else {
+            return false;
+        };
+        let Ok(unit) = self.db_tables.units.load_unit(unit_id).await else {
+            return false;
+        };
+
+        if let Some(item_id) = routine_product(routine, &self.game_state) {
+            let stock = self.stock(unit_id, item_id).await;
+            if stock >= routine.max_stock() {
+                return false;
+            }
+        }
+
+        let action = routine_action(routine, unit.current_chunk, unit.current_cell);
+        let (responses, _, _) = dispatch_client_message(
+            action.into_client_message(unit_id),
+            ROUTINE_SESSION_ID,
+            Some(player_id),
+            &self.sessions,
+            &self.db_tables,
+            &self.action_processor,
+            &self.name_generator,
+            &self.game_state,
+            &self.grid_config,
+            &self.dev_config,
+            &self.world_global_state,
+        )
+        .await;
+
+        let started = responses
+            .iter()
+            .any(|msg| matches!(msg, ServerMessage::ActionStatusUpdate { .. }));
+        if started {
+            tracing::info!("Unit {} started routine {:?}", unit_id, routine);
+            for msg in responses {
+                if let Err(e) = self.sessions.send_to_player(player_id, msg).await {
+                    tracing::debug!("Routine update not delivered to player {}: {}", player_id, e);
+                }
+            }
+        } else {
+            // Ingrédients manquants, ligne de production pleine... on réessaiera plus tard
+            tracing::debug!("Routine {:?} of unit {} could not start", routine, unit_id);
+        }
+        started
+    }
+
+    /// Stock de l'objet chez l'unité et dans le trésor de son organisation
+    async fn stock(&self, unit_id: u64, item_id: i32) -> i32 {
+        let carried = self
+            .db_tables
+            .resources
+            .load_inventory_summary(unit_id)
+            .await
+            .ok()
+            .and_then(|inventory| inventory.get(&item_id).copied())
+            .unwrap_or(0);
+
+        let stored = match self.db_tables.organizations.find_unit_organization(unit_id).await {
+            Ok(Some(org_id)) => self
+                .db_tables
+                .organizations
+                .load_treasury_stock(org_id)
+                .await
+                .unwrap_or_default()
+                .iter()
+                .filter(|item| item.item_id == item_id)
+                .map(|item| item.quantity)
+                .sum(),
+            _ => 0,
+        };
+
+        carried + stored
+    }
+}
+
+/// Démarre le moteur de routines des unités PNJ
+pub fn start_routine_runner(
+    sessions: Sessions,
+    db_tables: Arc<DatabaseTables>,
+    action_processor: Arc<ActionProcessor>,
+    name_generator: Arc<NameGenerator>,
+    game_state: Arc<GameState>,
+    grid_config: Arc<GridConfig>,
+    dev_config: Arc<DevConfig>,
+    world_global_state: Arc<WorldGlobalState>,
+) {
+    let interval_ms = dev_config.apply_speed(ROUTINE_INTERVAL_SECS * 1000);
+    let runner = RoutineRunner {
+        sessions,
+        db_tables,
+        action_processor,
+        name_generator,
+        game_state,
+        grid_config,
+        dev_config,
+        world_global_state,
+    };
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(interval_ms));
+        loop {
+            interval.tick().await;
+            runner.tick().await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::ResourceSpecificTypeEnum;
+
+    #[test]
+    fn test_routine_acts_where_the_unit_stands() {
+        let chunk_id = TerrainChunkId { x: 1, y: -2 };
+        let cell = GridCell { q: 4, r: 7 };
+
+        let harvest = Routine::Harvest {
+            resource_specific_type: ResourceSpecificTypeEnum::Wood,
+            max_stock: 20,
+        };
+        assert!(matches!(
+            routine_action(&harvest, chunk_id, cell),
+            QueuedAction::HarvestResource {
+                resource_specific_type: ResourceSpecificTypeEnum::Wood,
+                ..
+            }
+        ));
+
+        let craft = Routine::Craft {
+            recipe_id: "bread".to_string(),
+            max_stock: 5,
+        };
+        let action = routine_action(&craft, chunk_id, cell);
+        assert_eq!(action.cell(), cell);
+        assert!(matches!(
+            action,
+            QueuedAction::CraftResource { quantity: 1, .. }
+        ));
+    }
+}
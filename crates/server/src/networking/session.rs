@@ -1,17 +1,80 @@
 use bevy::prelude::*;
-use shared::protocol::ServerMessage;
-use std::collections::HashMap;
+use shared::TerrainChunkId;
+use shared::protocol::{ErrorCode, ServerMessage};
+use std::collections::{HashMap, HashSet};
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::sync::{RwLock, mpsc};
 
+use super::outbox::PlayerOutbox;
+use crate::auth::{RESUME_TOKEN_TTL_SECS, ResumeTokenSigner};
+
 pub type MessageSender = mpsc::UnboundedSender<ServerMessage>;
 
+/// Zone d'intérêt d'une session : les chunks que le client a chargés
+/// et qu'il n'a pas encore déchargés (même règle que `StreamingConfig` côté client).
+#[derive(Debug, Clone, Default)]
+pub struct ChunkInterest {
+    loaded: HashSet<TerrainChunkId>,
+    center: Option<TerrainChunkId>,
+    unload_distance: i32,
+}
+
+impl ChunkInterest {
+    /// Enregistre des chunks demandés via `RequestTerrainChunks`
+    pub fn mark_loaded(&mut self, chunk_ids: &[TerrainChunkId]) {
+        for chunk_id in chunk_ids {
+            if self.is_within_unload_distance(chunk_id) {
+                self.loaded.insert(*chunk_id);
+            }
+        }
+    }
+
+    /// Met à jour le centre de la zone d'intérêt et oublie les chunks que le client a déchargés
+    pub fn update_area(&mut self, center: TerrainChunkId, unload_distance: i32) {
+        self.center = Some(center);
+        self.unload_distance = unload_distance.max(0);
+
+        let Self {
+            loaded,
+            center,
+            unload_distance,
+        } = self;
+        loaded.retain(|id| Self::within(center.as_ref(), *unload_distance, id));
+    }
+
+    pub fn covers(&self, chunk_id: &TerrainChunkId) -> bool {
+        self.loaded.contains(chunk_id)
+    }
+
+    pub fn loaded_count(&self) -> usize {
+        self.loaded.len()
+    }
+
+    fn is_within_unload_distance(&self, chunk_id: &TerrainChunkId) -> bool {
+        Self::within(self.center.as_ref(), self.unload_distance, chunk_id)
+    }
+
+    /// Distance de Chebyshev, identique à `unload_distant` du WorldCache client
+    fn within(center: Option<&TerrainChunkId>, max_distance: i32, id: &TerrainChunkId) -> bool {
+        match center {
+            Some(center) => {
+                (id.x - center.x).abs() <= max_distance && (id.y - center.y).abs() <= max_distance
+            }
+            // Pas encore de centre connu : on garde tout ce qui a été demandé
+            None => true,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct SessionData {
     pub player_id: Option<u64>, // None avant login, Some(player_id) après
+    pub protocol_version: Option<u32>, // None tant que le client n'a pas envoyé Hello
     pub addr: SocketAddr,
     pub sender: MessageSender,
+    pub interest: ChunkInterest,
 }
 
 #[derive(Resource, Clone)]
@@ -20,13 +83,20 @@ pub struct Sessions {
     sessions: Arc<RwLock<HashMap<u64, SessionData>>>,
     // player_id -> session_id (pour envoyer des messages aux joueurs)
     player_to_session: Arc<RwLock<HashMap<u64, u64>>>,
+    // player_id -> derniers messages poussés, rejoués lors d'une reprise de session
+    outboxes: Arc<RwLock<HashMap<u64, PlayerOutbox>>>,
+    resume_tokens: Arc<ResumeTokenSigner>,
 }
 
+// Ordre de verrouillage : sessions -> player_to_session -> outboxes
+
 impl Default for Sessions {
     fn default() -> Self {
         Self {
             sessions: Arc::new(RwLock::new(HashMap::new())),
             player_to_session: Arc::new(RwLock::new(HashMap::new())),
+            outboxes: Arc::new(RwLock::new(HashMap::new())),
+            resume_tokens: Arc::new(ResumeTokenSigner::default()),
         }
     }
 }
@@ -40,8 +110,10 @@ impl Sessions {
     pub async fn insert(&self, session_id: u64, addr: SocketAddr, sender: MessageSender) {
         let session_data = SessionData {
             player_id: None, // Pas encore authentifié
+            protocol_version: None,
             addr,
             sender,
+            interest: ChunkInterest::default(),
         };
         self.sessions.write().await.insert(session_id, session_data);
     }
@@ -58,6 +130,12 @@ impl Sessions {
                 .await
                 .insert(player_id, session_id);
 
+            // Nouveau login : nouveau flux de messages, l'ancien ne peut plus être repris
+            self.outboxes
+                .write()
+                .await
+                .insert(player_id, PlayerOutbox::default());
+
             tracing::info!(
                 "Session {} authenticated as player {}",
                 session_id,
@@ -66,14 +144,137 @@ impl Sessions {
         }
     }
 
+    /// Enregistre la version de protocole acceptée lors du Hello
+    pub async fn complete_handshake(&self, session_id: u64, protocol_version: u32) {
+        if let Some(session_data) = self.sessions.write().await.get_mut(&session_id) {
+            session_data.protocol_version = Some(protocol_version);
+        }
+    }
+
+    pub async fn has_completed_handshake(&self, session_id: u64) -> bool {
+        self.sessions
+            .read()
+            .await
+            .get(&session_id)
+            .is_some_and(|s| s.protocol_version.is_some())
+    }
+
+    /// Jeton permettant de rattacher une future connexion à ce joueur
+    pub fn issue_resume_token(&self, player_id: u64) -> String {
+        self.resume_tokens.issue(player_id, now_secs())
+    }
+
+    /// Invalide les jetons de reprise déjà émis pour ce joueur (changement de mot de passe...)
+    pub fn revoke_resume_tokens(&self, player_id: u64) {
+        self.resume_tokens.revoke(player_id);
+    }
+
+    /// Rattache une nouvelle connexion au joueur d'un jeton de reprise et lui renvoie
+    /// les messages qu'il n'a pas vus. Retourne le player_id et le nombre de messages rejoués.
+    pub async fn resume_session(
+        &self,
+        session_id: u64,
+        token: &str,
+        last_seen_seq: u64,
+    ) -> Result<(u64, u32), ErrorCode> {
+        let player_id = self
+            .resume_tokens
+            .verify(token, now_secs())
+            .map_err(|e| {
+                tracing::warn!("Session {} sent an invalid resume token: {}", session_id, e);
+                ErrorCode::ResumeTokenInvalid
+            })?;
+
+        let mut sessions = self.sessions.write().await;
+        let mut player_to_session = self.player_to_session.write().await;
+        let mut outboxes = self.outboxes.write().await;
+
+        let outbox = outboxes
+            .get_mut(&player_id)
+            .ok_or(ErrorCode::ResumeUnavailable)?;
+        let replay = outbox
+            .replay_after(last_seen_seq)
+            .ok_or(ErrorCode::ResumeUnavailable)?;
+        if !sessions.contains_key(&session_id) {
+            return Err(ErrorCode::ResumeUnavailable);
+        }
+
+        // L'ancienne socket n'est peut-être pas encore détectée comme morte : on la détache
+        let previous_session = player_to_session
+            .insert(player_id, session_id)
+            .filter(|previous| *previous != session_id);
+        let interest = match previous_session.and_then(|id| sessions.get_mut(&id)) {
+            Some(previous) => {
+                previous.player_id = None;
+                outbox.mark_reconnected();
+                Some(previous.interest.clone())
+            }
+            None => outbox.mark_reconnected(),
+        };
+
+        let session_data = sessions
+            .get_mut(&session_id)
+            .ok_or(ErrorCode::ResumeUnavailable)?;
+        session_data.player_id = Some(player_id);
+        if let Some(interest) = interest {
+            session_data.interest = interest;
+        }
+
+        let replayed = replay.len() as u32;
+        for message in replay {
+            let _ = session_data.sender.send(message);
+        }
+
+        tracing::info!(
+            "Session {} resumed player {} ({} message(s) replayed)",
+            session_id,
+            player_id,
+            replayed
+        );
+        Ok((player_id, replayed))
+    }
+
+    /// Déconnecte le joueur de la session sans fermer la socket.
+    /// Son buffer de messages est jeté : aucun jeton de reprise ne peut plus le rattacher.
+    pub async fn logout(&self, session_id: u64) {
+        let mut sessions = self.sessions.write().await;
+        let Some(player_id) = sessions
+            .get_mut(&session_id)
+            .and_then(|session_data| session_data.player_id.take())
+        else {
+            return;
+        };
+
+        let mut player_to_session = self.player_to_session.write().await;
+        if player_to_session.get(&player_id) == Some(&session_id) {
+            player_to_session.remove(&player_id);
+        }
+        self.outboxes.write().await.remove(&player_id);
+
+        tracing::info!("Session {} logged out player {}", session_id, player_id);
+    }
+
     /// Retire une session
     pub async fn remove(&self, session_id: &u64) {
         let mut sessions = self.sessions.write().await;
 
         // Si la session avait un player_id, retirer le mapping inverse
-        if let Some(session_data) = sessions.remove(session_id) {
-            if let Some(player_id) = session_data.player_id {
-                self.player_to_session.write().await.remove(&player_id);
+        if let Some(session_data) = sessions.remove(session_id)
+            && let Some(player_id) = session_data.player_id
+        {
+            let mut player_to_session = self.player_to_session.write().await;
+            // Une reprise a pu rattacher le joueur à une autre session entre-temps
+            if player_to_session.get(&player_id) == Some(session_id) {
+                player_to_session.remove(&player_id);
+
+                // Garder les messages du joueur le temps que son jeton de reprise expire
+                let mut outboxes = self.outboxes.write().await;
+                if let Some(outbox) = outboxes.get_mut(&player_id) {
+                    outbox.mark_disconnected(session_data.interest);
+                }
+                outboxes.retain(|_, outbox| {
+                    !outbox.is_expired(Duration::from_secs(RESUME_TOKEN_TTL_SECS))
+                });
             }
         }
     }
@@ -88,36 +289,187 @@ impl Sessions {
         player_id: u64,
         message: ServerMessage,
     ) -> Result<(), String> {
+        let sessions = self.sessions.read().await;
         // Trouver la session correspondant au player_id
-        let player_to_session = self.player_to_session.read().await;
-        let session_id = player_to_session
-            .get(&player_id)
-            .ok_or_else(|| format!("Player {} not found in sessions", player_id))?;
+        let session_id = self.player_to_session.read().await.get(&player_id).copied();
+        let mut outboxes = self.outboxes.write().await;
 
         // Envoyer le message via la session
-        let sessions = self.sessions.read().await;
-        if let Some(session_data) = sessions.get(session_id) {
-            session_data
-                .sender
-                .send(message)
-                .map_err(|e| format!("Failed to send message to player {}: {}", player_id, e))?;
-            Ok(())
-        } else {
-            Err(format!("Session {} not found", session_id))
+        match session_id.and_then(|id| sessions.get(&id)) {
+            Some(session_data) => {
+                let message = Self::sequence(&mut outboxes, session_data, message);
+                session_data.sender.send(message).map_err(|e| {
+                    format!("Failed to send message to player {}: {}", player_id, e)
+                })?;
+                Ok(())
+            }
+            // Joueur déconnecté mais encore reprenable : le message attend dans son outbox
+            None => match outboxes.get_mut(&player_id) {
+                Some(outbox) if outbox.is_disconnected() => {
+                    outbox.push(message);
+                    Ok(())
+                }
+                _ => Err(format!("Player {} not found in sessions", player_id)),
+            },
         }
     }
 
     /// Broadcast un message à tous les joueurs connectés
+    /// (et le garde pour ceux qui peuvent encore reprendre leur session)
     pub async fn broadcast(&self, message: ServerMessage) {
         let sessions = self.sessions.read().await;
+        let mut outboxes = self.outboxes.write().await;
         for (_session_id, session_data) in sessions.iter() {
-            let _ = session_data.sender.send(message.clone());
+            let message = Self::sequence(&mut outboxes, session_data, message.clone());
+            let _ = session_data.sender.send(message);
+        }
+        for outbox in outboxes.values_mut().filter(|o| o.is_disconnected()) {
+            outbox.push(message.clone());
+        }
+    }
+
+    /// Numérote les messages des sessions authentifiées, les autres partent tels quels
+    fn sequence(
+        outboxes: &mut HashMap<u64, PlayerOutbox>,
+        session_data: &SessionData,
+        message: ServerMessage,
+    ) -> ServerMessage {
+        match session_data
+            .player_id
+            .and_then(|player_id| outboxes.get_mut(&player_id))
+        {
+            Some(outbox) => outbox.push(message),
+            None => message,
+        }
+    }
+
+    /// Broadcast un message aux sessions dont la zone d'intérêt couvre le chunk
+    pub async fn broadcast_to_chunk(&self, chunk_id: &TerrainChunkId, message: ServerMessage) {
+        self.broadcast_to_chunks(std::slice::from_ref(chunk_id), None, message)
+            .await;
+    }
+
+    /// Broadcast un message aux sessions qui ont chargé au moins un des chunks.
+    /// `also_player` reçoit le message même s'il ne regarde pas ces chunks
+    /// (ex: le propriétaire d'une unité qui se déplace).
+    /// Les joueurs déconnectés mais reprenables le reçoivent dans leur outbox.
+    pub async fn broadcast_to_chunks(
+        &self,
+        chunk_ids: &[TerrainChunkId],
+        also_player: Option<u64>,
+        message: ServerMessage,
+    ) {
+        let sessions = self.sessions.read().await;
+        let mut outboxes = self.outboxes.write().await;
+        let mut recipients = 0;
+        for session_data in sessions.values() {
+            let is_owner = also_player.is_some() && session_data.player_id == also_player;
+            let is_interested = chunk_ids.iter().any(|id| session_data.interest.covers(id));
+            if is_owner || is_interested {
+                let message = Self::sequence(&mut outboxes, session_data, message.clone());
+                let _ = session_data.sender.send(message);
+                recipients += 1;
+            }
+        }
+        for (player_id, outbox) in outboxes.iter_mut().filter(|(_, o)| o.is_disconnected()) {
+            if also_player == Some(*player_id) || outbox.watches_any(chunk_ids) {
+                outbox.push(message.clone());
+                recipients += 1;
+            }
+        }
+        tracing::debug!(
+            "Chunk broadcast to {}/{} sessions for {} chunk(s)",
+            recipients,
+            sessions.len(),
+            chunk_ids.len()
+        );
+    }
+
+    /// Ajoute des chunks à la zone d'intérêt d'une session (après `RequestTerrainChunks`)
+    pub async fn mark_chunks_loaded(&self, session_id: u64, chunk_ids: &[TerrainChunkId]) {
+        let mut sessions = self.sessions.write().await;
+        if let Some(session_data) = sessions.get_mut(&session_id) {
+            session_data.interest.mark_loaded(chunk_ids);
         }
     }
 
+    /// Met à jour le centre et le rayon de déchargement de la zone d'intérêt d'une session
+    pub async fn update_interest_area(
+        &self,
+        session_id: u64,
+        center: TerrainChunkId,
+        unload_distance: i32,
+    ) {
+        let mut sessions = self.sessions.write().await;
+        if let Some(session_data) = sessions.get_mut(&session_id) {
+            session_data.interest.update_area(center, unload_distance);
+            tracing::debug!(
+                "Session {} interest centered on ({},{}) radius {} ({} chunks loaded)",
+                session_id,
+                center.x,
+                center.y,
+                unload_distance,
+                session_data.interest.loaded_count()
+            );
+        }
+    }
+
+    /// Adresse du client d'une session (pour l'audit des connexions)
+    pub async fn get_addr(&self, session_id: u64) -> Option<SocketAddr> {
+        self.sessions.read().await.get(&session_id).map(|s| s.addr)
+    }
+
     /// Récupère le player_id d'une session (si authentifiée)
     pub async fn get_player_id(&self, session_id: u64) -> Option<u64> {
         let sessions = self.sessions.read().await;
         sessions.get(&session_id).and_then(|s| s.player_id)
     }
 }
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id(x: i32, y: i32) -> TerrainChunkId {
+        TerrainChunkId { x, y }
+    }
+
+    #[test]
+    fn test_requested_chunks_are_covered() {
+        let mut interest = ChunkInterest::default();
+        interest.mark_loaded(&[id(0, 0), id(1, 0)]);
+
+        assert!(interest.covers(&id(0, 0)));
+        assert!(interest.covers(&id(1, 0)));
+        assert!(!interest.covers(&id(2, 0)));
+    }
+
+    #[test]
+    fn test_moving_center_drops_distant_chunks() {
+        let mut interest = ChunkInterest::default();
+        interest.update_area(id(0, 0), 2);
+        interest.mark_loaded(&[id(0, 0), id(2, 2), id(-2, 0)]);
+
+        interest.update_area(id(3, 0), 2);
+
+        assert!(!interest.covers(&id(0, 0)));
+        assert!(!interest.covers(&id(-2, 0)));
+        assert!(interest.covers(&id(2, 2)));
+    }
+
+    #[test]
+    fn test_chunks_outside_unload_distance_are_ignored() {
+        let mut interest = ChunkInterest::default();
+        interest.update_area(id(0, 0), 1);
+        interest.mark_loaded(&[id(5, 5)]);
+
+        assert!(!interest.covers(&id(5, 5)));
+    }
+}
@@ -0,0 +1,558 @@
+use shared::protocol::{DepartureReason, PopulationDeparture, ServerMessage};
+use shared::{ConsumptionDemand, TerrainChunkId, WorldPosition, grid::GridCell};
+use sqlx::Row;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::action_processor::ActionProcessor;
+use crate::database::client::DatabaseTables;
+use crate::dev::DevConfig;
+use crate::networking::Sessions;
+use crate::utils::ticks::spawn_daily_tick;
+
+/// Les besoins de priorité 1 à 3 sont vitaux : s'en passer, c'est avoir faim
+pub const ESSENTIAL_PRIORITY: i32 = 3;
+
+/// Bonheur gagné pour chaque besoin satisfait
+const HAPPINESS_PER_MET_NEED: i32 = 3;
+/// Variation maximale du bonheur en une journée
+const MAX_HAPPINESS_GAIN: i32 = 10;
+const MAX_HAPPINESS_LOSS: i32 = 20;
+
+/// Points de vie perdus par jour de famine / regagnés par jour nourri (% des PV max)
+const STARVATION_HP_LOSS_PERCENT: i32 = 25;
+const FED_HP_GAIN_PERCENT: i32 = 10;
+
+/// Un villageois affamé depuis ce nombre de jours et malheureux part
+const EMIGRATION_STARVING_DAYS: i32 = 3;
+const EMIGRATION_MAX_HAPPINESS: i32 = 25;
+
+/// Une ration du jour : ce qu'un villageois a pris chez lui et au trésor pour un besoin
+#[derive(Debug, Clone, PartialEq)]
+pub struct Meal {
+    pub unit_id: u64,
+    pub item_id: i32,
+    pub priority: i32,
+    pub from_unit: i32,
+    pub from_treasury: i32,
+    pub met: bool,
+}
+
+/// Quantité consommée dans la journée : au moins un exemplaire par besoin
+pub fn daily_quantity(quantity_per_day: f32) -> i32 {
+    (quantity_per_day.ceil() as i32).max(1)
+}
+
+/// Répartit la nourriture entre les villageois, priorité par priorité : tout le monde
+/// mange son pain avant que quiconque ne boive sa bière. Chacun puise d'abord dans
+/// son inventaire, puis dans le trésor. Un besoin n'est servi que s'il peut l'être
+/// entièrement ; `holdings` et `treasury` sont décrémentés de ce qui est pris.
+pub fn plan_meals(
+    needs: &[(u64, Vec<ConsumptionDemand>)],
+    holdings: &mut HashMap<u64, HashMap<i32, i32>>,
+    treasury: &mut HashMap<i32, i32>,
+) -> Vec<Meal> {
+    let mut demands: Vec<(u64, &ConsumptionDemand)> = needs
+        .iter()
+        .flat_map(|(unit_id, demands)| demands.iter().map(move |d| (*unit_id, d)))
+        .collect();
+    demands.sort_by_key(|(_, demand)| demand.priority);
+
+    let mut meals = Vec::new();
+    for (unit_id, demand) in demands {
+        let needed = daily_quantity(demand.quantity_per_day);
+        let own = holdings
+            .get(&unit_id)
+            .and_then(|inventory| inventory.get(&demand.item_id))
+            .copied()
+            .unwrap_or(0);
+        let stored = treasury.get(&demand.item_id).copied().unwrap_or(0);
+
+        let mut meal = Meal {
+            unit_id,
+            item_id: demand.item_id,
+            priority: demand.priority,
+            from_unit: 0,
+            from_treasury: 0,
+            met: false,
+        };
+        if own + stored >= needed {
+            meal.from_unit = own.min(needed);
+            meal.from_treasury = needed - meal.from_unit;
+            meal.met = true;
+            if let Some(count) = holdings
+                .get_mut(&unit_id)
+                .and_then(|inventory| inventory.get_mut(&demand.item_id))
+            {
+                *count -= meal.from_unit;
+            }
+            if let Some(count) = treasury.get_mut(&demand.item_id) {
+                *count -= meal.from_treasury;
+            }
+        }
+        meals.push(meal);
+    }
+    meals
+}
+
+/// Effet d'une journée sur un villageois : (variation du bonheur, affamé ?)
+/// Un besoin non satisfait coûte d'autant plus de bonheur qu'il est important.
+pub fn needs_outcome(meals: &[&Meal]) -> (i32, bool) {
+    let mut happiness_delta = 0;
+    let mut starving = false;
+    for meal in meals {
+        if meal.met {
+            happiness_delta += HAPPINESS_PER_MET_NEED;
+        } else {
+            happiness_delta -= 11 - meal.priority.clamp(1, 10);
+            if meal.priority <= ESSENTIAL_PRIORITY {
+                starving = true;
+            }
+        }
+    }
+    (
+        happiness_delta.clamp(-MAX_HAPPINESS_LOSS, MAX_HAPPINESS_GAIN),
+        starving,
+    )
+}
+
+/// Variation des points de vie selon que le villageois a mangé ou non
+pub fn hp_change(max_hp: i32, starving: bool) -> i32 {
+    if starving {
+        -(max_hp * STARVATION_HP_LOSS_PERCENT / 100).max(1)
+    } else {
+        max_hp * FED_HP_GAIN_PERCENT / 100
+    }
+}
+
+/// Le villageois reste-t-il ? Mort de faim à 0 PV, départ s'il a faim depuis
+/// trop longtemps et n'est plus heureux.
+pub fn villager_departure(
+    current_hp: i32,
+    happiness: i32,
+    starving_days: i32,
+) -> Option<DepartureReason> {
+    if current_hp <= 0 {
+        Some(DepartureReason::Starved)
+    } else if starving_days >= EMIGRATION_STARVING_DAYS && happiness <= EMIGRATION_MAX_HAPPINESS {
+        Some(DepartureReason::Emigrated)
+    } else {
+        None
+    }
+}
+
+/// Villageois présent sur le territoire d'une organisation
+struct Villager {
+    unit_id: u64,
+    first_name: String,
+    last_name: String,
+    cell: GridCell,
+    chunk: TerrainChunkId,
+}
+
+pub struct ConsumptionSystem {
+    db_tables: Arc<DatabaseTables>,
+    sessions: Sessions,
+    // Un villageois qui part quitte d'abord l'action à laquelle il travaille
+    action_processor: Arc<ActionProcessor>,
+}
+
+impl ConsumptionSystem {
+    pub fn new(
+        db_tables: Arc<DatabaseTables>,
+        sessions: Sessions,
+        action_processor: Arc<ActionProcessor>,
+    ) -> Self {
+        Self {
+            db_tables,
+            sessions,
+            action_processor,
+        }
+    }
+
+    /// Tick journalier — chaque villageois consomme ses besoins
+    pub async fn tick(&self) {
+        match self
+            .db_tables
+            .units
+            .assign_default_consumption_demands()
+            .await
+        {
+            Ok(0) => {}
+            Ok(count) => tracing::info!("Consumption tick: {} default needs assigned", count),
+            Err(e) => tracing::warn!("Consumption tick: {}", e),
+        }
+
+        let orgs = match self.load_active_organizations().await {
+            Ok(orgs) => orgs,
+            Err(e) => {
+                tracing::error!("Consumption tick: failed to load orgs: {}", e);
+                return;
+            }
+        };
+
+        for (org_id, leader_unit_id) in &orgs {
+            if let Err(e) = self.tick_organization(*org_id, *leader_unit_id).await {
+                tracing::warn!("Consumption tick failed for org {}: {}", org_id, e);
+            }
+        }
+    }
+
+    /// Organisations fondées par un joueur (celles qui ont un leader)
+    async fn load_active_organizations(&self) -> Result<Vec<(u64, u64)>, String> {
+        let rows = sqlx::query(
+            "SELECT id, leader_unit_id FROM organizations.organizations WHERE leader_unit_id IS NOT NULL"
+        )
+        .fetch_all(&self.db_tables.pool)
+        .await
+        .map_err(|e| format!("Failed to load organizations: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| {
+                (
+                    r.get::<i64, _>("id") as u64,
+                    r.get::<i64, _>("leader_unit_id") as u64,
+                )
+            })
+            .collect())
+    }
+
+    /// Villageois (unités non-lord) présents sur le territoire
+    async fn load_villagers(&self, org_id: u64) -> Result<Vec<Villager>, String> {
+        let rows = sqlx::query(
+            r#"
+            SELECT u.id, u.first_name, u.last_name,
+                   u.current_cell_q, u.current_cell_r, u.current_chunk_x, u.current_chunk_y
+            FROM units.units u
+            INNER JOIN organizations.territory_cells tc
+                ON u.current_cell_q = tc.cell_q AND u.current_cell_r = tc.cell_r
+            WHERE tc.organization_id = $1
+              AND u.is_lord = false
+            ORDER BY u.id ASC
+            "#,
+        )
+        .bind(org_id as i64)
+        .fetch_all(&self.db_tables.pool)
+        .await
+        .map_err(|e| format!("Failed to load villagers: {}", e))?;
+
+        Ok(rows
+            .iter()
+            .map(|r| Villager {
+                unit_id: r.get::<i64, _>("id") as u64,
+                first_name: r.get("first_name"),
+                last_name: r.get("last_name"),
+                cell: GridCell {
+                    q: r.get("current_cell_q"),
+                    r: r.get("current_cell_r"),
+                },
+                chunk: TerrainChunkId {
+                    x: r.get("current_chunk_x"),
+                    y: r.get("current_chunk_y"),
+                },
+            })
+            .collect())
+    }
+
+    async fn tick_organization(&self, org_id: u64, leader_unit_id: u64) -> Result<(), String> {
+        let villagers = self.load_villagers(org_id).await?;
+        if villagers.is_empty() {
+            return Ok(());
+        }
+
+        // 1. Besoins et provisions
+        let mut needs = Vec::new();
+        let mut holdings = HashMap::new();
+        for villager in &villagers {
+            let demands = self
+                .db_tables
+                .units
+                .load_consumption_demands(villager.unit_id)
+                .await?;
+            let inventory = self
+                .db_tables
+                .resources
+                .load_inventory_summary(villager.unit_id)
+                .await?;
+            needs.push((villager.unit_id, demands));
+            holdings.insert(villager.unit_id, inventory);
+        }
+        let mut treasury: HashMap<i32, i32> = self
+            .db_tables
+            .organizations
+            .load_treasury_stock(org_id)
+            .await?
+            .iter()
+            .map(|item| (item.item_id, item.quantity))
+            .collect();
+
+        // 2. Repas du jour
+        let meals = plan_meals(&needs, &mut holdings, &mut treasury);
+        let mut treasury_used = false;
+        for meal in meals.iter().filter(|meal| meal.met) {
+            if meal.from_unit > 0
+                && let Err(e) = self
+                    .db_tables
+                    .resources
+                    .consume_items(meal.unit_id, meal.item_id, meal.from_unit)
+                    .await
+            {
+                tracing::warn!("Unit {} could not eat: {}", meal.unit_id, e);
+            }
+            if meal.from_treasury > 0 {
+                treasury_used = true;
+                let taken = self
+                    .db_tables
+                    .organizations
+                    .take_treasury_items(org_id, meal.item_id, meal.from_treasury)
+                    .await;
+                match taken {
+                    Ok(taken) => {
+                        let ids: Vec<u64> = taken.iter().map(|(id, _)| *id).collect();
+                        if let Err(e) = self.db_tables.resources.delete_item_instances(&ids).await {
+                            tracing::warn!("{}", e);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!("Unit {} could not eat from treasury: {}", meal.unit_id, e)
+                    }
+                }
+            }
+        }
+
+        // 3. Bonheur, santé et départs
+        let mut departures = Vec::new();
+        for villager in &villagers {
+            let villager_meals: Vec<&Meal> = meals
+                .iter()
+                .filter(|meal| meal.unit_id == villager.unit_id)
+                .collect();
+            if villager_meals.is_empty() {
+                continue;
+            }
+            let (happiness_delta, starving) = needs_outcome(&villager_meals);
+
+            let mut stats = self
+                .db_tables
+                .units
+                .load_derived_stats(villager.unit_id)
+                .await?;
+            stats.happiness = (stats.happiness + happiness_delta).clamp(0, 100);
+            stats.current_hp =
+                (stats.current_hp + hp_change(stats.max_hp, starving)).clamp(0, stats.max_hp);
+            self.db_tables
+                .units
+                .update_derived_stats(villager.unit_id, &stats)
+                .await?;
+            let starving_days = self
+                .db_tables
+                .units
+                .record_starvation(villager.unit_id, starving)
+                .await?;
+
+            if let Some(reason) =
+                villager_departure(stats.current_hp, stats.happiness, starving_days)
+            {
+                departures.push((villager, reason));
+            }
+        }
+
+        let leader_player_id = self
+            .db_tables
+            .units
+            .load_unit(leader_unit_id)
+            .await
+            .ok()
+            .and_then(|leader| leader.player_id);
+
+        let mut population = villagers.len() as i32;
+        for (villager, reason) in departures {
+            if let Err(e) = self.remove_villager(villager, reason).await {
+                tracing::warn!("Failed to remove villager {}: {}", villager.unit_id, e);
+                continue;
+            }
+            population -= 1;
+            if let Err(e) =
+                sqlx::query("UPDATE organizations.organizations SET population = $1 WHERE id = $2")
+                    .bind(population)
+                    .bind(org_id as i64)
+                    .execute(&self.db_tables.pool)
+                    .await
+            {
+                tracing::warn!("Failed to update population of org {}: {}", org_id, e);
+            }
+
+            tracing::info!(
+                "✗ Villager {} {} left org {} ({:?}, pop: {})",
+                villager.first_name,
+                villager.last_name,
+                org_id,
+                reason,
+                population
+            );
+
+            if let Some(player_id) = leader_player_id {
+                let msg = ServerMessage::PopulationChanged {
+                    organization_id: org_id,
+                    new_population: population,
+                    immigrant: None,
+                    departure: Some(PopulationDeparture {
+                        unit_id: villager.unit_id,
+                        first_name: villager.first_name.clone(),
+                        last_name: villager.last_name.clone(),
+                        reason,
+                    }),
+                };
+                let _ = self.sessions.send_to_player(player_id, msg).await;
+            }
+        }
+
+        // 4. Le joueur voit son trésor diminuer
+        if treasury_used && let Some(player_id) = leader_player_id {
+            let organization = self
+                .db_tables
+                .organizations
+                .load_organization(org_id)
+                .await?;
+            let officers = self.db_tables.organizations.load_officers(org_id).await?;
+            let items = self
+                .db_tables
+                .organizations
+                .load_treasury_stock(org_id)
+                .await?;
+            let msg = ServerMessage::TreasuryData {
+                organization_id: org_id,
+                gold: organization.treasury_gold,
+                items,
+                can_withdraw: organization.leader_unit_id.is_some_and(|leader_id| {
+                    organization.can_withdraw_from_treasury(leader_id, &officers)
+                }),
+            };
+            let _ = self.sessions.send_to_player(player_id, msg).await;
+        }
+
+        Ok(())
+    }
+
+    /// Retire un villageois du monde : un mort laisse ses affaires au sol,
+    /// un émigrant part avec. Son action en cours est annulée ou continue sans lui.
+    async fn remove_villager(
+        &self,
+        villager: &Villager,
+        reason: DepartureReason,
+    ) -> Result<(), String> {
+        self.action_processor.release_unit(villager.unit_id).await?;
+
+        match reason {
+            DepartureReason::Starved => {
+                let position = WorldPosition {
+                    cell_q: villager.cell.q,
+                    cell_r: villager.cell.r,
+                    chunk_x: villager.chunk.x,
+                    chunk_y: villager.chunk.y,
+                };
+                let dropped = self
+                    .db_tables
+                    .resources
+                    .drop_all_unit_items(villager.unit_id, position)
+                    .await?;
+                self.db_tables.units.delete_unit(villager.unit_id).await?;
+
+                if dropped > 0 {
+                    let items = self
+                        .db_tables
+                        .resources
+                        .load_cell_ground_items(&villager.cell)
+                        .await?;
+                    let msg = ServerMessage::GroundItemsUpdate {
+                        chunk_id: villager.chunk,
+                        cell: villager.cell,
+                        items,
+                    };
+                    self.sessions.broadcast_to_chunk(&villager.chunk, msg).await;
+                }
+            }
+            DepartureReason::Emigrated => {
+                self.db_tables
+                    .resources
+                    .delete_all_unit_items(villager.unit_id)
+                    .await?;
+                self.db_tables.units.delete_unit(villager.unit_id).await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn start_consumption_tick(system: Arc<ConsumptionSystem>, dev_config: Arc<DevConfig>) {
+    spawn_daily_tick(dev_config, move || {
+        let system = system.clone();
+        async move { system.tick().await }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn demand(item_id: i32, quantity_per_day: f32, priority: i32) -> ConsumptionDemand {
+        ConsumptionDemand {
+            item_id,
+            quantity_per_day,
+            priority,
+        }
+    }
+
+    #[test]
+    fn test_plan_meals_serves_vital_needs_first_from_inventory_then_treasury() {
+        const BREAD: i32 = 10;
+        const BEER: i32 = 66;
+        let needs = vec![
+            (1, vec![demand(BEER, 0.5, 7), demand(BREAD, 1.0, 1)]),
+            (2, vec![demand(BREAD, 1.0, 1)]),
+        ];
+        let mut holdings = HashMap::from([(1, HashMap::from([(BEER, 1)])), (2, HashMap::new())]);
+        let mut treasury = HashMap::from([(BREAD, 1)]);
+
+        let meals = plan_meals(&needs, &mut holdings, &mut treasury);
+
+        // Le seul pain du trésor va au premier villageois, pas de pain pour le second
+        let bread_1 = meals
+            .iter()
+            .find(|m| m.unit_id == 1 && m.item_id == BREAD)
+            .unwrap();
+        assert!(bread_1.met);
+        assert_eq!(bread_1.from_treasury, 1);
+        let bread_2 = meals.iter().find(|m| m.unit_id == 2).unwrap();
+        assert!(!bread_2.met);
+        // La bière vient de l'inventaire
+        let beer = meals.iter().find(|m| m.item_id == BEER).unwrap();
+        assert!(beer.met);
+        assert_eq!(beer.from_unit, 1);
+        assert_eq!(treasury[&BREAD], 0);
+    }
+
+    #[test]
+    fn test_starvation_leads_to_emigration_then_death() {
+        let hungry = Meal {
+            unit_id: 1,
+            item_id: 10,
+            priority: 1,
+            from_unit: 0,
+            from_treasury: 0,
+            met: false,
+        };
+        let (delta, starving) = needs_outcome(&[&hungry]);
+        assert!(starving);
+        assert_eq!(delta, -10);
+        assert_eq!(hp_change(100, true), -25);
+
+        assert_eq!(villager_departure(75, 40, 1), None);
+        assert_eq!(
+            villager_departure(25, 20, 3),
+            Some(DepartureReason::Emigrated)
+        );
+        assert_eq!(villager_departure(0, 80, 4), Some(DepartureReason::Starved));
+    }
+}
@@ -1,3 +1,5 @@
+mod consumption;
 mod systems;
 
+pub use consumption::{ConsumptionSystem, start_consumption_tick};
 pub use systems::{PopulationSystem, start_population_tick};
@@ -110,6 +110,7 @@ impl PopulationSystem {
                                 organization_id: org_id,
                                 new_population: new_pop as i32,
                                 immigrant: Some(unit_data.clone()),
+                                departure: None,
                             };
                             let _ = self.sessions.send_to_player(player_id, msg).await;
 
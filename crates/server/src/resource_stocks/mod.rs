@@ -0,0 +1,5 @@
+mod systems;
+
+pub use systems::{
+    RegrowthSystem, cell_resource_stock, draw_harvest, seed_chunk_stocks, start_regrowth_tick,
+};
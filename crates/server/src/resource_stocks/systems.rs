@@ -0,0 +1,332 @@
+use shared::protocol::ServerMessage;
+use shared::{
+    BiomeResourceStock, BiomeTypeEnum, BuildingData, BuildingSpecific, CellResourceStock,
+    GameState, ResourceSpecificTypeEnum, TerrainChunkId,
+    grid::{CellData, GridCell},
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::database::client::DatabaseTables;
+use crate::dev::DevConfig;
+use crate::networking::Sessions;
+use crate::utils::ticks::spawn_daily_tick;
+
+/// Densité minimale d'une forêt, comme dans `NaturalBuildingGenerator`
+const MIN_TREE_DENSITY: f32 = 0.3;
+
+/// Réserves d'une cellule vierge : le bois vient de son arbre (d'autant plus gros que
+/// la forêt est dense), le minerai et les pierres viennent du sol de son biome.
+pub fn seed_cell_stocks(
+    cell: &GridCell,
+    biome: BiomeTypeEnum,
+    tree_density: Option<f32>,
+    biome_stocks: &[BiomeResourceStock],
+) -> Vec<CellResourceStock> {
+    biome_stocks
+        .iter()
+        .filter(|bs| bs.biome == biome)
+        .filter_map(|bs| {
+            let max_stock = if bs.resource_specific_type == ResourceSpecificTypeEnum::Wood {
+                let density = tree_density?.clamp(MIN_TREE_DENSITY, 1.0);
+                ((bs.max_stock as f32 * density).round() as i32).max(1)
+            } else {
+                bs.max_stock
+            };
+            Some(CellResourceStock {
+                cell: *cell,
+                resource_specific_type: bs.resource_specific_type,
+                stock: max_stock,
+                max_stock,
+            })
+        })
+        .collect()
+}
+
+/// Réserves d'un chunk qui vient d'être généré
+pub fn seed_chunk_stocks(
+    cells: &[CellData],
+    buildings: &[BuildingData],
+    biome_stocks: &[BiomeResourceStock],
+) -> Vec<CellResourceStock> {
+    let tree_densities: HashMap<GridCell, f32> = buildings
+        .iter()
+        .filter_map(|building| match &building.specific_data {
+            BuildingSpecific::Tree(tree) => Some((building.base_data.cell, tree.density)),
+            _ => None,
+        })
+        .collect();
+
+    cells
+        .iter()
+        .flat_map(|cell_data| {
+            seed_cell_stocks(
+                &cell_data.cell,
+                cell_data.biome,
+                tree_densities.get(&cell_data.cell).copied(),
+                biome_stocks,
+            )
+        })
+        .collect()
+}
+
+/// Réserve après une journée de repousse
+pub fn regrow(stock: &CellResourceStock, regrowth_per_day: i32) -> CellResourceStock {
+    CellResourceStock {
+        stock: (stock.stock + regrowth_per_day.max(0)).min(stock.max_stock),
+        ..*stock
+    }
+}
+
+/// Réserve d'une ressource sur une cellule. Les chunks générés avant l'apparition
+/// des réserves sont ensemencés à la première récolte.
+pub async fn cell_resource_stock(
+    db_tables: &DatabaseTables,
+    game_state: &GameState,
+    chunk_id: &TerrainChunkId,
+    cell: &GridCell,
+    resource_specific_type: ResourceSpecificTypeEnum,
+) -> Result<Option<CellResourceStock>, String> {
+    let stocks = db_tables.resources.load_cell_resource_stocks(cell).await?;
+    if let Some(stock) = stocks
+        .into_iter()
+        .find(|s| s.resource_specific_type == resource_specific_type)
+    {
+        return Ok(Some(stock));
+    }
+
+    let Some(biome) = db_tables.cells.get_biome_at_cell(cell).await? else {
+        return Ok(None);
+    };
+    let tree_density = db_tables
+        .buildings
+        .find_tree_at_cell(cell)
+        .await?
+        .map(|(_, density)| density);
+
+    // Les réserves déjà entamées ne sont pas réensemencées (ON CONFLICT DO NOTHING)
+    let seeded = seed_cell_stocks(cell, biome, tree_density, &game_state.biome_resource_stocks);
+    db_tables
+        .resources
+        .save_cell_resource_stocks(chunk_id, &seeded)
+        .await?;
+
+    Ok(seeded
+        .into_iter()
+        .find(|s| s.resource_specific_type == resource_specific_type))
+}
+
+/// Prélève une récolte dans la réserve de la cellule et renvoie la quantité obtenue.
+/// Un arbre vidé de son bois est abattu ; les joueurs du chunk voient la réserve baisser.
+pub async fn draw_harvest(
+    db_tables: &DatabaseTables,
+    sessions: &Sessions,
+    chunk_id: &TerrainChunkId,
+    cell: &GridCell,
+    resource_specific_type: ResourceSpecificTypeEnum,
+    wanted: i32,
+) -> i32 {
+    let (before, after) = match db_tables
+        .resources
+        .draw_cell_resource_stock(cell, resource_specific_type, wanted)
+        .await
+    {
+        Ok(Some(drawn)) => drawn,
+        Ok(None) => {
+            tracing::warn!(
+                "No {:?} left to harvest at ({},{})",
+                resource_specific_type,
+                cell.q,
+                cell.r
+            );
+            return 0;
+        }
+        Err(e) => {
+            tracing::error!("{}", e);
+            return 0;
+        }
+    };
+
+    if after.is_depleted() && resource_specific_type == ResourceSpecificTypeEnum::Wood {
+        fell_tree(db_tables, sessions, chunk_id, cell).await;
+    }
+
+    if before.level() != after.level() {
+        broadcast_cell_stocks(db_tables, sessions, chunk_id, cell).await;
+    }
+
+    before.stock - after.stock
+}
+
+/// Supprime l'arbre d'une cellule dont tout le bois a été récolté
+async fn fell_tree(
+    db_tables: &DatabaseTables,
+    sessions: &Sessions,
+    chunk_id: &TerrainChunkId,
+    cell: &GridCell,
+) {
+    match db_tables.buildings.find_tree_at_cell(cell).await {
+        Ok(Some((building_id, _))) => {
+            if let Err(e) = db_tables.buildings.delete_building(building_id).await {
+                tracing::error!("{}", e);
+                return;
+            }
+            tracing::info!("Tree {} at ({},{}) felled", building_id, cell.q, cell.r);
+            sessions
+                .broadcast_to_chunk(
+                    chunk_id,
+                    ServerMessage::BuildingRemoved {
+                        chunk_id: *chunk_id,
+                        cell: *cell,
+                    },
+                )
+                .await;
+        }
+        Ok(None) => {}
+        Err(e) => tracing::error!("{}", e),
+    }
+
+    // Plus d'arbre, plus de bois à faire repousser
+    if let Err(e) = db_tables
+        .resources
+        .delete_cell_resource_stock(cell, ResourceSpecificTypeEnum::Wood)
+        .await
+    {
+        tracing::error!("{}", e);
+    }
+}
+
+async fn broadcast_cell_stocks(
+    db_tables: &DatabaseTables,
+    sessions: &Sessions,
+    chunk_id: &TerrainChunkId,
+    cell: &GridCell,
+) {
+    match db_tables.resources.load_cell_resource_stocks(cell).await {
+        Ok(stocks) => {
+            let msg = ServerMessage::CellResourcesUpdate {
+                chunk_id: *chunk_id,
+                cell: *cell,
+                stocks,
+            };
+            sessions.broadcast_to_chunk(chunk_id, msg).await;
+        }
+        Err(e) => tracing::warn!("{}", e),
+    }
+}
+
+pub struct RegrowthSystem {
+    db_tables: Arc<DatabaseTables>,
+    sessions: Sessions,
+    game_state: Arc<GameState>,
+}
+
+impl RegrowthSystem {
+    pub fn new(db_tables: Arc<DatabaseTables>, sessions: Sessions, game_state: Arc<GameState>) -> Self {
+        Self {
+            db_tables,
+            sessions,
+            game_state,
+        }
+    }
+
+    /// Tick journalier — les réserves entamées repoussent au rythme de leur biome
+    pub async fn tick(&self) {
+        let regrowing = match self.db_tables.resources.load_regrowing_stocks().await {
+            Ok(regrowing) => regrowing,
+            Err(e) => {
+                tracing::error!("Regrowth tick: {}", e);
+                return;
+            }
+        };
+
+        let mut updated = Vec::new();
+        let mut visible_changes: HashMap<GridCell, TerrainChunkId> = HashMap::new();
+        for entry in regrowing {
+            let regrowth_per_day = self
+                .game_state
+                .biome_resource_stock(entry.biome, entry.stock.resource_specific_type)
+                .map(|bs| bs.regrowth_per_day)
+                .unwrap_or(0);
+            if regrowth_per_day <= 0 {
+                continue;
+            }
+
+            let grown = regrow(&entry.stock, regrowth_per_day);
+            if grown.level() != entry.stock.level() {
+                visible_changes.insert(grown.cell, entry.chunk_id);
+            }
+            updated.push(grown);
+        }
+
+        if let Err(e) = self
+            .db_tables
+            .resources
+            .update_cell_resource_stocks(&updated)
+            .await
+        {
+            tracing::error!("Regrowth tick: {}", e);
+            return;
+        }
+        tracing::info!("Regrowth tick: {} reserves regrew", updated.len());
+
+        for (cell, chunk_id) in visible_changes {
+            broadcast_cell_stocks(&self.db_tables, &self.sessions, &chunk_id, &cell).await;
+        }
+    }
+}
+
+pub fn start_regrowth_tick(system: Arc<RegrowthSystem>, dev_config: Arc<DevConfig>) {
+    spawn_daily_tick(dev_config, move || {
+        let system = system.clone();
+        async move { system.tick().await }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wood_only_grows_on_trees_and_scales_with_density() {
+        let biome_stocks = vec![
+            BiomeResourceStock {
+                biome: BiomeTypeEnum::Taiga,
+                resource_specific_type: ResourceSpecificTypeEnum::Wood,
+                max_stock: 40,
+                regrowth_per_day: 2,
+            },
+            BiomeResourceStock {
+                biome: BiomeTypeEnum::Taiga,
+                resource_specific_type: ResourceSpecificTypeEnum::Ore,
+                max_stock: 30,
+                regrowth_per_day: 1,
+            },
+            BiomeResourceStock {
+                biome: BiomeTypeEnum::Desert,
+                resource_specific_type: ResourceSpecificTypeEnum::Mineral,
+                max_stock: 60,
+                regrowth_per_day: 2,
+            },
+        ];
+        let cell = GridCell { q: 3, r: -1 };
+
+        let bare = seed_cell_stocks(&cell, BiomeTypeEnum::Taiga, None, &biome_stocks);
+        assert_eq!(bare.len(), 1);
+        assert_eq!(bare[0].resource_specific_type, ResourceSpecificTypeEnum::Ore);
+        assert_eq!(bare[0].stock, 30);
+
+        let forest = seed_cell_stocks(&cell, BiomeTypeEnum::Taiga, Some(0.5), &biome_stocks);
+        let wood = forest
+            .iter()
+            .find(|s| s.resource_specific_type == ResourceSpecificTypeEnum::Wood)
+            .unwrap();
+        assert_eq!((wood.stock, wood.max_stock), (20, 20));
+
+        // La repousse ne dépasse jamais le maximum de la cellule
+        let cut = CellResourceStock { stock: 19, ..*wood };
+        assert_eq!(regrow(&cut, 2).stock, 20);
+        let felled = CellResourceStock { stock: 0, ..*wood };
+        assert_eq!(regrow(&felled, 2).stock, 2);
+    }
+}
@@ -0,0 +1,160 @@
+use shared::UnitDerivedStats;
+use std::collections::HashMap;
+
+use crate::database::client::DatabaseTables;
+
+use super::{UnitCalculator, load_unit_with_items};
+
+/// Charge portée d'une unité : stats dérivées avec le poids réel de l'inventaire,
+/// et capacité totale (base + profession + équipement)
+async fn carry_load(db_tables: &DatabaseTables, unit_id: u64) -> Result<(UnitDerivedStats, i32), String> {
+    let (unit, lookup) = load_unit_with_items(db_tables, unit_id).await?;
+
+    let equipment: Vec<_> = unit.equipment.iter().map(|e| (e.slot, e.item_id)).collect();
+    let equipment_bonus =
+        UnitCalculator::calculate_equipment_stat_bonus(&equipment, &lookup, "inventory_capacity_kg");
+    let capacity = UnitCalculator::calculate_total_inventory_capacity(
+        &unit.derived_stats,
+        unit.unit.profession,
+        equipment_bonus,
+    );
+
+    let inventory: Vec<_> = unit.inventory.iter().map(|i| (i.item_id, i.quantity)).collect();
+    let mut stats = unit.derived_stats;
+    stats.current_weight_kg = UnitCalculator::calculate_total_weight(&inventory, &lookup);
+
+    Ok((stats, capacity))
+}
+
+/// Capacité de portage restante d'une unité (kg)
+/// Formule: capacité totale (base + profession + équipement) - poids porté
+pub async fn free_carry_capacity_kg(db_tables: &DatabaseTables, unit_id: u64) -> Result<f32, String> {
+    let (stats, capacity) = carry_load(db_tables, unit_id).await?;
+    Ok((capacity as f32 - stats.current_weight_kg).max(0.0))
+}
+
+/// L'unité serait-elle surchargée après avoir reçu `extra_kg` de plus ?
+pub async fn would_be_overencumbered(
+    db_tables: &DatabaseTables,
+    unit_id: u64,
+    extra_kg: f32,
+) -> Result<bool, String> {
+    let (mut stats, capacity) = carry_load(db_tables, unit_id).await?;
+    stats.current_weight_kg += extra_kg;
+    Ok(stats.is_overencumbered(capacity))
+}
+
+/// Répartit `quantity` exemplaires d'un objet entre des unités selon leur capacité libre :
+/// chaque exemplaire va à l'unité qui a le plus de place (la première listée en cas d'égalité).
+/// Renvoie (unit_id, quantité) par unité servie et le reste qui ne rentre chez personne.
+pub fn split_by_capacity(
+    free_kg: &[(u64, f32)],
+    item_weight_kg: f32,
+    quantity: i32,
+) -> (Vec<(u64, i32)>, i32) {
+    let mut remaining_kg: Vec<f32> = free_kg.iter().map(|(_, kg)| *kg).collect();
+    let mut shares = vec![0; free_kg.len()];
+    let mut overflow = 0;
+
+    for _ in 0..quantity.max(0) {
+        let best = remaining_kg
+            .iter()
+            .enumerate()
+            .filter(|(_, kg)| **kg >= item_weight_kg)
+            .fold(None, |best: Option<(usize, f32)>, (i, kg)| match best {
+                Some((_, best_kg)) if best_kg >= *kg => best,
+                _ => Some((i, *kg)),
+            });
+        match best {
+            Some((i, _)) => {
+                remaining_kg[i] -= item_weight_kg;
+                shares[i] += 1;
+            }
+            None => overflow += 1,
+        }
+    }
+
+    let split = free_kg
+        .iter()
+        .zip(shares)
+        .filter(|(_, share)| *share > 0)
+        .map(|((unit_id, _), share)| (*unit_id, share))
+        .collect();
+    (split, overflow)
+}
+
+/// Prélève des matériaux (item_id, quantité) sur plusieurs inventaires, dans l'ordre des
+/// sources payeuses (unités, trésor...) : la première donne tout ce qu'elle a avant de
+/// passer à la suivante.
+/// Renvoie les prélèvements (source, item_id, quantité), ou les manques
+/// (item_id, besoin, possédé au total) si les inventaires réunis ne suffisent pas.
+pub fn plan_material_draw<K: Copy>(
+    holdings: &[(K, HashMap<i32, i32>)],
+    materials: &[(i32, i32)],
+) -> Result<Vec<(K, i32, i32)>, Vec<(i32, i32, i32)>> {
+    let mut draws = Vec::new();
+    let mut missing = Vec::new();
+
+    for &(item_id, needed) in materials {
+        if needed <= 0 {
+            continue;
+        }
+        let mut left = needed;
+        for (source, inventory) in holdings {
+            let have = inventory.get(&item_id).copied().unwrap_or(0);
+            let take = have.min(left);
+            if take > 0 {
+                draws.push((*source, item_id, take));
+                left -= take;
+            }
+            if left == 0 {
+                break;
+            }
+        }
+        if left > 0 {
+            missing.push((item_id, needed, needed - left));
+        }
+    }
+
+    if missing.is_empty() {
+        Ok(draws)
+    } else {
+        Err(missing)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_by_capacity_fills_the_emptiest_unit_first() {
+        let (split, overflow) = split_by_capacity(&[(1, 10.0), (2, 4.0)], 2.0, 5);
+        assert_eq!(split, vec![(1, 4), (2, 1)]);
+        assert_eq!(overflow, 0);
+
+        let (split, overflow) = split_by_capacity(&[(1, 3.0)], 2.0, 3);
+        assert_eq!(split, vec![(1, 1)]);
+        assert_eq!(overflow, 2);
+
+        let (split, overflow) = split_by_capacity(&[], 1.0, 2);
+        assert!(split.is_empty());
+        assert_eq!(overflow, 2);
+    }
+
+    #[test]
+    fn test_plan_material_draw_uses_payers_in_order() {
+        let holdings = vec![
+            (1, HashMap::from([(10, 2)])),
+            (2, HashMap::from([(10, 5), (11, 1)])),
+        ];
+        assert_eq!(
+            plan_material_draw(&holdings, &[(10, 4), (11, 1)]),
+            Ok(vec![(1, 10, 2), (2, 10, 2), (2, 11, 1)])
+        );
+        assert_eq!(
+            plan_material_draw(&holdings, &[(11, 3)]),
+            Err(vec![(11, 3, 1)])
+        );
+    }
+}
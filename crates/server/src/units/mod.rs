@@ -1,7 +1,13 @@
+mod carrying;
 mod unit_calculator;
 mod name_generator;
+mod outcome;
 mod portrait_generator;
+mod progression;
 
+pub use carrying::*;
 pub use unit_calculator::*;
 pub use name_generator::*;
+pub use outcome::*;
 pub use portrait_generator::*;
+pub use progression::*;
@@ -0,0 +1,113 @@
+use rand::Rng;
+use shared::HarvestYield;
+
+use super::UnitCalculator;
+
+/// Quantité et qualité tirées pour un rendement de récolte
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HarvestRoll {
+    pub quantity: i32,
+    pub quality: f32,
+}
+
+/// Tire le résultat d'une récolte selon le skill effectif des récolteurs
+/// Qualité : moitié basse de la fourchette sans skill, moitié haute au skill maximal
+/// Quantité : chance d'un exemplaire supplémentaire jusqu'à 50% au skill maximal
+pub fn roll_harvest(rng: &mut impl Rng, yield_def: &HarvestYield, effective_skill: i32) -> HarvestRoll {
+    let ratio = UnitCalculator::skill_quality_ratio(effective_skill);
+    let spread = yield_def.quality_max - yield_def.quality_min;
+    let quality = yield_def.quality_min + spread * 0.5 * (ratio + rng.random::<f32>());
+    let bonus = if rng.random::<f32>() < ratio * 0.5 { 1 } else { 0 };
+
+    HarvestRoll {
+        quantity: yield_def.base_quantity + bonus,
+        quality: quality.clamp(yield_def.quality_min, yield_def.quality_max),
+    }
+}
+
+/// Tire la qualité d'un objet fabriqué
+/// Formule: qualité des ingrédients × état de l'atelier × savoir-faire
+/// - atelier : quality × (50% + 50% × durability), 1.0 sans bâtiment
+/// - savoir-faire : entre 0.7 et 1.0, tiré vers le haut par le skill
+pub fn roll_craft_quality(
+    rng: &mut impl Rng,
+    ingredient_quality: f32,
+    workshop: Option<(f32, f32)>,
+    effective_skill: i32,
+) -> f32 {
+    let ratio = UnitCalculator::skill_quality_ratio(effective_skill);
+    let workmanship = 0.7 + 0.3 * 0.5 * (ratio + rng.random::<f32>());
+    let workshop_factor = workshop
+        .map(|(quality, durability)| quality * (0.5 + 0.5 * durability.clamp(0.0, 1.0)))
+        .unwrap_or(1.0);
+
+    (ingredient_quality * workshop_factor * workmanship).clamp(0.0, 1.0)
+}
+
+/// Qualité moyenne pondérée de lots (quantité, qualité), 1.0 si aucun lot
+pub fn average_quality(lots: &[(i32, f32)]) -> f32 {
+    let total: i32 = lots.iter().map(|(quantity, _)| *quantity).sum();
+    if total <= 0 {
+        return 1.0;
+    }
+    lots.iter()
+        .map(|(quantity, quality)| *quantity as f32 * quality)
+        .sum::<f32>()
+        / total as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    fn wood_yield() -> HarvestYield {
+        HarvestYield {
+            id: 1,
+            resource_specific_type_id: 1,
+            result_item_id: 1,
+            base_quantity: 3,
+            quality_min: 0.5,
+            quality_max: 1.0,
+            required_profession_id: None,
+            required_tool_item_id: None,
+            tool_bonus_quantity: 0,
+            duration_seconds: 30,
+            skill: None,
+        }
+    }
+
+    #[test]
+    fn test_rolls_are_reproducible_with_a_seed() {
+        let hy = wood_yield();
+        let first = roll_harvest(&mut StdRng::seed_from_u64(7), &hy, 20);
+        let second = roll_harvest(&mut StdRng::seed_from_u64(7), &hy, 20);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_skill_raises_harvest_quality() {
+        let hy = wood_yield();
+        let mut rng = StdRng::seed_from_u64(42);
+        for _ in 0..100 {
+            let novice = roll_harvest(&mut rng, &hy, 0);
+            assert!(novice.quality <= 0.75 && novice.quantity == 3);
+            let master = roll_harvest(&mut rng, &hy, 50);
+            assert!(master.quality >= 0.75 && master.quality <= 1.0);
+            assert!((3..=4).contains(&master.quantity));
+        }
+    }
+
+    #[test]
+    fn test_craft_quality_inherits_ingredients_and_workshop() {
+        let good = roll_craft_quality(&mut StdRng::seed_from_u64(3), 1.0, None, 25);
+        let poor = roll_craft_quality(&mut StdRng::seed_from_u64(3), 0.5, None, 25);
+        assert!((poor - good * 0.5).abs() < 1e-6);
+
+        let worn = roll_craft_quality(&mut StdRng::seed_from_u64(3), 1.0, Some((1.0, 0.0)), 25);
+        assert!((worn - good * 0.5).abs() < 1e-6);
+
+        assert_eq!(average_quality(&[]), 1.0);
+        assert_eq!(average_quality(&[(1, 0.5), (3, 1.0)]), 0.875);
+    }
+}
@@ -0,0 +1,110 @@
+use shared::{
+    FullUnitData, InventoryItem, ItemData, ItemsLookup, ProfessionSkillBonusesLookup, SkillCurve,
+    SkillEnum, UnitSkill,
+};
+use std::collections::HashMap;
+
+use crate::database::client::DatabaseTables;
+
+use super::UnitCalculator;
+
+/// Charge une unité avec son inventaire réel (instances d'items possédées) et le
+/// catalogue des objets qu'elle porte
+pub async fn load_unit_with_items(
+    db_tables: &DatabaseTables,
+    unit_id: u64,
+) -> Result<(FullUnitData, ItemsLookup), String> {
+    let mut unit = db_tables.units.load_full_unit(unit_id).await?;
+    let items = db_tables.resources.load_items_for_unit(unit_id).await?;
+
+    let mut lookup = ItemsLookup::new();
+    let mut counts: HashMap<i32, i32> = HashMap::new();
+    for item in &items {
+        *counts.entry(item.definition.id).or_insert(0) += 1;
+        if lookup.get(item.definition.id).is_none() {
+            lookup.add(ItemData::from(&item.definition));
+        }
+    }
+    unit.inventory = counts
+        .into_iter()
+        .map(|(item_id, quantity)| InventoryItem { item_id, quantity })
+        .collect();
+
+    Ok((unit, lookup))
+}
+
+/// Meilleur skill effectif parmi les unités affectées à une action
+/// (None si aucune unité n'a pu être chargée)
+pub async fn best_effective_skill(
+    db_tables: &DatabaseTables,
+    unit_ids: &[u64],
+    skill: SkillEnum,
+    profession_bonuses: &ProfessionSkillBonusesLookup,
+) -> Option<i32> {
+    let mut best = None;
+
+    for &unit_id in unit_ids {
+        let (unit, lookup) = match load_unit_with_items(db_tables, unit_id).await {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                tracing::warn!("Skill of unit {} not computed: {}", unit_id, e);
+                continue;
+            }
+        };
+
+        let equipment: Vec<_> = unit.equipment.iter().map(|e| (e.slot, e.item_id)).collect();
+        let equipment_bonuses = UnitCalculator::calculate_all_equipment_bonuses(&equipment, &lookup);
+        let effective = UnitCalculator::calculate_effective_skill(
+            skill,
+            unit.skills.get(&skill),
+            &unit.base_stats,
+            unit.unit.profession,
+            profession_bonuses,
+            &equipment_bonuses,
+        );
+        best = Some(best.map_or(effective, |b: i32| b.max(effective)));
+    }
+
+    best
+}
+
+/// Durée d'une action raccourcie par le meilleur skill des unités affectées
+pub async fn skill_adjusted_duration_ms(
+    db_tables: &DatabaseTables,
+    unit_ids: &[u64],
+    skill: Option<SkillEnum>,
+    profession_bonuses: &ProfessionSkillBonusesLookup,
+    duration_ms: u64,
+) -> u64 {
+    let Some(skill) = skill else {
+        return duration_ms;
+    };
+    match best_effective_skill(db_tables, unit_ids, skill, profession_bonuses).await {
+        Some(effective) => {
+            (duration_ms as f64 * UnitCalculator::skill_duration_factor(effective)) as u64
+        }
+        None => duration_ms,
+    }
+}
+
+/// Ajoute de l'XP à un skill d'une unité et sauvegarde
+/// Renvoie le skill mis à jour s'il a gagné un niveau
+pub async fn award_skill_xp(
+    db_tables: &DatabaseTables,
+    unit_id: u64,
+    skill: SkillEnum,
+    xp: i64,
+    curve: &SkillCurve,
+) -> Result<Option<UnitSkill>, String> {
+    let skills = db_tables.units.load_unit_skills(unit_id).await?;
+    let mut unit_skill = skills.get(&skill).copied().unwrap_or(UnitSkill {
+        skill,
+        xp: 0,
+        level: 1,
+    });
+
+    let leveled_up = unit_skill.gain_xp(xp, curve);
+    db_tables.units.upsert_unit_skill(unit_id, &unit_skill).await?;
+
+    Ok(leveled_up.then_some(unit_skill))
+}
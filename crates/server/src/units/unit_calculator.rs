@@ -200,8 +200,15 @@ impl UnitCalculator {
     ) -> UnitDerivedStats {
         let mut derived = unit.derived_stats;
 
-        // Recalculer max_hp
-        derived.max_hp = Self::calculate_max_hp(unit.base_stats.constitution);
+        // Recalculer max_hp (la constitution inclut les bonus d'équipement)
+        let equipment: Vec<_> = unit
+            .equipment
+            .iter()
+            .map(|e| (e.slot, e.item_id))
+            .collect();
+        let constitution_bonus =
+            Self::calculate_equipment_stat_bonus(&equipment, items_lookup, "constitution_bonus");
+        derived.max_hp = Self::calculate_max_hp(unit.base_stats.constitution + constitution_bonus);
 
         // S'assurer que current_hp ne dépasse pas max_hp
         if derived.current_hp > derived.max_hp {
@@ -239,18 +246,47 @@ impl UnitCalculator {
         }
 
         // Vérifier que l'unité possède l'item dans son inventaire
-        let has_item = unit.inventory.iter().any(|i| i.item_id == item.id && i.quantity > 0);
-        if !has_item {
+        let owned = unit
+            .inventory
+            .iter()
+            .filter(|i| i.item_id == item.id)
+            .map(|i| i.quantity)
+            .sum::<i32>();
+        if owned <= 0 {
             return Err("Item not in inventory".to_string());
         }
 
+        // Chaque exemplaire ne peut occuper qu'un slot (le slot visé sera remplacé)
+        let equipped_elsewhere = unit
+            .equipment
+            .iter()
+            .filter(|e| e.item_id == item.id && e.slot != slot)
+            .count() as i32;
+        if equipped_elsewhere >= owned {
+            return Err("Every copy of this item is already equipped".to_string());
+        }
+
         Ok(())
     }
+
+    /// Facteur appliqué à la durée d'une action selon le skill effectif
+    /// Formule: 1 / (1 + skill * 2%), jamais moins de la moitié de la durée
+    pub fn skill_duration_factor(effective_skill: i32) -> f64 {
+        (1.0 / (1.0 + effective_skill.max(0) as f64 * 0.02)).max(0.5)
+    }
+
+    /// Part (0..1) de l'écart de qualité gagnée grâce au skill effectif
+    /// Un skill de 50 atteint la qualité maximale
+    pub fn skill_quality_ratio(effective_skill: i32) -> f32 {
+        (effective_skill.max(0) as f32 / 50.0).min(1.0)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use shared::grid::GridCell;
+    use shared::{EquippedItem, InventoryItem, TerrainChunkId, UnitData};
 
     #[test]
     fn test_movement_speed_calculation() {
@@ -287,4 +323,78 @@ mod tests {
         let bonus = stats.stat_bonus("strength");
         assert_eq!(bonus, 2); // (14 - 10) / 2 = 2
     }
+
+    fn unit_with(inventory: Vec<InventoryItem>, equipment: Vec<EquippedItem>) -> FullUnitData {
+        FullUnitData {
+            unit: UnitData {
+                id: 1,
+                player_id: Some(1),
+                first_name: "Jehan".to_string(),
+                last_name: "Test".to_string(),
+                gender: "male".to_string(),
+                level: 1,
+                avatar_url: None,
+                portrait_variant_id: None,
+                current_cell: GridCell { q: 0, r: 0 },
+                current_chunk: TerrainChunkId { x: 0, y: 0 },
+                slot_type: None,
+                slot_index: None,
+                profession: ProfessionEnum::Warrior,
+                money: 0,
+                is_lord: false,
+                portrait_layers: None,
+            },
+            base_stats: UnitBaseStats::default(),
+            derived_stats: UnitDerivedStats::default(),
+            skills: HashMap::new(),
+            inventory,
+            equipment,
+            automated_actions: vec![],
+            consumption_demands: vec![],
+        }
+    }
+
+    #[test]
+    fn test_can_equip_item_requires_a_free_copy() {
+        let ring = ItemData {
+            id: 7,
+            name: "Anneau".to_string(),
+            item_type: shared::ItemTypeEnum::Unknown,
+            description: String::new(),
+            weight_kg: 0.1,
+            is_equipable: true,
+            equipment_slot: Some(EquipmentSlotEnum::Ring1),
+            stat_modifiers: HashMap::new(),
+        };
+        let owned = vec![InventoryItem {
+            item_id: 7,
+            quantity: 1,
+        }];
+
+        let unit = unit_with(vec![], vec![]);
+        assert!(UnitCalculator::can_equip_item(&unit, &ring, EquipmentSlotEnum::Ring1).is_err());
+
+        let unit = unit_with(owned.clone(), vec![]);
+        assert!(UnitCalculator::can_equip_item(&unit, &ring, EquipmentSlotEnum::Ring1).is_ok());
+        assert!(UnitCalculator::can_equip_item(&unit, &ring, EquipmentSlotEnum::Ring2).is_err());
+
+        // Le seul exemplaire est déjà porté ailleurs
+        let worn = vec![EquippedItem {
+            slot: EquipmentSlotEnum::Ring2,
+            item_id: 7,
+        }];
+        let unit = unit_with(owned, worn);
+        assert!(UnitCalculator::can_equip_item(&unit, &ring, EquipmentSlotEnum::Ring1).is_err());
+    }
+
+    #[test]
+    fn test_skill_shortens_duration_and_raises_quality() {
+        assert_eq!(UnitCalculator::skill_duration_factor(0), 1.0);
+        assert!(UnitCalculator::skill_duration_factor(10) < 1.0);
+        assert_eq!(UnitCalculator::skill_duration_factor(500), 0.5);
+
+        assert_eq!(UnitCalculator::skill_quality_ratio(-5), 0.0);
+        assert_eq!(UnitCalculator::skill_quality_ratio(25), 0.5);
+        assert_eq!(UnitCalculator::skill_quality_ratio(80), 1.0);
+    }
 }
@@ -5,3 +5,4 @@ pub mod geometry;
 pub mod hex;
 pub mod jittering;
 pub mod portraits;
+pub mod ticks;
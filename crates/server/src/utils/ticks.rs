@@ -0,0 +1,28 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use crate::dev::DevConfig;
+
+/// Une journée de jeu
+const DAY_SECS: u64 = 86_400;
+
+/// Lance en tâche de fond un traitement journalier (consommation, repousse, tribut...).
+/// La journée est raccourcie par le facteur de vitesse en mode dev, et le premier
+/// passage n'a lieu qu'au bout d'une journée complète.
+pub fn spawn_daily_tick<F, Fut>(dev_config: Arc<DevConfig>, mut f: F)
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: Future<Output = ()> + Send,
+{
+    tokio::task::spawn(async move {
+        let day_ms = dev_config.apply_speed(DAY_SECS * 1000);
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_millis(day_ms));
+        // Le premier tick de `interval` est immédiat : on attend une journée complète
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+            f().await;
+        }
+    });
+}
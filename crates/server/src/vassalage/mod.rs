@@ -0,0 +1,3 @@
+mod systems;
+
+pub use systems::{TributeSystem, start_tribute_tick};
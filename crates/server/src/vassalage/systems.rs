@@ -0,0 +1,90 @@
+use shared::protocol::ServerMessage;
+use shared::tribute_due;
+use std::sync::Arc;
+
+use crate::database::client::DatabaseTables;
+use crate::dev::DevConfig;
+use crate::networking::Sessions;
+use crate::utils::ticks::spawn_daily_tick;
+
+pub struct TributeSystem {
+    db_tables: Arc<DatabaseTables>,
+    sessions: Sessions,
+}
+
+impl TributeSystem {
+    pub fn new(db_tables: Arc<DatabaseTables>, sessions: Sessions) -> Self {
+        Self {
+            db_tables,
+            sessions,
+        }
+    }
+
+    /// Tick journalier — chaque vassal verse une part de son trésor à son suzerain
+    pub async fn tick(&self) {
+        let vassals = match self.db_tables.organizations.load_vassal_treasuries().await {
+            Ok(vassals) => vassals,
+            Err(e) => {
+                tracing::error!("Tribute tick: {}", e);
+                return;
+            }
+        };
+
+        let mut paid_count = 0;
+        for (vassal_id, liege_id, treasury_gold) in vassals {
+            let gold = tribute_due(treasury_gold);
+            if gold <= 0 {
+                continue;
+            }
+
+            match self
+                .db_tables
+                .organizations
+                .pay_tribute(vassal_id, liege_id, gold)
+                .await
+            {
+                Ok(true) => {}
+                // Le lien ou le trésor a changé entre-temps, on attend le prochain tick
+                Ok(false) => continue,
+                Err(e) => {
+                    tracing::error!("Tribute of organization {}: {}", vassal_id, e);
+                    continue;
+                }
+            }
+            paid_count += 1;
+
+            let msg = ServerMessage::TributePaid {
+                vassal_organization_id: vassal_id,
+                liege_organization_id: liege_id,
+                gold,
+            };
+            self.notify_leader(vassal_id, msg.clone()).await;
+            self.notify_leader(liege_id, msg).await;
+        }
+
+        tracing::info!("Tribute tick: {} vassals paid their liege", paid_count);
+    }
+
+    /// Prévient le joueur qui dirige l'organisation, s'il est connecté
+    async fn notify_leader(&self, organization_id: u64, msg: ServerMessage) {
+        let Ok(organization) = self.db_tables.organizations.load_organization(organization_id).await
+        else {
+            return;
+        };
+        let Some(leader) = organization.leader_unit_id else {
+            return;
+        };
+        if let Ok(Some(player_id)) = self.db_tables.units.load_controlling_player(leader).await
+            && let Err(e) = self.sessions.send_to_player(player_id, msg).await
+        {
+            tracing::debug!("Tribute not announced to player {}: {}", player_id, e);
+        }
+    }
+}
+
+pub fn start_tribute_tick(system: Arc<TributeSystem>, dev_config: Arc<DevConfig>) {
+    spawn_daily_tick(dev_config, move || {
+        let system = system.clone();
+        async move { system.tick().await }
+    });
+}
@@ -337,7 +337,8 @@ pub async fn load_or_generate_world_globals(
     }
 }
 
-/// Generate a single chunk's data on demand: terrain mesh, cells, buildings.
+/// Generate a single chunk's data on demand: terrain mesh, cells, buildings and
+/// the natural resource stocks of its cells.
 /// Saves everything to DB and returns the data for immediate client response.
 pub async fn generate_chunk_data(
     chunk_id: &TerrainChunkId,
@@ -348,6 +349,7 @@ pub async fn generate_chunk_data(
     shared::TerrainChunkData,
     Vec<shared::grid::CellData>,
     Vec<BuildingData>,
+    Vec<shared::CellResourceStock>,
 ) {
     let t_total = std::time::Instant::now();
     let map_name = &global.map_name;
@@ -392,6 +394,11 @@ pub async fn generate_chunk_data(
     let t3 = std::time::Instant::now();
     let trees = NaturalBuildingGenerator::generate(&chunk_cells, game_state);
     let building_data: Vec<BuildingData> = trees.buildings.values().cloned().collect();
+    let resource_stocks = crate::resource_stocks::seed_chunk_stocks(
+        &chunk_cells,
+        &building_data,
+        &game_state.biome_resource_stocks,
+    );
     let t3_elapsed = t3.elapsed();
 
     // 4. Save to DB
@@ -459,6 +466,14 @@ pub async fn generate_chunk_data(
             }
         }
     }
+
+    if let Err(e) = db_tables
+        .resources
+        .save_cell_resource_stocks(chunk_id, &resource_stocks)
+        .await
+    {
+        tracing::error!("{}", e);
+    }
     let t4_elapsed = t4.elapsed();
 
     tracing::info!(
@@ -474,7 +489,7 @@ pub async fn generate_chunk_data(
         t4_elapsed
     );
 
-    (terrain_data, chunk_cells, building_data)
+    (terrain_data, chunk_cells, building_data, resource_stocks)
 }
 
 /// Generate everything in batch (convenience for dev/testing).
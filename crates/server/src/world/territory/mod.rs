@@ -1,9 +1,11 @@
 mod sdf_generation;
 mod territory_contours;
+mod territory_claims;
 pub mod contour_generation;
 
 pub use sdf_generation::*;
 pub use territory_contours::*;
+pub use territory_claims::*;
 pub use contour_generation::*;
 
 use std::hash::{Hash, Hasher};
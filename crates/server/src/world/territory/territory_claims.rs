@@ -0,0 +1,181 @@
+use std::collections::{HashSet, VecDeque};
+
+use hexx::{Hex, HexLayout};
+use shared::grid::{GridCell, GridConfig};
+use shared::protocol::{ColorData, ServerMessage, TerritoryContourChunkData};
+use shared::{ContourSegment, ContourSegmentData, TerrainChunkId};
+
+use crate::database::client::DatabaseTables;
+use crate::utils::chunks::split_contour_into_chunks;
+use crate::world::territory::{build_contour, generate_org_colors};
+
+/// Une revendication est valide si chaque cellule est reliée au territoire,
+/// directement ou par d'autres cellules de la même revendication
+pub fn is_contiguous_claim(territory: &HashSet<GridCell>, cells: &[GridCell]) -> bool {
+    if territory.is_empty() || cells.is_empty() {
+        return false;
+    }
+
+    let mut pending: HashSet<GridCell> = cells.iter().copied().collect();
+    if pending.len() != cells.len() || pending.iter().any(|cell| territory.contains(cell)) {
+        return false;
+    }
+
+    // Parcours en largeur depuis le territoire existant
+    let mut frontier: VecDeque<GridCell> = territory.iter().copied().collect();
+    while let Some(cell) = frontier.pop_front() {
+        for neighbor in cell.neighbors() {
+            if pending.remove(&neighbor) {
+                frontier.push_back(neighbor);
+            }
+        }
+    }
+
+    pending.is_empty()
+}
+
+/// Prochaine cellule d'une revendication à borner : la première reliée au territoire
+/// actuel. Une cellule coupée du territoire (une autre organisation a pris une cellule
+/// du chemin entre-temps) n'est jamais proposée, elle formerait une enclave.
+pub fn next_connected_cell(territory: &HashSet<GridCell>, pending: &[GridCell]) -> Option<usize> {
+    pending.iter().position(|cell| {
+        !territory.contains(cell)
+            && cell
+                .neighbors()
+                .iter()
+                .any(|neighbor| territory.contains(neighbor))
+    })
+}
+
+/// Chunks dont le contour peut changer quand ces cellules changent de main :
+/// ceux des cellules et de leurs voisines, une bordure pouvant déborder sur le chunk d'à côté
+pub fn affected_chunks(layout: &HexLayout, changed_cells: &[GridCell]) -> HashSet<TerrainChunkId> {
+    changed_cells
+        .iter()
+        .flat_map(|cell| std::iter::once(*cell).chain(cell.neighbors()))
+        .map(|cell| cell.to_chunk_id(layout))
+        .collect()
+}
+
+/// Message de mise à jour du contour d'une organisation dans un chunk
+/// (sans segments, le client efface le contour)
+pub fn contour_update_message(
+    org_id: u64,
+    chunk_id: TerrainChunkId,
+    segments: &[ContourSegment],
+) -> ServerMessage {
+    let (border_color, fill_color) = generate_org_colors(org_id);
+    ServerMessage::TerritoryContourUpdate {
+        chunk_id,
+        contours: vec![TerritoryContourChunkData {
+            organization_id: org_id,
+            chunk_id,
+            segments: segments
+                .iter()
+                .map(ContourSegmentData::from_contour_segment)
+                .collect(),
+            border_color: ColorData::from_array(border_color),
+            fill_color: ColorData::from_array(fill_color),
+        }],
+    }
+}
+
+/// Régénère les contours d'une organisation après un changement de territoire,
+/// mais ne réécrit que les chunks touchés. Renvoie un TerritoryContourUpdate par chunk modifié.
+pub async fn refresh_territory_contours(
+    db_tables: &DatabaseTables,
+    grid_config: &GridConfig,
+    org_id: u64,
+    changed_cells: &[GridCell],
+) -> Result<Vec<ServerMessage>, String> {
+    let territory_hex: HashSet<Hex> = db_tables
+        .organizations
+        .load_territory_cells(org_id)
+        .await?
+        .iter()
+        .map(|cell| cell.to_hex())
+        .collect();
+
+    let contour_points = build_contour(&grid_config.layout, &territory_hex, 0.0, org_id);
+    let contour_chunks = split_contour_into_chunks(&contour_points);
+
+    // Les chunks touchés, plus ceux où un ancien contour n'a plus lieu d'être
+    let mut chunks = affected_chunks(&grid_config.layout, changed_cells);
+    for (x, y) in db_tables
+        .territory_contours
+        .get_organization_chunks(org_id)
+        .await?
+    {
+        let chunk_id = TerrainChunkId { x, y };
+        if !contour_chunks.contains_key(&chunk_id) {
+            chunks.insert(chunk_id);
+        }
+    }
+
+    let mut messages = Vec::new();
+    for chunk_id in chunks {
+        match contour_chunks.get(&chunk_id) {
+            Some(segments) => {
+                db_tables
+                    .territory_contours
+                    .store_contour(org_id, chunk_id.x, chunk_id.y, segments)
+                    .await?;
+                messages.push(contour_update_message(org_id, chunk_id, segments));
+            }
+            None => {
+                db_tables
+                    .territory_contours
+                    .delete_chunk_contour(org_id, chunk_id.x, chunk_id.y)
+                    .await?;
+                messages.push(contour_update_message(org_id, chunk_id, &[]));
+            }
+        }
+    }
+
+    tracing::info!(
+        "Refreshed {} territory contour chunks for organization {}",
+        messages.len(),
+        org_id
+    );
+
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_claim_must_touch_the_territory() {
+        let territory: HashSet<GridCell> = [GridCell { q: 0, r: 0 }].into_iter().collect();
+        let adjacent = GridCell { q: 1, r: 0 };
+        let beyond = GridCell { q: 2, r: 0 };
+        let far = GridCell { q: 5, r: 5 };
+
+        assert!(is_contiguous_claim(&territory, &[adjacent]));
+        // Une cellule éloignée est reliée par une autre cellule de la revendication
+        assert!(is_contiguous_claim(&territory, &[beyond, adjacent]));
+        assert!(!is_contiguous_claim(&territory, &[beyond]));
+        assert!(!is_contiguous_claim(&territory, &[adjacent, far]));
+        // Ni doublon, ni cellule déjà à soi
+        assert!(!is_contiguous_claim(&territory, &[adjacent, adjacent]));
+        assert!(!is_contiguous_claim(&territory, &[GridCell { q: 0, r: 0 }]));
+        assert!(!is_contiguous_claim(&HashSet::new(), &[adjacent]));
+    }
+
+    #[test]
+    fn test_claim_completion_skips_detached_cells() {
+        let mut territory: HashSet<GridCell> = [GridCell { q: 0, r: 0 }].into_iter().collect();
+        let adjacent = GridCell { q: 1, r: 0 };
+        let beyond = GridCell { q: 2, r: 0 };
+
+        // La cellule éloignée attend que sa voisine soit bornée
+        assert_eq!(next_connected_cell(&territory, &[beyond, adjacent]), Some(1));
+        territory.insert(adjacent);
+        assert_eq!(next_connected_cell(&territory, &[beyond]), Some(0));
+
+        // Sa voisine prise par une autre organisation, elle serait une enclave
+        territory.remove(&adjacent);
+        assert_eq!(next_connected_cell(&territory, &[beyond]), None);
+    }
+}
@@ -32,6 +32,26 @@ pub fn compress(data: &[u8]) -> Vec<u8> {
     }
 }
 
+/// Decompress data, refusing payloads that would exceed `max_len` bytes once decompressed.
+/// The LZ4 size prefix is checked before allocating anything.
+pub fn decompress_with_limit(data: &[u8], max_len: usize) -> Result<Vec<u8>, String> {
+    let announced_len = match data.first() {
+        Some(&UNCOMPRESSED_PREFIX) => data.len() - 1,
+        Some(&COMPRESSED_PREFIX) => data
+            .get(1..5)
+            .map(|size| u32::from_le_bytes([size[0], size[1], size[2], size[3]]) as usize)
+            .ok_or_else(|| "Truncated LZ4 size prefix".to_string())?,
+        _ => 0,
+    };
+    if announced_len > max_len {
+        return Err(format!(
+            "Decompressed size {} exceeds limit of {} bytes",
+            announced_len, max_len
+        ));
+    }
+    decompress(data)
+}
+
 /// Decompress data. Reads prefix byte to determine format.
 pub fn decompress(data: &[u8]) -> Result<Vec<u8>, String> {
     if data.is_empty() {
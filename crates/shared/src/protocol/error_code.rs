@@ -0,0 +1,202 @@
+use bincode::{Decode, Encode};
+
+/// Typed error sent by the server in `*Error` messages.
+/// The client maps it to a user-facing text (see `ErrorCode::message`)
+/// instead of matching on server strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Encode, Decode)]
+pub enum ErrorCode {
+    // ── Protocole / session ─────────────────────────────
+    IncompatibleProtocol,
+    HandshakeRequired,
+    NotAuthenticated,
+    ResumeTokenInvalid,
+    ResumeUnavailable,
+
+    // ── Authentification ────────────────────────────────
+    InvalidCredentials,
+    PasswordMigrationRequired,
+    AccountLocked,
+    TooManyAttempts,
+    LegacyLoginDisabled,
+    WeakPassword,
+    ResetCodeInvalid,
+
+    // ── Serveur ─────────────────────────────────────────
+    DatabaseError,
+    InternalError,
+    SchedulingFailed,
+
+    // ── Joueur / seigneur ───────────────────────────────
+    PlayerNotFound,
+    LordNotFound,
+    LordAlreadyExists,
+    OrganizationAlreadyExists,
+
+    // ── Unités ──────────────────────────────────────────
+    UnitNotFound,
+    UnitNotOwned,
+    UnitBusy,
+    UnitAlreadyAtTarget,
+    UnitNotAtTarget,
+    UnitsNotOnSameCell,
+
+    // ── Actions ─────────────────────────────────────────
+    ActionNotFound,
+    ActionNotCancellable,
+    ActionNotPausable,
+    ActionNotPaused,
+    ActionQueueFull,
+    InvalidQueueOrder,
+    InvalidRoutine,
+
+    // ── Production / ressources ─────────────────────────
+    UnknownRecipe,
+    MissingResources,
+    ProductionLinesFull,
+    NoHarvestYield,
+    ResourceDepleted,
+    InventoryUnavailable,
+    ItemNotFound,
+    CannotEquip,
+    NotEnoughItems,
+    Overencumbered,
+
+    // ── Cellules ────────────────────────────────────────
+    CellAlreadyClaimed,
+    CellNotAdjacent,
+    CellFull,
+
+    // ── Organisations ───────────────────────────────────
+    NoOrganization,
+    OrganizationNotFound,
+    OutsideTerritory,
+    InsufficientAuthority,
+    NoSettlementTier,
+    SettlementRequirementsNotMet,
+    NotEnoughGold,
+    AlreadyVassal,
+    InvalidVassalage,
+    VassalageOfferNotFound,
+    NotAnEnvoy,
+    InvalidEnvoyTarget,
+    NoHeadquarters,
+    RelationAlreadyExists,
+    DiplomaticProposalNotFound,
+    AlreadyMember,
+    NotAMember,
+    MembershipRequestNotFound,
+    LeaderCannotLeave,
+    InvalidOfficerRole,
+}
+
+impl ErrorCode {
+    /// Default French text, used by the client until proper localisation
+    pub fn message(self) -> &'static str {
+        match self {
+            ErrorCode::IncompatibleProtocol => "Version du client incompatible avec le serveur",
+            ErrorCode::HandshakeRequired => "Le client doit s'annoncer avant de se connecter",
+            ErrorCode::NotAuthenticated => "Non authentifié",
+            ErrorCode::ResumeTokenInvalid => "Jeton de reprise invalide ou expiré",
+            ErrorCode::ResumeUnavailable => "La session précédente ne peut pas être reprise",
+            ErrorCode::InvalidCredentials => "Identifiants invalides",
+            ErrorCode::PasswordMigrationRequired => {
+                "Ce compte n'a pas de mot de passe. Demandez un code de réinitialisation à un administrateur."
+            }
+            ErrorCode::AccountLocked => "Compte temporairement verrouillé après trop d'échecs",
+            ErrorCode::TooManyAttempts => "Trop de tentatives de connexion, réessayez plus tard",
+            ErrorCode::LegacyLoginDisabled => "Connexion sans mot de passe désactivée",
+            ErrorCode::WeakPassword => "Mot de passe refusé",
+            ErrorCode::ResetCodeInvalid => "Code de réinitialisation invalide ou expiré",
+            ErrorCode::DatabaseError => "Erreur de base de données",
+            ErrorCode::InternalError => "Erreur serveur",
+            ErrorCode::SchedulingFailed => "Erreur lors de la planification",
+            ErrorCode::PlayerNotFound => "Joueur introuvable",
+            ErrorCode::LordNotFound => "Aucun seigneur trouvé",
+            ErrorCode::LordAlreadyExists => "Vous avez déjà un Lord/Lady",
+            ErrorCode::OrganizationAlreadyExists => "Vous avez déjà une organisation",
+            ErrorCode::UnitNotFound => "Unité introuvable",
+            ErrorCode::UnitNotOwned => "Cette unité ne vous appartient pas",
+            ErrorCode::UnitBusy => "Unité déjà occupée",
+            ErrorCode::UnitAlreadyAtTarget => "L'unité est déjà sur cette cellule",
+            ErrorCode::UnitNotAtTarget => "L'unité n'est pas sur la cellule de l'action",
+            ErrorCode::UnitsNotOnSameCell => "Les unités ne sont pas sur la même cellule",
+            ErrorCode::ActionNotFound => "Action introuvable ou déjà terminée",
+            ErrorCode::ActionNotCancellable => "Cette action ne peut plus être annulée",
+            ErrorCode::ActionNotPausable => "Cette action ne peut pas être mise en pause",
+            ErrorCode::ActionNotPaused => "Cette action n'est pas en pause",
+            ErrorCode::ActionQueueFull => "La file d'actions de l'unité est pleine",
+            ErrorCode::InvalidQueueOrder => "Ordre de file invalide",
+            ErrorCode::InvalidRoutine => "Routine invalide",
+            ErrorCode::UnknownRecipe => "Recette inconnue",
+            ErrorCode::MissingResources => "Ressources manquantes",
+            ErrorCode::ProductionLinesFull => "Toutes les lignes de production sont occupées",
+            ErrorCode::NoHarvestYield => "Aucun rendement de récolte défini pour ce type de ressource",
+            ErrorCode::ResourceDepleted => "Cette ressource est épuisée ici",
+            ErrorCode::InventoryUnavailable => "Erreur de chargement de l'inventaire",
+            ErrorCode::ItemNotFound => "Objet introuvable",
+            ErrorCode::CannotEquip => "Impossible d'équiper cet objet",
+            ErrorCode::NotEnoughItems => "Pas assez d'objets",
+            ErrorCode::Overencumbered => "L'unité ne peut pas porter autant",
+            ErrorCode::CellAlreadyClaimed => "Cette cellule appartient déjà à un territoire",
+            ErrorCode::CellNotAdjacent => "Les cellules revendiquées doivent toucher le territoire",
+            ErrorCode::CellFull => "La cellule est pleine",
+            ErrorCode::NoOrganization => "Vous n'appartenez à aucune organisation",
+            ErrorCode::OrganizationNotFound => "Organisation introuvable",
+            ErrorCode::OutsideTerritory => "L'unité n'est pas sur le territoire de l'organisation",
+            ErrorCode::InsufficientAuthority => "Votre rang ne le permet pas",
+            ErrorCode::NoSettlementTier => "Votre établissement ne peut pas s'élever davantage",
+            ErrorCode::SettlementRequirementsNotMet => "Conditions d'élévation non remplies",
+            ErrorCode::NotEnoughGold => "Pas assez d'or dans le trésor",
+            ErrorCode::AlreadyVassal => "Cette organisation a déjà un suzerain",
+            ErrorCode::InvalidVassalage => "Ce lien de vassalité est impossible",
+            ErrorCode::VassalageOfferNotFound => "Proposition de vassalité introuvable",
+            ErrorCode::NotAnEnvoy => "Seuls un marchand, un érudit ou le seigneur peuvent servir d'émissaire",
+            ErrorCode::InvalidEnvoyTarget => "On n'envoie pas d'émissaire à sa propre organisation",
+            ErrorCode::NoHeadquarters => "Cette organisation n'a pas de siège où envoyer un émissaire",
+            ErrorCode::RelationAlreadyExists => "Cette relation est déjà en vigueur",
+            ErrorCode::DiplomaticProposalNotFound => "Proposition diplomatique introuvable",
+            ErrorCode::AlreadyMember => "Ce seigneur appartient déjà à une organisation",
+            ErrorCode::NotAMember => "Ce seigneur n'est pas membre de l'organisation",
+            ErrorCode::MembershipRequestNotFound => "Invitation ou candidature introuvable",
+            ErrorCode::LeaderCannotLeave => "Le chef ne peut pas quitter son organisation",
+            ErrorCode::InvalidOfficerRole => "Cette charge ne peut pas être confiée",
+        }
+    }
+
+    /// Text shown to the player: the default message, followed by the detail if any
+    pub fn describe(self, detail: Option<&str>) -> String {
+        match detail {
+            Some(detail) => format!("{} : {}", self.message(), detail),
+            None => self.message().to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_appends_detail() {
+        assert_eq!(
+            ErrorCode::MissingResources.describe(Some("wood x2")),
+            "Ressources manquantes : wood x2"
+        );
+        assert_eq!(ErrorCode::UnitBusy.describe(None), "Unité déjà occupée");
+    }
+
+    fn code_tag(code: ErrorCode) -> u8 {
+        bincode::encode_to_vec(code, bincode::config::standard()).unwrap()[0]
+    }
+
+    /// Les codes sont numérotés par position : si ce test casse, un code a été inséré
+    /// ou déplacé. Incrémenter `PROTOCOL_VERSION`, puis mettre à jour les valeurs attendues
+    #[test]
+    fn test_error_code_tags_match_protocol_version() {
+        assert_eq!(crate::protocol::PROTOCOL_VERSION, 18);
+        assert_eq!(code_tag(ErrorCode::InvalidCredentials), 5);
+        assert_eq!(code_tag(ErrorCode::AccountLocked), 7);
+        assert_eq!(code_tag(ErrorCode::LegacyLoginDisabled), 9);
+        assert_eq!(code_tag(ErrorCode::InvalidOfficerRole), 64);
+    }
+}
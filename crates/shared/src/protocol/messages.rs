@@ -1,9 +1,15 @@
 use bincode::{Decode, Encode};
 // use crate::types::*;
+use super::ErrorCode;
 use crate::{
-    BiomeChunkData, BuildingData, BuildingTypeEnum, ContourSegmentData, EquipmentSlotEnum, ItemTypeEnum, LakeData, OceanData, OrganizationSummary, OrganizationType, ProfessionEnum, ResourceSpecificTypeEnum, RoadChunkSdfData, SlotPosition, TerrainChunkId, UnitData, grid::{CellData, GridCell}, types::TerrainChunkData
+    AutomatedAction, BiomeChunkData, BuildingData, BuildingTypeEnum, CellResourceStock, ContourSegmentData, DiplomaticProposal, DiplomaticRelation, DiplomaticRelationType, EnvoyMission, EquipmentSlotEnum, EquippedItem, GroundItem, ItemTypeEnum, LakeData, MembershipRequest, OceanData, OrganizationHierarchy, OrganizationMemberSummary, OrganizationPermission, OrganizationSummary, OrganizationType, ProfessionEnum, ResourceSpecificTypeEnum, RoleType, RoadChunkSdfData, SlotPosition, TerrainChunkId, UnitData, UnitDerivedStats, VassalageOffer, grid::{CellData, GridCell}, types::TerrainChunkData
 };
 
+/// Bumped on every incompatible change of `ClientMessage` / `ServerMessage`.
+/// bincode numbers enum variants by position: a variant inserted anywhere but at
+/// the end of an enum renumbers the following ones and needs a bump too.
+pub const PROTOCOL_VERSION: u32 = 18;
+
 /// Simplified Player data for network protocol (without timestamps)
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct PlayerData {
@@ -157,9 +163,143 @@ pub struct InventoryItemData {
     pub equipment_slot: Option<EquipmentSlotEnum>,
 }
 
+/// Why a villager left its settlement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum DepartureReason {
+    /// Went looking for a better place after too many days without food
+    Emigrated,
+    /// Died of hunger
+    Starved,
+}
+
+/// A villager who left the organization (see `PopulationChanged`)
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct PopulationDeparture {
+    pub unit_id: u64,
+    pub first_name: String,
+    pub last_name: String,
+    pub reason: DepartureReason,
+}
+
+/// Stock du trésor d'une organisation, regroupé par item
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct TreasuryItemNet {
+    pub item_id: i32,
+    pub quantity: i32,
+    /// Qualité moyenne des exemplaires stockés
+    pub quality: f32,
+}
+
+// =============================================================================
+// ACTION QUEUE
+// =============================================================================
+
+/// Action mise en file d'attente d'une unité. Elle est lancée pour cette unité,
+/// avec les validations habituelles, quand l'action précédente se termine.
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub enum QueuedAction {
+    MoveUnit {
+        chunk_id: TerrainChunkId,
+        cell: GridCell,
+    },
+    HarvestResource {
+        chunk_id: TerrainChunkId,
+        cell: GridCell,
+        resource_specific_type: ResourceSpecificTypeEnum,
+    },
+    CraftResource {
+        chunk_id: TerrainChunkId,
+        cell: GridCell,
+        recipe_id: String,
+        quantity: u32,
+    },
+    TrainUnit {
+        chunk_id: TerrainChunkId,
+        cell: GridCell,
+        target_profession: ProfessionEnum,
+    },
+}
+
+impl QueuedAction {
+    pub fn action_type(&self) -> crate::ActionTypeEnum {
+        match self {
+            QueuedAction::MoveUnit { .. } => crate::ActionTypeEnum::MoveUnit,
+            QueuedAction::HarvestResource { .. } => crate::ActionTypeEnum::HarvestResource,
+            QueuedAction::CraftResource { .. } => crate::ActionTypeEnum::CraftResource,
+            QueuedAction::TrainUnit { .. } => crate::ActionTypeEnum::TrainUnit,
+        }
+    }
+
+    /// Cellule où l'action a lieu (destination pour un déplacement)
+    pub fn cell(&self) -> GridCell {
+        match self {
+            QueuedAction::MoveUnit { cell, .. }
+            | QueuedAction::HarvestResource { cell, .. }
+            | QueuedAction::CraftResource { cell, .. }
+            | QueuedAction::TrainUnit { cell, .. } => *cell,
+        }
+    }
+
+    /// Le message qu'aurait envoyé le client pour lancer l'action directement
+    pub fn into_client_message(self, unit_id: u64) -> ClientMessage {
+        match self {
+            QueuedAction::MoveUnit { chunk_id, cell } => ClientMessage::ActionMoveUnit {
+                unit_id,
+                chunk_id,
+                cell,
+            },
+            QueuedAction::HarvestResource {
+                chunk_id,
+                cell,
+                resource_specific_type,
+            } => ClientMessage::ActionHarvestResource {
+                chunk_id,
+                cell,
+                resource_specific_type,
+                unit_ids: vec![unit_id],
+            },
+            QueuedAction::CraftResource {
+                chunk_id,
+                cell,
+                recipe_id,
+                quantity,
+            } => ClientMessage::ActionCraftResource {
+                chunk_id,
+                cell,
+                recipe_id,
+                quantity,
+                unit_ids: vec![unit_id],
+            },
+            QueuedAction::TrainUnit {
+                chunk_id,
+                cell,
+                target_profession,
+            } => ClientMessage::ActionTrainUnit {
+                unit_id,
+                chunk_id,
+                cell,
+                target_profession,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Encode, Decode)]
+pub struct QueuedActionEntry {
+    pub entry_id: u64,
+    pub action: QueuedAction,
+}
+
 /// Messages Client → Server
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum ClientMessage {
+    /// First message of every connection. Must stay the first variant with
+    /// the same fields so that any client version can still be decoded
+    Hello {
+        protocol_version: u32,
+        client_build: String,
+    },
+
     /// Initial connection (legacy - kept for backward compatibility)
     Login {
         username: String,
@@ -177,10 +317,43 @@ pub enum ClientMessage {
         family_name: String,
         password: String,
     },
+
+    /// Re-bind a new connection to the player of a dropped one, using the token
+    /// received in `LoginSuccess`. The server replays every `Sequenced` message
+    /// after `last_seen_seq`
+    ResumeSession {
+        token: String,
+        last_seen_seq: u64,
+    },
+
+    /// Change the password of the logged-in account
+    ChangePassword {
+        current_password: String,
+        new_password: String,
+    },
+
+    /// Set a new password with a one-time code issued by an administrator
+    /// (forgotten password, or account created before passwords existed)
+    ResetPasswordWithCode {
+        family_name: String,
+        reset_code: String,
+        new_password: String,
+    },
+
+    /// Delete the logged-in account with all its units and the organizations they lead
+    DeleteAccount {
+        password: String,
+    },
     RequestTerrainChunks {
         terrain_name: String,
         terrain_chunk_ids: Vec<TerrainChunkId>,
     },
+    /// Camera chunk and unload radius of the client's streaming, used by the
+    /// server to only broadcast chunk updates to sessions that can see them
+    UpdateInterestArea {
+        center_chunk: TerrainChunkId,
+        unload_distance: i32,
+    },
     RequestTerrains {
         terrain_names: Vec<String>,
     },
@@ -196,18 +369,15 @@ pub enum ClientMessage {
     },
 
     ActionBuildBuilding {
-        player_id: u64,
         chunk_id: TerrainChunkId,
         cell: GridCell,
         building_type: BuildingTypeEnum,
     },
     ActionBuildRoad {
-        player_id: u64,
         start_cell: GridCell,
         end_cell: GridCell,
     },
     ActionMoveUnit {
-        player_id: u64,
         unit_id: u64,
         chunk_id: TerrainChunkId,
         cell: GridCell,
@@ -226,21 +396,18 @@ pub enum ClientMessage {
         slot: SlotPosition,
     },
     ActionSendMessage {
-        player_id: u64,
         chunk_id: TerrainChunkId,
         cell: GridCell,
         receivers: Vec<u64>,
         content: String,
     },
     ActionHarvestResource {
-        player_id: u64,
         chunk_id: TerrainChunkId,
         cell: GridCell,
         resource_specific_type: ResourceSpecificTypeEnum,
         unit_ids: Vec<u64>,
     },
     ActionCraftResource {
-        player_id: u64,
         chunk_id: TerrainChunkId,
         cell: GridCell,
         recipe_id: String,
@@ -248,12 +415,22 @@ pub enum ClientMessage {
         unit_ids: Vec<u64>,
     },
     ActionTrainUnit {
-        player_id: u64,
         unit_id: u64,
         chunk_id: TerrainChunkId,
         cell: GridCell,
         target_profession: ProfessionEnum,
     },
+    /// Extend the lord's territory onto adjacent unclaimed cells
+    ActionClaimCells {
+        unit_id: u64,
+        cells: Vec<GridCell>,
+    },
+    /// Send an envoy from the organization's seat to another organization's seat
+    ActionSendEnvoy {
+        unit_id: u64,
+        target_organization_id: u64,
+        mission: EnvoyMission,
+    },
 
     // ========================================================================
     // LORD CREATION
@@ -271,6 +448,63 @@ pub enum ClientMessage {
     /// Found a hamlet at the lord's current position
     FoundHamlet,
 
+    /// Raise the lord's settlement to its next tier (hamlet → village → town → city)
+    UpgradeOrganization,
+
+    /// Propose a vassalage bond; the player's organization must be one of the two
+    OfferVassalage {
+        liege_organization_id: u64,
+        vassal_organization_id: u64,
+    },
+    /// Accept an offer made by the other organization
+    AcceptVassalage {
+        offer_id: u64,
+    },
+    /// Break the bond with a liege or a vassal, or withdraw/decline a pending offer
+    RevokeVassalage {
+        organization_id: u64,
+    },
+    /// Feudal tree the organization belongs to, from its top liege down
+    RequestOrganizationHierarchy {
+        organization_id: u64,
+    },
+    /// Relations and pending proposals of the player's organization
+    RequestDiplomacy,
+
+    /// Invite another player's lord into the organization
+    InviteToOrganization {
+        unit_id: u64,
+    },
+    /// Ask to join an organization with the player's lord
+    ApplyToOrganization {
+        organization_id: u64,
+    },
+    /// Accept an invitation (invited lord) or an application (organization side)
+    AcceptMembershipRequest {
+        request_id: u64,
+    },
+    /// Decline or withdraw an invitation or an application
+    DeclineMembershipRequest {
+        request_id: u64,
+    },
+    /// Remove a member from the organization
+    ExpelMember {
+        unit_id: u64,
+    },
+    /// Leave the organization the player's lord belongs to
+    LeaveOrganization,
+    /// Give a member an officer role (leader only)
+    AppointOfficer {
+        unit_id: u64,
+        role: RoleType,
+    },
+    /// Take away a member's officer role (leader only)
+    DismissOfficer {
+        unit_id: u64,
+    },
+    /// Members, officers and pending requests of the player's organization
+    RequestMembers,
+
     // ========================================================================
     // DEBUG COMMANDS
     // ========================================================================
@@ -309,27 +543,216 @@ pub enum ClientMessage {
         terrain_name: String,
     },
     ActionExplore {
-        player_id: i64,
         cell: GridCell,
         radius: i32,
     },
 
+    /// Stop an action for good. Reserved materials are partially refunded
+    /// (in proportion to the remaining time) and its units are released
+    CancelAction {
+        action_id: u64,
+    },
+    /// Freeze an action's timer; its units stay assigned
+    PauseAction {
+        action_id: u64,
+    },
+    ResumeAction {
+        action_id: u64,
+    },
+
+    /// Append an action to a unit's queue. It starts right away if the unit is idle
+    EnqueueAction {
+        unit_id: u64,
+        action: QueuedAction,
+    },
+    /// New order of a unit's queue: must list every entry exactly once
+    ReorderActionQueue {
+        unit_id: u64,
+        entry_ids: Vec<u64>,
+    },
+    ClearActionQueue {
+        unit_id: u64,
+    },
+    RequestActionQueue {
+        unit_id: u64,
+    },
+
+    /// Create (id 0) or replace a routine of an NPC unit; see `Routine`
+    SaveUnitRoutine {
+        unit_id: u64,
+        routine: AutomatedAction,
+    },
+    DeleteUnitRoutine {
+        unit_id: u64,
+        routine_id: u64,
+    },
+    RequestUnitRoutines {
+        unit_id: u64,
+    },
+
+    /// Equip an item from the unit's inventory; replaces whatever the slot held
+    EquipItem {
+        unit_id: u64,
+        item_id: i32,
+        slot: EquipmentSlotEnum,
+    },
+    UnequipItem {
+        unit_id: u64,
+        slot: EquipmentSlotEnum,
+    },
+
+    /// Give items to another unit standing on the same cell
+    TransferItems {
+        from_unit: u64,
+        to_unit: u64,
+        item_id: i32,
+        quantity: i32,
+    },
+    /// Put items on the ground of the unit's cell
+    DropItems {
+        unit_id: u64,
+        item_id: i32,
+        quantity: i32,
+    },
+    /// Take items from the ground of the unit's cell
+    PickUpItems {
+        unit_id: u64,
+        item_id: i32,
+        quantity: i32,
+    },
+
+    /// Contents of the treasury of the player's organization
+    RequestTreasury,
+    /// Put items from a unit's inventory into the organization's storehouse
+    DepositToTreasury {
+        unit_id: u64,
+        item_id: i32,
+        quantity: i32,
+    },
+    /// Take items out of the storehouse (officers only)
+    WithdrawFromTreasury {
+        unit_id: u64,
+        item_id: i32,
+        quantity: i32,
+    },
+
     /// Ping (keep alive)
     Ping,
 }
 
+impl ClientMessage {
+    /// Gameplay messages act on behalf of the session's player and are
+    /// rejected with `ServerMessage::NotAuthenticated` before login.
+    pub fn requires_authentication(&self) -> bool {
+        matches!(
+            self,
+            ClientMessage::ActionBuildBuilding { .. }
+                | ClientMessage::ActionBuildRoad { .. }
+                | ClientMessage::ActionMoveUnit { .. }
+                | ClientMessage::MoveUnitToSlot { .. }
+                | ClientMessage::AssignUnitToSlot { .. }
+                | ClientMessage::ActionSendMessage { .. }
+                | ClientMessage::ActionHarvestResource { .. }
+                | ClientMessage::ActionCraftResource { .. }
+                | ClientMessage::ActionTrainUnit { .. }
+                | ClientMessage::ActionClaimCells { .. }
+                | ClientMessage::ActionSendEnvoy { .. }
+                | ClientMessage::CreateLord { .. }
+                | ClientMessage::FoundHamlet
+                | ClientMessage::UpgradeOrganization
+                | ClientMessage::OfferVassalage { .. }
+                | ClientMessage::AcceptVassalage { .. }
+                | ClientMessage::RevokeVassalage { .. }
+                | ClientMessage::RequestOrganizationHierarchy { .. }
+                | ClientMessage::RequestDiplomacy
+                | ClientMessage::InviteToOrganization { .. }
+                | ClientMessage::ApplyToOrganization { .. }
+                | ClientMessage::AcceptMembershipRequest { .. }
+                | ClientMessage::DeclineMembershipRequest { .. }
+                | ClientMessage::ExpelMember { .. }
+                | ClientMessage::LeaveOrganization
+                | ClientMessage::AppointOfficer { .. }
+                | ClientMessage::DismissOfficer { .. }
+                | ClientMessage::RequestMembers
+                | ClientMessage::RequestInventory { .. }
+                | ClientMessage::ActionExplore { .. }
+                | ClientMessage::CancelAction { .. }
+                | ClientMessage::PauseAction { .. }
+                | ClientMessage::ResumeAction { .. }
+                | ClientMessage::EnqueueAction { .. }
+                | ClientMessage::ReorderActionQueue { .. }
+                | ClientMessage::ClearActionQueue { .. }
+                | ClientMessage::RequestActionQueue { .. }
+                | ClientMessage::SaveUnitRoutine { .. }
+                | ClientMessage::DeleteUnitRoutine { .. }
+                | ClientMessage::RequestUnitRoutines { .. }
+                | ClientMessage::EquipItem { .. }
+                | ClientMessage::UnequipItem { .. }
+                | ClientMessage::TransferItems { .. }
+                | ClientMessage::DropItems { .. }
+                | ClientMessage::PickUpItems { .. }
+                | ClientMessage::RequestTreasury
+                | ClientMessage::DepositToTreasury { .. }
+                | ClientMessage::WithdrawFromTreasury { .. }
+                | ClientMessage::ChangePassword { .. }
+                | ClientMessage::DeleteAccount { .. }
+        )
+    }
+}
+
 /// Messages Server → Client
 #[derive(Debug, Clone, Encode, Decode)]
 pub enum ServerMessage {
+    /// Handshake accepted. Like `Hello`, the two handshake answers must stay
+    /// the first variants so an outdated client can still read them
+    HelloAck {
+        protocol_version: u32,
+        server_build: String,
+    },
+
+    /// Handshake refused, the server closes the connection right after
+    HelloRejected {
+        code: ErrorCode,
+        server_protocol_version: u32,
+    },
+
+    /// Message pushed outside of a request/response (action completions, broadcasts...).
+    /// `seq` increases per player and is what the client reports in `ResumeSession`
+    Sequenced {
+        seq: u64,
+        message: Box<ServerMessage>,
+    },
+
     /// Connection acknowledgement
     LoginSuccess {
         player: PlayerData,
         character: Option<CharacterData>,
+        resume_token: String,
+    },
+
+    /// The new connection took over the previous session; queued messages follow
+    SessionResumed {
+        resume_token: String,
+        replayed: u32,
+    },
+
+    /// The session could not be resumed, the client must log in again
+    ResumeFailed {
+        code: ErrorCode,
     },
 
     /// Connection error
     LoginError {
-        reason: String,
+        code: ErrorCode,
+        detail: Option<String>,
+    },
+
+    /// A gameplay message was sent by a session that is not logged in
+    NotAuthenticated,
+
+    /// The message was dropped because the session exceeded its budget for that kind of message
+    RateLimited {
+        retry_after_ms: u64,
     },
 
     /// Registration successful
@@ -342,6 +765,18 @@ pub enum ServerMessage {
         reason: String,
     },
 
+    /// Password changed (`ChangePassword` or `ResetPasswordWithCode`)
+    PasswordChanged,
+
+    /// Account deleted, the session is logged out
+    AccountDeleted,
+
+    /// `ChangePassword`, `ResetPasswordWithCode` or `DeleteAccount` failed
+    AccountError {
+        code: ErrorCode,
+        detail: Option<String>,
+    },
+
     /// Lord/Lady data sent after successful login (None if no lord yet)
     /// The client uses this to decide: InGame or CharacterCreation
     LordData {
@@ -355,7 +790,8 @@ pub enum ServerMessage {
 
     /// Lord creation failed
     LordCreateError {
-        reason: String,
+        code: ErrorCode,
+        detail: Option<String>,
     },
 
     TerrainChunkData {
@@ -364,6 +800,8 @@ pub enum ServerMessage {
         cell_data: Vec<CellData>,
         building_data: Vec<BuildingData>,
         unit_data: Vec<UnitData>,
+        ground_items: Vec<GroundItem>,
+        resource_stocks: Vec<CellResourceStock>,
     },
 
     // OrganizationData {
@@ -411,7 +849,8 @@ pub enum ServerMessage {
     },
 
     ActionError {
-        reason: String,
+        code: ErrorCode,
+        detail: Option<String>,
     },
 
     /// Action status update sent to the player who initiated the action
@@ -422,6 +861,7 @@ pub enum ServerMessage {
         cell: GridCell,
         status: crate::ActionStatusEnum,
         action_type: crate::ActionTypeEnum,
+        /// Unix time in milliseconds
         completion_time: u64,
         action_name: Option<String>,
         unit_ids: Vec<u64>, 
@@ -435,6 +875,61 @@ pub enum ServerMessage {
         action_type: crate::ActionTypeEnum,
     },
 
+    /// Current queue of a unit, sent after every change
+    ActionQueueUpdate {
+        unit_id: u64,
+        entries: Vec<QueuedActionEntry>,
+    },
+
+    /// A queued action could not start (its preconditions no longer hold)
+    /// and was dropped; the next one is tried
+    ActionQueueSkipped {
+        unit_id: u64,
+        action: QueuedAction,
+        code: ErrorCode,
+        detail: Option<String>,
+    },
+
+    /// Routines of a unit, sent after every change
+    UnitRoutines {
+        unit_id: u64,
+        routines: Vec<AutomatedAction>,
+    },
+
+    /// Équipement d'une unité et stats dérivées recalculées
+    EquipmentChanged {
+        unit_id: u64,
+        equipment: Vec<EquippedItem>,
+        derived_stats: UnitDerivedStats,
+    },
+
+    /// Objets au sol d'une cellule, envoyé aux joueurs du chunk après un dépôt ou un ramassage
+    GroundItemsUpdate {
+        chunk_id: TerrainChunkId,
+        cell: GridCell,
+        items: Vec<GroundItem>,
+    },
+
+    /// Réserves naturelles d'une cellule, envoyé aux joueurs du chunk quand leur niveau visible change
+    CellResourcesUpdate {
+        chunk_id: TerrainChunkId,
+        cell: GridCell,
+        stocks: Vec<CellResourceStock>,
+    },
+
+    /// Un bâtiment a disparu (arbre abattu jusqu'au dernier rondin)
+    BuildingRemoved {
+        chunk_id: TerrainChunkId,
+        cell: GridCell,
+    },
+
+    /// Une unité a gagné un niveau de compétence
+    SkillLevelUp {
+        unit_id: u64,
+        skill: crate::SkillEnum,
+        level: i32,
+    },
+
     /// Unit position changed (after move action completion)
     UnitPositionUpdated {
         unit_id: u64,
@@ -477,7 +972,122 @@ pub enum ServerMessage {
 
     /// Hamlet founding failed
     HamletFoundError {
-        reason: String,
+        code: ErrorCode,
+        detail: Option<String>,
+    },
+
+    /// A settlement reached a new tier, broadcast to everyone so labels follow
+    OrganizationUpgraded {
+        organization_id: u64,
+        organization_type: OrganizationType,
+        name: String,
+    },
+
+    /// Settlement upgrade refused (detail lists the unmet requirements)
+    OrganizationUpgradeError {
+        code: ErrorCode,
+        detail: Option<String>,
+    },
+
+    /// A vassalage offer was made (sent to both organizations' leaders)
+    VassalageOffered {
+        offer: VassalageOffer,
+    },
+
+    /// A pending offer was accepted, withdrawn or declined
+    VassalageOfferClosed {
+        offer_id: u64,
+    },
+
+    /// An organization swore fealty (`Some`) or became independent (`None`)
+    VassalageChanged {
+        vassal_organization_id: u64,
+        liege_organization_id: Option<u64>,
+    },
+
+    /// Vassalage offer, acceptance or revocation refused
+    VassalageError {
+        code: ErrorCode,
+        detail: Option<String>,
+    },
+
+    /// Feudal tree rooted at the top liege, with the offers of the requested organization
+    OrganizationHierarchyData {
+        root_organization_id: u64,
+        organizations: Vec<OrganizationSummary>,
+        hierarchy: Vec<OrganizationHierarchy>,
+        offers: Vec<VassalageOffer>,
+    },
+
+    /// Daily tribute paid by a vassal to its liege
+    TributePaid {
+        vassal_organization_id: u64,
+        liege_organization_id: u64,
+        gold: i32,
+    },
+
+    /// Relations and proposals of the player's organization, with the names involved
+    DiplomacyData {
+        organization_id: u64,
+        relations: Vec<DiplomaticRelation>,
+        proposals: Vec<DiplomaticProposal>,
+        organizations: Vec<OrganizationSummary>,
+    },
+
+    /// An envoy delivered a proposal (sent to the members of both organizations)
+    DiplomaticProposalReceived {
+        proposal: DiplomaticProposal,
+    },
+
+    /// An envoy brought back a refusal
+    DiplomaticProposalRejected {
+        proposal: DiplomaticProposal,
+    },
+
+    /// A relation was established (`Some`) or ended or expired (`None`)
+    DiplomaticRelationChanged {
+        organization_id: u64,
+        target_organization_id: u64,
+        relation_type: Option<DiplomaticRelationType>,
+    },
+
+    /// Members of the player's organization (if any), the invitations and applications
+    /// the player can answer, and what the player's rank allows
+    OrganizationMembersData {
+        organization_id: Option<u64>,
+        members: Vec<OrganizationMemberSummary>,
+        requests: Vec<MembershipRequest>,
+        permissions: Vec<OrganizationPermission>,
+    },
+
+    /// An invitation or application is waiting (sent to the lord and to the organization)
+    MembershipRequestReceived {
+        request: MembershipRequest,
+    },
+
+    /// A pending invitation or application was accepted, declined or withdrawn
+    MembershipRequestClosed {
+        request_id: u64,
+    },
+
+    /// A lord joined (`true`) or left or was expelled from (`false`) an organization
+    MembershipChanged {
+        organization_id: u64,
+        unit_id: u64,
+        member: bool,
+    },
+
+    /// A member was appointed (`Some`) or dismissed (`None`)
+    OfficerChanged {
+        organization_id: u64,
+        unit_id: u64,
+        role: Option<RoleType>,
+    },
+
+    /// Invitation, application, expulsion or appointment refused
+    MembershipError {
+        code: ErrorCode,
+        detail: Option<String>,
     },
 
     /// Player's own organization data (sent after login)
@@ -485,12 +1095,23 @@ pub enum ServerMessage {
         organization: Option<OrganizationSummary>,
     },
 
-    /// Population changed for an organization (immigration/death)
+    /// Treasury of the player's organization (gold and storehouse)
+    TreasuryData {
+        organization_id: u64,
+        gold: i32,
+        items: Vec<TreasuryItemNet>,
+        /// The player's lord may withdraw (leader or officer with enough authority)
+        can_withdraw: bool,
+    },
+
+    /// Population changed for an organization (immigration/emigration/death)
     PopulationChanged {
         organization_id: u64,
         new_population: i32,
         /// The unit that just arrived (if immigration)
         immigrant: Option<UnitData>,
+        /// The unit that just left (emigration or starvation)
+        departure: Option<PopulationDeparture>,
     },
 
     // ========================================================================
@@ -520,7 +1141,8 @@ pub enum ServerMessage {
 
     /// Debug error
     DebugError {
-        reason: String,
+        code: ErrorCode,
+        detail: Option<String>,
     },
 
     /// Inventaire complet d'une unité
@@ -554,3 +1176,23 @@ pub enum ServerMessage {
     /// Pong (ping answer)
     Pong,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant_tag(message: &ServerMessage) -> u8 {
+        bincode::encode_to_vec(message, bincode::config::standard()).unwrap()[0]
+    }
+
+    /// Si ce test casse, une variante a été insérée ou déplacée : incrémenter
+    /// `PROTOCOL_VERSION`, puis mettre à jour les valeurs attendues
+    #[test]
+    fn test_server_message_tags_match_protocol_version() {
+        assert_eq!(PROTOCOL_VERSION, 18);
+        assert_eq!(variant_tag(&ServerMessage::NotAuthenticated), 7);
+        assert_eq!(variant_tag(&ServerMessage::RateLimited { retry_after_ms: 0 }), 8);
+        assert_eq!(variant_tag(&ServerMessage::PasswordChanged), 11);
+        assert_eq!(variant_tag(&ServerMessage::Pong), 74);
+    }
+}
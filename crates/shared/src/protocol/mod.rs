@@ -1,4 +1,6 @@
 pub mod compression;
+mod error_code;
 mod messages;
 
+pub use error_code::*;
 pub use messages::*;
\ No newline at end of file
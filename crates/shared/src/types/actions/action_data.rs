@@ -3,7 +3,8 @@ use sqlx::prelude::FromRow;
 
 use crate::{
     ActionSpecificTypeEnum, ActionStatusEnum, ActionTypeEnum, BuildingSpecificTypeEnum,
-    BuildingTypeEnum, ProfessionEnum, ResourceSpecificTypeEnum, TerrainChunkId, grid::GridCell,
+    BuildingTypeEnum, EnvoyMission, ProfessionEnum, ResourceSpecificTypeEnum, TerrainChunkId,
+    grid::GridCell,
 };
 
 pub struct ActionContext {
@@ -235,6 +236,77 @@ impl SpecificActionData for TrainUnitAction {
     }
 }
 
+// ClaimCells
+/// Temps de revendication d'une cellule : on arpente et on borne le terrain
+pub const CLAIM_CELL_DURATION_MS: u64 = 20_000;
+
+#[derive(Clone, Debug, Encode, Decode)]
+pub struct ClaimCellsAction {
+    pub player_id: u64,
+    pub unit_id: u64,
+    pub organization_id: u64,
+    pub chunk_id: TerrainChunkId,
+    pub cell: GridCell,
+    /// Cellules revendiquées, toutes reliées au territoire existant
+    pub cells: Vec<GridCell>,
+}
+
+impl SpecificActionData for ClaimCellsAction {
+    fn action_type(&self) -> ActionTypeEnum {
+        ActionTypeEnum::ClaimCells
+    }
+
+    fn duration_ms(&self, _context: &ActionContext) -> u64 {
+        CLAIM_CELL_DURATION_MS * self.cells.len() as u64
+    }
+
+    fn validate(&self, _context: &ValidationContext) -> Result<(), String> {
+        if self.cells.is_empty() {
+            return Err("cells cannot be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+// SendEnvoy
+/// Temps de trajet d'un émissaire par cellule séparant les deux sièges
+pub const ENVOY_MS_PER_CELL: u64 = 2_000;
+/// Même entre voisins, on ne reçoit pas un émissaire sur-le-champ
+pub const ENVOY_MIN_DURATION_MS: u64 = 10_000;
+
+#[derive(Clone, Debug, Encode, Decode)]
+pub struct SendEnvoyAction {
+    pub player_id: u64,
+    /// L'émissaire (marchand, érudit ou le seigneur lui-même)
+    pub unit_id: u64,
+    pub organization_id: u64,
+    pub target_organization_id: u64,
+    /// Siège de départ
+    pub chunk_id: TerrainChunkId,
+    pub cell: GridCell,
+    /// Siège de l'organisation visée
+    pub target_cell: GridCell,
+    pub mission: EnvoyMission,
+}
+
+impl SpecificActionData for SendEnvoyAction {
+    fn action_type(&self) -> ActionTypeEnum {
+        ActionTypeEnum::SendEnvoy
+    }
+
+    fn duration_ms(&self, _context: &ActionContext) -> u64 {
+        let distance = self.cell.to_hex().unsigned_distance_to(self.target_cell.to_hex()) as u64;
+        (distance * ENVOY_MS_PER_CELL).max(ENVOY_MIN_DURATION_MS)
+    }
+
+    fn validate(&self, _context: &ValidationContext) -> Result<(), String> {
+        if self.organization_id == self.target_organization_id {
+            return Err("an organization cannot send an envoy to itself".to_string());
+        }
+        Ok(())
+    }
+}
+
 // ============ ENUM UNIFIÉ ============
 #[derive(Clone, Debug, Encode, Decode)]
 pub enum SpecificAction {
@@ -246,6 +318,8 @@ pub enum SpecificAction {
     HarvestResource(HarvestResourceAction),
     CraftResource(CraftResourceAction),
     TrainUnit(TrainUnitAction),
+    ClaimCells(ClaimCellsAction),
+    SendEnvoy(SendEnvoyAction),
 }
 
 impl SpecificAction {
@@ -258,6 +332,8 @@ impl SpecificAction {
             Self::HarvestResource(_) => 5,
             Self::CraftResource(_) => 6,
             Self::TrainUnit(_) => 7,
+            Self::ClaimCells(_) => 8,
+            Self::SendEnvoy(_) => 9,
             Self::Unknown() => 0,
         }
     }
@@ -271,6 +347,8 @@ impl SpecificAction {
             Self::HarvestResource(a) => a.action_type(),
             Self::CraftResource(a) => a.action_type(),
             Self::TrainUnit(a) => a.action_type(),
+            Self::ClaimCells(a) => a.action_type(),
+            Self::SendEnvoy(a) => a.action_type(),
             Self::Unknown() => ActionTypeEnum::Unknown,
         }
     }
@@ -284,6 +362,8 @@ impl SpecificAction {
             Self::HarvestResource(a) => a.duration_ms(context),
             Self::CraftResource(a) => a.duration_ms(context),
             Self::TrainUnit(a) => a.duration_ms(context),
+            Self::ClaimCells(a) => a.duration_ms(context),
+            Self::SendEnvoy(a) => a.duration_ms(context),
             Self::Unknown() => 5_000,
         }
     }
@@ -299,6 +379,7 @@ pub struct ActionBaseData {
     pub action_type: ActionTypeEnum,
     pub action_specific_type: ActionSpecificTypeEnum,
 
+    // Timestamps Unix en millisecondes
     pub start_time: u64,
     pub duration_ms: u64,
     pub completion_time: u64,
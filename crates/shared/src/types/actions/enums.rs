@@ -65,6 +65,8 @@ pub enum ActionStatusEnum {
     Pending = 2,
     Completed = 3,
     Failed = 4,
+    Cancelled = 5,
+    Paused = 6,
 }
 
 impl ActionStatusEnum {
@@ -78,6 +80,8 @@ impl ActionStatusEnum {
             2 => Some(Self::Pending),
             3 => Some(Self::Completed),
             4 => Some(Self::Failed),
+            5 => Some(Self::Cancelled),
+            6 => Some(Self::Paused),
             _ => None,
         }
     }
@@ -93,6 +97,8 @@ pub enum ActionTypeEnum {
     HarvestResource = 5,
     CraftResource = 6,
     TrainUnit = 7,
+    ClaimCells = 8,
+    SendEnvoy = 9,
 }
 
 impl ActionTypeEnum {
@@ -110,6 +116,8 @@ impl ActionTypeEnum {
             5 => Some(Self::HarvestResource),
             6 => Some(Self::CraftResource),
             7 => Some(Self::TrainUnit),
+            8 => Some(Self::ClaimCells),
+            9 => Some(Self::SendEnvoy),
             _ => None,
         }
     }
@@ -124,6 +132,8 @@ impl ActionTypeEnum {
             Self::HarvestResource => "Récolte",
             Self::CraftResource => "Fabrication",
             Self::TrainUnit => "Formation",
+            Self::ClaimCells => "Revendication",
+            Self::SendEnvoy => "Ambassade",
         }
     }
 }
@@ -137,6 +147,8 @@ pub enum ActionSpecificTypeEnum {
     HarvestResource = 5,
     CraftResource = 6,
     TrainUnit = 7,
+    ClaimCells = 8,
+    SendEnvoy = 9,
 }
 
 impl ActionSpecificTypeEnum {
@@ -153,6 +165,8 @@ impl ActionSpecificTypeEnum {
             5 => Some(Self::HarvestResource),
             6 => Some(Self::CraftResource),
             7 => Some(Self::TrainUnit),
+            8 => Some(Self::ClaimCells),
+            9 => Some(Self::SendEnvoy),
             _ => None,
         }
     }
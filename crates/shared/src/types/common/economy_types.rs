@@ -1,5 +1,7 @@
 use bincode::{Decode, Encode};
 
+use crate::{ActionTypeEnum, BiomeTypeEnum, ResourceSpecificTypeEnum, SkillEnum};
+
 /// Construction cost entry (building_type_id -> item costs)
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct ConstructionCost {
@@ -21,6 +23,46 @@ pub struct HarvestYield {
     pub required_tool_item_id: Option<i32>,
     pub tool_bonus_quantity: i32,
     pub duration_seconds: i32,
+    /// Skill trained (and used) by this harvest
+    pub skill: Option<SkillEnum>,
+}
+
+/// Natural reserve of a resource in a biome: cell stocks are seeded from it
+/// and regrow by `regrowth_per_day` up to their own maximum
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+pub struct BiomeResourceStock {
+    pub biome: BiomeTypeEnum,
+    pub resource_specific_type: ResourceSpecificTypeEnum,
+    /// Reserve of a fresh cell; for wood, the reserve of a tree of density 1.0
+    pub max_stock: i32,
+    pub regrowth_per_day: i32,
+}
+
+/// XP granted to each participating unit when an action completes
+#[derive(Debug, Clone, Copy, Encode, Decode)]
+pub struct ActionXp {
+    pub action_type: ActionTypeEnum,
+    pub base_xp: i32,
+    /// Skill trained when the action doesn't name one (recipe, harvest yield)
+    pub skill: Option<SkillEnum>,
+}
+
+/// Courbe d'XP : XP totale requise pour chaque niveau (index 0 = niveau 1)
+#[derive(Debug, Clone, Default, Encode, Decode)]
+pub struct SkillCurve {
+    pub xp_required: Vec<i64>,
+}
+
+impl SkillCurve {
+    /// Plus haut niveau dont le seuil est atteint (1 si la courbe est vide)
+    pub fn level_for_xp(&self, xp: i64) -> i32 {
+        let reached = self
+            .xp_required
+            .iter()
+            .take_while(|required| xp >= **required)
+            .count();
+        reached.max(1) as i32
+    }
 }
 
 /// Translation key for lookup
@@ -31,3 +73,21 @@ pub struct TranslationKey {
     pub language_id: i16,
     pub field: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level_for_xp_follows_curve() {
+        let curve = SkillCurve {
+            xp_required: vec![0, 50, 200, 450],
+        };
+        assert_eq!(curve.level_for_xp(0), 1);
+        assert_eq!(curve.level_for_xp(49), 1);
+        assert_eq!(curve.level_for_xp(50), 2);
+        assert_eq!(curve.level_for_xp(449), 3);
+        assert_eq!(curve.level_for_xp(10_000), 4);
+        assert_eq!(SkillCurve::default().level_for_xp(500), 1);
+    }
+}
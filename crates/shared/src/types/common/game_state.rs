@@ -3,9 +3,9 @@ use sqlx::{PgPool, Row};
 use std::collections::HashMap;
 
 use crate::{
-    BuildingCategory, BuildingSpecificType, BuildingType, ConstructionCost, HarvestYield,
-    ItemDefinition, Recipe, RecipeIngredient, ResourceCategory, ResourceSpecificType, ResourceType,
-    TranslationKey, atlas::TreeAtlas,
+    ActionTypeEnum, ActionXp, BiomeResourceStock, BiomeTypeEnum, BuildingCategory, BuildingTypeEnum, BuildingSpecificType, BuildingType, ConstructionCost, HarvestYield,
+    ItemDefinition, OrganizationType, ProfessionEnum, ProfessionSkillBonus, ProfessionSkillBonusesLookup, Recipe, RecipeIngredient, ResourceCategory, RoleType, SettlementTier, ResourceSpecificType, ResourceSpecificTypeEnum, ResourceType,
+    SkillCurve, SkillEnum, TranslationKey, atlas::TreeAtlas,
 };
 
 #[derive(Resource)]
@@ -29,7 +29,16 @@ pub struct GameState {
     pub recipes: Vec<Recipe>,
     pub construction_costs: HashMap<i32, Vec<ConstructionCost>>,
     pub harvest_yields: Vec<HarvestYield>,
+    pub biome_resource_stocks: Vec<BiomeResourceStock>,
     pub translations: HashMap<TranslationKey, String>,
+
+    // Organization cache
+    pub settlement_tiers: Vec<SettlementTier>,
+
+    // Progression cache
+    pub skill_curve: SkillCurve,
+    pub action_xp: Vec<ActionXp>,
+    pub profession_skill_bonuses: ProfessionSkillBonusesLookup,
 }
 
 impl GameState {
@@ -50,7 +59,12 @@ impl GameState {
             recipes: Vec::new(),
             construction_costs: HashMap::new(),
             harvest_yields: Vec::new(),
+            biome_resource_stocks: Vec::new(),
             translations: HashMap::new(),
+            settlement_tiers: Vec::new(),
+            skill_curve: SkillCurve::default(),
+            action_xp: Vec::new(),
+            profession_skill_bonuses: ProfessionSkillBonusesLookup::new(),
         }
     }
 
@@ -227,7 +241,8 @@ impl GameState {
         let yield_rows = sqlx::query(
             r#"SELECT id, resource_specific_type_id, result_item_id, base_quantity,
                       quality_min, quality_max, required_profession_id,
-                      required_tool_item_id, tool_bonus_quantity, duration_seconds
+                      required_tool_item_id, tool_bonus_quantity, duration_seconds,
+                      skill_id
                FROM resources.harvest_yields"#,
         )
         .fetch_all(&self.pool)
@@ -247,9 +262,133 @@ impl GameState {
                 required_tool_item_id: yr.get("required_tool_item_id"),
                 tool_bonus_quantity: yr.try_get("tool_bonus_quantity").unwrap_or(0),
                 duration_seconds: yr.get("duration_seconds"),
+                skill: yr
+                    .try_get::<Option<i16>, _>("skill_id")
+                    .ok()
+                    .flatten()
+                    .and_then(SkillEnum::from_id),
             })
             .collect();
 
+        // --- Natural reserves per biome ---
+        let stock_rows = sqlx::query(
+            r#"SELECT biome_id, resource_specific_type_id, max_stock, regrowth_per_day
+               FROM resources.biome_resource_stocks"#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        self.biome_resource_stocks = stock_rows
+            .iter()
+            .filter_map(|sr| {
+                Some(BiomeResourceStock {
+                    biome: BiomeTypeEnum::from_id(sr.get("biome_id"))?,
+                    resource_specific_type: ResourceSpecificTypeEnum::from_id(
+                        sr.get("resource_specific_type_id"),
+                    )?,
+                    max_stock: sr.get("max_stock"),
+                    regrowth_per_day: sr.get("regrowth_per_day"),
+                })
+            })
+            .collect();
+
+        // --- Settlement tiers ---
+        let tier_building_rows = sqlx::query(
+            "SELECT organization_type_id, building_type_id FROM organizations.settlement_tier_buildings",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        let tier_rows = sqlx::query(
+            r#"SELECT organization_type_id, previous_type_id, leader_role_type_id, name_prefix,
+                      claim_radius, min_population, min_territory_cells, cost_gold
+               FROM organizations.settlement_tiers
+               ORDER BY organization_type_id"#,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        self.settlement_tiers = tier_rows
+            .iter()
+            .map(|tr| {
+                let type_id: i16 = tr.get("organization_type_id");
+                SettlementTier {
+                    organization_type: OrganizationType::from_id(type_id),
+                    previous_type: tr
+                        .get::<Option<i16>, _>("previous_type_id")
+                        .map(OrganizationType::from_id),
+                    leader_role: RoleType::from_id(tr.get("leader_role_type_id")),
+                    name_prefix: tr.get("name_prefix"),
+                    claim_radius: tr.get::<i16, _>("claim_radius").max(0) as u32,
+                    min_population: tr.get("min_population"),
+                    min_territory_cells: tr.get("min_territory_cells"),
+                    required_buildings: tier_building_rows
+                        .iter()
+                        .filter(|br| br.get::<i16, _>("organization_type_id") == type_id)
+                        .filter_map(|br| {
+                            BuildingTypeEnum::from_id(br.get::<i32, _>("building_type_id") as i16)
+                        })
+                        .collect(),
+                    cost_gold: tr.get("cost_gold"),
+                }
+            })
+            .collect();
+
+        // --- Skill progression ---
+        let level_rows =
+            sqlx::query("SELECT level, xp_required FROM units.skill_levels ORDER BY level")
+                .fetch_all(&self.pool)
+                .await
+                .unwrap_or_default();
+
+        self.skill_curve = SkillCurve {
+            xp_required: level_rows.iter().map(|lr| lr.get("xp_required")).collect(),
+        };
+
+        let action_xp_rows =
+            sqlx::query("SELECT action_type_id, base_xp, skill_id FROM units.action_xp")
+                .fetch_all(&self.pool)
+                .await
+                .unwrap_or_default();
+
+        self.action_xp = action_xp_rows
+            .iter()
+            .filter_map(|ar| {
+                Some(ActionXp {
+                    action_type: ActionTypeEnum::from_id(ar.get("action_type_id"))?,
+                    base_xp: ar.get("base_xp"),
+                    skill: ar
+                        .get::<Option<i16>, _>("skill_id")
+                        .and_then(SkillEnum::from_id),
+                })
+            })
+            .collect();
+
+        let bonus_rows = sqlx::query(
+            "SELECT profession_id, skill_id, bonus_percentage FROM units.profession_skill_bonuses",
+        )
+        .fetch_all(&self.pool)
+        .await
+        .unwrap_or_default();
+
+        self.profession_skill_bonuses = ProfessionSkillBonusesLookup::new();
+        for br in &bonus_rows {
+            let (Some(profession), Some(skill)) = (
+                ProfessionEnum::from_id(br.get("profession_id")),
+                SkillEnum::from_id(br.get("skill_id")),
+            ) else {
+                continue;
+            };
+            self.profession_skill_bonuses.add(ProfessionSkillBonus {
+                profession,
+                skill,
+                bonus_percentage: br.get("bonus_percentage"),
+            });
+        }
+
         // --- Translations ---
         let translation_rows = sqlx::query(
             "SELECT entity_type, entity_id, language_id, field, value FROM game.translations",
@@ -360,6 +499,36 @@ impl GameState {
             .collect()
     }
 
+    /// Natural reserve of a resource in a biome, if the biome has any
+    /// Palier d'établissement correspondant à ce type d'organisation
+    pub fn settlement_tier(&self, organization_type: OrganizationType) -> Option<&SettlementTier> {
+        self.settlement_tiers
+            .iter()
+            .find(|t| t.organization_type == organization_type)
+    }
+
+    /// Palier suivant vers lequel un établissement de ce type peut s'élever
+    pub fn next_settlement_tier(&self, organization_type: OrganizationType) -> Option<&SettlementTier> {
+        self.settlement_tiers
+            .iter()
+            .find(|t| t.previous_type == Some(organization_type))
+    }
+
+    pub fn biome_resource_stock(
+        &self,
+        biome: BiomeTypeEnum,
+        resource_specific_type: ResourceSpecificTypeEnum,
+    ) -> Option<&BiomeResourceStock> {
+        self.biome_resource_stocks
+            .iter()
+            .find(|s| s.biome == biome && s.resource_specific_type == resource_specific_type)
+    }
+
+    /// XP granted for completing an action of this type
+    pub fn action_xp_for(&self, action_type: ActionTypeEnum) -> Option<&ActionXp> {
+        self.action_xp.iter().find(|a| a.action_type == action_type)
+    }
+
     /// Recipe by numeric ID
     pub fn get_recipe(&self, recipe_id: i32) -> Option<&Recipe> {
         self.recipes.iter().find(|r| r.id == recipe_id)
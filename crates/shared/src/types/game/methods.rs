@@ -87,6 +87,24 @@ pub async fn update_last_login(
     Ok(())
 }
 
+// Remplacer le hash du mot de passe
+pub async fn update_password_hash(
+    pool: &PgPool,
+    player_id: i64,
+    password_hash: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE game.players
+         SET password_hash = $2, updated_at = NOW()
+         WHERE id = $1"
+    )
+    .bind(player_id)
+    .bind(password_hash)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 // Récupérer ou créer un joueur
 pub async fn get_or_create_player(
     pool: &PgPool,
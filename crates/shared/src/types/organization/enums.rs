@@ -154,6 +154,14 @@ impl OrganizationType {
             Self::Unknown => OrganizationCategory::Unknown,
         }
     }
+
+    /// Rang féodal d'une organisation territoriale, du hameau (1) à l'empire (9)
+    pub fn territorial_rank(&self) -> Option<i16> {
+        match self.category() {
+            OrganizationCategory::Territorial => Some(self.to_id()),
+            _ => None,
+        }
+    }
 }
 
 // ============================================================================
@@ -330,6 +338,21 @@ impl RoleType {
         }
     }
 
+    /// Head of a territory, from the hamlet's headman up to the emperor
+    pub fn is_territorial_leader(&self) -> bool {
+        matches!(
+            self,
+            Self::Emperor
+                | Self::King
+                | Self::Duke
+                | Self::Count
+                | Self::Baron
+                | Self::Mayor
+                | Self::VillageElder
+                | Self::Headman
+        )
+    }
+
     pub fn authority_level(&self) -> i16 {
         match self {
             Self::Emperor | Self::Pope => 1,
@@ -373,6 +396,30 @@ impl RoleType {
     }
 }
 
+impl RoleType {
+    /// Charges que le chef peut confier à un membre de son organisation
+    pub const APPOINTABLE: [RoleType; 4] = [
+        Self::Steward,
+        Self::Treasurer,
+        Self::Marshal,
+        Self::TaxCollector,
+    ];
+
+    pub fn is_appointable(&self) -> bool {
+        Self::APPOINTABLE.contains(self)
+    }
+
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            Self::Steward => "Intendant",
+            Self::Treasurer => "Trésorier",
+            Self::Marshal => "Maréchal",
+            Self::TaxCollector => "Collecteur",
+            _ => "Officier",
+        }
+    }
+}
+
 // ============================================================================
 // MEMBERSHIP STATUS
 // ============================================================================
@@ -403,6 +450,33 @@ impl MembershipStatus {
     }
 }
 
+// ============================================================================
+// MEMBERSHIP REQUEST KIND
+// ============================================================================
+
+/// Invitation envoyée par l'organisation, ou candidature envoyée par le seigneur
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Encode, Decode)]
+pub enum MembershipRequestKind {
+    Invitation,
+    Application,
+}
+
+impl MembershipRequestKind {
+    pub fn to_string(&self) -> String {
+        match self {
+            Self::Invitation => "invitation".to_string(),
+            Self::Application => "application".to_string(),
+        }
+    }
+
+    pub fn from_string(s: &str) -> Self {
+        match s {
+            "application" => Self::Application,
+            _ => Self::Invitation,
+        }
+    }
+}
+
 // ============================================================================
 // DIPLOMATIC RELATION TYPE
 // ============================================================================
@@ -440,4 +514,21 @@ impl DiplomaticRelationType {
             _ => Self::Neutral,
         }
     }
+
+    pub fn to_name(&self) -> &'static str {
+        match self {
+            Self::Allied => "Alliance",
+            Self::Neutral => "Neutralité",
+            Self::Hostile => "Hostilité",
+            Self::AtWar => "Guerre",
+            Self::TradeAgreement => "Accord commercial",
+            Self::NonAggression => "Pacte de non-agression",
+        }
+    }
+
+    /// Les traités demandent l'accord de l'autre partie ; l'hostilité et la guerre
+    /// sont déclarées dès l'arrivée de l'émissaire
+    pub fn needs_consent(&self) -> bool {
+        !matches!(self, Self::Hostile | Self::AtWar)
+    }
 }
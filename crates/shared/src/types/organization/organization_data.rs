@@ -1,6 +1,9 @@
 use bincode::{Decode, Encode};
 use crate::grid::GridCell;
-use super::enums::{OrganizationType, RoleType, MembershipStatus, DiplomaticRelationType};
+use crate::BuildingTypeEnum;
+use super::enums::{
+    DiplomaticRelationType, MembershipRequestKind, MembershipStatus, OrganizationType, RoleType,
+};
 
 // ============================================================================
 // ORGANIZATION DATA
@@ -76,6 +79,29 @@ pub struct OrganizationMember {
     pub membership_status: MembershipStatus,
 }
 
+/// Membre tel qu'affiché dans le panneau de gestion : le seigneur d'un joueur
+/// et la charge qu'il occupe, s'il en a une
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct OrganizationMemberSummary {
+    pub unit_id: u64,
+    pub name: String,
+    pub is_leader: bool,
+    pub role: Option<RoleType>,
+    pub joined_at: i64,
+}
+
+/// Invitation ou candidature en attente de réponse
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct MembershipRequest {
+    pub id: u64,
+    pub organization_id: u64,
+    pub organization_name: String,
+    pub unit_id: u64,
+    pub unit_name: String,
+    pub kind: MembershipRequestKind,
+    pub created_at: i64,
+}
+
 // ============================================================================
 // ORGANIZATION BUILDING
 // ============================================================================
@@ -118,6 +144,88 @@ pub struct DiplomaticRelation {
     pub expires_at: Option<i64>,
 }
 
+// ============================================================================
+// DIPLOMATIC PROPOSAL
+// ============================================================================
+
+/// Proposition diplomatique remise par un émissaire : l'organisation visée
+/// répond en renvoyant un émissaire
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct DiplomaticProposal {
+    pub id: u64,
+    pub organization_id: u64,
+    pub organization_name: String,
+    pub target_organization_id: u64,
+    pub target_name: String,
+    pub relation_type: DiplomaticRelationType,
+    /// Durée de la relation une fois acceptée (None = permanente)
+    pub duration_days: Option<u32>,
+    pub created_at: i64,
+}
+
+/// Durée des traités proposés depuis le panneau d'actions
+pub const DEFAULT_TREATY_DAYS: u32 = 30;
+
+/// Ce que porte un émissaire jusqu'au siège de l'autre organisation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum EnvoyMission {
+    Propose {
+        relation_type: DiplomaticRelationType,
+        duration_days: Option<u32>,
+    },
+    Accept { proposal_id: u64 },
+    Reject { proposal_id: u64 },
+}
+
+impl EnvoyMission {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Self::Propose { .. } => "propose",
+            Self::Accept { .. } => "accept",
+            Self::Reject { .. } => "reject",
+        }
+    }
+
+    /// Reconstruit la mission depuis les colonnes de `actions.send_envoy_actions`
+    pub fn from_parts(
+        kind: &str,
+        relation_type: Option<&str>,
+        duration_days: Option<u32>,
+        proposal_id: Option<u64>,
+    ) -> Option<Self> {
+        match kind {
+            "propose" => Some(Self::Propose {
+                relation_type: DiplomaticRelationType::from_string(relation_type?),
+                duration_days,
+            }),
+            "accept" => Some(Self::Accept {
+                proposal_id: proposal_id?,
+            }),
+            "reject" => Some(Self::Reject {
+                proposal_id: proposal_id?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+// ============================================================================
+// VASSALAGE OFFER
+// ============================================================================
+
+/// Proposition de vassalité en attente : l'autre partie doit l'accepter
+#[derive(Debug, Clone, Encode, Decode)]
+pub struct VassalageOffer {
+    pub id: u64,
+    pub liege_organization_id: u64,
+    pub liege_name: String,
+    pub vassal_organization_id: u64,
+    pub vassal_name: String,
+    /// Organisation qui a fait la proposition (le suzerain ou le futur vassal)
+    pub offered_by_organization_id: u64,
+    pub created_at: i64,
+}
+
 // ============================================================================
 // CREATE ORGANIZATION REQUEST
 // ============================================================================
@@ -212,6 +320,162 @@ impl OrganizationData {
     }
 }
 
+/// Part du trésor en or qu'un vassal verse chaque jour à son suzerain (en %)
+pub const VASSAL_TRIBUTE_PERCENT: i32 = 10;
+
+/// Tribut dû par un vassal pour la journée
+pub fn tribute_due(vassal_gold: i32) -> i32 {
+    vassal_gold.max(0) * VASSAL_TRIBUTE_PERCENT / 100
+}
+
+impl OrganizationData {
+    /// Un établissement territorial ne prête allégeance qu'à un seigneur
+    /// territorial de rang supérieur (un hameau à une baronnie, une baronnie à un comté...)
+    pub fn can_swear_fealty_to(&self, liege: &OrganizationData) -> bool {
+        if self.id == liege.id || !liege.can_have_vassals() {
+            return false;
+        }
+        match (
+            self.organization_type.territorial_rank(),
+            liege.organization_type.territorial_rank(),
+        ) {
+            (Some(vassal_rank), Some(liege_rank)) => liege_rank > vassal_rank,
+            _ => false,
+        }
+    }
+}
+
+impl OrganizationHierarchy {
+    /// Construit les nœuds de l'arbre à partir des liens (organisation, suzerain)
+    pub fn from_links(links: &[(u64, Option<u64>)]) -> Vec<OrganizationHierarchy> {
+        links
+            .iter()
+            .map(|(organization_id, parent_id)| OrganizationHierarchy {
+                organization_id: *organization_id,
+                parent_id: *parent_id,
+                vassals: links
+                    .iter()
+                    .filter(|(_, parent)| *parent == Some(*organization_id))
+                    .map(|(vassal, _)| *vassal)
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Parcours en profondeur de l'arbre depuis `root` : (organisation, profondeur),
+    /// dans l'ordre d'affichage
+    pub fn walk(root: u64, hierarchy: &[OrganizationHierarchy]) -> Vec<(u64, usize)> {
+        let mut order = Vec::new();
+        let mut stack = vec![(root, 0)];
+        while let Some((id, depth)) = stack.pop() {
+            // Un arbre corrompu (cycle) ne doit pas boucler
+            if order.iter().any(|(seen, _)| *seen == id) {
+                continue;
+            }
+            order.push((id, depth));
+            if let Some(node) = hierarchy.iter().find(|node| node.organization_id == id) {
+                for vassal in node.vassals.iter().rev() {
+                    stack.push((*vassal, depth + 1));
+                }
+            }
+        }
+        order
+    }
+}
+
+/// Autorité minimale (1 = la plus haute) d'un officier pour puiser dans le trésor :
+/// trésoriers, intendants, maires et anciens, pas les collecteurs ni les sergents
+pub const TREASURY_WITHDRAW_AUTHORITY: i16 = 30;
+
+/// Droits délégués aux officiers selon leur niveau d'autorité ; le chef les a tous
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Encode, Decode)]
+pub enum OrganizationPermission {
+    /// Puiser dans le trésor
+    WithdrawTreasury,
+    /// Revendiquer des cellules et élever l'établissement
+    ManageTerritory,
+    /// Inviter, accepter et exclure des membres
+    ManageMembers,
+}
+
+impl OrganizationPermission {
+    pub const ALL: [OrganizationPermission; 3] = [
+        Self::WithdrawTreasury,
+        Self::ManageTerritory,
+        Self::ManageMembers,
+    ];
+
+    /// Autorité minimale (1 = la plus haute) d'un officier pour exercer ce droit :
+    /// le trésorier puise, l'intendant et le maréchal gèrent aussi le territoire
+    /// et les membres. Tout membre peut déposer au trésor.
+    pub fn required_authority(&self) -> i16 {
+        match self {
+            Self::WithdrawTreasury => TREASURY_WITHDRAW_AUTHORITY,
+            Self::ManageTerritory | Self::ManageMembers => RoleType::Steward.authority_level(),
+        }
+    }
+}
+
+/// Prix d'une cellule revendiquée, payé par le trésor quand la revendication aboutit
+pub const CLAIM_CELL_GOLD_COST: i32 = 25;
+
+impl OrganizationData {
+    /// Le chef de l'organisation a tous les droits ; un officier ceux de son rang
+    pub fn has_permission(
+        &self,
+        unit_id: u64,
+        officers: &[OrganizationOfficer],
+        permission: OrganizationPermission,
+    ) -> bool {
+        self.leader_unit_id == Some(unit_id)
+            || officers.iter().any(|officer| {
+                officer.unit_id == unit_id
+                    && officer.authority_level() <= permission.required_authority()
+            })
+    }
+
+    /// Le chef passe avant tout le monde ; sinon, il faut une charge strictement plus
+    /// haute (niveau d'autorité plus petit) que la meilleure charge de la cible
+    pub fn outranks(
+        &self,
+        unit_id: u64,
+        target_unit_id: u64,
+        officers: &[OrganizationOfficer],
+    ) -> bool {
+        if self.leader_unit_id == Some(unit_id) {
+            return true;
+        }
+        let best_authority = |id: u64| {
+            officers
+                .iter()
+                .filter(|officer| officer.unit_id == id)
+                .map(|officer| officer.authority_level())
+                .min()
+        };
+        match (best_authority(unit_id), best_authority(target_unit_id)) {
+            (Some(_), None) => true,
+            (Some(own), Some(target)) => own < target,
+            (None, _) => false,
+        }
+    }
+
+    /// Le chef de l'organisation, ou un officier de rang suffisant, peut puiser dans le trésor
+    pub fn can_withdraw_from_treasury(&self, unit_id: u64, officers: &[OrganizationOfficer]) -> bool {
+        self.has_permission(unit_id, officers, OrganizationPermission::WithdrawTreasury)
+    }
+
+    pub fn summary(&self) -> OrganizationSummary {
+        OrganizationSummary {
+            id: self.id,
+            name: self.name.clone(),
+            organization_type: self.organization_type,
+            leader_unit_id: self.leader_unit_id,
+            population: self.population,
+            emblem_url: self.emblem_url.clone(),
+        }
+    }
+}
+
 impl OrganizationOfficer {
     /// Get authority level of this officer
     pub fn authority_level(&self) -> i16 {
@@ -261,6 +525,82 @@ impl DiplomaticRelation {
             false
         }
     }
+
+    /// Date d'expiration d'une relation conclue à `established_at` pour `duration_days` jours
+    pub fn expiry(established_at: i64, duration_days: Option<u32>) -> Option<i64> {
+        duration_days.map(|days| established_at + days as i64 * 86_400)
+    }
+}
+
+// ============================================================================
+// SETTLEMENT TIERS
+// ============================================================================
+
+/// Palier d'un établissement (hameau, village, bourg, cité) et conditions pour l'atteindre.
+/// Chargé depuis `organizations.settlement_tiers` (données du game_seed)
+#[derive(Debug, Clone)]
+pub struct SettlementTier {
+    pub organization_type: OrganizationType,
+    /// Palier dont on s'élève vers celui-ci (None pour le hameau, que l'on fonde)
+    pub previous_type: Option<OrganizationType>,
+    /// Titre du chef à ce palier (Headman, VillageElder, Mayor)
+    pub leader_role: RoleType,
+    /// Préfixe du nom par défaut ("Hameau de ...", "Village de ...")
+    pub name_prefix: String,
+    /// Rayon en cellules revendiqué autour du siège
+    pub claim_radius: u32,
+    pub min_population: i32,
+    pub min_territory_cells: i32,
+    pub required_buildings: Vec<BuildingTypeEnum>,
+    /// Or prélevé dans le trésor pour la charte
+    pub cost_gold: i32,
+}
+
+/// Ce que réunit un établissement au moment où il demande à s'élever
+#[derive(Debug, Clone, Default)]
+pub struct SettlementStats {
+    pub population: i32,
+    pub territory_cells: i32,
+    /// Types des bâtiments achevés sur le territoire
+    pub buildings: Vec<BuildingTypeEnum>,
+    pub treasury_gold: i32,
+}
+
+impl SettlementTier {
+    /// Conditions non remplies, en clair pour le joueur (vide si l'élévation est possible)
+    pub fn missing_requirements(&self, stats: &SettlementStats) -> Vec<String> {
+        let mut missing = Vec::new();
+        if stats.population < self.min_population {
+            missing.push(format!(
+                "population {}/{}",
+                stats.population, self.min_population
+            ));
+        }
+        if stats.territory_cells < self.min_territory_cells {
+            missing.push(format!(
+                "territoire {}/{} cellules",
+                stats.territory_cells, self.min_territory_cells
+            ));
+        }
+        for building in &self.required_buildings {
+            if !stats.buildings.contains(building) {
+                missing.push(format!("bâtiment manquant : {:?}", building));
+            }
+        }
+        if stats.treasury_gold < self.cost_gold {
+            missing.push(format!("or {}/{}", stats.treasury_gold, self.cost_gold));
+        }
+        missing
+    }
+
+    /// Nom que prend l'établissement à ce palier : seul un nom par défaut
+    /// ("Hameau de X") est renommé, un nom choisi par le joueur est gardé
+    pub fn rename(&self, name: &str, previous: &SettlementTier) -> String {
+        match name.strip_prefix(&format!("{} ", previous.name_prefix)) {
+            Some(rest) => format!("{} {}", self.name_prefix, rest),
+            None => name.to_string(),
+        }
+    }
 }
 
 impl super::enums::OrganizationCategory {
@@ -274,3 +614,204 @@ impl super::enums::OrganizationCategory {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn officer(unit_id: u64, role: RoleType) -> OrganizationOfficer {
+        OrganizationOfficer {
+            id: unit_id,
+            organization_id: 1,
+            unit_id,
+            role,
+            appointed_at: 0,
+            appointed_by_unit_id: None,
+        }
+    }
+
+    #[test]
+    fn test_treasury_withdraw_requires_leader_or_senior_officer() {
+        let org = OrganizationData {
+            id: 1,
+            name: "Hameau".to_string(),
+            organization_type: OrganizationType::Hamlet,
+            parent_organization_id: None,
+            headquarters_cell: None,
+            total_area_km2: 0.0,
+            treasury_gold: 0,
+            leader_unit_id: Some(10),
+            emblem_url: None,
+            population: 1,
+            created_at: 0,
+            updated_at: 0,
+        };
+        let officers = vec![officer(11, RoleType::Treasurer), officer(12, RoleType::TaxCollector)];
+
+        assert!(org.can_withdraw_from_treasury(10, &officers));
+        assert!(org.can_withdraw_from_treasury(11, &officers));
+        assert!(!org.can_withdraw_from_treasury(12, &officers));
+        assert!(!org.can_withdraw_from_treasury(13, &officers));
+    }
+
+    #[test]
+    fn test_officer_permissions_follow_authority_level() {
+        let org = organization(1, OrganizationType::Hamlet);
+        let officers = vec![
+            officer(11, RoleType::Steward),
+            officer(12, RoleType::Treasurer),
+            officer(13, RoleType::TaxCollector),
+        ];
+        let allowed = |unit_id| {
+            OrganizationPermission::ALL
+                .into_iter()
+                .filter(|permission| org.has_permission(unit_id, &officers, *permission))
+                .count()
+        };
+
+        assert_eq!(allowed(11), 3);
+        assert!(org.has_permission(12, &officers, OrganizationPermission::WithdrawTreasury));
+        assert!(!org.has_permission(12, &officers, OrganizationPermission::ManageMembers));
+        assert!(!org.has_permission(13, &officers, OrganizationPermission::WithdrawTreasury));
+        assert_eq!(allowed(14), 0);
+    }
+
+    #[test]
+    fn test_only_higher_officers_outrank() {
+        let mut org = organization(1, OrganizationType::Hamlet);
+        org.leader_unit_id = Some(10);
+        let officers = vec![
+            officer(11, RoleType::Steward),
+            officer(12, RoleType::Steward),
+            officer(13, RoleType::TaxCollector),
+        ];
+
+        assert!(org.outranks(10, 11, &officers));
+        assert!(org.outranks(11, 13, &officers));
+        assert!(org.outranks(11, 14, &officers));
+        assert!(!org.outranks(11, 12, &officers));
+        assert!(!org.outranks(13, 11, &officers));
+        assert!(!org.outranks(14, 15, &officers));
+    }
+
+    fn organization(id: u64, organization_type: OrganizationType) -> OrganizationData {
+        OrganizationData {
+            id,
+            name: format!("{:?}", organization_type),
+            organization_type,
+            parent_organization_id: None,
+            headquarters_cell: None,
+            total_area_km2: 0.0,
+            treasury_gold: 0,
+            leader_unit_id: None,
+            emblem_url: None,
+            population: 1,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_fealty_goes_up_the_territorial_ranks() {
+        let hamlet = organization(1, OrganizationType::Hamlet);
+        let village = organization(2, OrganizationType::Village);
+        let barony = organization(3, OrganizationType::Barony);
+        let guild = organization(4, OrganizationType::MerchantGuild);
+
+        assert!(hamlet.can_swear_fealty_to(&barony));
+        assert!(!barony.can_swear_fealty_to(&hamlet));
+        // Un village ne peut pas avoir de vassaux
+        assert!(!hamlet.can_swear_fealty_to(&village));
+        // Ni guilde, ni soi-même
+        assert!(!hamlet.can_swear_fealty_to(&guild));
+        assert!(!barony.can_swear_fealty_to(&barony));
+
+        assert_eq!(tribute_due(250), 25);
+        assert_eq!(tribute_due(5), 0);
+    }
+
+    #[test]
+    fn test_diplomatic_relation_expires_after_its_duration() {
+        let established_at = 1_000;
+        let relation = DiplomaticRelation {
+            id: 1,
+            organization_id: 1,
+            target_organization_id: 2,
+            relation_type: DiplomaticRelationType::TradeAgreement,
+            established_at,
+            established_by_unit_id: None,
+            expires_at: DiplomaticRelation::expiry(established_at, Some(2)),
+        };
+        assert!(!relation.is_expired(established_at + 86_400));
+        assert!(relation.is_expired(established_at + 2 * 86_400));
+
+        let permanent = DiplomaticRelation {
+            expires_at: DiplomaticRelation::expiry(established_at, None),
+            ..relation
+        };
+        assert!(!permanent.is_expired(i64::MAX));
+
+        // Une mission relue depuis la base redonne la même mission
+        let mission = EnvoyMission::Propose {
+            relation_type: DiplomaticRelationType::NonAggression,
+            duration_days: Some(30),
+        };
+        assert_eq!(
+            EnvoyMission::from_parts(mission.kind(), Some("non_aggression"), Some(30), None),
+            Some(mission)
+        );
+        assert_eq!(EnvoyMission::from_parts("accept", None, None, None), None);
+    }
+
+    #[test]
+    fn test_hierarchy_walk_is_depth_first() {
+        let hierarchy = OrganizationHierarchy::from_links(&[
+            (1, None),
+            (2, Some(1)),
+            (3, Some(1)),
+            (4, Some(2)),
+        ]);
+        assert_eq!(hierarchy[0].vassals, vec![2, 3]);
+        assert_eq!(hierarchy[1].parent_id, Some(1));
+
+        assert_eq!(
+            OrganizationHierarchy::walk(1, &hierarchy),
+            vec![(1, 0), (2, 1), (4, 2), (3, 1)]
+        );
+    }
+
+    fn tier(organization_type: OrganizationType, prefix: &str) -> SettlementTier {
+        SettlementTier {
+            organization_type,
+            previous_type: None,
+            leader_role: RoleType::VillageElder,
+            name_prefix: prefix.to_string(),
+            claim_radius: 2,
+            min_population: 10,
+            min_territory_cells: 7,
+            required_buildings: vec![BuildingTypeEnum::Market],
+            cost_gold: 200,
+        }
+    }
+
+    #[test]
+    fn test_settlement_tier_requirements_and_name() {
+        let hamlet = tier(OrganizationType::Hamlet, "Hameau");
+        let village = tier(OrganizationType::Village, "Village");
+
+        let mut stats = SettlementStats {
+            population: 12,
+            territory_cells: 5,
+            buildings: vec![BuildingTypeEnum::Farm],
+            treasury_gold: 300,
+        };
+        assert_eq!(village.missing_requirements(&stats).len(), 2);
+
+        stats.territory_cells = 7;
+        stats.buildings.push(BuildingTypeEnum::Market);
+        assert!(village.missing_requirements(&stats).is_empty());
+
+        assert_eq!(village.rename("Hameau de Dubois", &hamlet), "Village de Dubois");
+        assert_eq!(village.rename("Bourg-la-Reine", &hamlet), "Bourg-la-Reine");
+    }
+}
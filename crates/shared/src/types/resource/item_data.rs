@@ -1,7 +1,7 @@
 use bincode::{Decode, Encode};
 use std::collections::HashMap;
 
-use crate::{EquipmentSlotEnum, ItemTypeEnum, SkillEnum};
+use crate::{EquipmentSlotEnum, ItemTypeEnum, SkillEnum, grid::GridCell};
 
 use super::{ResourceCategoryEnum};
 
@@ -114,6 +114,14 @@ pub struct WorldPosition {
     pub chunk_y: i32,
 }
 
+/// Objets posés au sol sur une cellule, regroupés par item
+#[derive(Debug, Clone, Copy, PartialEq, Encode, Decode)]
+pub struct GroundItem {
+    pub cell: GridCell,
+    pub item_id: i32,
+    pub quantity: i32,
+}
+
 impl ItemInstance {
     pub fn new(item_id: i32, quality: f32) -> Self {
         Self {
@@ -1,6 +1,6 @@
 use bincode::{Decode, Encode};
 
-use crate::{ResourceCategoryEnum, ResourceSpecificTypeEnum, ResourceType};
+use crate::{ResourceCategoryEnum, ResourceSpecificTypeEnum, ResourceType, grid::GridCell};
 
 
 pub trait ResourceSpecificData: Clone {
@@ -176,3 +176,52 @@ pub struct ResourceData {
     pub base_data: ResourceBaseData,
     pub specific_data: ResourceSpecific,
 }
+
+/// Réserve d'une ressource naturelle sur une cellule (bois d'un arbre, minerai du sol...)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub struct CellResourceStock {
+    pub cell: GridCell,
+    pub resource_specific_type: ResourceSpecificTypeEnum,
+    pub stock: i32,
+    pub max_stock: i32,
+}
+
+impl CellResourceStock {
+    /// Nombre de niveaux visibles d'une réserve pleine
+    pub const LEVELS: i32 = 4;
+
+    pub fn is_depleted(&self) -> bool {
+        self.stock <= 0
+    }
+
+    /// Niveau affiché : 0 quand la réserve est épuisée, `LEVELS` quand elle est pleine.
+    /// Les clients ne sont prévenus que lorsque ce niveau change.
+    pub fn level(&self) -> i32 {
+        if self.stock <= 0 || self.max_stock <= 0 {
+            return 0;
+        }
+        let stock = self.stock.min(self.max_stock);
+        ((stock * Self::LEVELS + self.max_stock - 1) / self.max_stock).clamp(1, Self::LEVELS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stock_level_only_reaches_zero_when_depleted() {
+        let stock = |stock| CellResourceStock {
+            cell: GridCell { q: 0, r: 0 },
+            resource_specific_type: ResourceSpecificTypeEnum::Wood,
+            stock,
+            max_stock: 40,
+        };
+        assert_eq!(stock(40).level(), 4);
+        assert_eq!(stock(31).level(), 4);
+        assert_eq!(stock(30).level(), 3);
+        assert_eq!(stock(1).level(), 1);
+        assert_eq!(stock(0).level(), 0);
+        assert!(stock(0).is_depleted());
+    }
+}
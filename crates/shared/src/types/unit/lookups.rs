@@ -2,6 +2,7 @@ use bincode::{Decode, Encode};
 use std::collections::HashMap;
 
 use super::{EquipmentSlotEnum, ItemTypeEnum, ProfessionEnum, SkillEnum};
+use crate::ItemDefinition;
 
 // ============ PROFESSION DATA (Données de profession depuis DB) ============
 #[derive(Debug, Clone, Encode, Decode)]
@@ -63,6 +64,21 @@ impl ItemData {
     }
 }
 
+impl From<&ItemDefinition> for ItemData {
+    fn from(definition: &ItemDefinition) -> Self {
+        Self {
+            id: definition.id,
+            name: definition.name.clone(),
+            item_type: definition.item_type,
+            description: definition.description.clone(),
+            weight_kg: definition.weight_kg,
+            is_equipable: definition.is_equipable,
+            equipment_slot: definition.equipment_slot,
+            stat_modifiers: definition.stat_modifiers.clone(),
+        }
+    }
+}
+
 // ============ LOOKUP TABLES (Caches côté client/serveur) ============
 
 /// Cache de toutes les professions
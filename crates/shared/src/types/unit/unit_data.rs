@@ -2,7 +2,7 @@ use bincode::{Decode, Encode};
 use std::collections::HashMap;
 
 use crate::grid::GridCell;
-use crate::TerrainChunkId;
+use crate::{ResourceSpecificTypeEnum, TerrainChunkId};
 
 use super::{EquipmentSlotEnum, ProfessionEnum, SkillEnum};
 
@@ -175,6 +175,15 @@ impl UnitSkill {
         }
     }
 
+    /// Ajoute de l'XP et recalcule le niveau. Renvoie vrai si le niveau a augmenté
+    pub fn gain_xp(&mut self, xp: i64, curve: &crate::SkillCurve) -> bool {
+        self.xp += xp;
+        let level = curve.level_for_xp(self.xp).max(self.level);
+        let leveled_up = level > self.level;
+        self.level = level;
+        leveled_up
+    }
+
     /// Calcule le skill effectif avec les bonus
     /// base_skill = level du skill
     /// stat_bonus = bonus de la statistique principale
@@ -209,6 +218,79 @@ pub struct AutomatedAction {
     pub parameters: HashMap<String, String>, // Paramètres JSON-like
 }
 
+/// Stock au-delà duquel une routine s'arrête, si le joueur n'a rien précisé
+pub const DEFAULT_ROUTINE_MAX_STOCK: i32 = 20;
+
+/// Routine d'une unité PNJ, lue depuis une `AutomatedAction`.
+/// Elle s'exécute là où se trouve l'unité.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Routine {
+    /// Récolter tant que le stock du produit reste sous `max_stock`
+    Harvest {
+        resource_specific_type: ResourceSpecificTypeEnum,
+        max_stock: i32,
+    },
+    /// Fabriquer dès que les ingrédients sont disponibles, tant que le stock reste sous `max_stock`
+    Craft { recipe_id: String, max_stock: i32 },
+}
+
+impl Routine {
+    pub const HARVEST: &'static str = "auto_harvest";
+    pub const CRAFT: &'static str = "auto_craft";
+
+    /// Interprète une action automatisée ; `None` si le type ou les paramètres sont inconnus
+    pub fn from_automated_action(action: &AutomatedAction) -> Option<Self> {
+        let max_stock = action
+            .parameters
+            .get("max_stock")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_ROUTINE_MAX_STOCK);
+        match action.action_type.as_str() {
+            Self::HARVEST => {
+                let id = action.parameters.get("resource_specific_type_id")?.parse().ok()?;
+                Some(Self::Harvest {
+                    resource_specific_type: ResourceSpecificTypeEnum::from_id(id)?,
+                    max_stock,
+                })
+            }
+            Self::CRAFT => Some(Self::Craft {
+                recipe_id: action.parameters.get("recipe_id")?.clone(),
+                max_stock,
+            }),
+            _ => None,
+        }
+    }
+
+    pub fn to_automated_action(&self, id: u64, is_enabled: bool) -> AutomatedAction {
+        let (action_type, key, value) = match self {
+            Self::Harvest {
+                resource_specific_type,
+                ..
+            } => (
+                Self::HARVEST,
+                "resource_specific_type_id",
+                resource_specific_type.to_id().to_string(),
+            ),
+            Self::Craft { recipe_id, .. } => (Self::CRAFT, "recipe_id", recipe_id.clone()),
+        };
+        AutomatedAction {
+            id,
+            action_type: action_type.to_string(),
+            is_enabled,
+            parameters: HashMap::from([
+                (key.to_string(), value),
+                ("max_stock".to_string(), self.max_stock().to_string()),
+            ]),
+        }
+    }
+
+    pub fn max_stock(&self) -> i32 {
+        match self {
+            Self::Harvest { max_stock, .. } | Self::Craft { max_stock, .. } => *max_stock,
+        }
+    }
+}
+
 // ============ CONSUMPTION DEMAND (Demande de consommation) ============
 #[derive(Debug, Clone, Encode, Decode)]
 pub struct ConsumptionDemand {
@@ -280,3 +362,34 @@ impl FullUnitData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_routine_round_trips_through_automated_action() {
+        let routine = Routine::Harvest {
+            resource_specific_type: ResourceSpecificTypeEnum::Wood,
+            max_stock: 40,
+        };
+        let action = routine.to_automated_action(7, true);
+        assert_eq!(action.action_type, Routine::HARVEST);
+        assert_eq!(Routine::from_automated_action(&action), Some(routine));
+
+        // Sans max_stock, la valeur par défaut s'applique
+        let mut craft = Routine::Craft {
+            recipe_id: "bread".to_string(),
+            max_stock: 1,
+        }
+        .to_automated_action(8, false);
+        craft.parameters.remove("max_stock");
+        assert_eq!(
+            Routine::from_automated_action(&craft).map(|r| r.max_stock()),
+            Some(DEFAULT_ROUTINE_MAX_STOCK)
+        );
+
+        craft.action_type = "auto_dance".to_string();
+        assert_eq!(Routine::from_automated_action(&craft), None);
+    }
+}
@@ -0,0 +1,22 @@
+-- Migration: login brute-force protection
+-- Per-account failure counter and lockout on game.players,
+-- audit trail of every password login attempt (also used for per-IP throttling).
+
+ALTER TABLE game.players
+    ADD COLUMN IF NOT EXISTS failed_login_count integer NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS locked_until timestamp with time zone;
+
+CREATE TABLE IF NOT EXISTS game.login_attempts (
+    id bigserial PRIMARY KEY,
+    player_id bigint REFERENCES game.players(id) ON DELETE SET NULL,
+    family_name character varying NOT NULL,
+    ip_address character varying(64) NOT NULL,
+    outcome character varying(32) NOT NULL,
+    attempted_at timestamp with time zone NOT NULL DEFAULT now(),
+    CONSTRAINT login_attempts_outcome_check CHECK (outcome IN ('success', 'invalid_password', 'unknown_account', 'locked'))
+);
+
+CREATE INDEX IF NOT EXISTS login_attempts_ip_time_idx
+    ON game.login_attempts (ip_address, attempted_at);
+CREATE INDEX IF NOT EXISTS login_attempts_player_time_idx
+    ON game.login_attempts (player_id, attempted_at);
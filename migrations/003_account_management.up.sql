@@ -0,0 +1,26 @@
+-- Migration: password reset codes and account deletion
+-- One-time reset codes issued by an administrator (only the Argon2 hash is stored),
+-- and a real foreign key from player units to their player so that deleting
+-- an account removes its units (and, through them, memberships and offices).
+
+CREATE TABLE IF NOT EXISTS game.password_reset_codes (
+    id bigserial PRIMARY KEY,
+    player_id bigint NOT NULL REFERENCES game.players(id) ON DELETE CASCADE,
+    code_hash character varying(255) NOT NULL,
+    expires_at timestamp with time zone NOT NULL,
+    used_at timestamp with time zone,
+    created_at timestamp with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS password_reset_codes_player_idx
+    ON game.password_reset_codes (player_id) WHERE used_at IS NULL;
+
+-- Units whose player no longer exists become NPCs
+UPDATE units.units
+SET player_id = NULL
+WHERE player_id IS NOT NULL
+  AND player_id NOT IN (SELECT id FROM game.players);
+
+ALTER TABLE units.units
+    ADD CONSTRAINT units_player_id_fkey FOREIGN KEY (player_id)
+    REFERENCES game.players(id) ON DELETE CASCADE;
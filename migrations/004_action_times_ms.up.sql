@@ -0,0 +1,8 @@
+-- Migration: scheduled action timestamps in milliseconds
+-- start_time / completion_time were Unix seconds (completion truncated to the second),
+-- the action processor now schedules to the millisecond.
+
+UPDATE actions.scheduled_actions
+SET start_time = start_time * 1000,
+    completion_time = start_time * 1000 + duration_ms
+WHERE start_time < 100000000000; -- encore en secondes (avant l'an 5000)
@@ -0,0 +1,18 @@
+-- Migration: cancelling and pausing actions
+-- New statuses, remaining time of paused actions, and the materials taken
+-- from an inventory when an action is scheduled (refunded on cancellation).
+
+INSERT INTO actions.action_statuses (id, name)
+VALUES (5, 'Cancelled'), (6, 'Paused')
+ON CONFLICT (id) DO NOTHING;
+
+ALTER TABLE actions.scheduled_actions
+    ADD COLUMN IF NOT EXISTS paused_remaining_ms bigint;
+
+CREATE TABLE IF NOT EXISTS actions.action_reservations (
+    action_id bigint NOT NULL REFERENCES actions.scheduled_actions(id) ON DELETE CASCADE,
+    unit_id bigint NOT NULL,
+    item_id integer NOT NULL REFERENCES resources.items(id),
+    quantity integer NOT NULL CHECK (quantity > 0),
+    PRIMARY KEY (action_id, unit_id, item_id)
+);
@@ -0,0 +1,14 @@
+-- Migration: per-unit action queues
+-- Ordered actions waiting for a unit to become idle. The action itself is the
+-- bincode-encoded shared::protocol::QueuedAction.
+
+CREATE TABLE IF NOT EXISTS actions.unit_action_queue (
+    id bigserial PRIMARY KEY,
+    unit_id bigint NOT NULL REFERENCES units.units(id) ON DELETE CASCADE,
+    position integer NOT NULL,
+    action_data bytea NOT NULL,
+    created_at timestamp with time zone NOT NULL DEFAULT now()
+);
+
+CREATE INDEX IF NOT EXISTS unit_action_queue_unit_position_idx
+    ON actions.unit_action_queue (unit_id, position);
@@ -0,0 +1,20 @@
+-- Migration: skill experience
+-- XP curve (total XP needed for each level) and XP granted per completed action,
+-- both filled by tools/game_seed from data/progression.json. A harvest trains the
+-- skill of its yield; a craft the skill required by its recipe.
+
+CREATE TABLE IF NOT EXISTS units.skill_levels (
+    level       INT PRIMARY KEY,
+    xp_required BIGINT NOT NULL,
+    CONSTRAINT chk_skill_level_xp CHECK (xp_required >= 0)
+);
+
+CREATE TABLE IF NOT EXISTS units.action_xp (
+    action_type_id SMALLINT PRIMARY KEY,
+    base_xp        INT NOT NULL,
+    skill_id       SMALLINT REFERENCES units.skills(id),
+    CONSTRAINT chk_action_xp CHECK (base_xp > 0)
+);
+
+ALTER TABLE resources.harvest_yields
+    ADD COLUMN IF NOT EXISTS skill_id SMALLINT REFERENCES units.skills(id);
@@ -0,0 +1,7 @@
+-- Migration: quality of reserved materials
+-- Ingredients taken from an inventory when a craft is scheduled keep their
+-- (average) quality, so the crafted result can inherit it and a refund gives
+-- back items of the same quality.
+
+ALTER TABLE actions.action_reservations
+    ADD COLUMN IF NOT EXISTS quality real NOT NULL DEFAULT 1.0;
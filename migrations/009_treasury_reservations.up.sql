@@ -0,0 +1,13 @@
+-- Migration: materials reserved from an organization's treasury
+-- Construction and crafting on an organization's territory may draw their
+-- materials from its storehouse. What is taken is recorded per organization,
+-- so a cancelled action gives it back to the treasury instead of a unit.
+
+CREATE TABLE IF NOT EXISTS actions.action_treasury_reservations (
+    action_id bigint NOT NULL REFERENCES actions.scheduled_actions(id) ON DELETE CASCADE,
+    organization_id bigint NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE,
+    item_id integer NOT NULL REFERENCES resources.items(id),
+    quantity integer NOT NULL CHECK (quantity > 0),
+    quality real NOT NULL DEFAULT 1.0,
+    PRIMARY KEY (action_id, organization_id, item_id)
+);
@@ -0,0 +1,16 @@
+-- Migration: villager needs
+-- Daily needs given to every villager (non-lord unit), filled by tools/game_seed
+-- from data/needs.json and copied into units.unit_consumption_demands by the
+-- consumption tick. A villager counts the days spent without its vital needs;
+-- after a while it leaves the settlement or starves to death.
+
+CREATE TABLE IF NOT EXISTS units.default_consumption_demands (
+    item_id          INT PRIMARY KEY REFERENCES resources.items(id) ON DELETE CASCADE,
+    quantity_per_day DECIMAL(10,3) NOT NULL,
+    priority         INT NOT NULL DEFAULT 5,
+    CONSTRAINT chk_default_demand_quantity CHECK (quantity_per_day > 0),
+    CONSTRAINT chk_default_demand_priority CHECK (priority BETWEEN 1 AND 10)
+);
+
+ALTER TABLE units.units
+    ADD COLUMN IF NOT EXISTS starving_days INT NOT NULL DEFAULT 0;
@@ -0,0 +1,33 @@
+-- Migration: finite natural resources
+-- Every harvestable cell holds a reserve per resource: wood on tree cells
+-- (scaled by the tree density), ore and minerals from the cell's biome.
+-- Reserves are seeded at chunk generation, or on the first harvest for chunks
+-- generated before this migration. Harvesting draws from them, a tree with no
+-- wood left is felled, and reserves regrow every day at their biome's rate.
+-- resources.biome_resource_stocks is filled by tools/game_seed from
+-- data/resource_stocks.json.
+
+CREATE TABLE IF NOT EXISTS resources.biome_resource_stocks (
+    biome_id                  SMALLINT NOT NULL REFERENCES terrain.biome_types(id) ON DELETE CASCADE,
+    resource_specific_type_id SMALLINT NOT NULL REFERENCES resources.resource_specific_types(id) ON DELETE CASCADE,
+    max_stock                 INT NOT NULL,
+    regrowth_per_day          INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (biome_id, resource_specific_type_id),
+    CONSTRAINT chk_biome_stock_max CHECK (max_stock > 0),
+    CONSTRAINT chk_biome_stock_regrowth CHECK (regrowth_per_day >= 0)
+);
+
+CREATE TABLE IF NOT EXISTS resources.cell_resource_stocks (
+    cell_q                    INT NOT NULL,
+    cell_r                    INT NOT NULL,
+    chunk_x                   INT NOT NULL,
+    chunk_y                   INT NOT NULL,
+    resource_specific_type_id SMALLINT NOT NULL REFERENCES resources.resource_specific_types(id) ON DELETE CASCADE,
+    stock                     INT NOT NULL,
+    max_stock                 INT NOT NULL,
+    PRIMARY KEY (cell_q, cell_r, resource_specific_type_id),
+    CONSTRAINT chk_cell_stock CHECK (stock BETWEEN 0 AND max_stock)
+);
+
+CREATE INDEX IF NOT EXISTS idx_cell_resource_stocks_chunk
+    ON resources.cell_resource_stocks (chunk_x, chunk_y);
@@ -0,0 +1,25 @@
+-- Migration: settlement growth
+-- A hamlet rises to village, town then city once it has enough population,
+-- territory, key buildings and gold in its treasury. Each tier names the
+-- title of the leader (Headman, VillageElder, Mayor) and how far around the
+-- headquarters the settlement claims land.
+-- Both tables are filled by tools/game_seed from data/settlements.json.
+
+CREATE TABLE IF NOT EXISTS organizations.settlement_tiers (
+    organization_type_id SMALLINT PRIMARY KEY REFERENCES organizations.organization_types(id) ON DELETE CASCADE,
+    previous_type_id     SMALLINT UNIQUE REFERENCES organizations.organization_types(id) ON DELETE CASCADE,
+    leader_role_type_id  SMALLINT NOT NULL REFERENCES organizations.role_types(id),
+    name_prefix          VARCHAR(50) NOT NULL,
+    claim_radius         SMALLINT NOT NULL DEFAULT 1,
+    min_population       INT NOT NULL DEFAULT 0,
+    min_territory_cells  INT NOT NULL DEFAULT 0,
+    cost_gold            INT NOT NULL DEFAULT 0,
+    CONSTRAINT chk_settlement_tier_radius CHECK (claim_radius >= 0),
+    CONSTRAINT chk_settlement_tier_cost CHECK (cost_gold >= 0)
+);
+
+CREATE TABLE IF NOT EXISTS organizations.settlement_tier_buildings (
+    organization_type_id SMALLINT NOT NULL REFERENCES organizations.settlement_tiers(organization_type_id) ON DELETE CASCADE,
+    building_type_id     INT NOT NULL REFERENCES buildings.building_types(id) ON DELETE CASCADE,
+    PRIMARY KEY (organization_type_id, building_type_id)
+);
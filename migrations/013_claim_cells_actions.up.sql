@@ -0,0 +1,27 @@
+-- Migration: territory expansion
+-- An organization leader can extend the territory onto adjacent unclaimed
+-- cells with a ClaimCells action. Cells are only added to
+-- organizations.territory_cells when the action completes; until then they
+-- are reserved here so two pending claims cannot target the same cell.
+
+INSERT INTO actions.action_types (id, name) VALUES (8, 'ClaimCells')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO actions.action_specific_types (id, name) VALUES (8, 'ClaimCells')
+ON CONFLICT DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS actions.claim_cells_actions (
+    action_id       BIGINT PRIMARY KEY REFERENCES actions.scheduled_actions(id) ON DELETE CASCADE,
+    unit_id         BIGINT NOT NULL,
+    organization_id BIGINT NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS actions.claim_cells_action_cells (
+    action_id BIGINT NOT NULL REFERENCES actions.claim_cells_actions(action_id) ON DELETE CASCADE,
+    cell_q    INT NOT NULL,
+    cell_r    INT NOT NULL,
+    PRIMARY KEY (action_id, cell_q, cell_r)
+);
+
+CREATE INDEX IF NOT EXISTS idx_claim_cells_action_cells_cell
+    ON actions.claim_cells_action_cells(cell_q, cell_r);
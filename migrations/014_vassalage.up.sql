@@ -0,0 +1,31 @@
+-- Migration: vassalage
+-- A territorial organization can swear fealty to a liege of higher rank (a
+-- hamlet to a barony, a barony to a county...). One side proposes, the other
+-- accepts; the bond is organizations.parent_organization_id. Either side may
+-- revoke it later. Vassals pay a daily tribute in gold to their liege.
+
+CREATE TABLE IF NOT EXISTS organizations.vassalage_offers (
+    id                         BIGSERIAL PRIMARY KEY,
+    liege_organization_id      BIGINT NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE,
+    vassal_organization_id     BIGINT NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE,
+    offered_by_organization_id BIGINT NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE,
+    created_at                 TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT uq_vassalage_offer UNIQUE (liege_organization_id, vassal_organization_id),
+    CONSTRAINT chk_vassalage_offer_parties CHECK (liege_organization_id <> vassal_organization_id),
+    CONSTRAINT chk_vassalage_offer_author CHECK (
+        offered_by_organization_id IN (liege_organization_id, vassal_organization_id)
+    )
+);
+
+CREATE INDEX IF NOT EXISTS idx_vassalage_offers_vassal
+    ON organizations.vassalage_offers(vassal_organization_id);
+
+-- The organizations trigger refuses a parent when can_have_parent is false:
+-- align the type flags with the feudal ranks used by the server.
+UPDATE organizations.organization_types
+SET can_have_parent = TRUE
+WHERE id BETWEEN 1 AND 8;
+
+UPDATE organizations.organization_types
+SET can_have_vassals = TRUE
+WHERE id BETWEEN 3 AND 9;
@@ -0,0 +1,45 @@
+-- Migration: diplomacy through envoys
+-- A SendEnvoy action carries a mission (propose, accept or reject) from an
+-- organization's seat to another's. Delivered proposals wait here until the
+-- other organization answers with its own envoy; accepted ones become rows of
+-- organizations.diplomatic_relations, in both directions, and expire with
+-- their expires_at.
+
+INSERT INTO actions.action_types (id, name) VALUES (9, 'SendEnvoy')
+ON CONFLICT DO NOTHING;
+
+INSERT INTO actions.action_specific_types (id, name) VALUES (9, 'SendEnvoy')
+ON CONFLICT DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS actions.send_envoy_actions (
+    action_id              BIGINT PRIMARY KEY REFERENCES actions.scheduled_actions(id) ON DELETE CASCADE,
+    unit_id                BIGINT NOT NULL,
+    organization_id        BIGINT NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE,
+    target_organization_id BIGINT NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE,
+    target_q               INT NOT NULL,
+    target_r               INT NOT NULL,
+    mission                VARCHAR(10) NOT NULL CHECK (mission IN ('propose', 'accept', 'reject')),
+    relation_type          VARCHAR(20),
+    duration_days          INT,
+    proposal_id            BIGINT
+);
+
+CREATE TABLE IF NOT EXISTS organizations.diplomatic_proposals (
+    id                     BIGSERIAL PRIMARY KEY,
+    organization_id        BIGINT NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE,
+    target_organization_id BIGINT NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE,
+    relation_type          VARCHAR(20) NOT NULL CHECK (relation_type IN
+        ('allied', 'neutral', 'hostile', 'at_war', 'trade_agreement', 'non_aggression')),
+    duration_days          INT CHECK (duration_days > 0),
+    proposed_by_unit_id    BIGINT REFERENCES units.units(id) ON DELETE SET NULL,
+    created_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (organization_id, target_organization_id),
+    CHECK (organization_id <> target_organization_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_diplomatic_proposals_target
+    ON organizations.diplomatic_proposals(target_organization_id);
+
+CREATE INDEX IF NOT EXISTS idx_diplomatic_expires
+    ON organizations.diplomatic_relations(expires_at)
+    WHERE expires_at IS NOT NULL;
@@ -0,0 +1,19 @@
+-- Migration: organization membership
+-- Players join an existing organization instead of only founding their own:
+-- a leader (or an officer allowed to manage members) invites a lord, or a
+-- lord applies to join. The other side accepts and the lord becomes an active
+-- row of organizations.members. Pending invitations and applications live
+-- here until accepted, declined or withdrawn.
+
+CREATE TABLE IF NOT EXISTS organizations.membership_requests (
+    id                 BIGSERIAL PRIMARY KEY,
+    organization_id    BIGINT NOT NULL REFERENCES organizations.organizations(id) ON DELETE CASCADE,
+    unit_id            BIGINT NOT NULL REFERENCES units.units(id) ON DELETE CASCADE,
+    kind               VARCHAR(20) NOT NULL CHECK (kind IN ('invitation', 'application')),
+    invited_by_unit_id BIGINT REFERENCES units.units(id) ON DELETE SET NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT uq_membership_request UNIQUE (organization_id, unit_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_membership_requests_unit
+    ON organizations.membership_requests(unit_id);
@@ -0,0 +1,13 @@
+-- Migration: item decay precision
+-- The decay tick runs every minute: with numeric(3,2), the decay gained by a
+-- slowly perishing item between two ticks was rounded away. current_decay is
+-- widened to double precision so every tick can store its exact accrual.
+
+DROP INDEX IF EXISTS resources.idx_item_instances_perishable;
+
+ALTER TABLE resources.item_instances
+    ALTER COLUMN current_decay TYPE DOUBLE PRECISION;
+
+CREATE INDEX IF NOT EXISTS idx_item_instances_perishable
+    ON resources.item_instances (current_decay, last_decay_update)
+    WHERE current_decay > 0;
@@ -0,0 +1,9 @@
+-- Migration: action queue encoding version
+-- unit_action_queue.action_data is the bincode encoding of
+-- shared::protocol::QueuedAction, which is not stable across protocol changes.
+-- Each entry now records the protocol version it was written with; the server
+-- drops entries written by another version at startup. Entries queued before
+-- this migration have version 0 and are dropped too.
+
+ALTER TABLE actions.unit_action_queue
+    ADD COLUMN IF NOT EXISTS protocol_version INTEGER NOT NULL DEFAULT 0;
@@ -83,3 +83,72 @@ CREATE TABLE IF NOT EXISTS resources.harvest_yields (
     CONSTRAINT chk_harvest_base_qty CHECK (base_quantity > 0),
     CONSTRAINT chk_harvest_quality  CHECK (quality_min <= quality_max)
 );
+
+-- ═══════════════════════════════════════════════════════════
+-- Skill progression
+-- ═══════════════════════════════════════════════════════════
+
+CREATE TABLE IF NOT EXISTS units.skill_levels (
+    level       INT PRIMARY KEY,
+    xp_required BIGINT NOT NULL,
+    CONSTRAINT chk_skill_level_xp CHECK (xp_required >= 0)
+);
+
+CREATE TABLE IF NOT EXISTS units.action_xp (
+    action_type_id SMALLINT PRIMARY KEY,
+    base_xp        INT NOT NULL,
+    skill_id       SMALLINT REFERENCES units.skills(id),
+    CONSTRAINT chk_action_xp CHECK (base_xp > 0)
+);
+
+ALTER TABLE resources.harvest_yields
+    ADD COLUMN IF NOT EXISTS skill_id SMALLINT REFERENCES units.skills(id);
+
+-- ═══════════════════════════════════════════════════════════
+-- Villager needs
+-- ═══════════════════════════════════════════════════════════
+
+CREATE TABLE IF NOT EXISTS units.default_consumption_demands (
+    item_id          INT PRIMARY KEY REFERENCES resources.items(id) ON DELETE CASCADE,
+    quantity_per_day DECIMAL(10,3) NOT NULL,
+    priority         INT NOT NULL DEFAULT 5,
+    CONSTRAINT chk_default_demand_quantity CHECK (quantity_per_day > 0),
+    CONSTRAINT chk_default_demand_priority CHECK (priority BETWEEN 1 AND 10)
+);
+
+-- ═══════════════════════════════════════════════════════════
+-- Natural reserves per biome
+-- ═══════════════════════════════════════════════════════════
+
+CREATE TABLE IF NOT EXISTS resources.biome_resource_stocks (
+    biome_id                  SMALLINT NOT NULL REFERENCES terrain.biome_types(id) ON DELETE CASCADE,
+    resource_specific_type_id SMALLINT NOT NULL REFERENCES resources.resource_specific_types(id) ON DELETE CASCADE,
+    max_stock                 INT NOT NULL,
+    regrowth_per_day          INT NOT NULL DEFAULT 0,
+    PRIMARY KEY (biome_id, resource_specific_type_id),
+    CONSTRAINT chk_biome_stock_max CHECK (max_stock > 0),
+    CONSTRAINT chk_biome_stock_regrowth CHECK (regrowth_per_day >= 0)
+);
+
+-- ═══════════════════════════════════════════════════════════
+-- Settlement tiers (hamlet → village → town → city)
+-- ═══════════════════════════════════════════════════════════
+
+CREATE TABLE IF NOT EXISTS organizations.settlement_tiers (
+    organization_type_id SMALLINT PRIMARY KEY REFERENCES organizations.organization_types(id) ON DELETE CASCADE,
+    previous_type_id     SMALLINT UNIQUE REFERENCES organizations.organization_types(id) ON DELETE CASCADE,
+    leader_role_type_id  SMALLINT NOT NULL REFERENCES organizations.role_types(id),
+    name_prefix          VARCHAR(50) NOT NULL,
+    claim_radius         SMALLINT NOT NULL DEFAULT 1,
+    min_population       INT NOT NULL DEFAULT 0,
+    min_territory_cells  INT NOT NULL DEFAULT 0,
+    cost_gold            INT NOT NULL DEFAULT 0,
+    CONSTRAINT chk_settlement_tier_radius CHECK (claim_radius >= 0),
+    CONSTRAINT chk_settlement_tier_cost CHECK (cost_gold >= 0)
+);
+
+CREATE TABLE IF NOT EXISTS organizations.settlement_tier_buildings (
+    organization_type_id SMALLINT NOT NULL REFERENCES organizations.settlement_tiers(organization_type_id) ON DELETE CASCADE,
+    building_type_id     INT NOT NULL REFERENCES buildings.building_types(id) ON DELETE CASCADE,
+    PRIMARY KEY (organization_type_id, building_type_id)
+);
@@ -24,6 +24,53 @@ LANG_CODES: dict[str, int] = {
     "it": 5,
 }
 
+# Action types are a Rust enum (`ActionTypeEnum`), not a seeded lookup
+ACTION_TYPE_IDS: dict[str, int] = {
+    "buildBuilding": 1,
+    "buildRoad": 2,
+    "moveUnit": 3,
+    "sendMessage": 4,
+    "harvestResource": 5,
+    "craftResource": 6,
+    "trainUnit": 7,
+    "claimCells": 8,
+    "sendEnvoy": 9,
+}
+
+# Mirrors shared::BiomeTypeEnum (terrain.biome_types ids)
+BIOME_IDS: dict[str, int] = {
+    "ocean": 1,
+    "deepOcean": 2,
+    "desert": 3,
+    "savanna": 4,
+    "grassland": 5,
+    "tropicalSeasonalForest": 6,
+    "tropicalRainForest": 7,
+    "tropicalDeciduousForest": 8,
+    "temperateRainForest": 9,
+    "wetland": 10,
+    "taiga": 11,
+    "tundra": 12,
+    "lake": 13,
+    "coldDesert": 14,
+    "ice": 15,
+}
+
+# Mirrors shared::OrganizationType (organizations.organization_types ids)
+ORGANIZATION_TYPE_IDS: dict[str, int] = {
+    "hamlet": 1,
+    "village": 2,
+    "town": 3,
+    "city": 4,
+}
+
+# Mirrors shared::RoleType (organizations.role_types ids), leader titles only
+LEADER_ROLE_IDS: dict[str, int] = {
+    "mayor": 6,
+    "villageElder": 7,
+    "headman": 8,
+}
+
 
 # ── Slug resolver ────────────────────────────────────────────
 
@@ -158,6 +205,60 @@ class HarvestYieldDef:
     required_tool_item_id: int | None = None
     tool_bonus_quantity: int = 0
     duration_seconds: int = 30
+    skill_id: int | None = None
+
+
+@dataclass
+class SkillLevelDef:
+    level: int
+    xp_required: int
+
+
+@dataclass
+class ActionXpDef:
+    action_type_id: int
+    base_xp: int
+    # Skill trained when the action itself doesn't name one (recipe, harvest yield)
+    skill_id: int | None = None
+
+
+@dataclass
+class ProfessionSkillBonusDef:
+    profession_id: int
+    skill_id: int
+    bonus_percentage: int
+
+
+@dataclass
+class ConsumptionDemandDef:
+    """Daily need given to every villager (non-lord unit)"""
+    item_id: int
+    quantity_per_day: float
+    # 1 = most important; 1-3 are vital, going without them starves the villager
+    priority: int
+
+
+@dataclass
+class BiomeResourceStockDef:
+    """Natural reserve of a resource in a biome (wood: per fully dense tree)"""
+    biome_id: int
+    resource_specific_type_id: int
+    max_stock: int
+    regrowth_per_day: int = 0
+
+
+@dataclass
+class SettlementTierDef:
+    """Settlement tier and what it takes to rise to it from the previous one"""
+    organization_type_id: int
+    leader_role_type_id: int
+    name_prefix: str
+    previous_type_id: int | None = None
+    claim_radius: int = 1
+    min_population: int = 0
+    min_territory_cells: int = 0
+    cost_gold: int = 0
+    required_building_ids: list[int] = field(default_factory=list)
 
 
 @dataclass
@@ -193,6 +294,15 @@ class SeedData:
     building_types: list[BuildingTypeDef] = field(default_factory=list)
     harvest_yields: list[HarvestYieldDef] = field(default_factory=list)
 
+    skill_levels: list[SkillLevelDef] = field(default_factory=list)
+    action_xp: list[ActionXpDef] = field(default_factory=list)
+    profession_skill_bonuses: list[ProfessionSkillBonusDef] = field(
+        default_factory=list
+    )
+    consumption_demands: list[ConsumptionDemandDef] = field(default_factory=list)
+    biome_resource_stocks: list[BiomeResourceStockDef] = field(default_factory=list)
+    settlement_tiers: list[SettlementTierDef] = field(default_factory=list)
+
     translations: list[TranslationEntry] = field(default_factory=list)
 
 
@@ -382,11 +492,130 @@ def _parse_harvest(
                 ),
                 tool_bonus_quantity=hy.get("tool_bonus_quantity", 0),
                 duration_seconds=hy.get("duration_seconds", 30),
+                skill_id=resolver.resolve("skill", hy.get("skill")),
             )
         )
     return yields
 
 
+# ── Progression parsing ──────────────────────────────────────
+
+
+def _parse_progression(
+    raw: dict[str, Any], resolver: SlugResolver
+) -> tuple[list[SkillLevelDef], list[ActionXpDef], list[ProfessionSkillBonusDef]]:
+    levels = [
+        SkillLevelDef(level=lv["level"], xp_required=lv["xp_required"])
+        for lv in raw.get("skill_levels", [])
+    ]
+
+    action_xp = []
+    for ax in raw.get("action_xp", []):
+        action_type = ax["action_type"]
+        action_type_id = ACTION_TYPE_IDS.get(action_type)
+        if action_type_id is None:
+            raise ValueError(f"Unknown action_type '{action_type}' in progression")
+        action_xp.append(
+            ActionXpDef(
+                action_type_id=action_type_id,
+                base_xp=ax["base_xp"],
+                skill_id=resolver.resolve("skill", ax.get("skill")),
+            )
+        )
+
+    bonuses = [
+        ProfessionSkillBonusDef(
+            profession_id=resolver.resolve_required("profession", pb["profession"]),
+            skill_id=resolver.resolve_required("skill", pb["skill"]),
+            bonus_percentage=pb["bonus_percentage"],
+        )
+        for pb in raw.get("profession_skill_bonuses", [])
+    ]
+    return levels, action_xp, bonuses
+
+
+# ── Needs parsing ────────────────────────────────────────────
+
+
+def _parse_needs(
+    raw: dict[str, Any], resolver: SlugResolver
+) -> list[ConsumptionDemandDef]:
+    return [
+        ConsumptionDemandDef(
+            item_id=resolver.resolve_required("item", cd["item"]),
+            quantity_per_day=cd["quantity_per_day"],
+            priority=cd.get("priority", 5),
+        )
+        for cd in raw.get("consumption_demands", [])
+    ]
+
+
+# ── Resource stocks parsing ──────────────────────────────────
+
+
+def _parse_resource_stocks(
+    raw: dict[str, Any], resolver: SlugResolver
+) -> list[BiomeResourceStockDef]:
+    stocks = []
+    for bs in raw.get("biome_resource_stocks", []):
+        biome = bs["biome"]
+        biome_id = BIOME_IDS.get(biome)
+        if biome_id is None:
+            raise ValueError(f"Unknown biome '{biome}' in resource_stocks")
+        stocks.append(
+            BiomeResourceStockDef(
+                biome_id=biome_id,
+                resource_specific_type_id=resolver.resolve_required(
+                    "resource_specific_type", bs["resource_type"]
+                ),
+                max_stock=bs["max_stock"],
+                regrowth_per_day=bs.get("regrowth_per_day", 0),
+            )
+        )
+    return stocks
+
+
+# ── Settlement parsing ───────────────────────────────────
+
+
+def _settlement_type_id(slug: str) -> int:
+    type_id = ORGANIZATION_TYPE_IDS.get(slug)
+    if type_id is None:
+        raise ValueError(f"Unknown organization type '{slug}' in settlements")
+    return type_id
+
+
+def _parse_settlements(
+    raw: dict[str, Any], resolver: SlugResolver
+) -> list[SettlementTierDef]:
+    tiers = []
+    for st in raw.get("settlement_tiers", []):
+        role = st["leader_role"]
+        role_id = LEADER_ROLE_IDS.get(role)
+        if role_id is None:
+            raise ValueError(f"Unknown leader role '{role}' in settlements")
+        previous = st.get("previous")
+        tiers.append(
+            SettlementTierDef(
+                organization_type_id=_settlement_type_id(st["organization_type"]),
+                leader_role_type_id=role_id,
+                name_prefix=st["name_prefix"],
+                previous_type_id=(
+                    _settlement_type_id(previous) if previous is not None else None
+                ),
+                claim_radius=st.get("claim_radius", 1),
+                min_population=st.get("min_population", 0),
+                min_territory_cells=st.get("min_territory_cells", 0),
+                cost_gold=st.get("cost_gold", 0),
+                required_building_ids=[
+                    resolver.resolve_required("building_type", b)
+                    for b in st.get("required_buildings", [])
+                ],
+            )
+        )
+    return tiers
+
+
 # ── Translation parsing ──────────────────────────────────────
 
 
@@ -465,8 +694,14 @@ def load_seed_data(data_dir: Path) -> SeedData:
       2. items (reference lookup slugs, register item slugs)
       3. buildings (reference lookup + item slugs, register building slugs)
       4. recipes (reference item + skill + building slugs)
-      5. harvest (reference resource_specific_type + item + profession slugs)
-      6. translations (resolve all slugs to IDs)
+      5. harvest (reference resource_specific_type + item + profession + skill slugs)
+      6. progression (XP curve, XP per action type, profession skill bonuses;
+         reference profession + skill slugs)
+      7. needs (daily consumption demands of villagers; reference item slugs)
+      8. resource stocks (natural reserves per biome; reference
+         resource_specific_type slugs)
+      9. settlements (settlement tiers; reference building slugs)
+     10. translations (resolve all slugs to IDs)
     """
     resolver = SlugResolver()
 
@@ -522,7 +757,25 @@ def load_seed_data(data_dir: Path) -> SeedData:
     harvest_raw = _load_json(data_dir / "harvest.json")
     harvest_yields = _parse_harvest(harvest_raw, resolver)
 
-    # 6. Translations (need all slugs)
+    # 6. Progression (need profession + skill slugs)
+    progression_raw = _load_json(data_dir / "progression.json")
+    skill_levels, action_xp, profession_skill_bonuses = _parse_progression(
+        progression_raw, resolver
+    )
+
+    # 7. Needs (need item slugs)
+    needs_raw = _load_json(data_dir / "needs.json")
+    consumption_demands = _parse_needs(needs_raw, resolver)
+
+    # 8. Resource stocks (need resource_specific_type slugs)
+    stocks_raw = _load_json(data_dir / "resource_stocks.json")
+    biome_resource_stocks = _parse_resource_stocks(stocks_raw, resolver)
+
+    # 9. Settlements (need building slugs)
+    settlements_raw = _load_json(data_dir / "settlements.json")
+    settlement_tiers = _parse_settlements(settlements_raw, resolver)
+
+    # 10. Translations (need all slugs)
     translations_raw = _load_json(data_dir / "translations.json")
     translations = _parse_translations(translations_raw, resolver)
 
@@ -539,5 +792,11 @@ def load_seed_data(data_dir: Path) -> SeedData:
         recipes=recipes,
         building_types=building_types,
         harvest_yields=harvest_yields,
+        skill_levels=skill_levels,
+        action_xp=action_xp,
+        profession_skill_bonuses=profession_skill_bonuses,
+        consumption_demands=consumption_demands,
+        biome_resource_stocks=biome_resource_stocks,
+        settlement_tiers=settlement_tiers,
         translations=translations,
     )
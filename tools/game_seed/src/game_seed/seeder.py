@@ -12,12 +12,18 @@ from typing import Any
 import psycopg
 
 from game_seed.loader import (
+    ActionXpDef,
+    BiomeResourceStockDef,
     BuildingTypeDef,
+    ConsumptionDemandDef,
     HarvestYieldDef,
     ItemDef,
     LookupEntry,
+    ProfessionSkillBonusDef,
     RecipeDef,
     SeedData,
+    SettlementTierDef,
+    SkillLevelDef,
     TranslationEntry,
 )
 
@@ -86,6 +92,26 @@ class Seeder:
                 report["resources.harvest_yields"] = self._seed_harvest_yields(
                     cur, data.harvest_yields
                 )
+                report["units.skill_levels"] = self._seed_skill_levels(
+                    cur, data.skill_levels
+                )
+                report["units.action_xp"] = self._seed_action_xp(
+                    cur, data.action_xp
+                )
+                report["units.profession_skill_bonuses"] = (
+                    self._seed_profession_skill_bonuses(
+                        cur, data.profession_skill_bonuses
+                    )
+                )
+                report["units.default_consumption_demands"] = (
+                    self._seed_consumption_demands(cur, data.consumption_demands)
+                )
+                report["resources.biome_resource_stocks"] = (
+                    self._seed_biome_resource_stocks(cur, data.biome_resource_stocks)
+                )
+                report["organizations.settlement_tiers"] = (
+                    self._seed_settlement_tiers(cur, data.settlement_tiers)
+                )
                 report["game.translations"] = self._seed_translations(
                     cur, data.translations
                 )
@@ -388,8 +414,8 @@ class Seeder:
                 "(id, resource_specific_type_id, result_item_id, "
                 "base_quantity, quality_min, quality_max, "
                 "required_profession_id, required_tool_item_id, "
-                "tool_bonus_quantity, duration_seconds) "
-                "VALUES (%s,%s,%s,%s,%s,%s,%s,%s,%s,%s) "
+                "tool_bonus_quantity, duration_seconds, skill_id) "
+                "VALUES (%s,%s,%s,%s,%s,%s,%s,%s,%s,%s,%s) "
                 "ON CONFLICT (id) DO UPDATE SET "
                 "resource_specific_type_id="
                 "EXCLUDED.resource_specific_type_id, "
@@ -400,12 +426,13 @@ class Seeder:
                 "required_profession_id=EXCLUDED.required_profession_id, "
                 "required_tool_item_id=EXCLUDED.required_tool_item_id, "
                 "tool_bonus_quantity=EXCLUDED.tool_bonus_quantity, "
-                "duration_seconds=EXCLUDED.duration_seconds",
+                "duration_seconds=EXCLUDED.duration_seconds, "
+                "skill_id=EXCLUDED.skill_id",
                 (
                     hy.id, hy.resource_specific_type_id, hy.result_item_id,
                     hy.base_quantity, hy.quality_min, hy.quality_max,
                     hy.required_profession_id, hy.required_tool_item_id,
-                    hy.tool_bonus_quantity, hy.duration_seconds,
+                    hy.tool_bonus_quantity, hy.duration_seconds, hy.skill_id,
                 ),
             )
         if seed_ids:
@@ -417,6 +444,201 @@ class Seeder:
             )
         return {"upserted": len(yields)}
 
+    # ── Progression ──────────────────────────────────────────
+
+    def _seed_skill_levels(
+        self, cur: psycopg.Cursor[Any], levels: list[SkillLevelDef]
+    ) -> TableStats:
+        if not levels:
+            return {}
+        seed_levels = {lv.level for lv in levels}
+        for lv in levels:
+            cur.execute(
+                "INSERT INTO units.skill_levels (level, xp_required) "
+                "VALUES (%s,%s) "
+                "ON CONFLICT (level) DO UPDATE SET "
+                "xp_required=EXCLUDED.xp_required",
+                (lv.level, lv.xp_required),
+            )
+        ph = ",".join(["%s"] * len(seed_levels))
+        cur.execute(
+            f"DELETE FROM units.skill_levels WHERE level NOT IN ({ph})",
+            list(seed_levels),
+        )
+        return {"upserted": len(levels)}
+
+    def _seed_action_xp(
+        self, cur: psycopg.Cursor[Any], action_xp: list[ActionXpDef]
+    ) -> TableStats:
+        if not action_xp:
+            return {}
+        seed_ids = {ax.action_type_id for ax in action_xp}
+        for ax in action_xp:
+            cur.execute(
+                "INSERT INTO units.action_xp (action_type_id, base_xp, skill_id) "
+                "VALUES (%s,%s,%s) "
+                "ON CONFLICT (action_type_id) DO UPDATE SET "
+                "base_xp=EXCLUDED.base_xp, skill_id=EXCLUDED.skill_id",
+                (ax.action_type_id, ax.base_xp, ax.skill_id),
+            )
+        ph = ",".join(["%s"] * len(seed_ids))
+        cur.execute(
+            f"DELETE FROM units.action_xp WHERE action_type_id NOT IN ({ph})",
+            list(seed_ids),
+        )
+        return {"upserted": len(action_xp)}
+
+    def _seed_profession_skill_bonuses(
+        self, cur: psycopg.Cursor[Any], bonuses: list[ProfessionSkillBonusDef]
+    ) -> TableStats:
+        if not bonuses:
+            return {}
+        seed_keys = {(pb.profession_id, pb.skill_id) for pb in bonuses}
+        for pb in bonuses:
+            cur.execute(
+                "INSERT INTO units.profession_skill_bonuses "
+                "(profession_id, skill_id, bonus_percentage) "
+                "VALUES (%s,%s,%s) "
+                "ON CONFLICT (profession_id, skill_id) DO UPDATE SET "
+                "bonus_percentage=EXCLUDED.bonus_percentage",
+                (pb.profession_id, pb.skill_id, pb.bonus_percentage),
+            )
+        cur.execute(
+            "SELECT profession_id, skill_id FROM units.profession_skill_bonuses"
+        )
+        orphans = {(row[0], row[1]) for row in cur.fetchall()} - seed_keys
+        for profession_id, skill_id in orphans:
+            cur.execute(
+                "DELETE FROM units.profession_skill_bonuses "
+                "WHERE profession_id=%s AND skill_id=%s",
+                (profession_id, skill_id),
+            )
+        return {"upserted": len(bonuses), "deleted": len(orphans)}
+
+    # ── Needs ────────────────────────────────────────────────
+
+    def _seed_consumption_demands(
+        self, cur: psycopg.Cursor[Any], demands: list[ConsumptionDemandDef]
+    ) -> TableStats:
+        if not demands:
+            return {}
+        seed_ids = {cd.item_id for cd in demands}
+        for cd in demands:
+            cur.execute(
+                "INSERT INTO units.default_consumption_demands "
+                "(item_id, quantity_per_day, priority) "
+                "VALUES (%s,%s,%s) "
+                "ON CONFLICT (item_id) DO UPDATE SET "
+                "quantity_per_day=EXCLUDED.quantity_per_day, "
+                "priority=EXCLUDED.priority",
+                (cd.item_id, cd.quantity_per_day, cd.priority),
+            )
+        ph = ",".join(["%s"] * len(seed_ids))
+        cur.execute(
+            f"DELETE FROM units.default_consumption_demands "
+            f"WHERE item_id NOT IN ({ph})",
+            list(seed_ids),
+        )
+        return {"upserted": len(demands)}
+
+    # ── Resource stocks ──────────────────────────────────────
+
+    def _seed_biome_resource_stocks(
+        self, cur: psycopg.Cursor[Any], stocks: list[BiomeResourceStockDef]
+    ) -> TableStats:
+        if not stocks:
+            return {}
+        seed_keys = {(bs.biome_id, bs.resource_specific_type_id) for bs in stocks}
+        for bs in stocks:
+            cur.execute(
+                "INSERT INTO resources.biome_resource_stocks "
+                "(biome_id, resource_specific_type_id, max_stock, regrowth_per_day) "
+                "VALUES (%s,%s,%s,%s) "
+                "ON CONFLICT (biome_id, resource_specific_type_id) DO UPDATE SET "
+                "max_stock=EXCLUDED.max_stock, "
+                "regrowth_per_day=EXCLUDED.regrowth_per_day",
+                (
+                    bs.biome_id,
+                    bs.resource_specific_type_id,
+                    bs.max_stock,
+                    bs.regrowth_per_day,
+                ),
+            )
+        cur.execute(
+            "SELECT biome_id, resource_specific_type_id "
+            "FROM resources.biome_resource_stocks"
+        )
+        orphans = {(row[0], row[1]) for row in cur.fetchall()} - seed_keys
+        for biome_id, resource_specific_type_id in orphans:
+            cur.execute(
+                "DELETE FROM resources.biome_resource_stocks "
+                "WHERE biome_id=%s AND resource_specific_type_id=%s",
+                (biome_id, resource_specific_type_id),
+            )
+        return {"upserted": len(stocks), "deleted": len(orphans)}
+
+    # ── Settlement tiers ─────────────────────────────────────
+
+    def _seed_settlement_tiers(
+        self, cur: psycopg.Cursor[Any], tiers: list[SettlementTierDef]
+    ) -> TableStats:
+        if not tiers:
+            return {}
+        seed_ids = {st.organization_type_id for st in tiers}
+        for st in tiers:
+            cur.execute(
+                "INSERT INTO organizations.settlement_tiers "
+                "(organization_type_id, previous_type_id, leader_role_type_id, "
+                "name_prefix, claim_radius, min_population, min_territory_cells, "
+                "cost_gold) "
+                "VALUES (%s,%s,%s,%s,%s,%s,%s,%s) "
+                "ON CONFLICT (organization_type_id) DO UPDATE SET "
+                "previous_type_id=EXCLUDED.previous_type_id, "
+                "leader_role_type_id=EXCLUDED.leader_role_type_id, "
+                "name_prefix=EXCLUDED.name_prefix, "
+                "claim_radius=EXCLUDED.claim_radius, "
+                "min_population=EXCLUDED.min_population, "
+                "min_territory_cells=EXCLUDED.min_territory_cells, "
+                "cost_gold=EXCLUDED.cost_gold",
+                (
+                    st.organization_type_id,
+                    st.previous_type_id,
+                    st.leader_role_type_id,
+                    st.name_prefix,
+                    st.claim_radius,
+                    st.min_population,
+                    st.min_territory_cells,
+                    st.cost_gold,
+                ),
+            )
+            cur.execute(
+                "DELETE FROM organizations.settlement_tier_buildings "
+                "WHERE organization_type_id = %s",
+                (st.organization_type_id,),
+            )
+            for building_id in st.required_building_ids:
+                cur.execute(
+                    "INSERT INTO organizations.settlement_tier_buildings "
+                    "(organization_type_id, building_type_id) VALUES (%s,%s)",
+                    (st.organization_type_id, building_id),
+                )
+            # The officer trigger only accepts a leader role declared for the type
+            cur.execute(
+                "INSERT INTO organizations.organization_role_compatibility "
+                "(organization_type_id, role_type_id, is_leader_role) "
+                "VALUES (%s,%s,TRUE) "
+                "ON CONFLICT (organization_type_id, role_type_id) "
+                "DO UPDATE SET is_leader_role = TRUE",
+                (st.organization_type_id, st.leader_role_type_id),
+            )
+        ph = ",".join(["%s"] * len(seed_ids))
+        cur.execute(
+            f"DELETE FROM organizations.settlement_tiers "
+            f"WHERE organization_type_id NOT IN ({ph})",
+            list(seed_ids),
+        )
+        return {"upserted": len(tiers), "deleted": cur.rowcount}
+
     # ── Translations ─────────────────────────────────────────
 
     def _seed_translations(
@@ -132,6 +132,11 @@ def validate_seed_data(data: SeedData) -> list[str]:
                     f"HarvestYield id={hy.id}: "
                     f"required_profession_id={hy.required_profession_id} unknown"
                 )
+        if hy.skill_id is not None and skill_ids:
+            if hy.skill_id not in skill_ids:
+                errors.append(
+                    f"HarvestYield id={hy.id}: skill_id={hy.skill_id} unknown"
+                )
         if hy.required_tool_item_id is not None:
             if hy.required_tool_item_id not in item_ids:
                 errors.append(
@@ -140,6 +145,123 @@ def validate_seed_data(data: SeedData) -> list[str]:
                     f"not in items"
                 )
 
+    # Progression: the XP curve starts at level 1 with 0 XP and keeps rising
+    _check_duplicates(errors, "skill_levels", [lv.level for lv in data.skill_levels])
+    levels = sorted(data.skill_levels, key=lambda lv: lv.level)
+    if levels and (levels[0].level != 1 or levels[0].xp_required != 0):
+        errors.append("SkillLevels: level 1 must require 0 XP")
+    for prev, cur in zip(levels, levels[1:]):
+        if cur.level != prev.level + 1:
+            errors.append(f"SkillLevels: gap between level {prev.level} and {cur.level}")
+        if cur.xp_required <= prev.xp_required:
+            errors.append(
+                f"SkillLevels: level {cur.level} must require more XP "
+                f"than level {prev.level}"
+            )
+
+    _check_duplicates(
+        errors, "action_xp", [ax.action_type_id for ax in data.action_xp]
+    )
+    for ax in data.action_xp:
+        if ax.base_xp <= 0:
+            errors.append(
+                f"ActionXp action_type_id={ax.action_type_id}: base_xp must be > 0"
+            )
+        if ax.skill_id is not None and skill_ids and ax.skill_id not in skill_ids:
+            errors.append(
+                f"ActionXp action_type_id={ax.action_type_id}: "
+                f"skill_id={ax.skill_id} unknown"
+            )
+
+    _check_duplicates(
+        errors,
+        "profession_skill_bonuses",
+        [f"{pb.profession_id}:{pb.skill_id}" for pb in data.profession_skill_bonuses],
+    )
+    for pb in data.profession_skill_bonuses:
+        if not 0 < pb.bonus_percentage <= 100:
+            errors.append(
+                f"ProfessionSkillBonus profession_id={pb.profession_id} "
+                f"skill_id={pb.skill_id}: bonus_percentage must be in 1..100"
+            )
+
+    # Needs: one demand per item, positive daily quantity, priority 1..10
+    _check_duplicates(
+        errors, "consumption_demands", [cd.item_id for cd in data.consumption_demands]
+    )
+    for cd in data.consumption_demands:
+        if cd.quantity_per_day <= 0:
+            errors.append(
+                f"ConsumptionDemand item_id={cd.item_id}: "
+                f"quantity_per_day must be > 0"
+            )
+        if not 1 <= cd.priority <= 10:
+            errors.append(
+                f"ConsumptionDemand item_id={cd.item_id}: priority must be in 1..10"
+            )
+
+    # Resource stocks: one reserve per biome and resource, positive maximum
+    _check_duplicates(
+        errors,
+        "biome_resource_stocks",
+        [
+            f"{bs.biome_id}:{bs.resource_specific_type_id}"
+            for bs in data.biome_resource_stocks
+        ],
+    )
+    for bs in data.biome_resource_stocks:
+        if bs.max_stock <= 0:
+            errors.append(
+                f"BiomeResourceStock biome_id={bs.biome_id} "
+                f"resource_specific_type_id={bs.resource_specific_type_id}: "
+                f"max_stock must be > 0"
+            )
+        if bs.regrowth_per_day < 0:
+            errors.append(
+                f"BiomeResourceStock biome_id={bs.biome_id} "
+                f"resource_specific_type_id={bs.resource_specific_type_id}: "
+                f"regrowth_per_day must be >= 0"
+            )
+
+    # Settlement tiers: one per type, a single chain starting from the hamlet
+    tier_ids = {st.organization_type_id for st in data.settlement_tiers}
+    _check_duplicates(
+        errors,
+        "settlement_tiers",
+        [st.organization_type_id for st in data.settlement_tiers],
+    )
+    _check_duplicates(
+        errors,
+        "settlement_tiers.previous",
+        [
+            st.previous_type_id
+            for st in data.settlement_tiers
+            if st.previous_type_id is not None
+        ],
+    )
+    for st in data.settlement_tiers:
+        if st.previous_type_id is not None and st.previous_type_id not in tier_ids:
+            errors.append(
+                f"SettlementTier type_id={st.organization_type_id}: "
+                f"previous type_id={st.previous_type_id} has no tier"
+            )
+        if st.claim_radius < 0:
+            errors.append(
+                f"SettlementTier type_id={st.organization_type_id}: "
+                f"claim_radius must be >= 0"
+            )
+        if st.cost_gold < 0:
+            errors.append(
+                f"SettlementTier type_id={st.organization_type_id}: "
+                f"cost_gold must be >= 0"
+            )
+        for building_id in st.required_building_ids:
+            if building_id not in building_type_ids:
+                errors.append(
+                    f"SettlementTier type_id={st.organization_type_id}: "
+                    f"building_type_id={building_id} not found"
+                )
+
     # Translations (already resolved to IDs)
     entity_id_sets = {
         "item": item_ids,
@@ -5,6 +5,7 @@ from pathlib import Path
 from game_seed.loader import (
     RecipeDef,
     RecipeIngredient,
+    SkillLevelDef,
     load_seed_data,
 )
 from game_seed.validators import validate_seed_data
@@ -80,6 +81,43 @@ class TestLoadSeedData:
         assert wood_harvest.result_item_id == 1  # wood item
         assert wood_harvest.required_profession_id == 13  # lumberjack
         assert wood_harvest.required_tool_item_id == 21  # ironAxe
+        assert wood_harvest.skill_id == 4  # lumberjacking
+
+    def test_progression_loaded(self) -> None:
+        data = load_seed_data(DATA_DIR)
+        assert data.skill_levels[0].level == 1
+        assert data.skill_levels[0].xp_required == 0
+        build = next(
+            (ax for ax in data.action_xp if ax.action_type_id == 1), None
+        )
+        assert build is not None  # buildBuilding
+        assert build.skill_id == 31  # engineering
+        lumberjack = next(
+            pb for pb in data.profession_skill_bonuses if pb.profession_id == 13
+        )
+        assert lumberjack.skill_id == 4  # lumberjacking
+
+    def test_needs_loaded(self) -> None:
+        data = load_seed_data(DATA_DIR)
+        bread = next(cd for cd in data.consumption_demands if cd.item_id == 10)
+        assert bread.priority == 1
+
+    def test_resource_stocks_loaded(self) -> None:
+        data = load_seed_data(DATA_DIR)
+        taiga_wood = next(
+            bs
+            for bs in data.biome_resource_stocks
+            if bs.biome_id == 11 and bs.resource_specific_type_id == 1
+        )
+        assert taiga_wood.max_stock > 0
+
+    def test_settlements_loaded(self) -> None:
+        data = load_seed_data(DATA_DIR)
+        village = next(
+            st for st in data.settlement_tiers if st.organization_type_id == 2
+        )
+        assert village.previous_type_id == 1
+        assert 55 in village.required_building_ids
 
     def test_translations_resolved_to_ids(self) -> None:
         data = load_seed_data(DATA_DIR)
@@ -124,6 +162,12 @@ class TestValidation:
         errors = validate_seed_data(data)
         assert len(errors) >= 2
 
+    def test_detects_non_increasing_xp_curve(self) -> None:
+        data = load_seed_data(DATA_DIR)
+        data.skill_levels.append(SkillLevelDef(level=21, xp_required=0))
+        errors = validate_seed_data(data)
+        assert any("SkillLevels" in e for e in errors)
+
     def test_detects_duplicate_slugs(self) -> None:
         data = load_seed_data(DATA_DIR)
         data.items.append(data.items[0])